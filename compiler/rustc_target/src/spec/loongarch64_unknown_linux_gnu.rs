@@ -10,6 +10,8 @@ pub fn target() -> Target {
             code_model: Some(CodeModel::Medium),
             cpu: "generic-la64".into(),
             features: "+d".into(),
+            // Already set by the linux base, spelled out since deployments depend on it.
+            has_rpath: true,
             llvm_abiname: "lp64d".into(),
             max_atomic_width: Some(64),
             ..super::linux_gnu_base::opts()
@@ -0,0 +1,21 @@
+use crate::spec::{CodeModel, Target, TargetOptions};
+
+pub fn target() -> Target {
+    Target {
+        llvm_target: "loongarch64-unknown-linux-gnusf".into(),
+        pointer_width: 64,
+        data_layout: "e-m:e-p:64:64-i64:64-i128:128-n64-S128".into(),
+        arch: "loongarch64".into(),
+        options: TargetOptions {
+            abi: "softfloat".into(),
+            code_model: Some(CodeModel::Medium),
+            cpu: "generic-la64".into(),
+            // The userland doesn't use the FPU, floats are passed in integer
+            // registers.
+            features: "".into(),
+            llvm_abiname: "lp64s".into(),
+            max_atomic_width: Some(64),
+            ..super::linux_gnu_base::opts()
+        },
+    }
+}
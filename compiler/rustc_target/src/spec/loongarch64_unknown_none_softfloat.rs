@@ -0,0 +1,18 @@
+use crate::spec::{Target, TargetOptions};
+
+pub fn target() -> Target {
+    Target {
+        llvm_target: "loongarch64-unknown-none".into(),
+        pointer_width: 64,
+        data_layout: "e-m:e-p:64:64-i64:64-i128:128-n64-S128".into(),
+        arch: "loongarch64".into(),
+        options: TargetOptions {
+            abi: "softfloat".into(),
+            // No FPU at all, not even single precision, so floats are passed in
+            // integer registers.
+            features: "".into(),
+            llvm_abiname: "lp64s".into(),
+            ..super::loongarch_none_base::opts()
+        },
+    }
+}
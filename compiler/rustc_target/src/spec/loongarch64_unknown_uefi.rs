@@ -0,0 +1,25 @@
+// This defines the loongarch64 target for UEFI systems as described in the UEFI specification.
+// See the uefi-base module for generic UEFI options.
+
+use super::uefi_msvc_base;
+use crate::spec::{CodeModel, Target};
+
+pub fn target() -> Target {
+    let mut base = uefi_msvc_base::opts();
+
+    // lld picks the machine type up from the input objects, so unlike the other UEFI targets no
+    // `/machine` flag is passed to it.
+    base.code_model = Some(CodeModel::Medium);
+    base.cpu = "generic-la64".into();
+    base.features = "+f,+d".into();
+    base.llvm_abiname = "lp64d".into();
+    base.max_atomic_width = Some(64);
+
+    Target {
+        llvm_target: "loongarch64-unknown-windows".into(),
+        pointer_width: 64,
+        data_layout: "e-m:w-p:64:64-i64:64-i128:128-n64-S128".into(),
+        arch: "loongarch64".into(),
+        options: base,
+    }
+}
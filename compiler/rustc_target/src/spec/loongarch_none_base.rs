@@ -0,0 +1,20 @@
+// Shared options for the freestanding `loongarch64-*-none*` targets. The targets themselves
+// only decide on the floating-point ABI.
+
+use crate::spec::{Cc, CodeModel, LinkerFlavor, Lld, PanicStrategy, RelocModel, TargetOptions};
+
+pub fn opts() -> TargetOptions {
+    TargetOptions {
+        linker_flavor: LinkerFlavor::Gnu(Cc::No, Lld::Yes),
+        linker: Some("rust-lld".into()),
+        cpu: "generic-la64".into(),
+        max_atomic_width: Some(64),
+        // There is no unwinder to rely on on bare metal.
+        panic_strategy: PanicStrategy::Abort,
+        relocation_model: RelocModel::Static,
+        code_model: Some(CodeModel::Medium),
+        emit_debug_gdb_scripts: false,
+        eh_frame_header: false,
+        ..Default::default()
+    }
+}
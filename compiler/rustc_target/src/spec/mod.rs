@@ -72,6 +72,7 @@
 mod linux_gnu_base;
 mod linux_musl_base;
 mod linux_uclibc_base;
+mod loongarch_none_base;
 mod msvc_base;
 mod netbsd_base;
 mod nto_qnx_base;
@@ -1003,6 +1004,7 @@ fn load_builtin(target: &str) -> Option<Target> {
         #[cfg(test)]
         mod tests {
             mod tests_impl;
+            mod loongarch;
 
             // Cannot put this into a separate file without duplication, make an exception.
             $(
@@ -1021,6 +1023,7 @@ fn $module() {
     ("i686-unknown-linux-gnu", i686_unknown_linux_gnu),
     ("i586-unknown-linux-gnu", i586_unknown_linux_gnu),
     ("loongarch64-unknown-linux-gnu", loongarch64_unknown_linux_gnu),
+    ("loongarch64-unknown-linux-gnusf", loongarch64_unknown_linux_gnusf),
     ("m68k-unknown-linux-gnu", m68k_unknown_linux_gnu),
     ("mips-unknown-linux-gnu", mips_unknown_linux_gnu),
     ("mips64-unknown-linux-gnuabi64", mips64_unknown_linux_gnuabi64),
@@ -1067,6 +1070,7 @@ fn $module() {
     ("mips64-unknown-linux-muslabi64", mips64_unknown_linux_muslabi64),
     ("mips64el-unknown-linux-muslabi64", mips64el_unknown_linux_muslabi64),
     ("hexagon-unknown-linux-musl", hexagon_unknown_linux_musl),
+    ("loongarch64-unknown-linux-musl", loongarch64_unknown_linux_musl),
 
     ("mips-unknown-linux-uclibc", mips_unknown_linux_uclibc),
     ("mipsel-unknown-linux-uclibc", mipsel_unknown_linux_uclibc),
@@ -1102,6 +1106,7 @@ fn $module() {
     ("armv6-unknown-netbsd-eabihf", armv6_unknown_netbsd_eabihf),
     ("armv7-unknown-netbsd-eabihf", armv7_unknown_netbsd_eabihf),
     ("i686-unknown-netbsd", i686_unknown_netbsd),
+    ("loongarch64-unknown-netbsd", loongarch64_unknown_netbsd),
     ("powerpc-unknown-netbsd", powerpc_unknown_netbsd),
     ("sparc64-unknown-netbsd", sparc64_unknown_netbsd),
     ("x86_64-unknown-netbsd", x86_64_unknown_netbsd),
@@ -1210,11 +1215,15 @@ fn $module() {
     ("aarch64-unknown-none", aarch64_unknown_none),
     ("aarch64-unknown-none-softfloat", aarch64_unknown_none_softfloat),
 
+    ("loongarch64-unknown-none", loongarch64_unknown_none),
+    ("loongarch64-unknown-none-softfloat", loongarch64_unknown_none_softfloat),
+
     ("x86_64-fortanix-unknown-sgx", x86_64_fortanix_unknown_sgx),
 
     ("x86_64-unknown-uefi", x86_64_unknown_uefi),
     ("i686-unknown-uefi", i686_unknown_uefi),
     ("aarch64-unknown-uefi", aarch64_unknown_uefi),
+    ("loongarch64-unknown-uefi", loongarch64_unknown_uefi),
 
     ("nvptx64-nvidia-cuda", nvptx64_nvidia_cuda),
 
@@ -0,0 +1,402 @@
+use super::super::*;
+use crate::abi::call::{ArgAbi, ArgAttributes, CastTarget, FnAbi, PassMode, Reg, Uniform};
+use crate::abi::{
+    self, AbiAndPrefAlign, FieldsShape, HasDataLayout, Layout, LayoutS, PointeeInfo, Scalar,
+    TyAbiInterface, TyAndLayout, VariantIdx, Variants, WrappingRange,
+};
+use rustc_data_structures::intern::Interned;
+
+// Check that the hardcoded data layout agrees with the target's declared
+// properties and the LP64 ABI from the LoongArch psABI.
+#[test]
+fn data_layout_matches_target() {
+    let target = loongarch64_unknown_linux_gnu::target();
+    let Ok(dl) = target.parse_data_layout() else {
+        panic!("failed to parse data layout `{}`", target.data_layout);
+    };
+
+    assert_eq!(dl.pointer_size.bits(), u64::from(target.pointer_width));
+    assert_eq!(dl.pointer_align.abi.bits(), 64);
+    assert_eq!(dl.i64_align.abi.bits(), 64);
+    assert_eq!(dl.i128_align.abi.bits(), 128);
+    // The stack alignment isn't part of `TargetDataLayout`, check the spec directly.
+    assert!(target.data_layout.split('-').any(|spec| spec == "S128"));
+}
+
+// `env` is what tells the gnu and musl flavours of the same triple apart, and
+// both only get it from their base.
+#[test]
+fn linux_env_comes_from_base() {
+    let gnu = loongarch64_unknown_linux_gnu::target();
+    let musl = loongarch64_unknown_linux_musl::target();
+    assert_eq!(gnu.os, "linux");
+    assert_eq!(gnu.env, "gnu");
+    assert_eq!(musl.os, "linux");
+    assert_eq!(musl.env, "musl");
+    assert!(musl.crt_static_default);
+}
+
+// Hosted Linux targets link through the system C compiler driver, which in turn
+// invokes GNU ld; none of that is overridden on top of `linux_gnu_base`.
+#[test]
+fn linux_gnu_links_with_cc() {
+    let target = loongarch64_unknown_linux_gnu::target();
+    assert_eq!(target.linker_flavor, LinkerFlavor::Gnu(Cc::Yes, Lld::No));
+    assert_eq!(target.linker, None);
+}
+
+// None of the platform quirks apply to Linux, it is a plain unix target.
+#[test]
+fn linux_gnu_is_plain_unix() {
+    let target = loongarch64_unknown_linux_gnu::target();
+    assert_eq!(&target.families[..], &["unix"]);
+    assert!(!target.is_like_aix);
+    assert!(!target.is_like_osx);
+    assert!(!target.is_like_solaris);
+    assert!(!target.is_like_windows);
+    assert!(!target.is_like_msvc);
+    assert!(!target.is_like_wasm);
+    assert!(!target.is_like_android);
+}
+
+#[test]
+fn linux_gnu_has_unknown_vendor() {
+    assert_eq!(loongarch64_unknown_linux_gnu::target().vendor, "unknown");
+}
+
+// The vendor is only set explicitly by vendor-specific targets, so it has to agree
+// with the triple LLVM gets for every one of them.
+#[test]
+fn vendor_matches_triple() {
+    for target in loongarch_targets() {
+        let triple = &target.llvm_target;
+        let vendor = triple.split('-').nth(1).unwrap();
+        assert_eq!(target.vendor, vendor, "{triple}");
+    }
+}
+
+// A board vendor's variant of a generic target only overrides `vendor`, which
+// has to survive being read back as a custom target to tell the two apart.
+#[test]
+fn vendor_variant_keeps_its_vendor() {
+    let generic = loongarch64_unknown_linux_gnu::target();
+    let mut variant = generic.clone();
+    variant.vendor = "loongson".into();
+    variant.is_builtin = false;
+    let Ok((variant, _)) = Target::from_json(variant.to_json()) else {
+        panic!("failed to read back the `loongson` variant");
+    };
+    assert_eq!(variant.vendor, "loongson");
+    assert_ne!(variant.vendor, generic.vendor);
+    assert_eq!(variant.llvm_target, generic.llvm_target);
+}
+
+#[test]
+fn netbsd_target() {
+    let target = loongarch64_unknown_netbsd::target();
+    assert_eq!(target.os, "netbsd");
+    assert_eq!(target.arch, "loongarch64");
+    assert_eq!(target.llvm_abiname, "lp64d");
+}
+
+fn loongarch_targets() -> impl Iterator<Item = Target> {
+    TARGETS.iter().filter(|triple| triple.starts_with("loongarch")).map(|triple| {
+        let Some(target) = load_builtin(triple) else {
+            panic!("failed to load built-in target `{triple}`");
+        };
+        target
+    })
+}
+
+// SIMD needs the floating-point unit, and the baseline `generic-la64` core has no
+// vector units at all, so a target enabling LSX or LASX has to pick a better CPU.
+fn check_simd_features(target: &Target) -> Result<(), String> {
+    let triple = &target.llvm_target;
+    let features = target.features.split(',').collect::<Vec<_>>();
+    if !features.iter().any(|feature| matches!(*feature, "+lsx" | "+lasx")) {
+        return Ok(());
+    }
+    if !features.contains(&"+d") {
+        return Err(format!("{triple}: SIMD features without `+d`"));
+    }
+    if target.cpu == "generic-la64" {
+        return Err(format!("{triple}: SIMD features on a non-SIMD CPU"));
+    }
+    Ok(())
+}
+
+fn simd_variant(cpu: &'static str, features: &'static str) -> Target {
+    let mut variant = loongarch64_unknown_linux_gnu::target();
+    variant.cpu = cpu.into();
+    variant.features = features.into();
+    variant.is_builtin = false;
+    let Ok((variant, _)) = Target::from_json(variant.to_json()) else {
+        panic!("failed to read back the `{cpu}` variant with `{features}`");
+    };
+    variant
+}
+
+#[test]
+fn simd_features_are_consistent() {
+    for target in loongarch_targets() {
+        if let Err(err) = check_simd_features(&target) {
+            panic!("{err}");
+        }
+    }
+
+    let lsx = simd_variant("la464", "+d,+lsx");
+    assert_eq!(lsx.features, "+d,+lsx");
+    assert_eq!(check_simd_features(&lsx), Ok(()));
+    assert!(check_simd_features(&simd_variant("la464", "+lasx")).is_err());
+    assert!(check_simd_features(&simd_variant("generic-la64", "+d,+lsx")).is_err());
+}
+
+// Vector registers overlap the FPRs but don't take part in the calling convention,
+// so a SIMD variant still passes floats as the plain hard-float target does.
+#[test]
+fn simd_variant_keeps_float_abi() {
+    let lsx = simd_variant("la464", "+d,+lsx");
+    assert_eq!(lsx.llvm_abiname, "lp64d");
+    let hard = lower_f64_pair(loongarch64_unknown_linux_gnu::target());
+    assert_eq!(lower_f64_pair(lsx), hard);
+}
+
+// `--target` resolves the triple through `expect_builtin`, and a custom target
+// derived from it goes through the JSON form, so both have to end up with the
+// double-precision FPU and no SIMD enabled by default.
+#[test]
+fn linux_gnu_enables_only_double_float() {
+    let triple = TargetTriple::from_triple("loongarch64-unknown-linux-gnu");
+    let builtin = Target::expect_builtin(&triple);
+    let mut custom = builtin.clone();
+    custom.is_builtin = false;
+    let Ok((custom, _)) = Target::from_json(custom.to_json()) else {
+        panic!("failed to read back `{}` as a custom target", builtin.llvm_target);
+    };
+    for target in [builtin, custom] {
+        assert_eq!(target.features, "+d");
+        assert_eq!(target.llvm_abiname, "lp64d");
+        assert_eq!(target.cpu, "generic-la64");
+        assert_eq!(check_simd_features(&target), Ok(()));
+    }
+}
+
+#[test]
+fn none_targets_use_bare_metal_base() {
+    for target in loongarch_targets().filter(|target| target.os == "none") {
+        let triple = &target.llvm_target;
+        assert_eq!(target.panic_strategy, PanicStrategy::Abort, "{triple}");
+        assert_eq!(target.relocation_model, RelocModel::Static, "{triple}");
+        assert_eq!(target.code_model, Some(CodeModel::Medium), "{triple}");
+        assert_eq!(target.max_atomic_width, Some(64), "{triple}");
+        assert_eq!(target.linker.as_deref(), Some("rust-lld"), "{triple}");
+    }
+    assert!(loongarch_targets().any(|target| target.os == "none"));
+}
+
+#[test]
+fn linux_targets_have_rpath() {
+    for target in loongarch_targets().filter(|target| target.os == "linux") {
+        assert!(target.has_rpath, "{}: rpath support disabled", target.llvm_target);
+    }
+}
+
+#[test]
+fn none_softfloat_target_has_no_fpu() {
+    let target = loongarch64_unknown_none_softfloat::target();
+    assert_eq!(target.abi, "softfloat");
+    assert_eq!(target.llvm_abiname, "lp64s");
+    assert!(
+        target.features.split(',').all(|feature| !matches!(feature, "+f" | "+d")),
+        "floating-point features enabled: `{}`",
+        target.features
+    );
+}
+
+// UEFI images are PE32+ files, which the UEFI base gets from the MSVC one.
+#[test]
+fn uefi_target_is_pe() {
+    let target = loongarch64_unknown_uefi::target();
+    assert_eq!(target.arch, "loongarch64");
+    assert_eq!(target.os, "uefi");
+    assert!(target.is_like_windows);
+    assert!(target.is_like_msvc);
+    assert_eq!(target.linker_flavor, LinkerFlavor::Msvc(Lld::Yes));
+    assert_eq!(target.exe_suffix, ".efi");
+    assert!(target.data_layout.starts_with("e-m:w-"), "not COFF mangling: `{}`", target.data_layout);
+}
+
+// `abi::call::loongarch` takes XLEN from the pointer size and FLEN from the ABI
+// name, and those decide whether a two-field struct such as `{ f64, f64 }` or
+// `{ i64, i64 }` is passed in registers as the psABI requires. Make sure every
+// target picks an ABI name that is understood there and backed by its features.
+#[test]
+fn abi_name_matches_register_widths() {
+    for target in loongarch_targets() {
+        let triple = &target.llvm_target;
+        let features = target.features.split(',').collect::<Vec<_>>();
+        let Ok(dl) = target.parse_data_layout() else {
+            panic!("{triple}: failed to parse data layout `{}`", target.data_layout);
+        };
+        assert_eq!(dl.pointer_size.bits(), 64, "{triple}: unexpected XLEN");
+        match &target.llvm_abiname[..] {
+            "lp64d" => assert!(features.contains(&"+d"), "{triple}: `lp64d` without `+d`"),
+            "lp64f" => assert!(
+                features.iter().any(|feature| matches!(*feature, "+f" | "+d")),
+                "{triple}: `lp64f` without `+f`"
+            ),
+            "lp64s" => assert_eq!(target.abi, "softfloat", "{triple}: `lp64s` on a hard-float ABI"),
+            abiname => panic!("{triple}: unknown ABI name `{abiname}`"),
+        }
+    }
+}
+
+// Stands in for `struct { f64, f64 }`, the only type lowered below, so that the
+// calling convention can be computed without a type context.
+#[derive(Clone, Copy)]
+struct F64Pair;
+
+struct AbiCx {
+    target: Target,
+    data_layout: TargetDataLayout,
+}
+
+impl HasDataLayout for AbiCx {
+    fn data_layout(&self) -> &TargetDataLayout {
+        &self.data_layout
+    }
+}
+
+impl HasTargetSpec for AbiCx {
+    fn target_spec(&self) -> &Target {
+        &self.target
+    }
+}
+
+fn f64_layout<C: HasDataLayout>(cx: &C) -> Layout<'static> {
+    let size = Size::from_bits(64);
+    let scalar = Scalar::Initialized { value: abi::F64, valid_range: WrappingRange::full(size) };
+    Layout(Interned::new_unchecked(Box::leak(Box::new(LayoutS::scalar(cx, scalar)))))
+}
+
+impl<C: HasDataLayout> TyAbiInterface<'static, C> for F64Pair {
+    fn ty_and_layout_for_variant(
+        this: TyAndLayout<'static, Self>,
+        _: &C,
+        _: VariantIdx,
+    ) -> TyAndLayout<'static, Self> {
+        this
+    }
+    fn ty_and_layout_field(_: TyAndLayout<'static, Self>, cx: &C, _: usize) -> TyAndLayout<'static, Self> {
+        TyAndLayout { ty: F64Pair, layout: f64_layout(cx) }
+    }
+    fn ty_and_layout_pointee_info_at(
+        _: TyAndLayout<'static, Self>,
+        _: &C,
+        _: Size,
+    ) -> Option<PointeeInfo> {
+        None
+    }
+    fn is_adt(_: TyAndLayout<'static, Self>) -> bool {
+        true
+    }
+    fn is_never(_: TyAndLayout<'static, Self>) -> bool {
+        false
+    }
+    fn is_tuple(_: TyAndLayout<'static, Self>) -> bool {
+        false
+    }
+    fn is_unit(_: TyAndLayout<'static, Self>) -> bool {
+        false
+    }
+}
+
+fn lower_f64_pair(target: Target) -> PassMode {
+    let Ok(data_layout) = target.parse_data_layout() else {
+        panic!("failed to parse data layout `{}`", target.data_layout);
+    };
+    let cx = AbiCx { target, data_layout };
+    let layout = LayoutS {
+        fields: FieldsShape::Arbitrary {
+            offsets: vec![Size::ZERO, Size::from_bytes(8)],
+            memory_index: vec![0, 1],
+        },
+        variants: Variants::Single { index: VariantIdx::from_u32(0) },
+        abi: abi::Abi::Aggregate { sized: true },
+        largest_niche: None,
+        align: AbiAndPrefAlign::new(cx.data_layout.f64_align.abi),
+        size: Size::from_bytes(16),
+    };
+    let layout = TyAndLayout {
+        ty: F64Pair,
+        layout: Layout(Interned::new_unchecked(Box::leak(Box::new(layout)))),
+    };
+    let arg = ArgAbi::new(&cx, layout, |_, _, _| ArgAttributes::new());
+    let mut fn_abi = FnAbi {
+        args: Box::new([arg]),
+        ret: ArgAbi { layout, mode: PassMode::Ignore },
+        c_variadic: false,
+        fixed_count: 1,
+        conv: Conv::C,
+        can_unwind: false,
+    };
+    let Ok(()) = fn_abi.adjust_for_foreign_abi(&cx, Abi::C { unwind: false }) else {
+        panic!("`extern \"C\"` is unsupported on `{}`", cx.target.llvm_target);
+    };
+    fn_abi.args.into_vec().remove(0).mode
+}
+
+// The ABI name alone decides whether floating-point arguments go in FPRs, so
+// the same struct has to be lowered differently for the soft and hard float ABIs.
+#[test]
+fn abi_name_changes_float_pair_lowering() {
+    let soft = lower_f64_pair(loongarch64_unknown_none_softfloat::target());
+    let hard = lower_f64_pair(loongarch64_unknown_linux_gnu::target());
+    assert_eq!(hard, PassMode::Cast(Box::new(CastTarget::pair(Reg::f64(), Reg::f64())), false));
+    let gprs = Uniform { unit: Reg::i64(), total: Size::from_bytes(16) };
+    assert_eq!(soft, PassMode::Cast(Box::new(CastTarget::from(gprs)), false));
+    assert_ne!(soft, hard);
+}
+
+// LLVM falls back to the generic model with only a warning for CPUs it doesn't
+// know, so a misspelled name would silently change the scheduling model. These
+// are the processors `LoongArch.td` defines.
+const KNOWN_CPUS: &[&str] = &["generic-la32", "generic-la64", "la464"];
+
+#[test]
+fn cpus_are_known_to_llvm() {
+    for target in loongarch_targets() {
+        let triple = &target.llvm_target;
+        assert!(KNOWN_CPUS.contains(&&target.cpu[..]), "{triple}: unknown CPU `{}`", target.cpu);
+    }
+}
+
+// Goes through the same path as `--target` for every registered triple and checks
+// what the generated per-target tests don't: that the data layout agrees with
+// `pointer_width`. All mismatches are collected so the failure names each target.
+#[test]
+fn builtin_data_layouts_match_pointer_width() {
+    let triples = TARGETS.iter().filter(|triple| triple.starts_with("loongarch"));
+    let mismatched = triples
+        .filter(|triple| {
+            let target = Target::expect_builtin(&TargetTriple::from_triple(triple));
+            target.parse_data_layout().map_or(true, |dl| {
+                dl.pointer_size.bits() != u64::from(target.pointer_width)
+            })
+        })
+        .collect::<Vec<_>>();
+    assert!(mismatched.is_empty(), "data layout disagrees with `pointer_width`: {mismatched:?}");
+}
+
+#[test]
+fn linux_gnusf_target_has_no_fpu() {
+    let target = loongarch64_unknown_linux_gnusf::target();
+    assert_eq!(target.os, "linux");
+    assert_eq!(target.env, "gnu");
+    assert_eq!(target.llvm_abiname, "lp64s");
+    assert!(
+        target.features.split(',').all(|feature| feature != "+d"),
+        "double precision enabled: `{}`",
+        target.features
+    );
+}
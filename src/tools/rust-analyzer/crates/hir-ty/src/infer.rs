@@ -930,18 +930,26 @@ fn resolve_lang_item(&self, item: LangItem) -> Option<LangItemTarget> {
     }
 
     fn resolve_into_iter_item(&self) -> Option<TypeAliasId> {
-        let ItemContainerId::TraitId(trait_) = self.resolve_lang_item(LangItem::IntoIterIntoIter)?
+        let ItemContainerId::TraitId(trait_) = self
+            .resolve_lang_item(LangItem::IntoIterIntoIter)?
             .as_function()?
-            .lookup(self.db.upcast()).container
-        else { return None };
+            .lookup(self.db.upcast())
+            .container
+        else {
+            return None;
+        };
         self.db.trait_data(trait_).associated_type_by_name(&name![IntoIter])
     }
 
     fn resolve_iterator_item(&self) -> Option<TypeAliasId> {
-        let ItemContainerId::TraitId(trait_) = self.resolve_lang_item(LangItem::IteratorNext)?
+        let ItemContainerId::TraitId(trait_) = self
+            .resolve_lang_item(LangItem::IteratorNext)?
             .as_function()?
-            .lookup(self.db.upcast()).container
-        else { return None };
+            .lookup(self.db.upcast())
+            .container
+        else {
+            return None;
+        };
         self.db.trait_data(trait_).associated_type_by_name(&name![Item])
     }
 
@@ -971,7 +979,9 @@ fn resolve_future_future_output(&self) -> Option<TypeAliasId> {
             .as_function()?
             .lookup(self.db.upcast())
             .container
-        else { return None };
+        else {
+            return None;
+        };
         self.resolve_output_on(trait_)
     }
 
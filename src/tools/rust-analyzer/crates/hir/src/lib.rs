@@ -1853,6 +1853,11 @@ pub fn items(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         db.trait_data(self.id).items.iter().map(|(_name, it)| (*it).into()).collect()
     }
 
+    /// The whole super trait hierarchy, including the trait itself.
+    pub fn all_supertraits(self, db: &dyn HirDatabase) -> Vec<Trait> {
+        all_super_traits(db.upcast(), self.into()).into_iter().map(Trait::from).collect()
+    }
+
     pub fn items_with_supertraits(self, db: &dyn HirDatabase) -> Vec<AssocItem> {
         let traits = all_super_traits(db.upcast(), self.into());
         traits.iter().flat_map(|tr| Trait::from(*tr).items(db)).collect()
@@ -1,12 +1,16 @@
 use hir::HirDisplay;
 use ide_db::syntax_helpers::node_ext::walk_ty;
-use syntax::ast::{self, AstNode, LetStmt, Param};
+use syntax::{
+    ast::{self, AstNode, LetStmt, Param},
+    T,
+};
 
 use crate::{AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: add_explicit_type
 //
-// Specify type for a let binding.
+// Specify type for a let binding. Also available on the `match` keyword of a `match` the
+// binding is initialized with.
 //
 // ```
 // fn main() {
@@ -23,7 +27,7 @@ pub(crate) fn add_explicit_type(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
     let (ascribed_ty, expr, pat) = if let Some(let_stmt) = ctx.find_node_at_offset::<LetStmt>() {
         let cursor_in_range = {
             let eq_range = let_stmt.eq_token()?.text_range();
-            ctx.offset() < eq_range.start()
+            ctx.offset() < eq_range.start() || is_on_match_initializer(ctx, &let_stmt)
         };
         if !cursor_in_range {
             cov_mark::hit!(add_explicit_type_not_applicable_if_cursor_after_equals);
@@ -85,6 +89,16 @@ pub(crate) fn add_explicit_type(acc: &mut Assists, ctx: &AssistContext<'_>) -> O
     )
 }
 
+/// Checks whether the cursor is on the `match` keyword of the initializer of `let_stmt`.
+fn is_on_match_initializer(ctx: &AssistContext<'_>, let_stmt: &LetStmt) -> bool {
+    let Some(match_kw) = ctx.find_token_syntax_at_offset(T![match]) else { return false };
+    let mut initializer = let_stmt.initializer();
+    while let Some(ast::Expr::ParenExpr(paren)) = &initializer {
+        initializer = paren.expr();
+    }
+    matches!(initializer, Some(ast::Expr::MatchExpr(it)) if it.match_token() == Some(match_kw))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -322,6 +336,82 @@ fn f() {
         y = Some(3);
     };
 }
+"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_on_match_initializer() {
+        check_assist(
+            add_explicit_type,
+            r#"
+//- minicore: option
+struct Page<T>(T);
+
+fn load(id: Option<u32>) {
+    let page = (m$0atch id {
+        Some(id) => Some(Page(id)),
+        None => None,
+    });
+}
+"#,
+            r#"
+struct Page<T>(T);
+
+fn load(id: Option<u32>) {
+    let page: Option<Page<u32>> = (match id {
+        Some(id) => Some(Page(id)),
+        None => None,
+    });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_not_applicable_on_nested_match() {
+        check_assist_not_applicable(
+            add_explicit_type,
+            r#"
+fn id(x: u8) -> u8 { x }
+fn pick(flag: bool) {
+    let x = id($0match flag {
+        true => 1,
+        false => 0,
+    });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_not_applicable_on_match_of_closures() {
+        cov_mark::check!(add_explicit_type_not_applicable_if_ty_not_inferred);
+        check_assist_not_applicable(
+            add_explicit_type,
+            r#"
+fn pick(flag: bool) {
+    let f = match$0 flag {
+        true => |x: u8| x,
+        false => loop {},
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_explicit_type_not_applicable_on_match_if_ty_already_specified() {
+        cov_mark::check!(add_explicit_type_not_applicable_if_ty_already_specified);
+        check_assist_not_applicable(
+            add_explicit_type,
+            r#"
+fn pick(flag: bool) {
+    let x: u8 = $0match flag {
+        true => 1,
+        false => 0,
+    };
+}
 "#,
         );
     }
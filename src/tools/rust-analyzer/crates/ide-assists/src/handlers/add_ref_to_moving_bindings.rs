@@ -0,0 +1,230 @@
+use hir::{BindingMode, PathResolution};
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_ref_to_moving_bindings
+//
+// Adds `ref` to the bindings of a match arm that move a non-`Copy` value out of the matched
+// place, when that place is borrowed or used again after the match.
+//
+// ```
+// # //- minicore: copy
+// struct Name;
+// struct Person { name: Name, age: u32 }
+//
+// fn greet(person: Person) -> Person {
+//     match person {
+//         Person { name, age }$0 => hello(name, age),
+//     }
+//     person
+// }
+// ```
+// ->
+// ```
+// struct Name;
+// struct Person { name: Name, age: u32 }
+//
+// fn greet(person: Person) -> Person {
+//     match person {
+//         Person { ref name, age } => hello(name, age),
+//     }
+//     person
+// }
+// ```
+pub(crate) fn add_ref_to_moving_bindings(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let match_expr = arm.syntax().ancestors().find_map(ast::MatchExpr::cast)?;
+    let pat = arm.pat()?;
+
+    let moving = pat
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter(|ident| ident.ref_token().is_none() && ident.mut_token().is_none())
+        .filter(|ident| ctx.sema.binding_mode_of_pat(ident) == Some(BindingMode::Move))
+        .filter(|ident| match ctx.sema.to_def(ident) {
+            Some(local) => !local.ty(ctx.db()).is_copy(ctx.db()),
+            None => false,
+        })
+        .collect::<Vec<_>>();
+
+    let partial_move = !moving.is_empty()
+        && match place_origin(ctx, &match_expr.expr()?) {
+            Some(PlaceOrigin::Borrowed) => true,
+            Some(PlaceOrigin::Local(local)) => used_after(ctx, &match_expr, local),
+            None => false,
+        };
+    if !partial_move {
+        cov_mark::hit!(add_ref_to_moving_bindings_no_partial_move);
+        return None;
+    }
+
+    acc.add(
+        AssistId("add_ref_to_moving_bindings", AssistKind::QuickFix),
+        "Bind by reference to avoid partial move",
+        pat.syntax().text_range(),
+        |builder| {
+            for ident in &moving {
+                builder.insert(ident.syntax().text_range().start(), "ref ");
+            }
+        },
+    )
+}
+
+enum PlaceOrigin {
+    /// The place is (a part of) a local owned by the function.
+    Local(hir::Local),
+    /// The place is reached through a reference or an index, which can't be moved out of.
+    Borrowed,
+}
+
+fn place_origin(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<PlaceOrigin> {
+    match expr {
+        ast::Expr::PathExpr(it) => match ctx.sema.resolve_path(&it.path()?)? {
+            PathResolution::Local(local) => Some(PlaceOrigin::Local(local)),
+            _ => None,
+        },
+        ast::Expr::FieldExpr(it) => {
+            let receiver = it.expr()?;
+            if ctx.sema.type_of_expr(&receiver)?.original.is_reference() {
+                Some(PlaceOrigin::Borrowed)
+            } else {
+                place_origin(ctx, &receiver)
+            }
+        }
+        ast::Expr::ParenExpr(it) => place_origin(ctx, &it.expr()?),
+        ast::Expr::PrefixExpr(it) if it.op_kind() == Some(ast::UnaryOp::Deref) => {
+            Some(PlaceOrigin::Borrowed)
+        }
+        ast::Expr::IndexExpr(_) => Some(PlaceOrigin::Borrowed),
+        _ => None,
+    }
+}
+
+fn used_after(ctx: &AssistContext<'_>, match_expr: &ast::MatchExpr, local: hir::Local) -> bool {
+    let body = match match_expr.syntax().ancestors().find_map(ast::Fn::cast) {
+        Some(func) => func.syntax().clone(),
+        None => return false,
+    };
+    let end = match_expr.syntax().text_range().end();
+    body.descendants()
+        .filter_map(ast::PathExpr::cast)
+        .filter(|it| it.syntax().text_range().start() >= end)
+        .any(|it| {
+            matches!(
+                it.path().and_then(|path| ctx.sema.resolve_path(&path)),
+                Some(PathResolution::Local(it)) if it == local
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_ref_when_struct_is_used_after_match() {
+        check_assist(
+            add_ref_to_moving_bindings,
+            r#"
+//- minicore: copy
+struct Name;
+struct Person { name: Name, nick: Name, age: u32 }
+
+fn greet(person: Person) -> Person {
+    let greeting = match person {
+        Person { name, nick: short, age }$0 => hello(name, short, age),
+    };
+    person
+}
+"#,
+            r#"
+struct Name;
+struct Person { name: Name, nick: Name, age: u32 }
+
+fn greet(person: Person) -> Person {
+    let greeting = match person {
+        Person { ref name, nick: ref short, age } => hello(name, short, age),
+    };
+    person
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_ref_when_matching_through_reference() {
+        check_assist(
+            add_ref_to_moving_bindings,
+            r#"
+//- minicore: copy
+struct Name;
+enum Kind { Named(Name), Anonymous }
+struct Item { kind: Kind }
+
+impl Item {
+    fn name(&self) -> Option<&Name> {
+        match self.kind {
+            Kind::Named(name$0) => Some(&name),
+            Kind::Anonymous => None,
+        }
+    }
+}
+"#,
+            r#"
+struct Name;
+enum Kind { Named(Name), Anonymous }
+struct Item { kind: Kind }
+
+impl Item {
+    fn name(&self) -> Option<&Name> {
+        match self.kind {
+            Kind::Named(ref name) => Some(&name),
+            Kind::Anonymous => None,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_value_is_consumed() {
+        cov_mark::check!(add_ref_to_moving_bindings_no_partial_move);
+        check_assist_not_applicable(
+            add_ref_to_moving_bindings,
+            r#"
+//- minicore: copy
+struct Name;
+struct Person { name: Name, age: u32 }
+
+fn greet(person: Person) -> Name {
+    match person {
+        Person { name, age }$0 => name,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_copy_bindings() {
+        check_assist_not_applicable(
+            add_ref_to_moving_bindings,
+            r#"
+//- minicore: copy
+struct Person { id: u32, age: u32 }
+
+fn greet(person: Person) -> Person {
+    match person {
+        Person { id, age }$0 => id + age,
+    };
+    person
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,169 @@
+use hir::PathResolution;
+use ide_db::defs::Definition;
+use stdx::to_lower_snake_case;
+use syntax::ast::{self, AstNode, HasName};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: add_whole_variant_binding
+//
+// Adds an `@` binding for the whole value to the variant pattern of a match arm whose body
+// still refers to the scrutinee, and uses the binding there instead.
+//
+// ```
+// enum Shape { Circle { radius: u32 }, Square(u32) }
+// fn draw(shape: &Shape) {}
+//
+// fn render(shape: &Shape) {
+//     match shape {
+//         $0Shape::Circle { radius } if *radius > 0 => draw(shape),
+//         _ => {}
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Shape { Circle { radius: u32 }, Square(u32) }
+// fn draw(shape: &Shape) {}
+//
+// fn render(shape: &Shape) {
+//     match shape {
+//         circle @ Shape::Circle { radius } if *radius > 0 => draw(circle),
+//         _ => {}
+//     }
+// }
+// ```
+pub(crate) fn add_whole_variant_binding(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let (pat, path): (ast::Pat, _) = match arm.pat()? {
+        ast::Pat::RecordPat(it) => (it.clone().into(), it.path()?),
+        ast::Pat::TupleStructPat(it) => (it.clone().into(), it.path()?),
+        _ => return None,
+    };
+    if !pat.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let variant = match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) => it,
+        _ => return None,
+    };
+
+    let match_expr = arm.syntax().ancestors().find_map(ast::MatchExpr::cast)?;
+    let ast::Expr::PathExpr(scrutinee) = match_expr.expr()? else { return None };
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee.clone().into())?.original;
+    let Some(PathResolution::Local(local)) = ctx.sema.resolve_path(&scrutinee.path()?) else {
+        return None;
+    };
+
+    // Only offer the binding where it replaces uses of the scrutinee.
+    let arm_range = arm.syntax().text_range();
+    let uses = Definition::Local(local)
+        .usages(&ctx.sema)
+        .all()
+        .iter()
+        .flat_map(|(_, refs)| refs.iter().map(|it| it.range))
+        .filter(|it| arm_range.contains_range(*it))
+        .collect::<Vec<_>>();
+    if uses.is_empty() {
+        cov_mark::hit!(add_whole_variant_binding_unused);
+        return None;
+    }
+
+    // Binding the whole value by move only works if the bindings inside it copy.
+    let bindings = pat.syntax().descendants().filter_map(ast::IdentPat::cast).collect::<Vec<_>>();
+    if !scrutinee_ty.is_reference() {
+        let all_copy = bindings.iter().all(|it| {
+            ctx.sema
+                .type_of_pat(&it.clone().into())
+                .map_or(false, |ty| ty.original.is_copy(ctx.db()))
+        });
+        if !all_copy {
+            return None;
+        }
+    }
+    let name = to_lower_snake_case(&variant.name(ctx.db()).to_smol_str());
+    if bindings.iter().any(|it| it.name().map_or(false, |it| it.text() == name.as_str())) {
+        return None;
+    }
+
+    acc.add(
+        AssistId("add_whole_variant_binding", AssistKind::RefactorRewrite),
+        format!("Bind the whole value as `{name}`"),
+        pat.syntax().text_range(),
+        |builder| {
+            builder.insert(pat.syntax().text_range().start(), format!("{name} @ "));
+            for range in uses {
+                builder.replace(range, name.clone());
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn bind_record_variant() {
+        check_assist(
+            add_whole_variant_binding,
+            r#"
+//- minicore: copy
+#[derive(Clone, Copy)]
+enum Event { KeyDown { code: u32, repeat: bool }, Quit }
+fn log(event: Event) {}
+
+fn handle(event: Event) -> u32 {
+    match event {
+        Event::KeyDown { code, .. }$0 if code > 0 => {
+            log(event);
+            code
+        }
+        Event::KeyDown { .. } | Event::Quit => {
+            log(event);
+            0
+        }
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+enum Event { KeyDown { code: u32, repeat: bool }, Quit }
+fn log(event: Event) {}
+
+fn handle(event: Event) -> u32 {
+    match event {
+        key_down @ Event::KeyDown { code, .. } if code > 0 => {
+            log(key_down);
+            code
+        }
+        Event::KeyDown { .. } | Event::Quit => {
+            log(event);
+            0
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_use_of_scrutinee() {
+        cov_mark::check!(add_whole_variant_binding_unused);
+        check_assist_not_applicable(
+            add_whole_variant_binding,
+            r#"
+enum Shape { Circle { radius: u32 }, Square(u32) }
+
+fn size(shape: &Shape) -> u32 {
+    match shape {
+        Shape::Square($0side) => *side,
+        Shape::Circle { radius } => *radius,
+    }
+}
+"#,
+        );
+    }
+}
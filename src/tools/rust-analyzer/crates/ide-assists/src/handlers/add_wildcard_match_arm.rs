@@ -0,0 +1,176 @@
+use std::iter;
+
+use hir::AnyDiagnostic;
+use syntax::{
+    ast::{self, make, AstNode},
+    AstPtr,
+};
+
+use crate::{
+    utils::{render_snippet, Cursor},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: add_wildcard_match_arm
+//
+// Makes a non-exhaustive `match` compile by adding a wildcard arm with a placeholder body.
+//
+// ```
+// enum Action { Move, Jump, Stop }
+//
+// fn handle(action: Action) -> u32 {
+//     match action {
+//         Action::Move => 1,$0
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Action { Move, Jump, Stop }
+//
+// fn handle(action: Action) -> u32 {
+//     match action {
+//         Action::Move => 1,
+//         $0_ => unimplemented!(),
+//     }
+// }
+// ```
+pub(crate) fn add_wildcard_match_arm(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let match_arm_list = match_expr.match_arm_list()?;
+    let scrutinee = match_expr.expr()?;
+
+    let body = match_expr.syntax().ancestors().find_map(|node| {
+        let def: hir::DefWithBody = match ast::Item::cast(node)? {
+            ast::Item::Fn(it) => ctx.sema.to_def(&it)?.into(),
+            ast::Item::Const(it) => ctx.sema.to_def(&it)?.into(),
+            ast::Item::Static(it) => ctx.sema.to_def(&it)?.into(),
+            _ => return None,
+        };
+        Some(def)
+    })?;
+    // The match checker reports the missing arms on the scrutinee.
+    let mut diagnostics = Vec::new();
+    body.diagnostics(ctx.db(), &mut diagnostics);
+    let ptr = AstPtr::new(&scrutinee);
+    let is_non_exhaustive = diagnostics.iter().any(|diag| match diag {
+        AnyDiagnostic::MissingMatchArms(it) => {
+            it.file == ctx.file_id().into() && it.match_expr == ptr
+        }
+        _ => false,
+    });
+    if !is_non_exhaustive {
+        cov_mark::hit!(add_wildcard_match_arm_exhaustive);
+        return None;
+    }
+
+    let is_unit = ctx
+        .sema
+        .type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))
+        .map_or(false, |it| it.original.is_unit());
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("add_wildcard_match_arm", AssistKind::QuickFix),
+        "Add wildcard arm",
+        target,
+        |builder| {
+            let placeholder =
+                if is_unit { make::expr_empty_block() } else { make::ext::expr_unimplemented() };
+            let arm = make::match_arm(iter::once(make::wildcard_pat().into()), None, placeholder)
+                .clone_for_update();
+            let new_match_arm_list = match_arm_list.clone_for_update();
+            new_match_arm_list.add_arm(arm.clone());
+
+            let range = match_arm_list.syntax().text_range();
+            match ctx.config.snippet_cap {
+                Some(cap) => {
+                    let snippet = render_snippet(
+                        cap,
+                        new_match_arm_list.syntax(),
+                        Cursor::Before(arm.syntax()),
+                    );
+                    builder.replace_snippet(cap, range, snippet);
+                }
+                None => builder.replace(range, new_match_arm_list.to_string()),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn add_wildcard_to_non_exhaustive_match() {
+        check_assist(
+            add_wildcard_match_arm,
+            r#"
+//- minicore: option
+fn describe(n: Option<bool>) -> &'static str {
+    match n$0 {
+        Some(true) => "yes",
+        None => "none",
+    }
+}
+"#,
+            r#"
+fn describe(n: Option<bool>) -> &'static str {
+    match n {
+        Some(true) => "yes",
+        None => "none",
+        $0_ => unimplemented!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn add_empty_block_for_unit_match() {
+        check_assist(
+            add_wildcard_match_arm,
+            r#"
+enum Key { Up, Down }
+
+fn press(key: Key) {
+    let mut y = 0;
+    match$0 key {
+        Key::Up => y += 1,
+    }
+}
+"#,
+            r#"
+enum Key { Up, Down }
+
+fn press(key: Key) {
+    let mut y = 0;
+    match key {
+        Key::Up => y += 1,
+        $0_ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_exhaustive_match() {
+        cov_mark::check!(add_wildcard_match_arm_exhaustive);
+        check_assist_not_applicable(
+            add_wildcard_match_arm,
+            r#"
+enum Key { Up, Down }
+
+fn press(key: Key) -> i32 {
+    match$0 key {
+        Key::Up => 1,
+        Key::Down => -1,
+    }
+}
+"#,
+        );
+    }
+}
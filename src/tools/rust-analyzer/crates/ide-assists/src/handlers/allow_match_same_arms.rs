@@ -0,0 +1,133 @@
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasAttrs};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: allow_match_same_arms
+//
+// Marks a match arm whose body is identical to the one of an earlier arm with
+// `#[allow(clippy::match_same_arms)]`, for arms that are meant to stay separate rather than
+// being merged.
+//
+// ```
+// enum Key { Up, Down }
+//
+// fn step(key: Key) -> i32 {
+//     match key {
+//         Key::Up => 1,
+//         $0Key::Down => 1,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Key { Up, Down }
+//
+// fn step(key: Key) -> i32 {
+//     match key {
+//         Key::Up => 1,
+//         #[allow(clippy::match_same_arms)]
+//         Key::Down => 1,
+//     }
+// }
+// ```
+pub(crate) fn allow_match_same_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let body = arm.expr()?;
+    let arm_list = arm.syntax().parent().and_then(ast::MatchArmList::cast)?;
+    // The lint points at the later one of a pair of arms, so that is where it is allowed.
+    let same_as_earlier = arm_list
+        .arms()
+        .take_while(|it| it.syntax() != arm.syntax())
+        .any(|it| it.expr().map_or(false, |it| it.syntax().text() == body.syntax().text()));
+    if !same_as_earlier {
+        return None;
+    }
+    if arm.attrs().any(|attr| attr.syntax().text().to_string().contains("match_same_arms")) {
+        cov_mark::hit!(allow_match_same_arms_already_allowed);
+        return None;
+    }
+
+    acc.add(
+        AssistId("allow_match_same_arms", AssistKind::None),
+        "Allow identical match arms",
+        arm.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(arm.syntax());
+            builder.insert(
+                arm.syntax().text_range().start(),
+                format!("#[allow(clippy::match_same_arms)]\n{indent}"),
+            );
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn allow_identical_arm() {
+        check_assist(
+            allow_match_same_arms,
+            r#"
+enum Op { Add, Sub, Mul }
+
+fn cost(op: Op) -> u32 {
+    match op {
+        Op::Add => { 1 }
+        Op::Sub => 2,
+        #[cfg(test)]
+        Op::Mul =>$0 { 1 }
+    }
+}
+"#,
+            r#"
+enum Op { Add, Sub, Mul }
+
+fn cost(op: Op) -> u32 {
+    match op {
+        Op::Add => { 1 }
+        Op::Sub => 2,
+        #[allow(clippy::match_same_arms)]
+        #[cfg(test)]
+        Op::Mul => { 1 }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_first_of_identical_arms() {
+        check_assist_not_applicable(
+            allow_match_same_arms,
+            r#"
+fn cost(n: u8) -> u32 {
+    match n {
+        $00 => 1,
+        _ => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_already_allowed() {
+        cov_mark::check!(allow_match_same_arms_already_allowed);
+        check_assist_not_applicable(
+            allow_match_same_arms,
+            r#"
+fn cost(n: u8) -> u32 {
+    match n {
+        0 => 1,
+        #[allow(clippy::match_same_arms)]
+        $0_ => 1,
+    }
+}
+"#,
+        );
+    }
+}
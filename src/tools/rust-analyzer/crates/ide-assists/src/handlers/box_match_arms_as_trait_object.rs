@@ -0,0 +1,258 @@
+use hir::{Access, AssocItem, ScopeDef};
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, AstNode, HasGenericParams},
+    T,
+};
+
+use crate::{utils::arm_value, AssistContext, AssistId, AssistKind, Assists, GroupLabel};
+
+// Assist: box_match_arms_as_trait_object
+//
+// Boxes the values of match arms evaluating to different types into trait objects of a trait
+// all of them implement, so that the arms have the same type.
+//
+// ```
+// trait Shape {}
+// struct Circle;
+// struct Square;
+// impl Shape for Circle {}
+// impl Shape for Square {}
+//
+// fn shape(round: bool) {
+//     let s = $0match round {
+//         true => Circle,
+//         false => Square,
+//     };
+// }
+// ```
+// ->
+// ```
+// trait Shape {}
+// struct Circle;
+// struct Square;
+// impl Shape for Circle {}
+// impl Shape for Square {}
+//
+// fn shape(round: bool) {
+//     let s = match round {
+//         true => Box::new(Circle) as Box<dyn Shape>,
+//         false => Box::new(Square) as Box<dyn Shape>,
+//     };
+// }
+// ```
+pub(crate) fn box_match_arms_as_trait_object(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let mut values = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let value = arm_value(arm.expr()?)?;
+        let ty = ctx.sema.type_of_expr(&value)?.original;
+        if ty.is_unknown() {
+            return None;
+        }
+        // Diverging arms coerce to the boxed type as they are.
+        if !ty.is_never() {
+            values.push((value, ty));
+        }
+    }
+    let (_, first_ty) = values.first()?;
+    if values.iter().all(|(_, ty)| ty == first_ty) {
+        return None;
+    }
+
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let mut candidates = Vec::new();
+    scope.process_all_names(&mut |name, def| {
+        let ScopeDef::ModuleDef(hir::ModuleDef::Trait(trait_)) = def else { return };
+        if trait_.is_auto(ctx.db()) || trait_.type_or_const_param_count(ctx.db(), false) != 0 {
+            return;
+        }
+        if values.iter().all(|(_, ty)| ty.impls_trait(ctx.db(), trait_, &[])) {
+            candidates.push((name.to_string(), trait_));
+        }
+    });
+    let sized = FamousDefs(&ctx.sema, scope.krate()).core_marker_Sized();
+    let mut traits: Vec<_> = candidates
+        .into_iter()
+        .filter(|(_, trait_)| is_object_safe(ctx, *trait_, sized))
+        .map(|(name, _)| name)
+        .collect();
+    if traits.is_empty() {
+        cov_mark::hit!(box_match_arms_as_trait_object_no_common_trait);
+        return None;
+    }
+    traits.sort();
+
+    let target = match_expr.syntax().text_range();
+    let group = GroupLabel("Box arms as trait objects".to_owned());
+    for trait_ in traits {
+        acc.add_group(
+            &group,
+            AssistId("box_match_arms_as_trait_object", AssistKind::RefactorRewrite),
+            format!("Box arms as `dyn {trait_}`"),
+            target,
+            |builder| {
+                for (value, _) in &values {
+                    let range = value.syntax().text_range();
+                    builder.replace(range, format!("Box::new({value}) as Box<dyn {trait_}>"));
+                }
+            },
+        );
+    }
+    Some(())
+}
+
+/// Whether `dyn Trait` can be written for the trait: it is not `Sized`, has no associated types
+/// or consts, and its methods take `self` by reference, are not generic and do not mention `Self`
+/// otherwise.
+fn is_object_safe(ctx: &AssistContext<'_>, trait_: hir::Trait, sized: Option<hir::Trait>) -> bool {
+    let db = ctx.db();
+    if sized.map_or(false, |sized| trait_.all_supertraits(db).contains(&sized)) {
+        return false;
+    }
+    trait_.items_with_supertraits(db).into_iter().all(|item| {
+        let AssocItem::Function(func) = item else { return false };
+        let by_ref = func.self_param(db).map_or(false, |it| it.access(db) != Access::Owned);
+        let Some(source) = ctx.sema.source(func).map(|it| it.value) else { return false };
+        let is_generic = source
+            .generic_param_list()
+            .map_or(false, |it| it.type_or_const_params().next().is_some());
+        let mentions_self = source
+            .param_list()
+            .into_iter()
+            .flat_map(|it| it.params())
+            .filter_map(|it| it.ty())
+            .chain(source.ret_type().and_then(|it| it.ty()))
+            .any(|ty| ty.syntax().descendants_with_tokens().any(|it| it.kind() == T![Self]));
+        by_ref && !is_generic && !mentions_self
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn box_two_impls() {
+        check_assist(
+            box_match_arms_as_trait_object,
+            r#"
+trait Render { fn render(&self); }
+struct Text(u8);
+struct Image;
+impl Render for Text { fn render(&self) {} }
+impl Render for Image { fn render(&self) {} }
+
+fn widget(kind: u8) {
+    let w = match$0 kind {
+        0 => Image,
+        n => {
+            let len = n * 2;
+            Text(len)
+        }
+    };
+}
+"#,
+            r#"
+trait Render { fn render(&self); }
+struct Text(u8);
+struct Image;
+impl Render for Text { fn render(&self) {} }
+impl Render for Image { fn render(&self) {} }
+
+fn widget(kind: u8) {
+    let w = match kind {
+        0 => Box::new(Image) as Box<dyn Render>,
+        n => {
+            let len = n * 2;
+            Box::new(Text(len)) as Box<dyn Render>
+        }
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_common_trait() {
+        cov_mark::check!(box_match_arms_as_trait_object_no_common_trait);
+        check_assist_not_applicable(
+            box_match_arms_as_trait_object,
+            r#"
+trait Render {}
+struct Text;
+struct Image;
+impl Render for Text {}
+
+fn widget(text: bool) {
+    let w = match$0 text {
+        true => Text,
+        false => Image,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_traits_that_are_not_object_safe() {
+        cov_mark::check!(box_match_arms_as_trait_object_no_common_trait);
+        check_assist_not_applicable(
+            box_match_arms_as_trait_object,
+            r#"
+//- minicore: copy, default
+trait Named { const NAME: &'static str; }
+trait Merge { fn merge(&self, other: &Self); }
+trait Visit { fn visit<T>(&self, t: T); }
+trait Consume { fn consume(self); }
+struct Text;
+struct Image;
+impl Clone for Text { fn clone(&self) -> Self { Text } }
+impl Clone for Image { fn clone(&self) -> Self { Image } }
+impl Copy for Text {}
+impl Copy for Image {}
+impl Default for Text { fn default() -> Self { Text } }
+impl Default for Image { fn default() -> Self { Image } }
+impl Named for Text { const NAME: &'static str = "text"; }
+impl Named for Image { const NAME: &'static str = "image"; }
+impl Merge for Text { fn merge(&self, _: &Self) {} }
+impl Merge for Image { fn merge(&self, _: &Self) {} }
+impl Visit for Text { fn visit<T>(&self, _: T) {} }
+impl Visit for Image { fn visit<T>(&self, _: T) {} }
+impl Consume for Text { fn consume(self) {} }
+impl Consume for Image { fn consume(self) {} }
+
+fn widget(text: bool) {
+    let w = match$0 text {
+        true => Text,
+        false => Image,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_same_types() {
+        check_assist_not_applicable(
+            box_match_arms_as_trait_object,
+            r#"
+trait Render {}
+struct Text(u8);
+impl Render for Text {}
+
+fn widget(text: bool) {
+    let w = match$0 text {
+        true => Text(0),
+        false => Text(1),
+    };
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode,
+    },
+    SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: collapse_uniform_match
+//
+// Replaces a match whose arms all evaluate the same expression with that expression.
+//
+// ```
+// enum Mode { Read, Write }
+// struct File { mode: Mode, name: String }
+//
+// impl File {
+//     fn name(&self) -> &String {
+//         $0match self.mode {
+//             Mode::Read => &self.name,
+//             Mode::Write => &self.name,
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Mode { Read, Write }
+// struct File { mode: Mode, name: String }
+//
+// impl File {
+//     fn name(&self) -> &String {
+//         &self.name
+//     }
+// }
+// ```
+pub(crate) fn collapse_uniform_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (first, rest) = arms.split_first()?;
+    let body = first.expr()?;
+    for arm in &arms {
+        if arm.guard().is_some() || binds_used_names(ctx, arm)? {
+            return None;
+        }
+    }
+    for arm in rest {
+        if !same_tokens(arm.expr()?.syntax(), body.syntax()) {
+            return None;
+        }
+    }
+    // Dropping the match drops its scrutinee as well.
+    if !is_pure(&scrutinee) {
+        cov_mark::hit!(collapse_uniform_match_impure_scrutinee);
+        return None;
+    }
+
+    acc.add(
+        AssistId("collapse_uniform_match", AssistKind::RefactorRewrite),
+        "Replace match with its common arm",
+        match_expr.syntax().text_range(),
+        |builder| {
+            builder.replace(
+                match_expr.syntax().text_range(),
+                body.dedent(IndentLevel(1)).syntax().text().to_string(),
+            );
+        },
+    )
+}
+
+/// Checks whether the body of `arm` refers to a binding from its own pattern, which would
+/// point somewhere else once the pattern is gone.
+fn binds_used_names(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<bool> {
+    let pat = arm.pat()?;
+    let body = arm.expr()?;
+    let bindings = pat
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| ctx.sema.to_def(&it))
+        .collect::<Vec<_>>();
+    let used = body.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if bindings.contains(&it))
+    });
+    Some(used)
+}
+
+fn same_tokens(a: &SyntaxNode, b: &SyntaxNode) -> bool {
+    let tokens = |node: &SyntaxNode| {
+        node.descendants_with_tokens()
+            .filter_map(|it| it.into_token())
+            .filter(|it| !it.kind().is_trivia())
+            .map(|it| (it.kind(), it.text().to_string()))
+            .collect::<Vec<_>>()
+    };
+    tokens(a) == tokens(b)
+}
+
+/// Whether evaluating `expr` can be skipped without changing behaviour.
+fn is_pure(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::PathExpr(_) | ast::Expr::Literal(_) => true,
+        ast::Expr::FieldExpr(it) => it.expr().map_or(false, |it| is_pure(&it)),
+        ast::Expr::RefExpr(it) => it.expr().map_or(false, |it| is_pure(&it)),
+        ast::Expr::ParenExpr(it) => it.expr().map_or(false, |it| is_pure(&it)),
+        ast::Expr::TupleExpr(it) => it.fields().all(|it| is_pure(&it)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn collapse_arms_with_same_block() {
+        check_assist(
+            collapse_uniform_match,
+            r#"
+struct Point { x: i32, y: i32 }
+
+fn norm(p: &Point, o: Option<u8>) -> i32 {
+    let n = match$0 (o, &p.x) {
+        (Some(_), _) => {
+            let x = p.x.abs();
+            x + p.y.abs()
+        }
+        (None, x) => {
+            let x = p.x.abs();
+            x   +   p.y.abs()
+        }
+    };
+    n
+}
+"#,
+            r#"
+struct Point { x: i32, y: i32 }
+
+fn norm(p: &Point, o: Option<u8>) -> i32 {
+    let n = {
+        let x = p.x.abs();
+        x + p.y.abs()
+    };
+    n
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_impure_scrutinee() {
+        cov_mark::check!(collapse_uniform_match_impure_scrutinee);
+        check_assist_not_applicable(
+            collapse_uniform_match,
+            r#"
+fn next() -> Option<u8> { None }
+
+fn run(v: &u8) -> &u8 {
+    match$0 next() {
+        Some(_) => v,
+        None => v,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_body_uses_bindings() {
+        check_assist_not_applicable(
+            collapse_uniform_match,
+            r#"
+fn run(o: Option<u8>, v: u8) -> u8 {
+    match$0 o {
+        Some(v) => v,
+        None => v,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_different_bodies() {
+        check_assist_not_applicable(
+            collapse_uniform_match,
+            r#"
+fn run(o: Option<u8>, a: u8, b: u8) -> u8 {
+    match$0 o {
+        Some(_) => a,
+        None => b,
+    }
+}
+"#,
+        );
+    }
+}
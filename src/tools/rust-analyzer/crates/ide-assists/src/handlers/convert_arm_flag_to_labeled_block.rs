@@ -0,0 +1,451 @@
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    SyntaxNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_arm_flag_to_labeled_block
+//
+// Replaces the flag a match arm uses to skip the rest of its body once the result is known
+// with a labeled block, which is left with the result right away.
+//
+// ```
+// fn price(item: Option<u32>) -> u32 {
+//     match item {
+//         Some(n) => {$0
+//             let mut price = 0;
+//             let mut done = false;
+//             if n == 0 {
+//                 price = 1;
+//                 done = true;
+//             }
+//             if !done {
+//                 let base = n * 2;
+//                 if base > 100 {
+//                     price = 100;
+//                     done = true;
+//                 }
+//                 if !done {
+//                     price = base + 1;
+//                 }
+//             }
+//             price
+//         }
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn price(item: Option<u32>) -> u32 {
+//     match item {
+//         Some(n) => 'arm: {
+//             if n == 0 {
+//                 break 'arm 1;
+//             }
+//             let base = n * 2;
+//             if base > 100 {
+//                 break 'arm 100;
+//             }
+//             base + 1
+//         }
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn convert_arm_flag_to_labeled_block(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let ast::Expr::BlockExpr(body) = arm.expr()? else { return None };
+    if body.modifier().is_some() || body.label().is_some() {
+        return None;
+    }
+    let stmt_list = body.stmt_list()?;
+    let flags = flags(&stmt_list)?;
+    if body.syntax().descendants_with_tokens().any(|it| it.to_string() == "'arm") {
+        return None;
+    }
+
+    // Once the flag may be set, everything that follows has to be skipped when it is.
+    let nodes = stmt_list.statements().skip(2).map(|it| it.syntax().clone()).collect::<Vec<_>>();
+    let Some((mut items, true)) = parse_list(nodes, &flags, true) else {
+        cov_mark::hit!(convert_arm_flag_to_labeled_block_multiple_exits);
+        return None;
+    };
+
+    let target = body.syntax().text_range();
+    acc.add(
+        AssistId("convert_arm_flag_to_labeled_block", AssistKind::RefactorRewrite),
+        "Convert flag to labeled block",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(body.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let tail = pop_tail(&mut items).unwrap_or_else(|| flags.init.clone());
+            let mut buf = String::from("'arm: {");
+            render(&items, inner, &mut buf);
+            format_to!(buf, "\n{inner}{}\n{indent}}}", reindent(tail.syntax(), inner));
+            builder.replace(target, buf);
+        },
+    )
+}
+
+/// The result of an arm and the flag telling whether it is final.
+struct Flags {
+    value: String,
+    init: ast::Expr,
+    done: String,
+}
+
+enum Item {
+    Other(SyntaxNode),
+    /// Leaves the arm with the value.
+    Exit(ast::Expr),
+    /// The contents of an `if !done { .. }` block.
+    Flatten(Vec<Item>),
+    If(IfItem),
+}
+
+struct IfItem {
+    condition: ast::Expr,
+    then: Vec<Item>,
+    els: Option<Box<Else>>,
+}
+
+enum Else {
+    Block(Vec<Item>),
+    If(IfItem),
+}
+
+/// Matches a body starting with `let mut value = init; let mut done = false;` and ending with
+/// `value`, in either order of the declarations.
+fn flags(stmt_list: &ast::StmtList) -> Option<Flags> {
+    let ast::Expr::PathExpr(tail) = stmt_list.tail_expr()? else { return None };
+    let value = tail.path()?.as_single_name_ref()?.text().to_string();
+    let mut decls = stmt_list.statements().take(2).map(|stmt| {
+        let ast::Stmt::LetStmt(let_stmt) = stmt else { return None };
+        let ast::Pat::IdentPat(pat) = let_stmt.pat()? else { return None };
+        pat.mut_token()?;
+        Some((pat.name()?.text().to_string(), let_stmt.initializer()?))
+    });
+    let (first, second) = (decls.next()??, decls.next()??);
+    let ((_, init), (done, flag)) =
+        if first.0 == value { (first, second) } else { (second, first) };
+    if !is_literal(&flag, "false") || done == value {
+        return None;
+    }
+    let flags = Flags { value, init, done };
+    if mentions(flags.init.syntax(), &flags) {
+        return None;
+    }
+    Some(flags)
+}
+
+/// Parses the statements of a block, and returns whether they may set the flag. In the `last`
+/// block before the end of the arm, an assignment without setting the flag is final as well.
+fn parse_list(mut nodes: Vec<SyntaxNode>, flags: &Flags, last: bool) -> Option<(Vec<Item>, bool)> {
+    let exit = match nodes.as_slice() {
+        [.., a, b] if sets_done(b, flags) => assigned(a, &flags.value).map(|it| (it, 2)),
+        [.., a, b] if sets_done(a, flags) => assigned(b, &flags.value).map(|it| (it, 2)),
+        [.., a] if last => assigned(a, &flags.value).map(|it| (it, 1)),
+        _ => None,
+    };
+    let exit = match exit {
+        Some((value, _)) if mentions(value.syntax(), flags) => return None,
+        Some((value, len)) => {
+            nodes.truncate(nodes.len() - len);
+            Some(value)
+        }
+        None => None,
+    };
+
+    let mut items = Vec::new();
+    let mut may_be_done = false;
+    let count = nodes.len();
+    for (idx, node) in nodes.into_iter().enumerate() {
+        let last = last && exit.is_none() && idx + 1 == count;
+        let (item, sets) = match not_done_body(&node, flags) {
+            Some(body) => {
+                let (items, sets) = parse_list(nodes_of(&body), flags, last)?;
+                (Item::Flatten(items), sets)
+            }
+            None if may_be_done => return None,
+            None => match if_expr_of(&node) {
+                Some(if_expr) if mentions(&node, flags) => {
+                    let (item, sets) = parse_if(&if_expr, flags)?;
+                    (Item::If(item), sets)
+                }
+                _ if mentions(&node, flags) => return None,
+                _ => (Item::Other(node), false),
+            },
+        };
+        may_be_done |= sets;
+        items.push(item);
+    }
+    if let Some(value) = exit {
+        items.push(Item::Exit(value));
+        may_be_done = true;
+    }
+    Some((items, may_be_done))
+}
+
+fn parse_if(if_expr: &ast::IfExpr, flags: &Flags) -> Option<(IfItem, bool)> {
+    let condition = if_expr.condition()?;
+    if mentions(condition.syntax(), flags) {
+        return None;
+    }
+    let (then, mut sets) =
+        parse_list(nodes_of(&if_expr.then_branch()?.stmt_list()?), flags, false)?;
+    let els = match if_expr.else_branch() {
+        Some(ast::ElseBranch::Block(block)) => {
+            let (items, else_sets) = parse_list(nodes_of(&block.stmt_list()?), flags, false)?;
+            sets |= else_sets;
+            Some(Box::new(Else::Block(items)))
+        }
+        Some(ast::ElseBranch::IfExpr(if_expr)) => {
+            let (item, else_sets) = parse_if(&if_expr, flags)?;
+            sets |= else_sets;
+            Some(Box::new(Else::If(item)))
+        }
+        None => None,
+    };
+    Some((IfItem { condition, then, els }, sets))
+}
+
+fn nodes_of(stmt_list: &ast::StmtList) -> Vec<SyntaxNode> {
+    let nodes = stmt_list.statements().map(|it| it.syntax().clone());
+    nodes.chain(stmt_list.tail_expr().map(|it| it.syntax().clone())).collect()
+}
+
+fn if_expr_of(node: &SyntaxNode) -> Option<ast::IfExpr> {
+    match ast::ExprStmt::cast(node.clone()) {
+        Some(stmt) => match stmt.expr()? {
+            ast::Expr::IfExpr(it) => Some(it),
+            _ => None,
+        },
+        None => ast::IfExpr::cast(node.clone()),
+    }
+}
+
+/// Returns the block of `if !done { .. }`.
+fn not_done_body(node: &SyntaxNode, flags: &Flags) -> Option<ast::StmtList> {
+    let if_expr = if_expr_of(node)?;
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let ast::Expr::PrefixExpr(not) = if_expr.condition()? else { return None };
+    if not.op_kind()? != ast::UnaryOp::Not || !is_name(&not.expr()?, &flags.done) {
+        return None;
+    }
+    if_expr.then_branch()?.stmt_list()
+}
+
+/// Returns the value of `name = value`.
+fn assigned(node: &SyntaxNode, name: &str) -> Option<ast::Expr> {
+    let expr = match ast::ExprStmt::cast(node.clone()) {
+        Some(stmt) => stmt.expr()?,
+        None => ast::Expr::cast(node.clone())?,
+    };
+    let ast::Expr::BinExpr(assign) = expr else { return None };
+    if assign.op_kind()? != (ast::BinaryOp::Assignment { op: None })
+        || !is_name(&assign.lhs()?, name)
+    {
+        return None;
+    }
+    assign.rhs()
+}
+
+fn sets_done(node: &SyntaxNode, flags: &Flags) -> bool {
+    assigned(node, &flags.done).map_or(false, |it| is_literal(&it, "true"))
+}
+
+fn is_name(expr: &ast::Expr, name: &str) -> bool {
+    let ast::Expr::PathExpr(path) = expr else { return false };
+    path.path().and_then(|it| it.as_single_name_ref()).map_or(false, |it| it.text() == name)
+}
+
+fn is_literal(expr: &ast::Expr, text: &str) -> bool {
+    matches!(expr, ast::Expr::Literal(it) if it.syntax().text() == text)
+}
+
+fn mentions(node: &SyntaxNode, flags: &Flags) -> bool {
+    node.descendants()
+        .filter_map(ast::NameRef::cast)
+        .any(|it| it.text() == flags.value.as_str() || it.text() == flags.done.as_str())
+}
+
+/// Takes the value the arm falls through to at its end.
+fn pop_tail(items: &mut Vec<Item>) -> Option<ast::Expr> {
+    match items.last_mut()? {
+        Item::Exit(_) => match items.pop() {
+            Some(Item::Exit(value)) => Some(value),
+            _ => None,
+        },
+        Item::Flatten(inner) => pop_tail(inner),
+        _ => None,
+    }
+}
+
+fn render(items: &[Item], indent: IndentLevel, buf: &mut String) {
+    for item in items {
+        match item {
+            Item::Other(node) => format_to!(buf, "\n{indent}{}", reindent(node, indent)),
+            Item::Exit(value) => {
+                format_to!(buf, "\n{indent}break 'arm {};", reindent(value.syntax(), indent))
+            }
+            Item::Flatten(inner) => render(inner, indent, buf),
+            Item::If(it) => {
+                format_to!(buf, "\n{indent}");
+                render_if(it, indent, buf);
+            }
+        }
+    }
+}
+
+fn render_if(item: &IfItem, indent: IndentLevel, buf: &mut String) {
+    let inner = IndentLevel(indent.0 + 1);
+    format_to!(buf, "if {} {{", item.condition);
+    render(&item.then, inner, buf);
+    format_to!(buf, "\n{indent}}}");
+    match item.els.as_deref() {
+        Some(Else::Block(items)) => {
+            buf.push_str(" else {");
+            render(items, inner, buf);
+            format_to!(buf, "\n{indent}}}");
+        }
+        Some(Else::If(it)) => {
+            buf.push_str(" else ");
+            render_if(it, indent, buf);
+        }
+        None => {}
+    }
+}
+
+/// Moves the text of `node` to `indent`, which is never deeper than where it was.
+fn reindent(node: &SyntaxNode, indent: IndentLevel) -> String {
+    let mut text = node.to_string();
+    for _ in indent.0..IndentLevel::from_node(node).0 {
+        text = text.replace(&format!("\n{}", IndentLevel(1)), "\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_flag_in_arm() {
+        check_assist(
+            convert_arm_flag_to_labeled_block,
+            r#"
+fn check(input: Result<&str, ()>) -> Option<u8> {
+    match input {
+        Ok(s) => {
+            let mut done = false;
+            let mut res$0ult = None;
+            if s.is_empty() {
+                done = true;
+                result = Some(0);
+            } else if s.len() > 8 {
+                result = Some(8);
+                done = true
+            }
+            if !done {
+                let first = s.len();
+                if first == 1 {
+                    result = Some(
+                        1,
+                    );
+                    done = true;
+                }
+            }
+            result
+        }
+        Err(()) => None,
+    }
+}
+"#,
+            r#"
+fn check(input: Result<&str, ()>) -> Option<u8> {
+    match input {
+        Ok(s) => 'arm: {
+            if s.is_empty() {
+                break 'arm Some(0);
+            } else if s.len() > 8 {
+                break 'arm Some(8);
+            }
+            let first = s.len();
+            if first == 1 {
+                break 'arm Some(
+                    1,
+                );
+            }
+            None
+        }
+        Err(()) => None,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_flag_is_set_in_loop() {
+        cov_mark::check!(convert_arm_flag_to_labeled_block_multiple_exits);
+        check_assist_not_applicable(
+            convert_arm_flag_to_labeled_block,
+            r#"
+fn find(items: &[u32], key: Option<u32>) -> u32 {
+    match key {
+        Some(k) => {$0
+            let mut found = 0;
+            let mut done = false;
+            for item in items {
+                if *item == k {
+                    found = *item;
+                    done = true;
+                }
+            }
+            found
+        }
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_rest_is_not_skipped() {
+        cov_mark::check!(convert_arm_flag_to_labeled_block_multiple_exits);
+        check_assist_not_applicable(
+            convert_arm_flag_to_labeled_block,
+            r#"
+fn log() {}
+
+fn run(x: Option<u32>) -> u32 {
+    match x {
+        Some(n) => {$0
+            let mut out = 0;
+            let mut done = false;
+            if n == 0 {
+                out = 1;
+                done = true;
+            }
+            log();
+            out
+        }
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use hir::{HirDisplay, PathResolution, StructKind};
+use stdx::format_to;
+use syntax::ast::{self, AstNode, HasName};
+
+use crate::{utils::generate_impl_text, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_bit_match_to_const_fn
+//
+// Moves a match mapping every variant of a fieldless enum to a distinct power of two into
+// a `const fn as_bit` on the enum.
+//
+// ```
+// enum Perm { Read, Write, Exec }
+//
+// fn mask(perm: Perm) -> u32 {
+//     $0match perm {
+//         Perm::Read => 1,
+//         Perm::Write => 2,
+//         Perm::Exec => 4,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Perm { Read, Write, Exec }
+//
+// impl Perm {
+//     const fn $0as_bit(&self) -> u32 {
+//         match self {
+//             Self::Read => 1,
+//             Self::Write => 2,
+//             Self::Exec => 4,
+//         }
+//     }
+// }
+//
+// fn mask(perm: Perm) -> u32 {
+//     perm.as_bit()
+// }
+// ```
+pub(crate) fn convert_bit_match_to_const_fn(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.adjusted().strip_references();
+    let enum_ = match scrutinee_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let variants = enum_.variants(ctx.db());
+    if variants.iter().any(|variant| variant.kind(ctx.db()) != StructKind::Unit) {
+        return None;
+    }
+
+    let mut bits = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let variant = arm_variant(ctx, &arm.pat()?)?;
+        let literal = match arm.expr()? {
+            ast::Expr::Literal(it) => it,
+            _ => return None,
+        };
+        let value = match literal.kind() {
+            ast::LiteralKind::IntNumber(number) => number.value()?,
+            _ => return None,
+        };
+        if !value.is_power_of_two() || bits.iter().any(|(_, _, it)| *it == value) {
+            cov_mark::hit!(convert_bit_match_to_const_fn_not_power_of_two);
+            return None;
+        }
+        bits.push((variant, literal, value));
+    }
+    if bits.len() != variants.len() || variants.iter().any(|v| !bits.iter().any(|(it, ..)| it == v))
+    {
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let ret_ty = ctx
+        .sema
+        .type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?
+        .original
+        .display_source_code(ctx.db(), module.into())
+        .ok()?;
+
+    // The method has to be added next to the enum.
+    let enum_src = ctx.sema.source(enum_)?;
+    if enum_src.file_id.is_macro() || enum_src.file_id.original_file(ctx.db()) != ctx.file_id() {
+        return None;
+    }
+    let enum_ast = enum_src.value;
+
+    acc.add(
+        AssistId("convert_bit_match_to_const_fn", AssistKind::RefactorRewrite),
+        "Convert to `const fn as_bit`",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let receiver = match scrutinee {
+                ast::Expr::PathExpr(_)
+                | ast::Expr::FieldExpr(_)
+                | ast::Expr::MethodCallExpr(_)
+                | ast::Expr::CallExpr(_)
+                | ast::Expr::ParenExpr(_) => scrutinee.to_string(),
+                _ => format!("({scrutinee})"),
+            };
+            builder.replace(match_expr.syntax().text_range(), format!("{receiver}.as_bit()"));
+
+            let name = if ctx.config.snippet_cap.is_some() { "$0as_bit" } else { "as_bit" };
+            let mut method =
+                format!("    const fn {name}(&self) -> {ret_ty} {{\n        match self {{");
+            for (variant, literal, _) in &bits {
+                format_to!(method, "\n            Self::{} => {literal},", variant.name(ctx.db()));
+            }
+            method.push_str("\n        }\n    }");
+            let impl_def = generate_impl_text(&ast::Adt::Enum(enum_ast.clone()), &method);
+            let offset = enum_ast.syntax().text_range().end();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, impl_def),
+                None => builder.insert(offset, impl_def),
+            }
+        },
+    )
+}
+
+fn arm_variant(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<hir::Variant> {
+    let resolution = match pat {
+        ast::Pat::PathPat(it) => ctx.sema.resolve_path(&it.path()?)?,
+        ast::Pat::IdentPat(it) if it.name().is_some() => {
+            return match ctx.sema.resolve_bind_pat_to_const(it)? {
+                hir::ModuleDef::Variant(it) => Some(it),
+                _ => None,
+            }
+        }
+        _ => return None,
+    };
+    match resolution {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_variant_mapping() {
+        check_assist(
+            convert_bit_match_to_const_fn,
+            r#"
+enum Flag { A, B, C }
+
+fn mask(flag: &Flag) -> u8 {
+    let bit = match$0 *flag {
+        Flag::C => 0b100,
+        Flag::A => 0x1,
+        Flag::B => 2u8,
+    };
+    bit
+}
+"#,
+            r#"
+enum Flag { A, B, C }
+
+impl Flag {
+    const fn $0as_bit(&self) -> u8 {
+        match self {
+            Self::C => 0b100,
+            Self::A => 0x1,
+            Self::B => 2u8,
+        }
+    }
+}
+
+fn mask(flag: &Flag) -> u8 {
+    let bit = (*flag).as_bit();
+    bit
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_power_of_two() {
+        cov_mark::check!(convert_bit_match_to_const_fn_not_power_of_two);
+        check_assist_not_applicable(
+            convert_bit_match_to_const_fn,
+            r#"
+enum Flag { A, B, C }
+
+fn mask(flag: Flag) -> u32 {
+    match$0 flag {
+        Flag::A => 1,
+        Flag::B => 2,
+        Flag::C => 3,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_duplicate_bits() {
+        cov_mark::check!(convert_bit_match_to_const_fn_not_power_of_two);
+        check_assist_not_applicable(
+            convert_bit_match_to_const_fn,
+            r#"
+enum Flag { A, B }
+
+fn mask(flag: Flag) -> u32 {
+    match$0 flag {
+        Flag::A => 4,
+        Flag::B => 4,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_wildcard_arm() {
+        check_assist_not_applicable(
+            convert_bit_match_to_const_fn,
+            r#"
+enum Flag { A, B, C }
+
+fn mask(flag: Flag) -> u32 {
+    match$0 flag {
+        Flag::A => 1,
+        _ => 2,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use syntax::ast::{self, AstNode};
+
+use crate::{utils::variants, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_bool_match_to_matches_macro
+//
+// Converts a match with more than two arms that evaluates to a boolean into a `matches!`
+// invocation listing the patterns of the arms that return the less common value.
+//
+// ```
+// enum State { Active, Idle, Done }
+//
+// fn is_idle(state: State) -> bool {
+//     match state$0 {
+//         State::Active => false,
+//         State::Idle => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum State { Active, Idle, Done }
+//
+// fn is_idle(state: State) -> bool {
+//     matches!(state, State::Idle)
+// }
+// ```
+pub(crate) fn convert_bool_match_to_matches_macro(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    // Two arms are handled by `convert_two_arm_bool_match_to_matches_macro`.
+    if arms.len() < 3 {
+        return None;
+    }
+    let values = arms.iter().map(|arm| bool_literal(&arm.expr()?)).collect::<Option<Vec<_>>>()?;
+    let (fallback_arm, _) = arms.split_last()?;
+    let fallback = *values.last()?;
+    if fallback_arm.guard().is_some() {
+        return None;
+    }
+
+    // Everything the other arms don't catch ends up in an arm returning the fallback value, so
+    // only arms returning the other value are kept. An arm returning the fallback in front of
+    // them must not catch anything they would.
+    let arms = arms.iter().zip(values).collect::<Vec<_>>();
+    for (idx, (arm, value)) in arms.iter().enumerate() {
+        if *value != fallback {
+            continue;
+        }
+        let mut hits_after = arms[idx..].iter().filter(|(_, it)| *it != fallback);
+        if hits_after.any(|(hit, _)| arm.guard().is_some() || !disjoint(ctx, arm, hit)) {
+            cov_mark::hit!(convert_bool_match_to_matches_macro_overlapping);
+            return None;
+        }
+    }
+    let hits = arms.iter().filter(|(_, it)| *it != fallback).map(|(arm, _)| *arm);
+    let hits = hits.collect::<Vec<_>>();
+    if hits.is_empty() {
+        return None;
+    }
+    if hits.len() > 1 && hits.iter().any(|arm| arm.guard().is_some()) {
+        return None;
+    }
+
+    let target_range = ctx.sema.original_range(match_expr.syntax()).range;
+    let expr = match_expr.expr()?;
+    acc.add(
+        AssistId("convert_bool_match_to_matches_macro", AssistKind::RefactorRewrite),
+        "Convert to matches!",
+        target_range,
+        |builder| {
+            let mut arm_str = hits
+                .iter()
+                .filter_map(|arm| arm.pat())
+                .map(|pat| pat.to_string())
+                .collect::<Vec<_>>()
+                .join(" | ");
+            if let Some(guard) = hits[0].guard() {
+                arm_str += &format!(" {guard}");
+            }
+            let not = if fallback { "!" } else { "" };
+            builder.replace(target_range, format!("{not}matches!({expr}, {arm_str})"));
+        },
+    )
+}
+
+fn bool_literal(expr: &ast::Expr) -> Option<bool> {
+    let ast::Expr::Literal(lit) = expr else { return None };
+    match lit.kind() {
+        ast::LiteralKind::Bool(b) => Some(b),
+        _ => None,
+    }
+}
+
+/// Checks whether the patterns of two arms are known to match distinct enum variants.
+fn disjoint(ctx: &AssistContext<'_>, a: &ast::MatchArm, b: &ast::MatchArm) -> bool {
+    let variants = |arm: &ast::MatchArm| variants(ctx, &arm.pat()?);
+    let (Some(a), Some(b)) = (variants(a), variants(b)) else { return false };
+    a.iter().all(|it| !b.contains(it))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn collapse_two_false_arms() {
+        check_assist(
+            convert_bool_match_to_matches_macro,
+            r#"
+enum Light { Red, Yellow(u8), Green }
+
+fn stop(light: &Light) -> bool {
+    match light$0 {
+        Light::Green => false,
+        Light::Red | Light::Yellow(_) => true,
+        _ => false,
+    }
+}
+"#,
+            r#"
+enum Light { Red, Yellow(u8), Green }
+
+fn stop(light: &Light) -> bool {
+    matches!(light, Light::Red | Light::Yellow(_))
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn collapse_into_negated_matches() {
+        check_assist(
+            convert_bool_match_to_matches_macro,
+            r#"
+fn valid(n: Option<u8>) -> bool {
+    match n$0 {
+        Some(0) => false,
+        None => false,
+        _ => true,
+    }
+}
+"#,
+            r#"
+fn valid(n: Option<u8>) -> bool {
+    !matches!(n, Some(0) | None)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_earlier_arm_overlaps() {
+        cov_mark::check!(convert_bool_match_to_matches_macro_overlapping);
+        check_assist_not_applicable(
+            convert_bool_match_to_matches_macro,
+            r#"
+fn small(n: u8) -> bool {
+    match n$0 {
+        0 => false,
+        0..=9 => true,
+        _ => false,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_boxed_error_to_into
+//
+// Replaces `Err(Box::new(error))` in a match arm with `Err(error.into())` when the box can be
+// created by the `From` conversion instead, as it is for `Box<dyn Error>`.
+//
+// ```
+// # //- minicore: from, error, result
+// # struct Box<T: ?Sized>(T);
+// # impl<T> Box<T> { pub fn new(x: T) -> Self { loop {} } }
+// # impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+// #     fn from(err: E) -> Self { loop {} }
+// # }
+// # #[derive(Debug)]
+// # struct ParseError;
+// # impl core::fmt::Display for ParseError {}
+// # impl core::error::Error for ParseError {}
+// fn parse(s: Option<u8>) -> Result<u8, Box<dyn core::error::Error>> {
+//     match s {
+//         Some(b) => Ok(b),
+//         $0None => Err(Box::new(ParseError)),
+//     }
+// }
+// ```
+// ->
+// ```
+// # struct Box<T: ?Sized>(T);
+// # impl<T> Box<T> { pub fn new(x: T) -> Self { loop {} } }
+// # impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+// #     fn from(err: E) -> Self { loop {} }
+// # }
+// # #[derive(Debug)]
+// # struct ParseError;
+// # impl core::fmt::Display for ParseError {}
+// # impl core::error::Error for ParseError {}
+// fn parse(s: Option<u8>) -> Result<u8, Box<dyn core::error::Error>> {
+//     match s {
+//         Some(b) => Ok(b),
+//         None => Err(ParseError.into()),
+//     }
+// }
+// ```
+pub(crate) fn convert_boxed_error_to_into(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let krate = ctx.sema.scope(arm.syntax())?.krate();
+    let famous_defs = FamousDefs(&ctx.sema, krate);
+    let result = famous_defs.core_result_Result()?;
+    let from = famous_defs.core_convert_From()?;
+
+    // `?` and `into` go through `From`, so that is what has to be able to box the error.
+    let func = arm.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let ret_ty = ctx.sema.to_def(&func)?.ret_type(ctx.db());
+    if ret_ty.as_adt() != Some(hir::Adt::Enum(result)) {
+        return None;
+    }
+    let err_ty = ret_ty.type_arguments().nth(1)?;
+
+    let mut boxed = Vec::new();
+    for call in arm.expr()?.syntax().descendants().filter_map(ast::CallExpr::cast) {
+        let Some(error) = boxed_error(ctx, &call, result) else { continue };
+        let Some(error_ty) = ctx.sema.type_of_expr(&error) else { continue };
+        if !err_ty.impls_trait(ctx.db(), from, &[error_ty.original]) {
+            cov_mark::hit!(convert_boxed_error_to_into_no_conversion);
+            continue;
+        }
+        boxed.push((call, error));
+    }
+    if boxed.is_empty() {
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_boxed_error_to_into", AssistKind::RefactorRewrite),
+        "Convert `Box::new` to `into`",
+        arm.syntax().text_range(),
+        |builder| {
+            for (call, error) in boxed {
+                let error = match error {
+                    ast::Expr::PathExpr(_)
+                    | ast::Expr::CallExpr(_)
+                    | ast::Expr::MethodCallExpr(_)
+                    | ast::Expr::FieldExpr(_)
+                    | ast::Expr::RecordExpr(_)
+                    | ast::Expr::ParenExpr(_) => error.to_string(),
+                    _ => format!("({error})"),
+                };
+                builder.replace(call.syntax().text_range(), format!("{error}.into()"));
+            }
+        },
+    )
+}
+
+/// Returns the argument of `call` if it is `Box::new(arg)` passed directly to `Err`.
+fn boxed_error(
+    ctx: &AssistContext<'_>,
+    call: &ast::CallExpr,
+    result: hir::Enum,
+) -> Option<ast::Expr> {
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    let callee = callee.path()?;
+    if callee.segment()?.name_ref()?.text() != "new"
+        || callee.qualifier()?.segment()?.name_ref()?.text() != "Box"
+    {
+        return None;
+    }
+    let err = call.syntax().parent()?.parent().and_then(ast::CallExpr::cast)?;
+    let ast::Expr::PathExpr(err_path) = err.expr()? else { return None };
+    match ctx.sema.resolve_path(&err_path.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it))
+            if it.parent_enum(ctx.db()) == result && it.name(ctx.db()).to_smol_str() == "Err" => {}
+        _ => return None,
+    }
+    let mut args = call.arg_list()?.args();
+    let arg = args.next()?;
+    args.next().is_none().then_some(arg)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_boxed_error_arm() {
+        check_assist(
+            convert_boxed_error_to_into,
+            r#"
+//- minicore: from, error, result
+struct Box<T: ?Sized>(T);
+impl<T> Box<T> {
+    pub fn new(x: T) -> Self { loop {} }
+}
+impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+    fn from(err: E) -> Self { loop {} }
+}
+#[derive(Debug)]
+enum IoError { Closed, Code(i32) }
+impl core::fmt::Display for IoError {}
+impl core::error::Error for IoError {}
+
+fn read(code: i32) -> Result<(), Box<dyn core::error::Error>> {
+    match code {
+        0 => Ok(()),
+        -1 => Err(Box::new(IoError::Closed)),
+        n => {
+            $0return Err(Box::new(IoError::Code(n)));
+        }
+    }
+}
+"#,
+            r#"
+struct Box<T: ?Sized>(T);
+impl<T> Box<T> {
+    pub fn new(x: T) -> Self { loop {} }
+}
+impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+    fn from(err: E) -> Self { loop {} }
+}
+#[derive(Debug)]
+enum IoError { Closed, Code(i32) }
+impl core::fmt::Display for IoError {}
+impl core::error::Error for IoError {}
+
+fn read(code: i32) -> Result<(), Box<dyn core::error::Error>> {
+    match code {
+        0 => Ok(()),
+        -1 => Err(Box::new(IoError::Closed)),
+        n => {
+            return Err(IoError::Code(n).into());
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_from_conversion() {
+        cov_mark::check!(convert_boxed_error_to_into_no_conversion);
+        check_assist_not_applicable(
+            convert_boxed_error_to_into,
+            r#"
+//- minicore: from, error, result
+struct Box<T: ?Sized>(T);
+impl<T> Box<T> {
+    pub fn new(x: T) -> Self { loop {} }
+}
+#[derive(Debug)]
+struct Plain;
+
+fn read(code: i32) -> Result<(), Box<Plain>> {
+    match code {
+        0 => Ok(()),
+        _ => $0Err(Box::new(Plain)),
+    }
+}
+"#,
+        );
+    }
+}
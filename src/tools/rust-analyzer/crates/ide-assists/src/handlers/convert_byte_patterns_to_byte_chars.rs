@@ -0,0 +1,130 @@
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_byte_patterns_to_byte_chars
+//
+// Rewrites the `u8` integer patterns of a match that stand for printable ASCII characters as
+// byte literals. Other bytes are left as they are.
+//
+// ```
+// fn classify(b: u8) -> u32 {
+//     $0match b {
+//         0x41..=0x5a => 1,
+//         0x0a | 32 => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn classify(b: u8) -> u32 {
+//     match b {
+//         b'A'..=b'Z' => 1,
+//         0x0a | b' ' => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+pub(crate) fn convert_byte_patterns_to_byte_chars(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let mut edits = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        for pat in arm.pat()?.syntax().descendants().filter_map(ast::LiteralPat::cast) {
+            let Some(literal) = pat.literal() else { continue };
+            let ast::LiteralKind::IntNumber(number) = literal.kind() else { continue };
+            // The bounds of a range pattern aren't typed on their own.
+            let typed = match pat.syntax().parent().and_then(ast::RangePat::cast) {
+                Some(range) => range.into(),
+                None => pat.clone().into(),
+            };
+            let is_u8 = ctx.sema.type_of_pat(&typed).map_or(false, |ty| {
+                ty.original.as_builtin().map_or(false, |it| it.name().to_smol_str() == "u8")
+            });
+            if !is_u8 {
+                continue;
+            }
+            let Some(byte) = number.value().and_then(|it| u8::try_from(it).ok()) else { continue };
+            if let Some(text) = byte_char(byte) {
+                edits.push((literal.syntax().text_range(), text));
+            }
+        }
+    }
+    if edits.is_empty() {
+        cov_mark::hit!(convert_byte_patterns_to_byte_chars_nothing_printable);
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_byte_patterns_to_byte_chars", AssistKind::RefactorRewrite),
+        "Convert byte patterns to byte literals",
+        match_expr.syntax().text_range(),
+        |builder| {
+            for (range, text) in edits {
+                builder.replace(range, text);
+            }
+        },
+    )
+}
+
+fn byte_char(byte: u8) -> Option<String> {
+    match byte {
+        b'\'' => Some(r"b'\''".to_string()),
+        b'\\' => Some(r"b'\\'".to_string()),
+        b' '..=b'~' => Some(format!("b'{}'", byte as char)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_printable_bytes() {
+        check_assist(
+            convert_byte_patterns_to_byte_chars,
+            r#"
+fn token(input: (u8, u32)) -> u32 {
+    match$0 input {
+        (0x41, 0x41) => 1,
+        (39 | 92 | 0x7f, _) => 2,
+        (0x30..=0x39u8, n) => n,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn token(input: (u8, u32)) -> u32 {
+    match input {
+        (b'A', 0x41) => 1,
+        (b'\'' | b'\\' | 0x7f, _) => 2,
+        (b'0'..=b'9', n) => n,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_control_bytes() {
+        cov_mark::check!(convert_byte_patterns_to_byte_chars_nothing_printable);
+        check_assist_not_applicable(
+            convert_byte_patterns_to_byte_chars,
+            r#"
+fn is_newline(b: u8) -> bool {
+    match$0 b {
+        0x0a | 0x0d => true,
+        _ => false,
+    }
+}
+"#,
+        );
+    }
+}
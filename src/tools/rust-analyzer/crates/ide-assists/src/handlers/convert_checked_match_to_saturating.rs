@@ -0,0 +1,181 @@
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{utils::is_variant, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_checked_match_to_saturating
+//
+// Replaces a match on a checked arithmetic operation that falls back to the value the
+// operation saturates to with the saturating operation.
+//
+// ```
+// # //- minicore: option
+// fn next(n: u32, step: u32) -> u32 {
+//     $0match n.checked_add(step) {
+//         Some(v) => v,
+//         None => u32::MAX,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn next(n: u32, step: u32) -> u32 {
+//     n.saturating_add(step)
+// }
+// ```
+pub(crate) fn convert_checked_match_to_saturating(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let ast::Expr::MethodCallExpr(call) = match_expr.expr()? else { return None };
+    let method = call.name_ref()?.text().to_string();
+    let op = method.strip_prefix("checked_")?;
+    let receiver = call.receiver()?;
+    let mut args = call.arg_list()?.args();
+    let (operand, None) = (args.next()?, args.next()) else { return None };
+    let ty = ctx.sema.type_of_expr(&receiver)?.adjusted();
+    let ty = ty.as_builtin().filter(|_| ty.is_int_or_uint())?.name().to_smol_str();
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = &arms[..] else { return None };
+    if first.guard().is_some() || second.guard().is_some() {
+        return None;
+    }
+    let fallback = match (unwrapped(ctx, first), unwrapped(ctx, second)) {
+        (Some(true), _) if is_none(ctx, &second.pat()?) => second.expr()?,
+        (_, Some(true)) if is_none(ctx, &first.pat()?) => first.expr()?,
+        _ => return None,
+    };
+
+    let fallback = fallback.syntax().text().to_string();
+    let unsigned = ty.starts_with('u');
+    let is_one = operand.syntax().text() == "1";
+    // Stepping by one only overflows at the bound, where the old value is the saturated one.
+    let saturates = match op {
+        "add" => {
+            (unsigned && fallback == format!("{ty}::MAX"))
+                || (is_one && fallback == receiver.syntax().text().to_string())
+        }
+        "mul" => unsigned && fallback == format!("{ty}::MAX"),
+        "sub" => {
+            (unsigned && (fallback == "0" || fallback == format!("{ty}::MIN")))
+                || (is_one && fallback == receiver.syntax().text().to_string())
+        }
+        _ => false,
+    };
+    if !saturates {
+        cov_mark::hit!(convert_checked_match_to_saturating_other_fallback);
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_checked_match_to_saturating", AssistKind::RefactorRewrite),
+        format!("Replace match with `saturating_{op}`"),
+        match_expr.syntax().text_range(),
+        |builder| {
+            builder.replace(
+                match_expr.syntax().text_range(),
+                format!("{receiver}.saturating_{op}({operand})"),
+            );
+        },
+    )
+}
+
+/// Checks whether `arm` is `Some(v) => v`.
+fn unwrapped(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<bool> {
+    let ast::Pat::TupleStructPat(pat) = arm.pat()? else { return Some(false) };
+    if !is_variant(ctx, &pat.path()?, "Some") {
+        return Some(false);
+    }
+    let mut fields = pat.fields();
+    let (Some(ast::Pat::IdentPat(binding)), None) = (fields.next(), fields.next()) else {
+        return Some(false);
+    };
+    Some(arm.expr()?.syntax().text() == binding.syntax().text())
+}
+
+fn is_none(ctx: &AssistContext<'_>, pat: &ast::Pat) -> bool {
+    match pat {
+        ast::Pat::WildcardPat(_) => true,
+        ast::Pat::IdentPat(it) => matches!(
+            ctx.sema.resolve_bind_pat_to_const(it),
+            Some(hir::ModuleDef::Variant(it)) if it.name(ctx.db()).to_smol_str() == "None"
+        ),
+        ast::Pat::PathPat(it) => it.path().map_or(false, |it| is_variant(ctx, &it, "None")),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_increment_to_saturating_add() {
+        check_assist(
+            convert_checked_match_to_saturating,
+            r#"
+//- minicore: option
+struct Counter { hits: i64 }
+
+impl Counter {
+    fn hit(&mut self) {
+        self.hits = match$0 self.hits.checked_add(1) {
+            None => self.hits,
+            Some(hits) => hits,
+        };
+    }
+}
+"#,
+            r#"
+struct Counter { hits: i64 }
+
+impl Counter {
+    fn hit(&mut self) {
+        self.hits = self.hits.saturating_add(1);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_to_saturating_sub() {
+        check_assist(
+            convert_checked_match_to_saturating,
+            r#"
+//- minicore: option
+fn remaining(total: usize, used: usize) -> usize {
+    $0match total.checked_sub(used) {
+        Some(n) => n,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn remaining(total: usize, used: usize) -> usize {
+    total.saturating_sub(used)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_fallback() {
+        cov_mark::check!(convert_checked_match_to_saturating_other_fallback);
+        check_assist_not_applicable(
+            convert_checked_match_to_saturating,
+            r#"
+//- minicore: option
+fn next(n: u32, step: u32) -> u32 {
+    $0match n.checked_add(step) {
+        Some(v) => v,
+        None => n,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use hir::PathResolution;
+use syntax::ast::{self, AstNode, HasArgList, HasName};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_closure_arm_to_fn_item
+//
+// Replaces a closure returned from a match arm that only forwards its parameters to a free
+// function with the function item itself.
+//
+// ```
+// fn double(x: u32) -> u32 { x * 2 }
+//
+// fn pick(twice: bool) -> fn(u32) -> u32 {
+//     match twice {
+//         true => $0|x| double(x),
+//         false => |x| x,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn double(x: u32) -> u32 { x * 2 }
+//
+// fn pick(twice: bool) -> fn(u32) -> u32 {
+//     match twice {
+//         true => double,
+//         false => |x| x,
+//     }
+// }
+// ```
+pub(crate) fn convert_closure_arm_to_fn_item(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let closure = ctx.find_node_at_offset::<ast::ClosureExpr>()?;
+    closure.syntax().parent().and_then(ast::MatchArm::cast)?;
+    if closure.async_token().is_some() || closure.ret_type().is_some() {
+        return None;
+    }
+    let params = closure
+        .param_list()?
+        .params()
+        .map(|param| match param.pat()? {
+            ast::Pat::IdentPat(it) if it.ref_token().is_none() && it.pat().is_none() => {
+                Some(it.name()?.to_string())
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let call = match closure.body()? {
+        ast::Expr::CallExpr(it) => it,
+        ast::Expr::MethodCallExpr(_) => {
+            // There are no bound method references to turn `|x| self.method(x)` into.
+            cov_mark::hit!(convert_closure_arm_to_fn_item_method_call);
+            return None;
+        }
+        _ => return None,
+    };
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    let callee = callee.path()?;
+    match ctx.sema.resolve_path(&callee)? {
+        PathResolution::Def(hir::ModuleDef::Function(_)) => {}
+        _ => return None,
+    }
+    let args = call.arg_list()?.args().map(|arg| arg.syntax().to_string()).collect::<Vec<_>>();
+    if args != params {
+        return None;
+    }
+
+    acc.add(
+        AssistId("convert_closure_arm_to_fn_item", AssistKind::RefactorRewrite),
+        format!("Replace closure with `{callee}`"),
+        closure.syntax().text_range(),
+        |builder| builder.replace(closure.syntax().text_range(), callee.to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_forwarding_closure() {
+        check_assist(
+            convert_closure_arm_to_fn_item,
+            r#"
+mod ops {
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn sub(a: i32, b: i32) -> i32 { a - b }
+}
+
+fn op(c: char) -> Option<fn(i32, i32) -> i32> {
+    let f: fn(i32, i32) -> i32 = match c {
+        '+' => |a, b| ops::add(a, b),
+        '-' => $0|lhs, rhs| ops::sub(lhs, rhs),
+        _ => return None,
+    };
+    Some(f)
+}
+"#,
+            r#"
+mod ops {
+    pub fn add(a: i32, b: i32) -> i32 { a + b }
+    pub fn sub(a: i32, b: i32) -> i32 { a - b }
+}
+
+fn op(c: char) -> Option<fn(i32, i32) -> i32> {
+    let f: fn(i32, i32) -> i32 = match c {
+        '+' => |a, b| ops::add(a, b),
+        '-' => ops::sub,
+        _ => return None,
+    };
+    Some(f)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_reordered_arguments() {
+        check_assist_not_applicable(
+            convert_closure_arm_to_fn_item,
+            r#"
+fn sub(a: i32, b: i32) -> i32 { a - b }
+
+fn op(flip: bool) -> fn(i32, i32) -> i32 {
+    match flip {
+        true => $0|a, b| sub(b, a),
+        false => sub,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_method_calls() {
+        cov_mark::check!(convert_closure_arm_to_fn_item_method_call);
+        check_assist_not_applicable(
+            convert_closure_arm_to_fn_item,
+            r#"
+struct Counter(u32);
+
+impl Counter {
+    fn add(&self, n: u32) -> u32 { self.0 + n }
+
+    fn adder(&self, enabled: bool) -> Option<u32> {
+        let f = match enabled {
+            true => $0|n| self.add(n),
+            false => return None,
+        };
+        Some(f(1))
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use hir::{PathResolution, StructKind};
+use ide_db::famous_defs::FamousDefs;
+use itertools::Itertools;
+use syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::{discriminants, needs_parens_as_receiver, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_cyclic_match_to_modular_arithmetic
+//
+// Replaces a match mapping each variant of a fieldless enum to the variant a fixed number of
+// places after it, wrapping around at the end, by indexing the variants with modular arithmetic.
+// The enum has to be `Copy`, as the variant is copied out of the array.
+//
+// ```
+// # //- minicore: copy, derive
+// #[derive(Clone, Copy)]
+// enum Light { Red, Green, Yellow }
+//
+// fn next(light: Light) -> Light {
+//     $0match light {
+//         Light::Red => Light::Green,
+//         Light::Green => Light::Yellow,
+//         Light::Yellow => Light::Red,
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone, Copy)]
+// enum Light { Red, Green, Yellow }
+//
+// fn next(light: Light) -> Light {
+//     [Light::Red, Light::Green, Light::Yellow][(light as usize + 1) % 3]
+// }
+// ```
+pub(crate) fn convert_cyclic_match_to_modular_arithmetic(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    let enum_ = match scrutinee_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let all_variants = enum_.variants(ctx.db());
+    let count = all_variants.len();
+    if count < 2 || all_variants.iter().any(|it| it.kind(ctx.db()) != StructKind::Unit) {
+        return None;
+    }
+    // The discriminants are the positions in the array of variants.
+    let in_order = discriminants(ctx, &all_variants)?.into_iter().eq(0..count as u128);
+    if !in_order {
+        return None;
+    }
+
+    let mut qualifier = None;
+    let mut step = None;
+    let mut seen = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let pat = arm.pat()?;
+        let [from]: [hir::Variant; 1] = variants(ctx, &pat)?.try_into().ok()?;
+        let ast::Expr::PathExpr(to) = arm.expr()? else { return None };
+        let to_path = to.path()?;
+        let to = match ctx.sema.resolve_path(&to_path)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it))
+                if it.parent_enum(ctx.db()) == enum_ =>
+            {
+                it
+            }
+            _ => return None,
+        };
+        let from_pos = all_variants.iter().position(|it| *it == from)?;
+        let to_pos = all_variants.iter().position(|it| *it == to)?;
+        let arm_step = (to_pos + count - from_pos) % count;
+        if arm_step == 0 || *step.get_or_insert(arm_step) != arm_step || seen.contains(&from) {
+            cov_mark::hit!(convert_cyclic_match_to_modular_arithmetic_not_cyclic);
+            return None;
+        }
+        seen.push(from);
+        qualifier.get_or_insert_with(|| to_path.qualifier());
+    }
+    if seen.len() != count {
+        cov_mark::hit!(convert_cyclic_match_to_modular_arithmetic_not_cyclic);
+        return None;
+    }
+    let step = step?;
+    let qualifier = qualifier?;
+    let copy =
+        FamousDefs(&ctx.sema, ctx.sema.scope(match_expr.syntax())?.krate()).core_marker_Copy()?;
+    if !scrutinee_ty.impls_trait(ctx.db(), copy, &[]) {
+        cov_mark::hit!(convert_cyclic_match_to_modular_arithmetic_not_copy);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_cyclic_match_to_modular_arithmetic", AssistKind::RefactorRewrite),
+        "Convert match to modular arithmetic",
+        target,
+        |builder| {
+            let cycle = all_variants
+                .iter()
+                .map(|it| match &qualifier {
+                    Some(qualifier) => format!("{qualifier}::{}", it.name(ctx.db())),
+                    None => it.name(ctx.db()).to_string(),
+                })
+                .join(", ");
+            let value = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(target, format!("[{cycle}][({value} as usize + {step}) % {count}]"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_weekday_successor() {
+        check_assist(
+            convert_cyclic_match_to_modular_arithmetic,
+            r#"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Weekday { Mon, Tue, Wed, Thu, Fri, Sat, Sun }
+
+impl Weekday {
+    fn succ(self) -> Weekday {
+        match$0 self {
+            Weekday::Mon => Weekday::Tue,
+            Weekday::Tue => Weekday::Wed,
+            Weekday::Wed => Weekday::Thu,
+            Weekday::Sun => Weekday::Mon,
+            Weekday::Thu => Weekday::Fri,
+            Weekday::Fri => Weekday::Sat,
+            Weekday::Sat => Weekday::Sun,
+        }
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+enum Weekday { Mon, Tue, Wed, Thu, Fri, Sat, Sun }
+
+impl Weekday {
+    fn succ(self) -> Weekday {
+        [Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri, Weekday::Sat, Weekday::Sun][(self as usize + 1) % 7]
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_predecessor() {
+        check_assist(
+            convert_cyclic_match_to_modular_arithmetic,
+            r#"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Dir { North, East, South, West }
+use Dir::*;
+
+fn left(dir: Dir) -> Dir {
+    match$0 dir {
+        North => West,
+        East => North,
+        South => East,
+        West => South,
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+enum Dir { North, East, South, West }
+use Dir::*;
+
+fn left(dir: Dir) -> Dir {
+    [North, East, South, West][(dir as usize + 3) % 4]
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_enum_that_is_not_copy() {
+        cov_mark::check!(convert_cyclic_match_to_modular_arithmetic_not_copy);
+        check_assist_not_applicable(
+            convert_cyclic_match_to_modular_arithmetic,
+            r#"
+//- minicore: copy
+enum Light { Red, Green, Yellow }
+
+fn next(light: Light) -> Light {
+    match$0 light {
+        Light::Red => Light::Green,
+        Light::Green => Light::Yellow,
+        Light::Yellow => Light::Red,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_saturating_mapping() {
+        cov_mark::check!(convert_cyclic_match_to_modular_arithmetic_not_cyclic);
+        check_assist_not_applicable(
+            convert_cyclic_match_to_modular_arithmetic,
+            r#"
+enum Level { Low, Mid, High }
+
+fn up(level: Level) -> Level {
+    match$0 level {
+        Level::Low => Level::Mid,
+        Level::Mid => Level::High,
+        Level::High => Level::High,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_partial_mapping() {
+        check_assist_not_applicable(
+            convert_cyclic_match_to_modular_arithmetic,
+            r#"
+enum Level { Low, Mid, High }
+
+fn up(level: Level) -> Level {
+    match$0 level {
+        Level::Low => Level::Mid,
+        Level::Mid => Level::High,
+        _ => Level::Low,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use ide_db::famous_defs::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasArgList, HasLoopBody,
+    },
+    T,
+};
+
+use crate::{utils::is_variant, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_find_loop_to_find_map
+//
+// Replaces a loop that returns the first `Some` produced by a match on each item, followed by
+// a final `None`, with a call to `Iterator::find_map`.
+//
+// ```
+// # //- minicore: iterator, option
+// enum Entry { File(u32), Dir }
+//
+// fn first_file(entries: impl Iterator<Item = Entry>) -> Option<u32> {
+//     $0for entry in entries {
+//         match entry {
+//             Entry::File(size) => return Some(size),
+//             Entry::Dir => {}
+//         }
+//     }
+//     None
+// }
+// ```
+// ->
+// ```
+// enum Entry { File(u32), Dir }
+//
+// fn first_file(entries: impl Iterator<Item = Entry>) -> Option<u32> {
+//     entries.find_map(|entry| match entry {
+//         Entry::File(size) => Some(size),
+//         Entry::Dir => None,
+//     })
+// }
+// ```
+pub(crate) fn convert_find_loop_to_find_map(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_expr = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let for_stmt = for_expr.syntax().parent().and_then(ast::ExprStmt::cast)?;
+    let stmt_list = for_stmt.syntax().parent().and_then(ast::StmtList::cast)?;
+    // Returning from the closure is only the same as returning from the function when the
+    // loop is what the function ends with.
+    stmt_list.syntax().parent()?.parent().and_then(ast::Fn::cast)?;
+    if stmt_list.statements().last()?.syntax() != for_stmt.syntax() {
+        return None;
+    }
+    let tail = stmt_list.tail_expr()?;
+    let ast::Expr::PathExpr(tail) = tail else { return None };
+    if !is_variant(ctx, &tail.path()?, "None") {
+        return None;
+    }
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+    let match_expr = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::MatchExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let mut arms = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let expr = arm.expr()?;
+        let value = match returned_some(ctx, &expr) {
+            Some(value) => Some(value),
+            None if skips(&expr) => None,
+            None => {
+                cov_mark::hit!(convert_find_loop_to_find_map_not_a_find);
+                return None;
+            }
+        };
+        arms.push((arm, value));
+    }
+    if arms.iter().all(|(_, value)| value.is_none()) {
+        return None;
+    }
+
+    let iterable = for_expr.iterable()?;
+    let krate = ctx.sema.scope(for_expr.syntax())?.krate();
+    let iterator = FamousDefs(&ctx.sema, krate).core_iter_Iterator()?;
+    let is_iterator =
+        ctx.sema.type_of_expr(&iterable)?.original.impls_trait(ctx.db(), iterator, &[]);
+    let pat = for_expr.pat()?;
+
+    let target = for_stmt.syntax().text_range().cover(tail.syntax().text_range());
+    acc.add(
+        AssistId("convert_find_loop_to_find_map", AssistKind::RefactorRewrite),
+        "Convert loop to `find_map`",
+        target,
+        |builder| {
+            let iter = match (&iterable, is_iterator) {
+                (_, true) => iterable.to_string(),
+                (ast::Expr::PathExpr(_) | ast::Expr::MethodCallExpr(_), false) => {
+                    format!("{iterable}.into_iter()")
+                }
+                (_, false) => format!("({iterable}).into_iter()"),
+            };
+            let indent = IndentLevel::from_node(for_stmt.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let scrutinee = match_expr.expr().map(|it| it.to_string()).unwrap_or_default();
+            let mut text = format!("{iter}.find_map(|{pat}| match {scrutinee} {{");
+            for (arm, value) in &arms {
+                let pat = arm.pat().map(|it| it.to_string()).unwrap_or_default();
+                let guard = arm.guard().map(|it| format!(" {it}")).unwrap_or_default();
+                match value {
+                    Some(value) => {
+                        let value = value.reset_indent().indent(inner);
+                        format_to!(text, "\n{inner}{pat}{guard} => Some({value}),")
+                    }
+                    None => format_to!(text, "\n{inner}{pat}{guard} => None,"),
+                }
+            }
+            format_to!(text, "\n{indent}}})");
+            builder.replace(target, text);
+        },
+    )
+}
+
+/// Returns the value of `return Some(value)`, optionally in a block.
+fn returned_some(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<ast::Expr> {
+    let expr = match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmts = block.stmt_list()?;
+            match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+                ([], Some(tail)) => tail,
+                ([ast::Stmt::ExprStmt(stmt)], None) => stmt.expr()?,
+                _ => return None,
+            }
+        }
+        _ => expr.clone(),
+    };
+    let ast::Expr::ReturnExpr(ret) = expr else { return None };
+    let ast::Expr::CallExpr(call) = ret.expr()? else { return None };
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    if !is_variant(ctx, &callee.path()?, "Some") {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    let value = args.next()?;
+    args.next().is_none().then_some(value)
+}
+
+/// Checks whether `expr` just moves on to the next item.
+fn skips(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::TupleExpr(it) => it.fields().next().is_none(),
+        ast::Expr::ContinueExpr(it) => it.lifetime().is_none(),
+        ast::Expr::BlockExpr(it) if it.modifier().is_none() => it.stmt_list().map_or(false, |it| {
+            it.statements().next().is_none() && it.tail_expr().map_or(true, |it| skips(&it))
+        }),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_simple_find_map_loop() {
+        check_assist(
+            convert_find_loop_to_find_map,
+            r#"
+//- minicore: iterator, option
+enum Arg { Flag(u8), Value(u32), Rest }
+
+impl Arg {
+    fn first_value(args: &[Arg], limit: u32) -> Option<u32> {
+        let _ = limit;
+        fo$0r arg in args {
+            match *arg {
+                Arg::Value(v) if v < limit => {
+                    return Some(v * 2);
+                }
+                Arg::Flag(_) => continue,
+                _ => (),
+            }
+        }
+        None
+    }
+}
+"#,
+            r#"
+enum Arg { Flag(u8), Value(u32), Rest }
+
+impl Arg {
+    fn first_value(args: &[Arg], limit: u32) -> Option<u32> {
+        let _ = limit;
+        args.into_iter().find_map(|arg| match *arg {
+            Arg::Value(v) if v < limit => Some(v * 2),
+            Arg::Flag(_) => None,
+            _ => None,
+        })
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_loop_accumulates() {
+        cov_mark::check!(convert_find_loop_to_find_map_not_a_find);
+        check_assist_not_applicable(
+            convert_find_loop_to_find_map,
+            r#"
+//- minicore: iterator, option
+fn first(items: impl Iterator<Item = Option<u32>>, skipped: &mut u32) -> Option<u32> {
+    $0for item in items {
+        match item {
+            Some(v) => return Some(v),
+            None => *skipped += 1,
+        }
+    }
+    None
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_trailing_none() {
+        check_assist_not_applicable(
+            convert_find_loop_to_find_map,
+            r#"
+//- minicore: iterator, option
+fn first(items: impl Iterator<Item = Option<u32>>) -> Option<u32> {
+    $0for item in items {
+        match item {
+            Some(v) => return Some(v),
+            None => {}
+        }
+    }
+    Some(0)
+}
+"#,
+        );
+    }
+}
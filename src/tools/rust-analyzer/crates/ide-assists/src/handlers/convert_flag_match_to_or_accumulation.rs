@@ -0,0 +1,223 @@
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_flag_match_to_or_accumulation
+//
+// Replaces a match whose arms each OR a different flag into the same accumulator with a single
+// `|=` of the flag the match maps the value to.
+//
+// ```
+// enum Perm { Read, Write, Exec }
+// const READ: u8 = 4;
+// const WRITE: u8 = 2;
+// const EXEC: u8 = 1;
+//
+// fn add(mode: &mut u8, perm: Perm) {
+//     $0match perm {
+//         Perm::Read => *mode |= READ,
+//         Perm::Write => *mode |= WRITE,
+//         Perm::Exec => *mode |= EXEC,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Perm { Read, Write, Exec }
+// const READ: u8 = 4;
+// const WRITE: u8 = 2;
+// const EXEC: u8 = 1;
+//
+// fn add(mode: &mut u8, perm: Perm) {
+//     *mode |= match perm {
+//         Perm::Read => READ,
+//         Perm::Write => WRITE,
+//         Perm::Exec => EXEC,
+//     };
+// }
+// ```
+pub(crate) fn convert_flag_match_to_or_accumulation(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() < 2 {
+        return None;
+    }
+
+    let mut accumulator = None::<ast::Expr>;
+    let mut flags: Vec<(ast::MatchArm, ast::Expr)> = Vec::with_capacity(arms.len());
+    for arm in &arms {
+        let Some(assignment) = or_assignment(&arm.expr()?) else {
+            cov_mark::hit!(convert_flag_match_to_or_accumulation_mixed_operations);
+            return None;
+        };
+        let lhs = assignment.lhs()?;
+        let flag = assignment.rhs()?;
+        let accumulator = accumulator.get_or_insert_with(|| lhs.clone());
+        let distinct = flags.iter().all(|(_, it)| it.syntax().text() != flag.syntax().text());
+        if lhs.syntax().text() != accumulator.syntax().text() || !distinct {
+            return None;
+        }
+        flags.push((arm.clone(), flag));
+    }
+    let accumulator = accumulator?;
+
+    let semicolon = match match_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(it) if it.semicolon_token().is_some() => "",
+        _ => ";",
+    };
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_flag_match_to_or_accumulation", AssistKind::RefactorRewrite),
+        "Convert match to `|=` of the matched flag",
+        target,
+        |builder| {
+            builder.insert(target.start(), format!("{accumulator} |= "));
+            for (arm, flag) in flags {
+                let Some(expr) = arm.expr() else { continue };
+                let comma = if arm.comma_token().is_some() { "" } else { "," };
+                builder.replace(expr.syntax().text_range(), format!("{flag}{comma}"));
+            }
+            builder.insert(target.end(), semicolon);
+        },
+    )
+}
+
+/// Finds the `acc |= flag` an arm consists of.
+fn or_assignment(expr: &ast::Expr) -> Option<ast::BinExpr> {
+    let expr = match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmts = block.stmt_list()?;
+            match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+                ([], Some(it)) => it,
+                ([ast::Stmt::ExprStmt(it)], None) => it.expr()?,
+                _ => return None,
+            }
+        }
+        it => it.clone(),
+    };
+    match expr {
+        ast::Expr::BinExpr(it)
+            if it.op_kind()
+                == Some(ast::BinaryOp::Assignment { op: Some(ast::ArithOp::BitOr) }) =>
+        {
+            Some(it)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_flag_accumulation() {
+        check_assist(
+            convert_flag_match_to_or_accumulation,
+            r#"
+enum Style { Bold, Italic, Underline }
+struct Text { attrs: u32 }
+const BOLD: u32 = 1 << 0;
+const ITALIC: u32 = 1 << 1;
+const UNDERLINE: u32 = 1 << 2;
+
+fn apply(text: &mut Text, styles: &[Style]) {
+    for style in styles {
+        match$0 style {
+            Style::Bold => text.attrs |= BOLD,
+            Style::Italic => {
+                text.attrs |= ITALIC;
+            }
+            Style::Underline => { text.attrs |= UNDERLINE }
+        }
+    }
+}
+"#,
+            r#"
+enum Style { Bold, Italic, Underline }
+struct Text { attrs: u32 }
+const BOLD: u32 = 1 << 0;
+const ITALIC: u32 = 1 << 1;
+const UNDERLINE: u32 = 1 << 2;
+
+fn apply(text: &mut Text, styles: &[Style]) {
+    for style in styles {
+        text.attrs |= match style {
+            Style::Bold => BOLD,
+            Style::Italic => ITALIC,
+            Style::Underline => UNDERLINE,
+        };
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_keeps_guards_and_semicolon() {
+        check_assist(
+            convert_flag_match_to_or_accumulation,
+            r#"
+fn collect(c: char, shift: bool) -> u8 {
+    let mut mask = 0;
+    match$0 c {
+        'a'..='z' if shift => mask |= 1,
+        'a'..='z' => mask |= 2,
+        _ => mask |= 4,
+    };
+    mask
+}
+"#,
+            r#"
+fn collect(c: char, shift: bool) -> u8 {
+    let mut mask = 0;
+    mask |= match c {
+        'a'..='z' if shift => 1,
+        'a'..='z' => 2,
+        _ => 4,
+    };
+    mask
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_mixed_operations() {
+        cov_mark::check!(convert_flag_match_to_or_accumulation_mixed_operations);
+        check_assist_not_applicable(
+            convert_flag_match_to_or_accumulation,
+            r#"
+enum Op { Set, Clear, Toggle }
+
+fn update(flags: &mut u8, op: Op) {
+    match$0 op {
+        Op::Set => *flags |= 1,
+        Op::Clear => *flags &= !1,
+        Op::Toggle => *flags ^= 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_accumulators() {
+        check_assist_not_applicable(
+            convert_flag_match_to_or_accumulation,
+            r#"
+fn split(c: u8, low: &mut u8, high: &mut u8) {
+    match$0 c {
+        0 => *low |= 1,
+        _ => *high |= 1,
+    }
+}
+"#,
+        );
+    }
+}
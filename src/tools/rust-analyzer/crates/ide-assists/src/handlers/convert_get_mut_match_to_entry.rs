@@ -0,0 +1,387 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasArgList, HasName,
+    },
+    SyntaxKind,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_get_mut_match_to_entry
+//
+// Replaces a match updating the value of a key in a map, or inserting it when it is missing,
+// with the entry API.
+//
+// ```
+// # //- minicore: option
+// # //- /main.rs crate:main deps:std
+// use std::collections::HashMap;
+//
+// fn count(map: &mut HashMap<char, u32>, c: char) {
+//     $0match map.get_mut(&c) {
+//         Some(n) => *n += 1,
+//         None => {
+//             map.insert(c, 1);
+//         }
+//     }
+// }
+// # //- /std.rs crate:std
+// # pub mod collections {
+// #     pub struct HashMap<K, V>(K, V);
+// #     impl<K, V> HashMap<K, V> {
+// #         pub fn get_mut(&mut self, k: &K) -> Option<&mut V> { loop {} }
+// #         pub fn insert(&mut self, k: K, v: V) -> Option<V> { loop {} }
+// #     }
+// # }
+// ```
+// ->
+// ```
+// use std::collections::HashMap;
+//
+// fn count(map: &mut HashMap<char, u32>, c: char) {
+//     map.entry(c).and_modify(|n| *n += 1).or_insert(1);
+// }
+// ```
+pub(crate) fn convert_get_mut_match_to_entry(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let ast::Expr::MethodCallExpr(get_mut) = match_expr.expr()? else { return None };
+    let map = get_mut.receiver()?;
+    let [ast::Expr::RefExpr(key)]: [ast::Expr; 1] =
+        get_mut.arg_list()?.args().collect::<Vec<_>>().try_into().ok()?
+    else {
+        return None;
+    };
+    let key = key.expr()?;
+    if get_mut.name_ref()?.text() != "get_mut" || !is_map(ctx, &map) {
+        return None;
+    }
+    if !ctx.sema.type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?.original.is_unit() {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = arms.as_slice() else { return None };
+    if first.guard().is_some() || second.guard().is_some() {
+        return None;
+    }
+    let (some_arm, none_arm) = match (first.pat()?, second.pat()?) {
+        (ast::Pat::TupleStructPat(_), _) => (first, second),
+        (_, ast::Pat::TupleStructPat(_)) => (second, first),
+        _ => return None,
+    };
+    let ast::Pat::TupleStructPat(some_pat) = some_arm.pat()? else { return None };
+    let binding = match some_pat.fields().collect::<Vec<_>>().as_slice() {
+        [ast::Pat::IdentPat(it)]
+            if it.ref_token().is_none() && it.mut_token().is_none() && it.pat().is_none() =>
+        {
+            it.name()?
+        }
+        _ => return None,
+    };
+    let update = some_arm.expr()?;
+    // The update runs in a closure now, where these would leave the closure instead.
+    let leaves_closure = update.syntax().descendants().any(|it| {
+        matches!(
+            it.kind(),
+            SyntaxKind::RETURN_EXPR
+                | SyntaxKind::BREAK_EXPR
+                | SyntaxKind::CONTINUE_EXPR
+                | SyntaxKind::TRY_EXPR
+                | SyntaxKind::AWAIT_EXPR
+        )
+    });
+    if leaves_closure {
+        return None;
+    }
+
+    let Some(insert) = only_insert(&none_arm.expr()?) else {
+        cov_mark::hit!(convert_get_mut_match_to_entry_not_only_insert);
+        return None;
+    };
+    let [insert_key, value]: [ast::Expr; 2] =
+        insert.arg_list()?.args().collect::<Vec<_>>().try_into().ok()?;
+    let same_map = insert.receiver()?.syntax().text() == map.syntax().text();
+    if insert.name_ref()?.text() != "insert"
+        || !same_map
+        || insert_key.syntax().text() != key.syntax().text()
+    {
+        return None;
+    }
+
+    let stmt = match_expr.syntax().parent().and_then(ast::ExprStmt::cast);
+    let semicolon = match stmt.and_then(|it| it.semicolon_token()) {
+        Some(_) => "",
+        None => ";",
+    };
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_get_mut_match_to_entry", AssistKind::RefactorRewrite),
+        "Convert match to entry API",
+        target,
+        |builder| {
+            let update = update.dedent(IndentLevel(1));
+            // Only compute the inserted value when the key is missing, like the match did.
+            let insert = match is_constant(&value) {
+                true => format!("or_insert({value})"),
+                false => format!("or_insert_with(|| {value})"),
+            };
+            builder.replace(
+                target,
+                format!("{map}.entry({key}).and_modify(|{binding}| {update}).{insert}{semicolon}"),
+            );
+        },
+    )
+}
+
+fn is_map(ctx: &AssistContext<'_>, map: &ast::Expr) -> bool {
+    let Some(ty) = ctx.sema.type_of_expr(map) else { return false };
+    let Some(scope) = ctx.sema.scope(map.syntax()) else { return false };
+    let famous_defs = FamousDefs(&ctx.sema, scope.krate());
+    let Some(hir::Adt::Struct(adt)) = ty.original.strip_references().as_adt() else {
+        return false;
+    };
+    [famous_defs.std_collections_HashMap(), famous_defs.alloc_collections_BTreeMap()]
+        .contains(&Some(adt))
+}
+
+/// Whether `expr` is cheap enough to be evaluated even when the key is present.
+fn is_constant(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Literal(_) | ast::Expr::PathExpr(_) => true,
+        ast::Expr::PrefixExpr(it) => {
+            it.op_kind() == Some(ast::UnaryOp::Neg)
+                && matches!(it.expr(), Some(ast::Expr::Literal(_)))
+        }
+        _ => false,
+    }
+}
+
+/// Finds the `insert` call of a `None` arm doing nothing else.
+fn only_insert(expr: &ast::Expr) -> Option<ast::MethodCallExpr> {
+    let ast::Expr::BlockExpr(block) = expr else { return None };
+    let stmts = block.stmt_list()?;
+    if stmts.tail_expr().is_some() {
+        return None;
+    }
+    match stmts.statements().collect::<Vec<_>>().as_slice() {
+        [ast::Stmt::ExprStmt(stmt)] => match stmt.expr()? {
+            ast::Expr::MethodCallExpr(it) => Some(it),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const STD: &str = r#"//- /std.rs crate:std
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    impl<K, V> HashMap<K, V> {
+        pub fn get_mut(&mut self, k: &K) -> Option<&mut V> { loop {} }
+        pub fn insert(&mut self, k: K, v: V) -> Option<V> { loop {} }
+    }
+}
+"#;
+
+    #[test]
+    fn convert_increment_or_insert() {
+        check_assist(
+            convert_get_mut_match_to_entry,
+            &format!(
+                r#"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+struct Stats {{ hits: HashMap<u32, u64> }}
+
+fn hit(stats: &mut Stats, page: u32) {{
+    match$0 stats.hits.get_mut(&page) {{
+        None => {{
+            stats.hits.insert(page, 1);
+        }}
+        Some(hits) => *hits += 1,
+    }};
+}}
+{STD}"#
+            ),
+            r#"
+use std::collections::HashMap;
+
+struct Stats { hits: HashMap<u32, u64> }
+
+fn hit(stats: &mut Stats, page: u32) {
+    stats.hits.entry(page).and_modify(|hits| *hits += 1).or_insert(1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_float_increment_keeps_inserted_value() {
+        check_assist(
+            convert_get_mut_match_to_entry,
+            &format!(
+                r#"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+fn add(totals: &mut HashMap<u8, f64>, k: u8, x: f64) {{
+    match$0 totals.get_mut(&k) {{
+        Some(t) => *t += x,
+        None => {{
+            totals.insert(k, x);
+        }}
+    }}
+}}
+{STD}"#
+            ),
+            r#"
+use std::collections::HashMap;
+
+fn add(totals: &mut HashMap<u8, f64>, k: u8, x: f64) {
+    totals.entry(k).and_modify(|t| *t += x).or_insert(x);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_update_or_insert() {
+        check_assist(
+            convert_get_mut_match_to_entry,
+            &format!(
+                r#"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+fn scale(map: &mut HashMap<u8, u32>, k: u8) {{
+    match$0 map.get_mut(&k) {{
+        Some(v) => {{
+            *v *= 2;
+        }}
+        None => {{
+            map.insert(k, 1);
+        }}
+    }}
+}}
+{STD}"#
+            ),
+            r#"
+use std::collections::HashMap;
+
+fn scale(map: &mut HashMap<u8, u32>, k: u8) {
+    map.entry(k).and_modify(|v| {
+        *v *= 2;
+    }).or_insert(1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_computed_value_to_or_insert_with() {
+        check_assist(
+            convert_get_mut_match_to_entry,
+            &format!(
+                r#"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+struct Vec;
+impl Vec {{
+    fn new() -> Self {{ Vec }}
+    fn push(&mut self, n: u8) {{}}
+}}
+
+fn group(map: &mut HashMap<u8, Vec>, k: u8) {{
+    match$0 map.get_mut(&k) {{
+        Some(v) => v.push(k),
+        None => {{
+            map.insert(k, Vec::new());
+        }}
+    }}
+}}
+{STD}"#
+            ),
+            r#"
+use std::collections::HashMap;
+
+struct Vec;
+impl Vec {
+    fn new() -> Self { Vec }
+    fn push(&mut self, n: u8) {}
+}
+
+fn group(map: &mut HashMap<u8, Vec>, k: u8) {
+    map.entry(k).and_modify(|v| v.push(k)).or_insert_with(|| Vec::new());
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_none_arm_does_more() {
+        cov_mark::check!(convert_get_mut_match_to_entry_not_only_insert);
+        check_assist_not_applicable(
+            convert_get_mut_match_to_entry,
+            &format!(
+                r#"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+fn log(k: char) {{}}
+
+fn count(map: &mut HashMap<char, u32>, c: char) {{
+    match$0 map.get_mut(&c) {{
+        Some(n) => *n += 1,
+        None => {{
+            log(c);
+            map.insert(c, 1);
+        }}
+    }}
+}}
+{STD}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_maps() {
+        check_assist_not_applicable(
+            convert_get_mut_match_to_entry,
+            r#"
+//- minicore: option
+struct HashMap<K, V>(K, V);
+impl<K, V> HashMap<K, V> {
+    fn get_mut(&mut self, k: &K) -> Option<&mut V> { loop {} }
+    fn insert(&mut self, k: K, v: V) -> Option<V> { loop {} }
+}
+
+fn count(map: &mut HashMap<char, u32>, c: char) {
+    match$0 map.get_mut(&c) {
+        Some(n) => *n += 1,
+        None => {
+            map.insert(c, 1);
+        }
+    }
+}
+"#,
+        );
+    }
+}
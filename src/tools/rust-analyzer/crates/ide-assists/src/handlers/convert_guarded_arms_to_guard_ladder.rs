@@ -0,0 +1,211 @@
+use std::iter::successors;
+
+use syntax::{
+    algo::neighbor,
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode, HasAttrs,
+    },
+    Direction, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_guarded_arms_to_guard_ladder
+//
+// Merges consecutive match arms with the same pattern and different guards into a single
+// arm that binds the pattern once and checks the guards in a nested match.
+//
+// ```
+// fn classify(x: Option<u32>) -> u8 {
+//     match x {
+//         $0Some(n) if n > 10 => 2,
+//         Some(n) if n > 5 => 1,
+//         Some(n) => 0,
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn classify(x: Option<u32>) -> u8 {
+//     match x {
+//         Some(n) => match () {
+//             _ if n > 10 => 2,
+//             _ if n > 5 => 1,
+//             _ => 0,
+//         },
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn convert_guarded_arms_to_guard_ladder(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let current_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    current_arm.guard()?;
+    let pat = current_arm.pat()?;
+    let pat_text = pat.syntax().text();
+
+    // Same pattern text means the same bindings, so every guard and arm body still sees
+    // the bindings it was written against.
+    let mut arms = Vec::new();
+    for arm in successors(Some(current_arm.clone()), |it| neighbor(it, Direction::Next)) {
+        if arm.pat()?.syntax().text() != pat_text {
+            break;
+        }
+        if arm.attrs().next().is_some() {
+            return None;
+        }
+        let has_guard = arm.guard().is_some();
+        arms.push(arm);
+        if !has_guard {
+            break;
+        }
+    }
+    if arms.len() < 2 {
+        cov_mark::hit!(convert_guarded_arms_to_guard_ladder_different_patterns);
+        return None;
+    }
+    // A nested match can't fall through to the outer arms, so a catch-all is needed.
+    let last = arms.last()?;
+    if last.guard().is_some() {
+        cov_mark::hit!(convert_guarded_arms_to_guard_ladder_no_fallback);
+        return None;
+    }
+
+    let ladder_arms = arms
+        .iter()
+        .map(|arm| {
+            let guard = match arm.guard() {
+                Some(guard) => Some(guard.condition()?),
+                None => None,
+            };
+            // Arm bodies move one level deeper, into the nested match.
+            let expr = arm.expr()?.dedent(arm.indent_level()).indent(IndentLevel(1));
+            Some(make::match_arm([make::wildcard_pat().into()], guard, expr))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let target =
+        TextRange::new(current_arm.syntax().text_range().start(), last.syntax().text_range().end());
+    acc.add(
+        AssistId("convert_guarded_arms_to_guard_ladder", AssistKind::RefactorRewrite),
+        "Convert to guard ladder",
+        target,
+        |builder| {
+            let ladder = make::expr_match(make::expr_unit(), make::match_arm_list(ladder_arms))
+                .indent(current_arm.indent_level());
+            builder.replace(target, format!("{pat} => {ladder},"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_same_pattern_arms() {
+        check_assist(
+            convert_guarded_arms_to_guard_ladder,
+            r#"
+enum Shape { Rect { w: u32, h: u32 }, Dot }
+
+fn describe(shape: Shape) -> &'static str {
+    match shape {
+        Shape::Rect { w, h } $0if w == h => "square",
+        Shape::Rect { w, h } if w > h => {
+            "wide"
+        }
+        Shape::Rect { w, h } => "tall",
+        Shape::Dot => "dot",
+    }
+}
+"#,
+            r#"
+enum Shape { Rect { w: u32, h: u32 }, Dot }
+
+fn describe(shape: Shape) -> &'static str {
+    match shape {
+        Shape::Rect { w, h } => match () {
+            _ if w == h => "square",
+            _ if w > h => {
+                "wide"
+            }
+            _ => "tall",
+        },
+        Shape::Dot => "dot",
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_stops_at_first_unguarded_arm() {
+        check_assist(
+            convert_guarded_arms_to_guard_ladder,
+            r#"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        Some(n) $0if n > 10 => 2,
+        Some(n) => 1,
+        Some(n) => 0,
+        None => 0,
+    }
+}
+"#,
+            r#"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        Some(n) => match () {
+            _ if n > 10 => 2,
+            _ => 1,
+        },
+        Some(n) => 0,
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_patterns() {
+        cov_mark::check!(convert_guarded_arms_to_guard_ladder_different_patterns);
+        check_assist_not_applicable(
+            convert_guarded_arms_to_guard_ladder,
+            r#"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        Some(n) $0if n > 10 => 2,
+        Some(m) => 1,
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_fallback_arm() {
+        cov_mark::check!(convert_guarded_arms_to_guard_ladder_no_fallback);
+        check_assist_not_applicable(
+            convert_guarded_arms_to_guard_ladder,
+            r#"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        Some(n) $0if n > 10 => 2,
+        Some(n) if n > 5 => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
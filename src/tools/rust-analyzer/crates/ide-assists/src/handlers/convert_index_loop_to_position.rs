@@ -0,0 +1,360 @@
+use hir::PathResolution;
+use ide_db::{defs::Definition, famous_defs::FamousDefs, search::ReferenceCategory};
+use syntax::{
+    ast::{self, AstNode, HasArgList, HasLoopBody, HasName},
+    T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_index_loop_to_position
+//
+// Replaces a loop over an enumerated iterator that only records the index of the first item
+// matching a pattern with a call to `Iterator::position`.
+//
+// ```
+// # //- minicore: iterator, option
+// enum Cell { Empty, Full }
+//
+// fn first_empty(cells: impl Iterator<Item = Cell>) -> Option<usize> {
+//     let mut found = None;
+//     $0for (i, cell) in cells.enumerate() {
+//         match cell {
+//             Cell::Empty => {
+//                 found = Some(i);
+//                 break;
+//             }
+//             _ => {}
+//         }
+//     }
+//     found
+// }
+// ```
+// ->
+// ```
+// enum Cell { Empty, Full }
+//
+// fn first_empty(cells: impl Iterator<Item = Cell>) -> Option<usize> {
+//     let found = cells.into_iter().position(|cell| matches!(cell, Cell::Empty));
+//     found
+// }
+// ```
+pub(crate) fn convert_index_loop_to_position(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_expr = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let for_stmt = for_expr.syntax().parent().and_then(ast::ExprStmt::cast);
+    let loop_node = match &for_stmt {
+        Some(stmt) => stmt.syntax().clone(),
+        None => for_expr.syntax().clone(),
+    };
+    let let_stmt = loop_node.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let ast::Pat::IdentPat(found) = let_stmt.pat()? else { return None };
+    if !is_none(ctx, &let_stmt.initializer()?) {
+        return None;
+    }
+    let found_local = ctx.sema.to_def(&found)?;
+
+    let ast::Expr::MethodCallExpr(enumerate) = for_expr.iterable()? else { return None };
+    if enumerate.name_ref()?.text() != "enumerate" || enumerate.arg_list()?.args().next().is_some()
+    {
+        return None;
+    }
+    let iter = enumerate.receiver()?;
+    let krate = ctx.sema.scope(for_expr.syntax())?.krate();
+    let iterator = FamousDefs(&ctx.sema, krate).core_iter_Iterator()?;
+    if !ctx.sema.type_of_expr(&iter)?.original.impls_trait(ctx.db(), iterator, &[]) {
+        return None;
+    }
+    let ast::Pat::TuplePat(tuple) = for_expr.pat()? else { return None };
+    let (index, index_local, item) = match tuple.fields().collect::<Vec<_>>()[..] {
+        [ast::Pat::IdentPat(ref index), ast::Pat::IdentPat(ref item)] => {
+            (index.name()?.to_string(), ctx.sema.to_def(index)?, item.name()?.to_string())
+        }
+        _ => return None,
+    };
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+    let match_expr = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::MatchExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [hit, miss] = &arms[..] else { return None };
+    if !matches!(miss.pat()?, ast::Pat::WildcardPat(_))
+        || miss.guard().is_some()
+        || !is_empty(&miss.expr()?)
+        || !records_and_breaks(&hit.expr()?, &found.name()?.to_string(), &index)
+    {
+        cov_mark::hit!(convert_index_loop_to_position_extra_work);
+        return None;
+    }
+    let mut pattern = hit.pat()?.to_string();
+    if let Some(guard) = hit.guard() {
+        pattern = format!("{pattern} {guard}");
+    }
+    let scrutinee = match_expr.expr()?;
+    // The closure passed to `position` only gets the item.
+    let uses_index = scrutinee.syntax().descendants().chain(
+        hit.guard().into_iter().flat_map(|it| it.syntax().descendants()),
+    )
+    .filter_map(ast::Path::cast)
+    .any(|path| {
+        matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if it == index_local)
+    });
+    if uses_index {
+        cov_mark::hit!(convert_index_loop_to_position_index_used);
+        return None;
+    }
+    // `position` takes the iterator by mutable reference, which an immutable binding or a field
+    // can't give out, so these are moved into a fresh iterator like `enumerate` did.
+    let iter = match &iter {
+        ast::Expr::PathExpr(path) => match path.path().and_then(|it| ctx.sema.resolve_path(&it)) {
+            Some(PathResolution::Local(local))
+                if !local.is_mut(ctx.db()) && !local.ty(ctx.db()).is_mutable_reference() =>
+            {
+                format!("{iter}.into_iter()")
+            }
+            _ => iter.to_string(),
+        },
+        ast::Expr::FieldExpr(_) | ast::Expr::IndexExpr(_) => format!("{iter}.into_iter()"),
+        _ => iter.to_string(),
+    };
+
+    // The loop was the only thing assigning to the binding, unless something else does too.
+    let writes = Definition::Local(found_local)
+        .usages(&ctx.sema)
+        .all()
+        .iter()
+        .flat_map(|(_, refs)| refs.iter())
+        .filter(|it| it.category == Some(ReferenceCategory::Write))
+        .count();
+    let keep_mut = found.mut_token().is_some() && writes > 1;
+
+    let target = let_stmt.syntax().text_range().cover(loop_node.text_range());
+    acc.add(
+        AssistId("convert_index_loop_to_position", AssistKind::RefactorRewrite),
+        "Convert loop to `position`",
+        target,
+        |builder| {
+            let name = found.name().map(|it| it.to_string()).unwrap_or_default();
+            let binding = if keep_mut { format!("mut {name}") } else { name };
+            let ty = let_stmt.ty().map(|it| format!(": {it}")).unwrap_or_default();
+            builder.replace(
+                target,
+                format!(
+                    "let {binding}{ty} = {iter}.position(|{item}| matches!({scrutinee}, {pattern}));"
+                ),
+            );
+        },
+    )
+}
+
+fn is_none(ctx: &AssistContext<'_>, expr: &ast::Expr) -> bool {
+    let ast::Expr::PathExpr(path) = expr else { return false };
+    matches!(
+        path.path().and_then(|it| ctx.sema.resolve_path(&it)),
+        Some(PathResolution::Def(hir::ModuleDef::Variant(it)))
+            if it.name(ctx.db()).to_smol_str() == "None"
+    )
+}
+
+fn is_empty(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::TupleExpr(it) => it.fields().next().is_none(),
+        ast::Expr::BlockExpr(it) => it
+            .stmt_list()
+            .map_or(false, |it| it.statements().next().is_none() && it.tail_expr().is_none()),
+        _ => false,
+    }
+}
+
+/// Checks that `expr` is `{ found = Some(index); break; }`.
+fn records_and_breaks(expr: &ast::Expr, found: &str, index: &str) -> bool {
+    let ast::Expr::BlockExpr(block) = expr else { return false };
+    let Some(stmts) = block.stmt_list() else { return false };
+    let exprs = stmts
+        .statements()
+        .map(|stmt| match stmt {
+            ast::Stmt::ExprStmt(it) => it.expr(),
+            _ => None,
+        })
+        .chain(stmts.tail_expr().map(Some))
+        .collect::<Option<Vec<_>>>();
+    let Some([ast::Expr::BinExpr(assign), ast::Expr::BreakExpr(brk)]) = exprs.as_deref() else {
+        return false;
+    };
+    if brk.expr().is_some() || brk.lifetime().is_some() {
+        return false;
+    }
+    let lhs_ok = assign.lhs().map_or(false, |it| it.syntax().text() == found);
+    let rhs_ok = match assign.rhs() {
+        Some(ast::Expr::CallExpr(call)) => {
+            let args = call.arg_list().map(|it| it.args().collect::<Vec<_>>()).unwrap_or_default();
+            call.expr().map_or(false, |it| it.syntax().text() == "Some")
+                && matches!(&args[..], [arg] if arg.syntax().text() == index)
+        }
+        _ => false,
+    };
+    matches!(assign.op_kind(), Some(ast::BinaryOp::Assignment { op: None })) && lhs_ok && rhs_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_find_first_index_loop() {
+        check_assist(
+            convert_index_loop_to_position,
+            r#"
+//- minicore: iterator, option
+enum Token { Word(u32), Comma, End }
+
+fn split_at<'a>(tokens: impl Iterator<Item = &'a Token>, limit: u32) -> Option<usize> {
+    let mut at: Option<usize> = None;
+    f$0or (idx, tok) in tokens.enumerate() {
+        match *tok {
+            Token::Word(n) if n > limit => {
+                at = Some(idx);
+                break;
+            }
+            _ => (),
+        }
+    }
+    at
+}
+"#,
+            r#"
+enum Token { Word(u32), Comma, End }
+
+fn split_at<'a>(tokens: impl Iterator<Item = &'a Token>, limit: u32) -> Option<usize> {
+    let at: Option<usize> = tokens.into_iter().position(|tok| matches!(*tok, Token::Word(n) if n > limit));
+    at
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keeps_mut_with_later_writes() {
+        check_assist(
+            convert_index_loop_to_position,
+            r#"
+//- minicore: iterator, option
+fn find(items: impl Iterator<Item = u8>) -> Option<usize> {
+    let mut found = None;
+    $0for (i, item) in items.enumerate() {
+        match item {
+            0 => {
+                found = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    if found.is_none() {
+        found = Some(0);
+    }
+    found
+}
+"#,
+            r#"
+fn find(items: impl Iterator<Item = u8>) -> Option<usize> {
+    let mut found = items.into_iter().position(|item| matches!(item, 0));
+    if found.is_none() {
+        found = Some(0);
+    }
+    found
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_loop_does_more() {
+        cov_mark::check!(convert_index_loop_to_position_extra_work);
+        check_assist_not_applicable(
+            convert_index_loop_to_position,
+            r#"
+//- minicore: iterator, option
+fn find(items: impl Iterator<Item = u8>, seen: &mut u32) -> Option<usize> {
+    let mut found = None;
+    $0for (i, item) in items.enumerate() {
+        match item {
+            0 => {
+                found = Some(i);
+                break;
+            }
+            _ => *seen += 1,
+        }
+    }
+    found
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_mutable_iterator_in_place() {
+        check_assist(
+            convert_index_loop_to_position,
+            r#"
+//- minicore: iterator, option
+fn find(mut items: impl Iterator<Item = u8>) -> Option<usize> {
+    let mut found = None;
+    $0for (i, item) in items.enumerate() {
+        match item {
+            0 => {
+                found = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    found
+}
+"#,
+            r#"
+fn find(mut items: impl Iterator<Item = u8>) -> Option<usize> {
+    let found = items.position(|item| matches!(item, 0));
+    found
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_guard_uses_index() {
+        cov_mark::check!(convert_index_loop_to_position_index_used);
+        check_assist_not_applicable(
+            convert_index_loop_to_position,
+            r#"
+//- minicore: iterator, option
+fn find(items: impl Iterator<Item = u8>) -> Option<usize> {
+    let mut found = None;
+    $0for (i, item) in items.enumerate() {
+        match item {
+            0 if i > 2 => {
+                found = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    found
+}
+"#,
+        );
+    }
+}
@@ -1,4 +1,3 @@
-use hir::known;
 use ide_db::famous_defs::FamousDefs;
 use stdx::format_to;
 use syntax::{
@@ -6,7 +5,7 @@
     AstNode,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{utils::iterator_of, AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: convert_iter_for_each_to_for
 //
@@ -112,25 +111,7 @@ pub(crate) fn convert_for_loop_with_for_each(
         "Replace this for loop with `Iterator::for_each`",
         for_loop.syntax().text_range(),
         |builder| {
-            let mut buf = String::new();
-
-            if let Some((expr_behind_ref, method)) =
-                is_ref_and_impls_iter_method(&ctx.sema, &iterable)
-            {
-                // We have either "for x in &col" and col implements a method called iter
-                //             or "for x in &mut col" and col implements a method called iter_mut
-                format_to!(buf, "{expr_behind_ref}.{method}()");
-            } else if let ast::Expr::RangeExpr(..) = iterable {
-                // range expressions need to be parenthesized for the syntax to be correct
-                format_to!(buf, "({iterable})");
-            } else if impls_core_iter(&ctx.sema, &iterable) {
-                format_to!(buf, "{iterable}");
-            } else if let ast::Expr::RefExpr(_) = iterable {
-                format_to!(buf, "({iterable}).into_iter()");
-            } else {
-                format_to!(buf, "{iterable}.into_iter()");
-            }
-
+            let mut buf = iterator_of(&ctx.sema, &iterable);
             format_to!(buf, ".for_each(|{pat}| {body});");
 
             builder.replace(for_loop.syntax().text_range(), buf)
@@ -138,61 +119,6 @@ pub(crate) fn convert_for_loop_with_for_each(
     )
 }
 
-/// If iterable is a reference where the expression behind the reference implements a method
-/// returning an Iterator called iter or iter_mut (depending on the type of reference) then return
-/// the expression behind the reference and the method name
-fn is_ref_and_impls_iter_method(
-    sema: &hir::Semantics<'_, ide_db::RootDatabase>,
-    iterable: &ast::Expr,
-) -> Option<(ast::Expr, hir::Name)> {
-    let ref_expr = match iterable {
-        ast::Expr::RefExpr(r) => r,
-        _ => return None,
-    };
-    let wanted_method = if ref_expr.mut_token().is_some() { known::iter_mut } else { known::iter };
-    let expr_behind_ref = ref_expr.expr()?;
-    let ty = sema.type_of_expr(&expr_behind_ref)?.adjusted();
-    let scope = sema.scope(iterable.syntax())?;
-    let krate = scope.krate();
-    let iter_trait = FamousDefs(sema, krate).core_iter_Iterator()?;
-
-    let has_wanted_method = ty
-        .iterate_method_candidates(
-            sema.db,
-            &scope,
-            &scope.visible_traits().0,
-            None,
-            Some(&wanted_method),
-            |func| {
-                if func.ret_type(sema.db).impls_trait(sema.db, iter_trait, &[]) {
-                    return Some(());
-                }
-                None
-            },
-        )
-        .is_some();
-    if !has_wanted_method {
-        return None;
-    }
-
-    Some((expr_behind_ref, wanted_method))
-}
-
-/// Whether iterable implements core::Iterator
-fn impls_core_iter(sema: &hir::Semantics<'_, ide_db::RootDatabase>, iterable: &ast::Expr) -> bool {
-    (|| {
-        let it_typ = sema.type_of_expr(iterable)?.adjusted();
-
-        let module = sema.scope(iterable.syntax())?.module();
-
-        let krate = module.krate();
-        let iter_trait = FamousDefs(sema, krate).core_iter_Iterator()?;
-        cov_mark::hit!(test_already_impls_iterator);
-        Some(it_typ.impls_trait(sema.db, iter_trait, &[]))
-    })()
-    .unwrap_or(false)
-}
-
 fn validate_method_call_expr(
     ctx: &AssistContext<'_>,
     expr: ast::MethodCallExpr,
@@ -0,0 +1,240 @@
+use hir::PathResolution;
+use ide_db::FxHashSet;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasLoopBody},
+    match_ast, SyntaxNode, T,
+};
+
+use crate::{utils::iterator_of, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_loop_to_for_each
+//
+// Replaces a `for` loop that only runs a match for its side effects on each item with a
+// `for_each` call doing the match.
+//
+// ```
+// # //- minicore: iterator
+// enum Event { Click(u32), Key(char) }
+// fn click(x: u32) {}
+//
+// fn dispatch(events: impl Iterator<Item = Event>) {
+//     $0for event in events {
+//         match event {
+//             Event::Click(x) => click(x),
+//             Event::Key(_) => {}
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Event { Click(u32), Key(char) }
+// fn click(x: u32) {}
+//
+// fn dispatch(events: impl Iterator<Item = Event>) {
+//     events.for_each(|event| match event {
+//         Event::Click(x) => click(x),
+//         Event::Key(_) => {}
+//     });
+// }
+// ```
+pub(crate) fn convert_match_loop_to_for_each(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_loop = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let body = for_loop.loop_body()?.stmt_list()?;
+    let match_expr = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::MatchExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if !ctx.sema.type_of_expr(&match_expr.clone().into())?.original.is_unit() {
+        return None;
+    }
+    if escapes_closure(&for_loop, &match_expr) {
+        return None;
+    }
+    if carries_state(ctx, &for_loop, &match_expr) {
+        cov_mark::hit!(convert_match_loop_to_for_each_loop_carried_state);
+        return None;
+    }
+    let iterable = for_loop.iterable()?;
+    let pat = for_loop.pat()?;
+
+    acc.add(
+        AssistId("convert_match_loop_to_for_each", AssistKind::RefactorRewrite),
+        "Convert loop to `for_each` with the match",
+        for_loop.syntax().text_range(),
+        |builder| {
+            // The match was nested in the loop body.
+            let match_text = match_expr.to_string().replace(&format!("\n{}", IndentLevel(1)), "\n");
+            let iter = iterator_of(&ctx.sema, &iterable);
+            builder.replace(
+                for_loop.syntax().text_range(),
+                format!("{iter}.for_each(|{pat}| {match_text});"),
+            );
+        },
+    )
+}
+
+/// Checks for control flow that can't leave a closure, or leaves it instead of the loop.
+fn escapes_closure(for_loop: &ast::ForExpr, match_expr: &ast::MatchExpr) -> bool {
+    match_expr.syntax().descendants().any(|node| {
+        match_ast! {
+            match node {
+                ast::ReturnExpr(_) => true,
+                ast::TryExpr(_) => true,
+                ast::AwaitExpr(_) => true,
+                ast::BreakExpr(it) => it.lifetime().is_some() || targets(&node, for_loop),
+                ast::ContinueExpr(it) => it.lifetime().is_some() || targets(&node, for_loop),
+                _ => false,
+            }
+        }
+    })
+}
+
+/// Checks whether an unlabeled `break` or `continue` belongs to `for_loop`.
+fn targets(node: &SyntaxNode, for_loop: &ast::ForExpr) -> bool {
+    for ancestor in node.ancestors() {
+        if &ancestor == for_loop.syntax() {
+            return true;
+        }
+        if ast::ForExpr::can_cast(ancestor.kind())
+            || ast::WhileExpr::can_cast(ancestor.kind())
+            || ast::LoopExpr::can_cast(ancestor.kind())
+            || ast::ClosureExpr::can_cast(ancestor.kind())
+        {
+            return false;
+        }
+    }
+    false
+}
+
+/// Checks whether the arms assign to a variable declared outside the loop, which then carries
+/// state from one item to the next.
+fn carries_state(
+    ctx: &AssistContext<'_>,
+    for_loop: &ast::ForExpr,
+    match_expr: &ast::MatchExpr,
+) -> bool {
+    let inner = for_loop
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| ctx.sema.to_def(&it))
+        .collect::<FxHashSet<_>>();
+    match_expr.syntax().descendants().filter_map(ast::BinExpr::cast).any(|bin| {
+        let Some(ast::BinaryOp::Assignment { .. }) = bin.op_kind() else { return false };
+        let Some(ast::Expr::PathExpr(lhs)) = bin.lhs() else { return false };
+        match lhs.path().and_then(|it| ctx.sema.resolve_path(&it)) {
+            Some(PathResolution::Local(local)) => !inner.contains(&local),
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_side_effect_match() {
+        check_assist(
+            convert_match_loop_to_for_each,
+            r#"
+//- minicore: iterator
+struct Log;
+impl Log {
+    fn line(&mut self, s: &str) {}
+}
+enum Level { Warn, Error(u32) }
+
+fn report(levels: impl Iterator<Item = Level>, log: &mut Log) {
+    f$0or level in levels {
+        match level {
+            Level::Warn => log.line("warn"),
+            Level::Error(code) => {
+                let mut n = code;
+                n += 1;
+                for _ in 0..n {
+                    continue;
+                }
+                log.line("error");
+            }
+        };
+    }
+}
+"#,
+            r#"
+struct Log;
+impl Log {
+    fn line(&mut self, s: &str) {}
+}
+enum Level { Warn, Error(u32) }
+
+fn report(levels: impl Iterator<Item = Level>, log: &mut Log) {
+    levels.for_each(|level| match level {
+        Level::Warn => log.line("warn"),
+        Level::Error(code) => {
+            let mut n = code;
+            n += 1;
+            for _ in 0..n {
+                continue;
+            }
+            log.line("error");
+        }
+    });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_loop_carried_state() {
+        cov_mark::check!(convert_match_loop_to_for_each_loop_carried_state);
+        check_assist_not_applicable(
+            convert_match_loop_to_for_each,
+            r#"
+//- minicore: iterator, option
+fn count(items: impl Iterator<Item = Option<u8>>) {
+    let mut missing = 0;
+    $0for item in items {
+        match item {
+            Some(_) => {}
+            None => missing += 1,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_continue() {
+        check_assist_not_applicable(
+            convert_match_loop_to_for_each,
+            r#"
+//- minicore: iterator, option
+fn show(x: u8) {}
+
+fn all(items: impl Iterator<Item = Option<u8>>) {
+    $0for item in items {
+        match item {
+            Some(x) => show(x),
+            None => continue,
+        }
+    }
+}
+"#,
+        );
+    }
+}
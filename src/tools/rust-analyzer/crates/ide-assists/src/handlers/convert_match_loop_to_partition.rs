@@ -0,0 +1,283 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, AstNode, HasArgList, HasLoopBody, HasName},
+    T,
+};
+
+use crate::{utils::new_vec_binding, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_loop_to_partition
+//
+// Replaces a loop that pushes every item into one of two new vectors, depending on which of
+// two match arms it hits, with a call to `Iterator::partition`.
+//
+// ```
+// # //- minicore: iterator, option
+// # struct Vec<T>(T);
+// # impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+// fn split(items: impl Iterator<Item = Option<u32>>) {
+//     let mut some = Vec::new();
+//     let mut none = Vec::new();
+//     $0for item in items {
+//         match item {
+//             Some(_) => some.push(item),
+//             None => none.push(item),
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// # struct Vec<T>(T);
+// # impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+// fn split(items: impl Iterator<Item = Option<u32>>) {
+//     let (some, none): (Vec<_>, Vec<_>) = items.partition(|item| matches!(*item, Some(_)));
+// }
+// ```
+pub(crate) fn convert_match_loop_to_partition(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_expr = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let loop_node = match for_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(stmt) => stmt.syntax().clone(),
+        None => for_expr.syntax().clone(),
+    };
+    let ast::Pat::IdentPat(item) = for_expr.pat()? else { return None };
+    if item.ref_token().is_some() || item.pat().is_some() {
+        return None;
+    }
+    let item = item.name()?.to_string();
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+    let match_expr = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::MatchExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    if match_expr.expr()?.syntax().text() != item.as_str() {
+        return None;
+    }
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [matching, rest] = &arms[..] else {
+        cov_mark::hit!(convert_match_loop_to_partition_not_two_way);
+        return None;
+    };
+    if matching.guard().is_some() || rest.guard().is_some() {
+        return None;
+    }
+    let left = pushed_to(&matching.expr()?, &item)?;
+    let right = pushed_to(&rest.expr()?, &item)?;
+    if left == right {
+        return None;
+    }
+
+    // Both vectors have to be created right before the loop.
+    let first_let = loop_node.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let second_let = first_let.syntax().prev_sibling().and_then(ast::LetStmt::cast)?;
+    let target = second_let.syntax().text_range().cover(loop_node.text_range());
+    let mut declared =
+        [new_vec_binding(ctx, &first_let, target)?, new_vec_binding(ctx, &second_let, target)?];
+    declared.sort();
+    let mut pushed = [left.clone(), right.clone()];
+    pushed.sort();
+    if declared.iter().map(|(name, _)| name).ne(&pushed) {
+        return None;
+    }
+    let binding = |name: &str| match declared.iter().any(|(it, keep_mut)| it == name && *keep_mut) {
+        true => format!("mut {name}"),
+        false => name.to_owned(),
+    };
+    let (left, right) = (binding(&left), binding(&right));
+
+    let iterable = for_expr.iterable()?;
+    let krate = ctx.sema.scope(for_expr.syntax())?.krate();
+    let iterator = FamousDefs(&ctx.sema, krate).core_iter_Iterator()?;
+    let is_iterator =
+        ctx.sema.type_of_expr(&iterable)?.original.impls_trait(ctx.db(), iterator, &[]);
+    let pat = matching.pat()?;
+    // `partition` hands out references, which can only be dereferenced for patterns that
+    // don't move anything out.
+    let binds = pat
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .any(|it| ctx.sema.to_def(&it).is_some());
+    let scrutinee = if binds { item.clone() } else { format!("*{item}") };
+
+    acc.add(
+        AssistId("convert_match_loop_to_partition", AssistKind::RefactorRewrite),
+        "Convert loop to `partition`",
+        target,
+        |builder| {
+            let iter = match (&iterable, is_iterator) {
+                (_, true) => iterable.to_string(),
+                (ast::Expr::PathExpr(_) | ast::Expr::MethodCallExpr(_), false) => {
+                    format!("{iterable}.into_iter()")
+                }
+                (_, false) => format!("({iterable}).into_iter()"),
+            };
+            builder.replace(
+                target,
+                format!(
+                    "let ({left}, {right}): (Vec<_>, Vec<_>) = \
+                     {iter}.partition(|{item}| matches!({scrutinee}, {pat}));"
+                ),
+            );
+        },
+    )
+}
+
+/// Returns the name of the vector `expr` pushes `item` to.
+fn pushed_to(expr: &ast::Expr, item: &str) -> Option<String> {
+    let expr = match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmts = block.stmt_list()?;
+            match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+                ([], Some(tail)) => tail,
+                ([ast::Stmt::ExprStmt(stmt)], None) => stmt.expr()?,
+                _ => return None,
+            }
+        }
+        _ => expr.clone(),
+    };
+    let ast::Expr::MethodCallExpr(call) = expr else { return None };
+    if call.name_ref()?.text() != "push" {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    if args.next()?.syntax().text() != item || args.next().is_some() {
+        return None;
+    }
+    let ast::Expr::PathExpr(receiver) = call.receiver()? else { return None };
+    let receiver = receiver.path()?;
+    receiver.qualifier().is_none().then(|| receiver.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_binary_partition_loop() {
+        check_assist(
+            convert_match_loop_to_partition,
+            r#"
+//- minicore: iterator, option
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+enum Token { Word(u32), Space }
+
+fn split(tokens: &[Token]) {
+    let mut spaces = Vec::new();
+    let mut words = Vec::new();
+    f$0or token in tokens {
+        match token {
+            Token::Space => {
+                spaces.push(token);
+            }
+            Token::Word(_) => words.push(token),
+        }
+    }
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+enum Token { Word(u32), Space }
+
+fn split(tokens: &[Token]) {
+    let (spaces, words): (Vec<_>, Vec<_>) = tokens.into_iter().partition(|token| matches!(*token, Token::Space));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keep_vector_mutable_if_changed_after_the_loop() {
+        check_assist(
+            convert_match_loop_to_partition,
+            r#"
+//- minicore: iterator, option
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+    fn len(&self) -> usize { 0 }
+}
+enum Token { Word(u32), Space }
+
+fn split<'a>(tokens: &'a [Token], end: &'a Token) -> usize {
+    let mut spaces = Vec::new();
+    let mut words = Vec::new();
+    f$0or token in tokens {
+        match token {
+            Token::Space => spaces.push(token),
+            Token::Word(_) => words.push(token),
+        }
+    }
+    spaces.push(end);
+    spaces.len() + words.len()
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+    fn len(&self) -> usize { 0 }
+}
+enum Token { Word(u32), Space }
+
+fn split<'a>(tokens: &'a [Token], end: &'a Token) -> usize {
+    let (mut spaces, words): (Vec<_>, Vec<_>) = tokens.into_iter().partition(|token| matches!(*token, Token::Space));
+    spaces.push(end);
+    spaces.len() + words.len()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_three_way_split() {
+        cov_mark::check!(convert_match_loop_to_partition_not_two_way);
+        check_assist_not_applicable(
+            convert_match_loop_to_partition,
+            r#"
+//- minicore: iterator, option
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+
+fn split(items: impl Iterator<Item = u8>) {
+    let mut zeros = Vec::new();
+    let mut ones = Vec::new();
+    let mut others = Vec::new();
+    $0for item in items {
+        match item {
+            0 => zeros.push(item),
+            1 => ones.push(item),
+            _ => others.push(item),
+        }
+    }
+}
+"#,
+        );
+    }
+}
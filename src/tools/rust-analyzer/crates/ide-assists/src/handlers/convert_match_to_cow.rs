@@ -0,0 +1,280 @@
+use hir::{ModuleDef, PathResolution};
+use ide_db::{
+    famous_defs::FamousDefs,
+    helpers::mod_path_to_ast,
+    imports::insert_use::{insert_use, ImportScope},
+};
+use syntax::{
+    ast::{self, make, AstNode},
+    ted,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_cow
+//
+// Unifies match arms producing a mix of `&str` and `String` by wrapping them in
+// `Cow::Borrowed` and `Cow::Owned`.
+//
+// ```
+// # //- /main.rs crate:main deps:std,alloc
+// use std::string::String;
+//
+// fn describe(n: u32) -> String { String }
+//
+// fn name(n: u32) {
+//     let name = $0match n {
+//         0 => "zero",
+//         n => describe(n),
+//     };
+// }
+// # //- /std.rs crate:std deps:alloc
+// # pub use alloc::{borrow, string};
+// # //- /alloc.rs crate:alloc
+// # pub mod string { pub struct String; }
+// # pub mod borrow { pub enum Cow<'a, B: ?Sized> { Borrowed(&'a B), Owned(crate::string::String) } }
+// ```
+// ->
+// ```
+// use std::{string::String, borrow::Cow};
+//
+// fn describe(n: u32) -> String { String }
+//
+// fn name(n: u32) {
+//     let name = match n {
+//         0 => Cow::Borrowed("zero"),
+//         n => Cow::Owned(describe(n)),
+//     };
+// }
+// ```
+pub(crate) fn convert_match_to_cow(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let famous_defs = FamousDefs(&ctx.sema, scope.krate());
+    let (string, cow) = (famous_defs.alloc_string_String()?, famous_defs.alloc_borrow_Cow()?);
+    let arm_exprs =
+        match_expr.match_arm_list()?.arms().map(|arm| arm.expr()).collect::<Option<Vec<_>>>()?;
+
+    let kinds = arm_exprs
+        .iter()
+        .map(|expr| {
+            let ty = ctx.sema.type_of_expr(expr)?.original;
+            let is_str_ref = ty
+                .as_reference()
+                .and_then(|(inner, _)| inner.as_builtin())
+                .map_or(false, |it| it.is_str());
+            if is_str_ref {
+                Some(CowKind::Borrowed)
+            } else if ty.as_adt() == Some(hir::Adt::Struct(string)) {
+                Some(CowKind::Owned)
+            } else {
+                None
+            }
+        })
+        .collect::<Option<Vec<_>>>();
+    let kinds = match kinds {
+        Some(it) => it,
+        None => {
+            cov_mark::hit!(convert_match_to_cow_not_strings);
+            return None;
+        }
+    };
+    // With a single kind there is nothing to unify.
+    if !kinds.contains(&CowKind::Borrowed) || !kinds.contains(&CowKind::Owned) {
+        return None;
+    }
+
+    let cow_in_scope = matches!(
+        scope.speculative_resolve(&make::ext::ident_path("Cow")),
+        Some(PathResolution::Def(ModuleDef::Adt(hir::Adt::Enum(it)))) if it == cow
+    );
+    let cow_path = scope.module().find_use_path(
+        ctx.db(),
+        ModuleDef::Adt(hir::Adt::Enum(cow)),
+        ctx.config.prefer_no_std,
+    )?;
+    let import_scope = ImportScope::find_insert_use_container(match_expr.syntax(), &ctx.sema)?;
+
+    acc.add(
+        AssistId("convert_match_to_cow", AssistKind::RefactorRewrite),
+        "Convert match arms to `Cow`",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let arm_exprs =
+                arm_exprs.into_iter().map(|expr| builder.make_mut(expr)).collect::<Vec<_>>();
+            let import_scope = match import_scope {
+                ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+            };
+
+            for (expr, kind) in arm_exprs.iter().zip(kinds) {
+                let ctor = match kind {
+                    CowKind::Borrowed => "Cow::Borrowed",
+                    CowKind::Owned => "Cow::Owned",
+                };
+                let wrapped = make::expr_call(
+                    make::expr_path(make::path_from_text(ctor)),
+                    make::arg_list([expr.clone_subtree()]),
+                )
+                .clone_for_update();
+                ted::replace(expr.syntax(), wrapped.syntax());
+            }
+            if !cow_in_scope {
+                insert_use(&import_scope, mod_path_to_ast(&cow_path), &ctx.config.insert_use);
+            }
+        },
+    )
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CowKind {
+    Borrowed,
+    Owned,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const STD: &str = r#"//- /std.rs crate:std deps:alloc
+pub use alloc::{borrow, string};
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+pub mod borrow { pub enum Cow<'a, B: ?Sized> { Borrowed(&'a B), Owned(crate::string::String) } }
+"#;
+
+    #[test]
+    fn convert_mixed_borrowed_and_owned() {
+        check_assist(
+            convert_match_to_cow,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:std,alloc
+use std::string::String;
+
+fn describe(n: u32) -> String {{ String }}
+
+fn name(n: u32) {{
+    let name = match$0 n {{
+        0 => "zero",
+        1 => "one",
+        n => describe(n),
+    }};
+}}
+{STD}"#
+            ),
+            r#"
+use std::{string::String, borrow::Cow};
+
+fn describe(n: u32) -> String { String }
+
+fn name(n: u32) {
+    let name = match n {
+        0 => Cow::Borrowed("zero"),
+        1 => Cow::Borrowed("one"),
+        n => Cow::Owned(describe(n)),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_cow_in_scope() {
+        check_assist(
+            convert_match_to_cow,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:std,alloc
+use std::{{borrow::Cow, string::String}};
+
+fn describe(s: &str) -> String {{ String }}
+
+fn name(s: &str, owned: bool) {{
+    let name = match$0 owned {{
+        false => s,
+        true => describe(s),
+    }};
+}}
+{STD}"#
+            ),
+            r#"
+use std::{borrow::Cow, string::String};
+
+fn describe(s: &str) -> String { String }
+
+fn name(s: &str, owned: bool) {
+    let name = match owned {
+        false => Cow::Borrowed(s),
+        true => Cow::Owned(describe(s)),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_string_arms() {
+        cov_mark::check!(convert_match_to_cow_not_strings);
+        check_assist_not_applicable(
+            convert_match_to_cow,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:std,alloc
+use std::string::String;
+
+fn describe(n: u32) -> String {{ String }}
+
+fn name(n: u32) {{
+    let name = match$0 n {{
+        0 => "zero",
+        1 => 1,
+        n => describe(n),
+    }};
+}}
+{STD}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_all_arms_borrowed() {
+        check_assist_not_applicable(
+            convert_match_to_cow,
+            r#"
+fn name(n: u32) -> &'static str {
+    match$0 n {
+        0 => "zero",
+        _ => "many",
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_string_types() {
+        cov_mark::check!(convert_match_to_cow_not_strings);
+        check_assist_not_applicable(
+            convert_match_to_cow,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:std,alloc
+struct String;
+
+fn describe(n: u32) -> String {{ String }}
+
+fn name(n: u32) {{
+    let name = match$0 n {{
+        0 => "zero",
+        n => describe(n),
+    }};
+}}
+{STD}"#
+            ),
+        );
+    }
+}
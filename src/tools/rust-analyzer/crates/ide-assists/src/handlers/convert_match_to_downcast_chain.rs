@@ -0,0 +1,236 @@
+use std::iter;
+
+use hir::{HirDisplay, PathResolution};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{
+    self,
+    edit::{AstNodeEdit, IndentLevel},
+    make, AstNode,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_downcast_chain
+//
+// Turns a match on an enum whose variants wrap concrete types into a chain of
+// `downcast_ref` calls, as a starting point for replacing the enum with `dyn Any`.
+// The result only compiles once the matched value has been changed to a `dyn Any`.
+//
+// ```
+// struct Circle;
+// struct Square;
+// enum Shape { Circle(Circle), Square(Square) }
+//
+// fn draw(shape: &Shape) {
+//     $0match shape {
+//         Shape::Circle(c) => circle(c),
+//         Shape::Square(s) => square(s),
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Circle;
+// struct Square;
+// enum Shape { Circle(Circle), Square(Square) }
+//
+// fn draw(shape: &Shape) {
+//     if let Some(c) = shape.downcast_ref::<Circle>() {
+//         circle(c)
+//     } else if let Some(s) = shape.downcast_ref::<Square>() {
+//         square(s)
+//     } else {
+//         ${0:todo!()}
+//     }
+// }
+// ```
+pub(crate) fn convert_match_to_downcast_chain(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+
+    let mut candidates = Vec::new();
+    let mut fallback = None;
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() || fallback.is_some() {
+            return None;
+        }
+        let body = arm.expr()?;
+        let pat = match arm.pat()? {
+            ast::Pat::WildcardPat(_) => {
+                fallback = Some(body);
+                continue;
+            }
+            ast::Pat::TupleStructPat(it) => it,
+            _ => return None,
+        };
+        let variant = match ctx.sema.resolve_path(&pat.path()?)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it)) => it,
+            _ => return None,
+        };
+        let payload = match &*variant.fields(ctx.db()) {
+            [field] => field.ty(ctx.db()),
+            _ => return None,
+        };
+        let binding = pat.fields().exactly_one().ok()?;
+        let ty = payload.display_source_code(ctx.db(), module.into()).ok()?;
+        candidates.push((binding, ty, body));
+    }
+    if candidates.is_empty() {
+        cov_mark::hit!(convert_match_to_downcast_chain_no_candidates);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_downcast_chain", AssistKind::RefactorRewrite),
+        "Convert match to `downcast_ref` chain (requires `dyn Any`)",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let mut buf = String::new();
+            for (binding, ty, body) in &candidates {
+                let block = make_block_expr(body.reset_indent()).indent(indent);
+                format_to!(
+                    buf,
+                    "if let Some({binding}) = {scrutinee}.downcast_ref::<{ty}>() {block} else "
+                );
+            }
+            match (&fallback, ctx.config.snippet_cap) {
+                (None, Some(cap)) => {
+                    format_to!(buf, "{{\n{indent}    ${{0:todo!()}}\n{indent}}}");
+                    builder.replace_snippet(cap, target, buf);
+                }
+                (fallback, _) => {
+                    let fallback = fallback.clone().unwrap_or_else(make::ext::expr_todo);
+                    format_to!(buf, "{}", make_block_expr(fallback.reset_indent()).indent(indent));
+                    builder.replace(target, buf);
+                }
+            }
+        },
+    )
+}
+
+fn make_block_expr(expr: ast::Expr) -> ast::BlockExpr {
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => block,
+        expr => make::block_expr(iter::empty(), Some(expr)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_no_snippet_cap, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_candidates() {
+        check_assist(
+            convert_match_to_downcast_chain,
+            r#"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square), Empty }
+
+fn area(shape: &Shape) -> u32 {
+    match$0 shape {
+        Shape::Circle(c) => 3,
+        Shape::Square(_) => {
+            let side = 2;
+            side * side
+        }
+        _ => 0,
+    }
+}
+"#,
+            r#"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square), Empty }
+
+fn area(shape: &Shape) -> u32 {
+    if let Some(c) = shape.downcast_ref::<Circle>() {
+        3
+    } else if let Some(_) = shape.downcast_ref::<Square>() {
+        let side = 2;
+        side * side
+    } else {
+        0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_without_fallback() {
+        check_assist_no_snippet_cap(
+            convert_match_to_downcast_chain,
+            r#"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square) }
+
+fn area(shape: &Shape) -> u32 {
+    match$0 shape {
+        Shape::Circle(c) => 3,
+        Shape::Square(s) => 4,
+    }
+}
+"#,
+            r#"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square) }
+
+fn area(shape: &Shape) -> u32 {
+    if let Some(c) = shape.downcast_ref::<Circle>() {
+        3
+    } else if let Some(s) = shape.downcast_ref::<Square>() {
+        4
+    } else {
+        todo!()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_candidates() {
+        cov_mark::check!(convert_match_to_downcast_chain_no_candidates);
+        check_assist_not_applicable(
+            convert_match_to_downcast_chain,
+            r#"
+fn area(shape: &u32) -> u32 {
+    match$0 shape {
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_unit_variants() {
+        check_assist_not_applicable(
+            convert_match_to_downcast_chain,
+            r#"
+struct Circle;
+enum Shape { Circle(Circle), Empty }
+
+fn area(shape: &Shape) -> u32 {
+    match$0 shape {
+        Shape::Circle(c) => 3,
+        Shape::Empty => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,400 @@
+use hir::{Access, HirDisplay, PathResolution};
+use itertools::Itertools;
+use stdx::{format_to, to_upper_camel_case};
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasArgList, HasName};
+
+use crate::{utils::same_signature, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_dyn_dispatch
+//
+// Replaces a match on `self` whose arms call differently named methods with an identical
+// signature on each variant's payload with a dynamically dispatched call through a new trait.
+//
+// ```
+// struct Circle;
+// impl Circle { fn circle_area(&self) -> u32 { 3 } }
+// struct Square;
+// impl Square { fn square_area(&self) -> u32 { 4 } }
+//
+// enum Shape { Circle(Circle), Square(Square) }
+//
+// impl Shape {
+//     fn area(&self) -> u32 {
+//         $0match self {
+//             Shape::Circle(c) => c.circle_area(),
+//             Shape::Square(s) => s.square_area(),
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Circle;
+// impl Circle { fn circle_area(&self) -> u32 { 3 } }
+// struct Square;
+// impl Square { fn square_area(&self) -> u32 { 4 } }
+//
+// enum Shape { Circle(Circle), Square(Square) }
+//
+// impl Shape {
+//     fn area(&self) -> u32 {
+//         self.as_dyn().area()
+//     }
+// }
+//
+// trait Area {
+//     fn area(&self) -> u32;
+// }
+//
+// impl Area for Circle {
+//     fn area(&self) -> u32 {
+//         self.circle_area()
+//     }
+// }
+//
+// impl Area for Square {
+//     fn area(&self) -> u32 {
+//         self.square_area()
+//     }
+// }
+//
+// impl Shape {
+//     fn as_dyn(&self) -> &dyn Area {
+//         match self {
+//             Shape::Circle(c) => c,
+//             Shape::Square(s) => s,
+//         }
+//     }
+// }
+// ```
+pub(crate) fn convert_match_to_dyn_dispatch(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    if scrutinee.syntax().text() != "self" {
+        return None;
+    }
+    let func = match_expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let impl_ = func.syntax().ancestors().find_map(ast::Impl::cast)?;
+    if impl_.trait_().is_some() {
+        return None;
+    }
+    let self_ty = ctx.sema.to_def(&impl_)?.self_ty(ctx.db());
+    let enum_ = match self_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+
+    let arms = match_expr
+        .match_arm_list()?
+        .arms()
+        .map(|arm| DispatchArm::new(ctx, &arm, enum_))
+        .collect::<Option<Vec<_>>>()?;
+    let (first, rest) = arms.split_first()?;
+    if arms.len() != enum_.variants(ctx.db()).len()
+        || !arms.iter().map(|arm| arm.variant).all_unique()
+    {
+        return None;
+    }
+    if first.method.self_param(ctx.db())?.access(ctx.db()) != Access::Shared {
+        return None;
+    }
+    if rest
+        .iter()
+        .any(|arm| !same_signature(&ctx.sema, first.method, arm.method) || arm.args != first.args)
+    {
+        cov_mark::hit!(convert_match_to_dyn_dispatch_signature_mismatch);
+        return None;
+    }
+
+    // Forward the parameters of the trait method by name, so only plain bindings work.
+    let param_list = ctx.sema.source(first.method)?.value.param_list()?;
+    let params = param_list.params().collect::<Vec<_>>();
+    let forwarded = params
+        .iter()
+        .map(|param| match param.pat()? {
+            ast::Pat::IdentPat(it) if it.ref_token().is_none() && it.pat().is_none() => {
+                Some(it.name()?.to_string())
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?
+        .join(", ");
+    let params = params.iter().map(|param| format!(", {param}")).collect::<String>();
+    let ret = match ctx.sema.source(first.method)?.value.ret_type() {
+        Some(ret) => format!(" {ret}"),
+        None => String::new(),
+    };
+
+    let method_name = func.name()?.to_string();
+    let trait_name = to_upper_camel_case(&method_name);
+    let enum_name = self_ty.display_source_code(ctx.db(), module.into()).ok()?;
+    let payload_names = arms
+        .iter()
+        .map(|arm| arm.payload.display_source_code(ctx.db(), module.into()).ok())
+        .collect::<Option<Vec<_>>>()?;
+    // Every payload type gets its own impl of the trait.
+    if !payload_names.iter().all_unique() {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_dyn_dispatch", AssistKind::RefactorRewrite),
+        "Convert match to dynamic dispatch",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(impl_.syntax());
+            let mut buf = String::new();
+            format_to!(buf, "\n\n{indent}trait {trait_name} {{");
+            format_to!(buf, "\n{indent}    fn {method_name}(&self{params}){ret};\n{indent}}}");
+            for (arm, payload_name) in arms.iter().zip(&payload_names) {
+                format_to!(buf, "\n\n{indent}impl {trait_name} for {payload_name} {{");
+                format_to!(buf, "\n{indent}    fn {method_name}(&self{params}){ret} {{");
+                format_to!(buf, "\n{indent}        self.{}({forwarded})", arm.method_name);
+                format_to!(buf, "\n{indent}    }}\n{indent}}}");
+            }
+            format_to!(buf, "\n\n{indent}impl {enum_name} {{");
+            format_to!(buf, "\n{indent}    fn as_dyn(&self) -> &dyn {trait_name} {{");
+            format_to!(buf, "\n{indent}        match self {{");
+            for arm in &arms {
+                format_to!(buf, "\n{indent}            {} => {},", arm.pat, arm.binding);
+            }
+            format_to!(buf, "\n{indent}        }}\n{indent}    }}\n{indent}}}");
+
+            builder.replace(target, format!("self.as_dyn().{method_name}({})", first.args));
+            builder.insert(impl_.syntax().text_range().end(), buf);
+        },
+    )
+}
+
+struct DispatchArm {
+    variant: hir::Variant,
+    payload: hir::Type,
+    pat: ast::TupleStructPat,
+    binding: String,
+    method: hir::Function,
+    method_name: String,
+    args: String,
+}
+
+impl DispatchArm {
+    fn new(ctx: &AssistContext<'_>, arm: &ast::MatchArm, enum_: hir::Enum) -> Option<Self> {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let pat = match arm.pat()? {
+            ast::Pat::TupleStructPat(it) => it,
+            _ => return None,
+        };
+        let variant = match ctx.sema.resolve_path(&pat.path()?)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it))
+                if it.parent_enum(ctx.db()) == enum_ =>
+            {
+                it
+            }
+            _ => return None,
+        };
+        let payload = match &*variant.fields(ctx.db()) {
+            [field] => field.ty(ctx.db()),
+            _ => return None,
+        };
+        let (binding, local) = match pat.fields().exactly_one().ok()? {
+            ast::Pat::IdentPat(it) if it.ref_token().is_none() && it.pat().is_none() => {
+                (it.name()?.to_string(), ctx.sema.to_def(&it)?)
+            }
+            _ => return None,
+        };
+
+        let call = match arm.expr()? {
+            ast::Expr::MethodCallExpr(it) => it,
+            _ => return None,
+        };
+        if call.receiver()?.syntax().text() != binding.as_str() {
+            return None;
+        }
+        let method = ctx.sema.resolve_method_call(&call)?;
+        let method_name = call.name_ref()?.to_string();
+        let arg_list = call.arg_list()?;
+        // The arguments move out of the match, where the payload isn't bound anymore.
+        let uses_binding = arg_list.syntax().descendants().filter_map(ast::Path::cast).any(|it| {
+            matches!(ctx.sema.resolve_path(&it), Some(PathResolution::Local(it)) if it == local)
+        });
+        if uses_binding {
+            cov_mark::hit!(convert_match_to_dyn_dispatch_arg_uses_binding);
+            return None;
+        }
+        let args = arg_list.args().join(", ");
+
+        Some(DispatchArm { variant, payload, pat, binding, method, method_name, args })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_variant_match() {
+        check_assist(
+            convert_match_to_dyn_dispatch,
+            r#"
+struct Circle;
+impl Circle { fn circle_area(&self, scale: u32) -> u32 { 3 * scale } }
+struct Square;
+impl Square { fn square_area(&self, factor: u32) -> u32 { 4 * factor } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn scaled_area(&self, n: u32) -> u32 {
+        match self$0 {
+            Shape::Circle(c) => c.circle_area(n),
+            Shape::Square(s) => s.square_area(n),
+        }
+    }
+}
+"#,
+            r#"
+struct Circle;
+impl Circle { fn circle_area(&self, scale: u32) -> u32 { 3 * scale } }
+struct Square;
+impl Square { fn square_area(&self, factor: u32) -> u32 { 4 * factor } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn scaled_area(&self, n: u32) -> u32 {
+        self.as_dyn().scaled_area(n)
+    }
+}
+
+trait ScaledArea {
+    fn scaled_area(&self, scale: u32) -> u32;
+}
+
+impl ScaledArea for Circle {
+    fn scaled_area(&self, scale: u32) -> u32 {
+        self.circle_area(scale)
+    }
+}
+
+impl ScaledArea for Square {
+    fn scaled_area(&self, scale: u32) -> u32 {
+        self.square_area(scale)
+    }
+}
+
+impl Shape {
+    fn as_dyn(&self) -> &dyn ScaledArea {
+        match self {
+            Shape::Circle(c) => c,
+            Shape::Square(s) => s,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_differing_signatures() {
+        cov_mark::check!(convert_match_to_dyn_dispatch_signature_mismatch);
+        check_assist_not_applicable(
+            convert_match_to_dyn_dispatch,
+            r#"
+struct Circle;
+impl Circle { fn circle_area(&self) -> u32 { 3 } }
+struct Square;
+impl Square { fn square_area(&self) -> u64 { 4 } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn area(&self) -> u32 {
+        match self$0 {
+            Shape::Circle(c) => c.circle_area(),
+            Shape::Square(s) => s.square_area(),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_exhaustive_arms() {
+        check_assist_not_applicable(
+            convert_match_to_dyn_dispatch,
+            r#"
+struct Circle;
+impl Circle { fn circle_area(&self) -> u32 { 3 } }
+
+enum Shape { Circle(Circle), Empty }
+
+impl Shape {
+    fn area(&self) -> u32 {
+        match self$0 {
+            Shape::Circle(c) => c.circle_area(),
+            _ => 0,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_mutable_receivers() {
+        check_assist_not_applicable(
+            convert_match_to_dyn_dispatch,
+            r#"
+struct Circle;
+impl Circle { fn grow_circle(&mut self) {} }
+struct Square;
+impl Square { fn grow_square(&mut self) {} }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn grow(&mut self) {
+        match self$0 {
+            Shape::Circle(c) => c.grow_circle(),
+            Shape::Square(s) => s.grow_square(),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_args_use_the_payload() {
+        cov_mark::check!(convert_match_to_dyn_dispatch_arg_uses_binding);
+        check_assist_not_applicable(
+            convert_match_to_dyn_dispatch,
+            r#"
+struct Circle(u32);
+impl Circle { fn circle_area(&self, scale: u32) -> u32 { 3 * scale } }
+struct Square(u32);
+impl Square { fn square_area(&self, factor: u32) -> u32 { 4 * factor } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn area(&self) -> u32 {
+        match self$0 {
+            Shape::Circle(p) => p.circle_area(p.0),
+            Shape::Square(p) => p.square_area(p.0),
+        }
+    }
+}
+"#,
+        );
+    }
+}
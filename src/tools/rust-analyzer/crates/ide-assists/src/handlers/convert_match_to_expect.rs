@@ -0,0 +1,288 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, make, AstNode, HasName},
+    SyntaxKind::{L_PAREN, R_PAREN, STRING},
+};
+
+use crate::{utils::needs_parens_as_receiver, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_expect
+//
+// Replaces a match on an `Option` that unwraps `Some` and panics on `None` with a call to
+// `expect`, or to `unwrap` if the panic has no message.
+//
+// ```
+// # //- minicore: option
+// fn port(config: Option<u16>) -> u16 {
+//     $0match config {
+//         Some(port) => port,
+//         None => panic!("missing config"),
+//     }
+// }
+// ```
+// ->
+// ```
+// fn port(config: Option<u16>) -> u16 {
+//     config.expect("missing config")
+// }
+// ```
+pub(crate) fn convert_match_to_expect(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let krate = ctx.sema.scope(match_expr.syntax())?.krate();
+    let option_enum = FamousDefs(&ctx.sema, krate).core_option_Option()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    if !matches!(scrutinee_ty.as_adt(), Some(hir::Adt::Enum(it)) if it == option_enum) {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (some_arm, none_arm) = match arms.as_slice() {
+        [first, second] if is_identity_some_arm(ctx, option_enum, first) => (first, second),
+        [first, second] if is_identity_some_arm(ctx, option_enum, second) => (second, first),
+        _ => return None,
+    };
+    if some_arm.guard().is_some()
+        || none_arm.guard().is_some()
+        || !is_none_pat(ctx, option_enum, none_arm)
+    {
+        return None;
+    }
+    let message = match panic_message(none_arm) {
+        Some(it) => it,
+        None => {
+            cov_mark::hit!(convert_match_to_expect_not_panic);
+            return None;
+        }
+    };
+
+    let (label, method, args) = match message {
+        Some(message) => {
+            ("Replace match with `expect`", "expect", vec![make::expr_literal(&message).into()])
+        }
+        None => ("Replace match with `unwrap`", "unwrap", vec![]),
+    };
+    let receiver =
+        if needs_parens_as_receiver(&scrutinee) { make::expr_paren(scrutinee) } else { scrutinee };
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_expect", AssistKind::RefactorRewrite),
+        label,
+        target,
+        |builder| {
+            let call =
+                make::expr_method_call(receiver, make::name_ref(method), make::arg_list(args));
+            builder.replace(target, call.to_string());
+        },
+    )
+}
+
+/// Checks for `Some(x) => x`.
+fn is_identity_some_arm(
+    ctx: &AssistContext<'_>,
+    option_enum: hir::Enum,
+    arm: &ast::MatchArm,
+) -> bool {
+    let (pat, expr) = match (arm.pat(), arm.expr()) {
+        (Some(ast::Pat::TupleStructPat(pat)), Some(ast::Expr::PathExpr(expr))) => (pat, expr),
+        _ => return false,
+    };
+    let variant = match pat.path().and_then(|path| ctx.sema.resolve_path(&path)) {
+        Some(PathResolution::Def(def)) => Some(def),
+        _ => None,
+    };
+    let is_some = is_option_variant(ctx, option_enum, variant, "Some");
+    let mut fields = pat.fields();
+    let binding = match (fields.next(), fields.next()) {
+        (Some(ast::Pat::IdentPat(it)), None) if it.ref_token().is_none() && it.pat().is_none() => {
+            it
+        }
+        _ => return false,
+    };
+    is_some && binding.name().map(|name| name.to_string()) == Some(expr.syntax().to_string())
+}
+
+fn is_none_pat(ctx: &AssistContext<'_>, option_enum: hir::Enum, arm: &ast::MatchArm) -> bool {
+    let variant = match arm.pat() {
+        Some(ast::Pat::WildcardPat(_)) => return true,
+        Some(ast::Pat::IdentPat(it)) => ctx.sema.resolve_bind_pat_to_const(&it),
+        Some(ast::Pat::PathPat(it)) => {
+            match it.path().and_then(|path| ctx.sema.resolve_path(&path)) {
+                Some(PathResolution::Def(def)) => Some(def),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    is_option_variant(ctx, option_enum, variant, "None")
+}
+
+fn is_option_variant(
+    ctx: &AssistContext<'_>,
+    option_enum: hir::Enum,
+    def: Option<hir::ModuleDef>,
+    name: &str,
+) -> bool {
+    matches!(
+        def,
+        Some(hir::ModuleDef::Variant(it))
+            if it.parent_enum(ctx.db()) == option_enum && it.name(ctx.db()).to_smol_str() == name
+    )
+}
+
+/// Returns the message of a `panic!` arm, or `Some(None)` if the panic has no message.
+/// Messages with format arguments can't be passed to `expect`.
+fn panic_message(arm: &ast::MatchArm) -> Option<Option<String>> {
+    let macro_call = match arm.expr()? {
+        ast::Expr::MacroExpr(it) => it.macro_call()?,
+        _ => return None,
+    };
+    if macro_call.path()?.segment()?.name_ref()?.text() != "panic" {
+        return None;
+    }
+    let tokens = macro_call
+        .token_tree()?
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|it| it.into_token())
+        .filter(|it| !it.kind().is_trivia() && !matches!(it.kind(), L_PAREN | R_PAREN))
+        .collect::<Vec<_>>();
+    match tokens.as_slice() {
+        [] => Some(None),
+        [message] if message.kind() == STRING && !message.text().contains(['{', '}']) => {
+            Some(Some(message.text().to_owned()))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_to_expect() {
+        check_assist_by_label(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+fn port(config: Option<u16>) -> u16 {
+    match config$0 {
+        Some(port) => port,
+        None => panic!("missing config"),
+    }
+}
+"#,
+            r#"
+fn port(config: Option<u16>) -> u16 {
+    config.expect("missing config")
+}
+"#,
+            "Replace match with `expect`",
+        );
+    }
+
+    #[test]
+    fn convert_to_unwrap() {
+        check_assist_by_label(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+fn port(config: Option<u16>) -> u16 {
+    let port = match config$0 {
+        None => panic!(),
+        Some(it) => it,
+    };
+    port
+}
+"#,
+            r#"
+fn port(config: Option<u16>) -> u16 {
+    let port = config.unwrap();
+    port
+}
+"#,
+            "Replace match with `unwrap`",
+        );
+    }
+
+    #[test]
+    fn convert_with_wildcard_and_method_receiver() {
+        check_assist(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+struct Config;
+impl Config { fn port(&self) -> Option<u16> { None } }
+
+fn port(config: Config) -> u16 {
+    match config.port()$0 {
+        Some(port) => port,
+        _ => panic!("no port"),
+    }
+}
+"#,
+            r#"
+struct Config;
+impl Config { fn port(&self) -> Option<u16> { None } }
+
+fn port(config: Config) -> u16 {
+    config.port().expect("no port")
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_panic_none_arm() {
+        cov_mark::check!(convert_match_to_expect_not_panic);
+        check_assist_not_applicable(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+fn port(config: Option<u16>) -> u16 {
+    match config$0 {
+        Some(port) => port,
+        None => 8080,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_formatted_panic() {
+        check_assist_not_applicable(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+fn port(config: Option<u16>, name: &str) -> u16 {
+    match config$0 {
+        Some(port) => port,
+        None => panic!("missing {}", name),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_transformed_some_arm() {
+        check_assist_not_applicable(
+            convert_match_to_expect,
+            r#"
+//- minicore: option
+fn port(config: Option<u16>) -> u16 {
+    match config$0 {
+        Some(port) => port + 1,
+        None => panic!(),
+    }
+}
+"#,
+        );
+    }
+}
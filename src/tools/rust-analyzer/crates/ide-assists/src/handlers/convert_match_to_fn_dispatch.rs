@@ -0,0 +1,336 @@
+use hir::{HirDisplay, PathResolution};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasArgList, HasName};
+
+use crate::{
+    utils::{same_signature, suggest_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_match_to_fn_dispatch
+//
+// Replaces a match whose arms call different free functions with the same signature and the
+// same arguments with a match selecting the function, followed by a single call.
+//
+// ```
+// enum Event { Key, Click }
+// fn on_key(code: u32) -> bool { true }
+// fn on_click(code: u32) -> bool { false }
+//
+// fn dispatch(event: Event, code: u32) -> bool {
+//     $0match event {
+//         Event::Key => on_key(code),
+//         Event::Click => on_click(code),
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Event { Key, Click }
+// fn on_key(code: u32) -> bool { true }
+// fn on_click(code: u32) -> bool { false }
+//
+// fn dispatch(event: Event, code: u32) -> bool {
+//     let f: fn(u32) -> bool = match event {
+//         Event::Key => on_key,
+//         Event::Click => on_click,
+//     };
+//     f(code)
+// }
+// ```
+pub(crate) fn convert_match_to_fn_dispatch(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    // The function is picked in a new statement, so nothing may be evaluated before the match.
+    let stmt = match_expr.syntax().parent()?;
+    let is_whole_stmt = ast::StmtList::can_cast(stmt.kind())
+        || ast::ExprStmt::can_cast(stmt.kind())
+        || ast::LetStmt::cast(stmt.clone()).map_or(false, |it| {
+            it.initializer().map_or(false, |init| init.syntax() == match_expr.syntax())
+        });
+    if !is_whole_stmt {
+        return None;
+    }
+    let insert_at = match ast::StmtList::can_cast(stmt.kind()) {
+        true => match_expr.syntax().text_range().start(),
+        false => stmt.text_range().start(),
+    };
+
+    let arms = match_expr
+        .match_arm_list()?
+        .arms()
+        .map(|arm| DispatchArm::new(ctx, &arm))
+        .collect::<Option<Vec<_>>>()?;
+    let (first, rest) = arms.split_first()?;
+    if rest.is_empty() || arms.iter().map(|arm| arm.func).all_equal() {
+        return None;
+    }
+    if rest.iter().any(|arm| arm.args != first.args) {
+        cov_mark::hit!(convert_match_to_fn_dispatch_different_args);
+        return None;
+    }
+    if rest.iter().any(|arm| !same_signature(&ctx.sema, first.func, arm.func)) {
+        return None;
+    }
+    // The arguments move out of the arms, where the bindings of the patterns are gone.
+    let uses_binding = |arm: &DispatchArm| {
+        arm.arm.pat().map_or(false, |pat| {
+            pat.syntax().descendants().filter_map(ast::IdentPat::cast).any(|binding| {
+                binding.name().map_or(false, |name| arm.arg_names.contains(&name.to_string()))
+            })
+        })
+    };
+    if arms.iter().any(uses_binding) {
+        return None;
+    }
+
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let name = suggest_name::unique_in_scope("f", &scope, &first.arg_names);
+    let module = scope.module();
+    let abi = match ctx.sema.source(first.func)?.value.abi() {
+        Some(abi) => format!("{abi} "),
+        None => String::new(),
+    };
+    let params = first
+        .func
+        .params_without_self(ctx.db())
+        .into_iter()
+        .map(|param| param.ty().display_source_code(ctx.db(), module.into()).ok())
+        .collect::<Option<Vec<_>>>()?
+        .join(", ");
+    let ret_ty = first.func.ret_type(ctx.db());
+    let ret = match ret_ty.is_unit() {
+        true => String::new(),
+        false => format!(" -> {}", ret_ty.display_source_code(ctx.db(), module.into()).ok()?),
+    };
+    let scrutinee = match_expr.expr()?;
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_fn_dispatch", AssistKind::RefactorRewrite),
+        "Convert match to function dispatch",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let mut buf = format!("let {name}: {abi}fn({params}){ret} = match {scrutinee} {{");
+            for arm in &arms {
+                let pat = arm.arm.pat().map(|it| it.to_string()).unwrap_or_default();
+                let guard = arm.arm.guard().map(|it| format!(" {it}")).unwrap_or_default();
+                format_to!(buf, "\n{indent}    {pat}{guard} => {},", arm.callee);
+            }
+            format_to!(buf, "\n{indent}}};\n{indent}");
+
+            builder.insert(insert_at, buf);
+            builder.replace(target, format!("{name}({})", first.args));
+        },
+    )
+}
+
+struct DispatchArm {
+    arm: ast::MatchArm,
+    func: hir::Function,
+    callee: ast::Path,
+    args: String,
+    arg_names: Vec<String>,
+}
+
+impl DispatchArm {
+    fn new(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<Self> {
+        let call = match arm.expr()? {
+            ast::Expr::CallExpr(it) => it,
+            _ => return None,
+        };
+        let callee = match call.expr()? {
+            ast::Expr::PathExpr(it) => it.path()?,
+            _ => return None,
+        };
+        let func = match ctx.sema.resolve_path(&callee)? {
+            PathResolution::Def(hir::ModuleDef::Function(it)) => it,
+            _ => return None,
+        };
+        // Only functions that coerce to a plain `fn` pointer can be picked this way.
+        let db = ctx.db();
+        if func.has_self_param(db)
+            || func.is_async(db)
+            || func.is_unsafe_to_call(db)
+            || !hir::GenericDef::Function(func).params(db).is_empty()
+        {
+            return None;
+        }
+        let arg_list = call.arg_list()?;
+        let args = arg_list.args().join(", ");
+        let arg_names = arg_list
+            .syntax()
+            .descendants()
+            .filter_map(ast::NameRef::cast)
+            .map(|it| it.to_string())
+            .collect();
+
+        Some(DispatchArm { arm: arm.clone(), func, callee, args, arg_names })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_function_arms() {
+        check_assist(
+            convert_match_to_fn_dispatch,
+            r#"
+mod handlers {
+    pub fn open(path: &str, flags: u32) {}
+    pub fn close(path: &str, flags: u32) {}
+}
+
+fn run(op: u8, path: &str) {
+    let flags = 0;
+    match$0 op {
+        0 => handlers::open(path, flags | 1),
+        n if n > 1 => handlers::close(path, flags | 1),
+        _ => handlers::open(path, flags | 1),
+    };
+}
+"#,
+            r#"
+mod handlers {
+    pub fn open(path: &str, flags: u32) {}
+    pub fn close(path: &str, flags: u32) {}
+}
+
+fn run(op: u8, path: &str) {
+    let flags = 0;
+    let f: fn(&str, u32) = match op {
+        0 => handlers::open,
+        n if n > 1 => handlers::close,
+        _ => handlers::open,
+    };
+    f(path, flags | 1);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_match_in_let() {
+        check_assist(
+            convert_match_to_fn_dispatch,
+            r#"
+fn double(x: i32) -> i32 { x * 2 }
+fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, x: i32) -> i32 {
+    let y = $0match neg {
+        true => negate(x),
+        false => double(x),
+    };
+    y
+}
+"#,
+            r#"
+fn double(x: i32) -> i32 { x * 2 }
+fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, x: i32) -> i32 {
+    let f: fn(i32) -> i32 = match neg {
+        true => negate,
+        false => double,
+    };
+    let y = f(x);
+    y
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_arguments() {
+        cov_mark::check!(convert_match_to_fn_dispatch_different_args);
+        check_assist_not_applicable(
+            convert_match_to_fn_dispatch,
+            r#"
+fn double(x: i32) -> i32 { x * 2 }
+fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, x: i32) -> i32 {
+    $0match neg {
+        true => negate(x),
+        false => double(x + 1),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_signatures() {
+        check_assist_not_applicable(
+            convert_match_to_fn_dispatch,
+            r#"
+fn double(x: i32) -> i32 { x * 2 }
+fn widen(x: i32) -> i64 { x as i64 }
+
+fn apply(neg: bool, x: i32) {
+    $0match neg {
+        true => double(x),
+        false => widen(x),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_extern_functions_with_name_in_scope() {
+        check_assist(
+            convert_match_to_fn_dispatch,
+            r#"
+extern "C" fn double(x: i32) -> i32 { x * 2 }
+extern "C" fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, f: i32) -> i32 {
+    $0match neg {
+        true => negate(f),
+        false => double(f),
+    }
+}
+"#,
+            r#"
+extern "C" fn double(x: i32) -> i32 { x * 2 }
+extern "C" fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, f: i32) -> i32 {
+    let f1: extern "C" fn(i32) -> i32 = match neg {
+        true => negate,
+        false => double,
+    };
+    f1(f)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_abis() {
+        check_assist_not_applicable(
+            convert_match_to_fn_dispatch,
+            r#"
+extern "C" fn double(x: i32) -> i32 { x * 2 }
+fn negate(x: i32) -> i32 { -x }
+
+fn apply(neg: bool, x: i32) -> i32 {
+    $0match neg {
+        true => negate(x),
+        false => double(x),
+    }
+}
+"#,
+        );
+    }
+}
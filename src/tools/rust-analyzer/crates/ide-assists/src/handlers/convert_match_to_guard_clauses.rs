@@ -0,0 +1,385 @@
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasName,
+    },
+    TextRange, TextSize,
+};
+
+use ide_db::famous_defs::FamousDefs;
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_guard_clauses
+//
+// Turns the arms of a trailing match that return early into guard clauses in front of the
+// main logic, which moves out of the remaining arm.
+//
+// Temporaries of the scrutinee would be dropped at the end of the new `let` instead of after the
+// main logic, so this is not offered when one of them has drop glue.
+//
+// ```
+// # //- minicore: result
+// fn parse(input: Option<u8>) -> Result<u8, ()> {
+//     $0match input {
+//         None => return Err(()),
+//         Some(0) => return Err(()),
+//         Some(n) => {
+//             let doubled = n * 2;
+//             Ok(doubled)
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn parse(input: Option<u8>) -> Result<u8, ()> {
+//     let n = match input {
+//         None => return Err(()),
+//         Some(0) => return Err(()),
+//         Some(n) => n,
+//     };
+//     let doubled = n * 2;
+//     Ok(doubled)
+// }
+// ```
+pub(crate) fn convert_match_to_guard_clauses(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    // The main logic ends up as the tail of the block, so there must be nothing after the match.
+    let stmt_list = match_expr.syntax().parent().and_then(ast::StmtList::cast)?;
+    if stmt_list.tail_expr()?.syntax() != match_expr.syntax() {
+        return None;
+    }
+
+    let mut early_arms = 0;
+    let mut main_arm = None;
+    for arm in match_expr.match_arm_list()?.arms() {
+        let body = arm.expr()?;
+        if diverges(ctx, &body)? {
+            early_arms += 1;
+        } else if main_arm.replace((arm, body)).is_some() {
+            return None;
+        }
+    }
+    let (main_arm, main_body) = main_arm?;
+    if early_arms == 0 {
+        return None;
+    }
+    if let ast::Expr::BlockExpr(block) = &main_body {
+        let has_modifier = block.label().is_some()
+            || block.unsafe_token().is_some()
+            || block.async_token().is_some()
+            || block.const_token().is_some()
+            || block.try_token().is_some();
+        if has_modifier {
+            cov_mark::hit!(convert_match_to_guard_clauses_main_arm_not_flattenable);
+            return None;
+        }
+    }
+
+    // The temporaries of a tail expression live until the end of the block, but those of the `let`
+    // are dropped before the main logic runs.
+    let drop_trait =
+        FamousDefs(&ctx.sema, ctx.sema.scope(match_expr.syntax())?.krate()).core_ops_Drop();
+    for expr in match_expr.expr()?.syntax().descendants().filter_map(ast::Expr::cast) {
+        if is_place(&expr) {
+            continue;
+        }
+        if has_drop_glue(ctx, drop_trait, &ctx.sema.type_of_expr(&expr)?.original) {
+            cov_mark::hit!(convert_match_to_guard_clauses_temporary_drop_glue);
+            return None;
+        }
+    }
+
+    let pat = main_arm.pat()?;
+    let mut bindings = Vec::new();
+    let mut mut_tokens = Vec::new();
+    for ident_pat in pat.syntax().descendants().filter_map(ast::IdentPat::cast) {
+        if ctx.sema.resolve_bind_pat_to_const(&ident_pat).is_some() {
+            continue;
+        }
+        // A by-value `mut` binding moves to the `let`, `ref mut` keeps borrowing in the arm.
+        let is_mut = ident_pat.ref_token().is_none() && ident_pat.mut_token().is_some();
+        if let (true, Some(mut_token)) = (is_mut, ident_pat.mut_token()) {
+            let next = mut_token.next_token()?;
+            mut_tokens
+                .push(TextRange::new(mut_token.text_range().start(), next.text_range().end()));
+        }
+        bindings.push((ident_pat.name()?, is_mut));
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_guard_clauses", AssistKind::RefactorRewrite),
+        "Convert to guard clauses",
+        target,
+        |builder| {
+            let binding = |(name, is_mut): &(ast::Name, bool)| match is_mut {
+                true => format!("mut {name}"),
+                false => name.to_string(),
+            };
+            let (let_pat, value) = match bindings.as_slice() {
+                [] => (None, "()".to_owned()),
+                [it] => (Some(binding(it)), it.0.to_string()),
+                _ => {
+                    let pats = bindings.iter().map(binding).collect::<Vec<_>>().join(", ");
+                    let names = bindings.iter().map(|(it, _)| it.to_string());
+                    (
+                        Some(format!("({pats})")),
+                        format!("({})", names.collect::<Vec<_>>().join(", ")),
+                    )
+                }
+            };
+
+            let start = target.start();
+            let body_range = main_body.syntax().text_range();
+            let mut guards = match_expr.syntax().text().to_string();
+            let comma = if main_arm.comma_token().is_some() { "" } else { "," };
+            guards.replace_range(range_in(body_range, start), &format!("{value}{comma}"));
+            for range in mut_tokens.iter().rev() {
+                guards.replace_range(range_in(*range, start), "");
+            }
+
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let main = match &main_body {
+                ast::Expr::BlockExpr(block) => {
+                    let block =
+                        block.reset_indent().indent(IndentLevel(indent.0.saturating_sub(1)));
+                    let text = block.syntax().text().to_string();
+                    let inner = text.trim_start_matches('{').trim_end_matches('}').trim();
+                    inner.to_owned()
+                }
+                _ => main_body.reset_indent().indent(indent).to_string(),
+            };
+            let guards = match let_pat {
+                Some(pat) => format!("let {pat} = {guards};"),
+                None => guards,
+            };
+            builder.replace(target, format!("{guards}\n{indent}{main}"));
+        },
+    )
+}
+
+/// Checks whether `expr` never finishes, looking into the last statement of blocks.
+fn diverges(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<bool> {
+    if ctx.sema.type_of_expr(expr)?.original.is_never() {
+        return Some(true);
+    }
+    let ast::Expr::BlockExpr(block) = expr else { return Some(false) };
+    let stmts = block.stmt_list()?;
+    match (stmts.tail_expr(), stmts.statements().last()) {
+        (None, Some(ast::Stmt::ExprStmt(stmt))) => diverges(ctx, &stmt.expr()?),
+        _ => Some(false),
+    }
+}
+
+/// Whether `expr` refers to an existing value instead of creating a temporary.
+fn is_place(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::IndexExpr(_)
+        | ast::Expr::ParenExpr(_) => true,
+        ast::Expr::PrefixExpr(it) => it.op_kind() == Some(ast::UnaryOp::Deref),
+        _ => false,
+    }
+}
+
+/// Whether dropping a value of type `ty` runs any code, erring on the side of `true` for types
+/// that are not known well enough.
+fn has_drop_glue(ctx: &AssistContext<'_>, drop_trait: Option<hir::Trait>, ty: &hir::Type) -> bool {
+    let db = ctx.db();
+    if ty.is_reference() || ty.is_raw_ptr() || ty.as_builtin().is_some() || ty.is_copy(db) {
+        return false;
+    }
+    if drop_trait.map_or(false, |it| ty.impls_trait(db, it, &[])) {
+        return true;
+    }
+    let tuple_fields = ty.tuple_fields(db);
+    if !tuple_fields.is_empty() || ty.is_unit() {
+        return tuple_fields.iter().any(|it| has_drop_glue(ctx, drop_trait, it));
+    }
+    if let Some((elem, _)) = ty.as_array(db) {
+        return has_drop_glue(ctx, drop_trait, &elem);
+    }
+    match ty.as_adt() {
+        Some(hir::Adt::Struct(_) | hir::Adt::Union(_)) => {
+            ty.fields(db).iter().any(|(_, it)| has_drop_glue(ctx, drop_trait, it))
+        }
+        // Variant fields come with placeholders for the type parameters, the type arguments
+        // stand in for those.
+        Some(hir::Adt::Enum(enum_)) => {
+            ty.type_arguments().any(|it| has_drop_glue(ctx, drop_trait, &it))
+                || enum_.variants(db).into_iter().flat_map(|it| it.fields(db)).any(|it| {
+                    let ty = it.ty(db);
+                    ty.as_type_param(db).is_none() && has_drop_glue(ctx, drop_trait, &ty)
+                })
+        }
+        None => true,
+    }
+}
+
+fn range_in(range: TextRange, start: TextSize) -> std::ops::Range<usize> {
+    let range = range - start;
+    range.start().into()..range.end().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_early_returns() {
+        check_assist(
+            convert_match_to_guard_clauses,
+            r#"
+//- minicore: result
+enum Request { Empty, Invalid(u16), Valid(u32) }
+enum Error { Empty, Code(u16) }
+
+fn log(code: u16) {}
+
+fn handle(req: Request) -> Result<u32, Error> {
+    match$0 req {
+        Request::Empty => return Err(Error::Empty),
+        Request::Invalid(code) => {
+            log(code);
+            return Err(Error::Code(code));
+        }
+        Request::Valid(mut body) => {
+            body += 1;
+            // doubled
+            Ok(body * 2)
+        }
+    }
+}
+"#,
+            r#"
+enum Request { Empty, Invalid(u16), Valid(u32) }
+enum Error { Empty, Code(u16) }
+
+fn log(code: u16) {}
+
+fn handle(req: Request) -> Result<u32, Error> {
+    let mut body = match req {
+        Request::Empty => return Err(Error::Empty),
+        Request::Invalid(code) => {
+            log(code);
+            return Err(Error::Code(code));
+        }
+        Request::Valid(body) => body,
+    };
+    body += 1;
+    // doubled
+    Ok(body * 2)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_in_loop_with_several_bindings() {
+        check_assist(
+            convert_match_to_guard_clauses,
+            r#"
+enum Entry { Done, Skip, Pair(u8, u32) }
+struct Queue;
+impl Queue {
+    fn pop(&mut self) -> Entry { loop {} }
+    fn store(&mut self, key: u8, value: u32) {}
+}
+
+fn drain(queue: &mut Queue) {
+    loop {
+        match$0 queue.pop() {
+            Entry::Done => break,
+            Entry::Skip => continue,
+            Entry::Pair(key, value) => queue.store(key, value),
+        }
+    }
+}
+"#,
+            r#"
+enum Entry { Done, Skip, Pair(u8, u32) }
+struct Queue;
+impl Queue {
+    fn pop(&mut self) -> Entry { loop {} }
+    fn store(&mut self, key: u8, value: u32) {}
+}
+
+fn drain(queue: &mut Queue) {
+    loop {
+        let (key, value) = match queue.pop() {
+            Entry::Done => break,
+            Entry::Skip => continue,
+            Entry::Pair(key, value) => (key, value),
+        };
+        queue.store(key, value)
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_unsafe_main_arm() {
+        cov_mark::check!(convert_match_to_guard_clauses_main_arm_not_flattenable);
+        check_assist_not_applicable(
+            convert_match_to_guard_clauses,
+            r#"
+//- minicore: option
+fn read(ptr: Option<*const u8>) -> u8 {
+    match$0 ptr {
+        None => return 0,
+        Some(ptr) => unsafe { *ptr },
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_temporary_with_drop_glue() {
+        cov_mark::check!(convert_match_to_guard_clauses_temporary_drop_glue);
+        check_assist_not_applicable(
+            convert_match_to_guard_clauses,
+            r#"
+//- minicore: drop
+struct Guard(u8);
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+fn lock() -> Guard { Guard(0) }
+
+fn read() -> u8 {
+    match$0 lock().0 {
+        0 => return 0,
+        n => n * 2,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_early_return() {
+        check_assist_not_applicable(
+            convert_match_to_guard_clauses,
+            r#"
+//- minicore: option
+fn read(v: Option<u8>) -> u8 {
+    match$0 v {
+        None => 0,
+        Some(v) => v,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use hir::StructKind;
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::{discriminants, int_literal, needs_parens_as_receiver, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_match_to_indexed_access
+//
+// Replaces a match on a fieldless enum indexing the same array with the discriminant of each
+// variant by indexing it with the value cast to `usize`. The enum has to be `Copy`, as the cast
+// takes the value.
+//
+// ```
+// # //- minicore: copy, derive
+// #[derive(Clone, Copy)]
+// enum Size { Small, Medium, Large }
+//
+// fn price(size: Size, prices: [u32; 3]) -> u32 {
+//     $0match size {
+//         Size::Small => prices[0],
+//         Size::Medium => prices[1],
+//         Size::Large => prices[2],
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone, Copy)]
+// enum Size { Small, Medium, Large }
+//
+// fn price(size: Size, prices: [u32; 3]) -> u32 {
+//     prices[size as usize]
+// }
+// ```
+pub(crate) fn convert_match_to_indexed_access(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    let enum_ = match scrutinee_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let all_variants = enum_.variants(ctx.db());
+    if all_variants.iter().any(|it| it.kind(ctx.db()) != StructKind::Unit) {
+        return None;
+    }
+    let discriminants = discriminants(ctx, &all_variants)?;
+
+    let mut base: Option<ast::Expr> = None;
+    let mut seen = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let [variant]: [hir::Variant; 1] = variants(ctx, &arm.pat()?)?.try_into().ok()?;
+        let ast::Expr::IndexExpr(index_expr) = arm.expr()? else { return None };
+        let (arm_base, index) = (index_expr.base()?, index_expr.index()?);
+        match &base {
+            Some(it) if it.syntax().text() != arm_base.syntax().text() => return None,
+            Some(_) => (),
+            None => base = Some(arm_base),
+        }
+        let index = int_literal(&index)?;
+        let pos = all_variants.iter().position(|it| *it == variant)?;
+        if discriminants[pos] != index {
+            cov_mark::hit!(convert_match_to_indexed_access_mismatched_index);
+            return None;
+        }
+        if !seen.contains(&variant) {
+            seen.push(variant);
+        }
+    }
+    if seen.len() != all_variants.len() {
+        return None;
+    }
+    let base = base?;
+    let copy =
+        FamousDefs(&ctx.sema, ctx.sema.scope(match_expr.syntax())?.krate()).core_marker_Copy()?;
+    if !scrutinee_ty.impls_trait(ctx.db(), copy, &[]) {
+        cov_mark::hit!(convert_match_to_indexed_access_not_copy);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_match_to_indexed_access", AssistKind::RefactorRewrite),
+        "Convert match to indexed access",
+        target,
+        |builder| {
+            let value = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            let base = match needs_parens_as_receiver(&base) {
+                true => format!("({base})"),
+                false => base.to_string(),
+            };
+            builder.replace(target, format!("{base}[{value} as usize]"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_variants() {
+        check_assist(
+            convert_match_to_indexed_access,
+            r#"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Axis { X, Y, Z }
+struct Point { coords: [f32; 3] }
+
+fn get(p: &Point, axis: Axis) -> f32 {
+    match$0 axis {
+        Axis::Z => p.coords[2],
+        Axis::X => p.coords[0],
+        Axis::Y => p.coords[1],
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+enum Axis { X, Y, Z }
+struct Point { coords: [f32; 3] }
+
+fn get(p: &Point, axis: Axis) -> f32 {
+    p.coords[axis as usize]
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_explicit_discriminants() {
+        check_assist(
+            convert_match_to_indexed_access,
+            r#"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Level { Low = 1, Mid, High = 0x3 }
+
+fn name(level: Level, names: &[&str]) -> &str {
+    match$0 level {
+        Level::Low => names[1],
+        Level::Mid => names[2],
+        Level::High => names[3],
+    }
+}
+"#,
+            r#"
+#[derive(Clone, Copy)]
+enum Level { Low = 1, Mid, High = 0x3 }
+
+fn name(level: Level, names: &[&str]) -> &str {
+    names[level as usize]
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_mismatched_index() {
+        cov_mark::check!(convert_match_to_indexed_access_mismatched_index);
+        check_assist_not_applicable(
+            convert_match_to_indexed_access,
+            r#"
+enum Axis { X, Y, Z }
+
+fn get(coords: [f32; 3], axis: Axis) -> f32 {
+    match$0 axis {
+        Axis::X => coords[0],
+        Axis::Y => coords[2],
+        Axis::Z => coords[1],
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_arrays() {
+        check_assist_not_applicable(
+            convert_match_to_indexed_access,
+            r#"
+enum Axis { X, Y }
+
+fn get(a: [f32; 2], b: [f32; 2], axis: Axis) -> f32 {
+    match$0 axis {
+        Axis::X => a[0],
+        Axis::Y => b[1],
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_enum_without_copy() {
+        cov_mark::check!(convert_match_to_indexed_access_not_copy);
+        check_assist_not_applicable(
+            convert_match_to_indexed_access,
+            r#"
+//- minicore: copy
+enum Axis { X, Y }
+
+fn get(coords: [f32; 2], axis: &Axis) -> f32 {
+    match$0 *axis {
+        Axis::X => coords[0],
+        Axis::Y => coords[1],
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use hir::{PathResolution, StructKind};
+use itertools::Itertools;
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_match_to_variant_table
+//
+// Turns an exhaustive match on a fieldless enum into an array built by mapping every
+// variant, in declaration order, through the match.
+//
+// ```
+// enum Size { Small, Medium, Large }
+//
+// fn widths(size: Size) {
+//     let width = $0match size {
+//         Size::Small => 8,
+//         Size::Medium => 16,
+//         Size::Large => 32,
+//     };
+// }
+// ```
+// ->
+// ```
+// enum Size { Small, Medium, Large }
+//
+// fn widths(size: Size) {
+//     let width = [Size::Small, Size::Medium, Size::Large].map(|size| match size {
+//         Size::Small => 8,
+//         Size::Medium => 16,
+//         Size::Large => 32,
+//     });
+// }
+// ```
+pub(crate) fn convert_match_to_variant_table(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let enum_ = match ctx.sema.type_of_expr(&scrutinee)?.adjusted().as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let variants = enum_.variants(ctx.db());
+    if variants.iter().any(|variant| variant.kind(ctx.db()) != StructKind::Unit) {
+        cov_mark::hit!(convert_match_to_variant_table_not_fieldless);
+        return None;
+    }
+
+    let mut paths = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let pats = match arm.pat()? {
+            ast::Pat::OrPat(it) => it.pats().collect(),
+            pat => vec![pat],
+        };
+        for pat in pats {
+            let variant = match &pat {
+                ast::Pat::PathPat(it) => match ctx.sema.resolve_path(&it.path()?)? {
+                    PathResolution::Def(hir::ModuleDef::Variant(it)) => it,
+                    _ => return None,
+                },
+                ast::Pat::IdentPat(it) => match ctx.sema.resolve_bind_pat_to_const(it)? {
+                    hir::ModuleDef::Variant(it) => it,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            paths.push((variant, pat.syntax().text().to_string()));
+        }
+    }
+    // The array lists the variants in declaration order, written the way the arms do.
+    let elements = variants
+        .iter()
+        .map(|variant| paths.iter().find(|(it, _)| it == variant).map(|(_, path)| path))
+        .collect::<Option<Vec<_>>>()?;
+
+    let param = match &scrutinee {
+        ast::Expr::PathExpr(it) => it.path()?.as_single_name_ref()?.to_string(),
+        _ => "v".to_string(),
+    };
+    acc.add(
+        AssistId("convert_match_to_variant_table", AssistKind::RefactorRewrite),
+        "Convert match to variant table",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let start = match_expr.syntax().text_range().start();
+            builder.insert(start, format!("[{}].map(|{param}| ", elements.iter().join(", ")));
+            if scrutinee.syntax().text() != param.as_str() {
+                builder.replace(scrutinee.syntax().text_range(), param.clone());
+            }
+            builder.insert(match_expr.syntax().text_range().end(), ")");
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_variant_table() {
+        check_assist(
+            convert_match_to_variant_table,
+            r#"
+mod color {
+    pub enum Color { Red, Green, Blue }
+}
+use color::Color::{self, *};
+
+fn names(c: &Color) -> [&'static str; 3] {
+    match$0 *c {
+        Blue => "blue",
+        Color::Red | Green => "warm",
+    }
+}
+"#,
+            r#"
+mod color {
+    pub enum Color { Red, Green, Blue }
+}
+use color::Color::{self, *};
+
+fn names(c: &Color) -> [&'static str; 3] {
+    [Color::Red, Green, Blue].map(|v| match v {
+        Blue => "blue",
+        Color::Red | Green => "warm",
+    })
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_data_carrying_variants() {
+        cov_mark::check!(convert_match_to_variant_table_not_fieldless);
+        check_assist_not_applicable(
+            convert_match_to_variant_table,
+            r#"
+enum Shape { Dot, Line(u32) }
+
+fn names(shape: Shape) -> u32 {
+    match$0 shape {
+        Shape::Dot => 0,
+        Shape::Line(n) => n,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_wildcard() {
+        check_assist_not_applicable(
+            convert_match_to_variant_table,
+            r#"
+enum Size { Small, Medium, Large }
+
+fn width(size: Size) -> u32 {
+    match$0 size {
+        Size::Small => 8,
+        _ => 16,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,316 @@
+use hir::{AsAssocItem, PathResolution};
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, make, AstNode, HasArgList};
+
+use crate::{utils::needs_parens_as_receiver, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_ordering_match_to_min_max
+//
+// Replaces a match on the result of `cmp` that returns one of the compared values in
+// every arm with a call to `min` or `max`.
+//
+// ```
+// # //- minicore: ord
+// use core::cmp::Ordering;
+//
+// fn smaller(a: u32, b: u32) -> u32 {
+//     $0match a.cmp(&b) {
+//         Ordering::Less | Ordering::Equal => a,
+//         Ordering::Greater => b,
+//     }
+// }
+// ```
+// ->
+// ```
+// use core::cmp::Ordering;
+//
+// fn smaller(a: u32, b: u32) -> u32 {
+//     a.min(b)
+// }
+// ```
+pub(crate) fn convert_ordering_match_to_min_max(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let call = match match_expr.expr()? {
+        ast::Expr::MethodCallExpr(it) => it,
+        _ => return None,
+    };
+    if call.name_ref()?.text() != "cmp" {
+        return None;
+    }
+    let krate = ctx.sema.scope(match_expr.syntax())?.krate();
+    let famous_defs = FamousDefs(&ctx.sema, krate);
+    let trait_ = ctx
+        .sema
+        .resolve_method_call(&call)?
+        .as_assoc_item(ctx.db())?
+        .containing_trait_or_trait_impl(ctx.db());
+    if trait_ != Some(famous_defs.core_cmp_Ord()?) {
+        cov_mark::hit!(convert_ordering_match_to_min_max_not_ord);
+        return None;
+    }
+    let lhs = without_parens(call.receiver()?)?;
+    let rhs = match call.arg_list()?.args().collect::<Vec<_>>().as_slice() {
+        [ast::Expr::RefExpr(it)] if it.mut_token().is_none() => without_parens(it.expr()?)?,
+        _ => return None,
+    };
+
+    let ordering = famous_defs.core_cmp_Ordering()?;
+    let variants = ordering.variants(ctx.db());
+    // The operand returned for `Less`, `Equal` and `Greater`, in declaration order.
+    let mut results: Vec<Option<Operand>> = vec![None; variants.len()];
+
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let expr = without_parens(arm.expr()?)?;
+        let operand = if expr.syntax().text() == lhs.syntax().text() {
+            Operand::Lhs
+        } else if expr.syntax().text() == rhs.syntax().text() {
+            Operand::Rhs
+        } else {
+            cov_mark::hit!(convert_ordering_match_to_min_max_unrelated_arm);
+            return None;
+        };
+        for idx in arm_variants(ctx, &arm.pat()?, &variants)? {
+            results[idx].get_or_insert(operand);
+        }
+    }
+
+    let (receiver, arg, method) = match results.as_slice() {
+        [Some(Operand::Lhs), Some(Operand::Lhs), Some(Operand::Rhs)] => (lhs, rhs, "min"),
+        [Some(Operand::Lhs), Some(Operand::Rhs), Some(Operand::Rhs)] => (rhs, lhs, "min"),
+        [Some(Operand::Rhs), Some(Operand::Rhs), Some(Operand::Lhs)] => (lhs, rhs, "max"),
+        [Some(Operand::Rhs), Some(Operand::Lhs), Some(Operand::Lhs)] => (rhs, lhs, "max"),
+        _ => return None,
+    };
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_ordering_match_to_min_max", AssistKind::RefactorRewrite),
+        format!("Replace match with `{method}`"),
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&receiver) {
+                true => make::expr_paren(receiver),
+                false => receiver,
+            };
+            let call =
+                make::expr_method_call(receiver, make::name_ref(method), make::arg_list([arg]));
+            builder.replace(target, call.to_string())
+        },
+    )
+}
+
+fn without_parens(expr: ast::Expr) -> Option<ast::Expr> {
+    match expr {
+        ast::Expr::ParenExpr(it) => without_parens(it.expr()?),
+        it => Some(it),
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Operand {
+    Lhs,
+    Rhs,
+}
+
+/// Returns the indices of the `Ordering` variants matched by `pat`.
+fn arm_variants(
+    ctx: &AssistContext<'_>,
+    pat: &ast::Pat,
+    variants: &[hir::Variant],
+) -> Option<Vec<usize>> {
+    let variant = match pat {
+        ast::Pat::WildcardPat(_) => return Some((0..variants.len()).collect()),
+        ast::Pat::OrPat(it) => {
+            let mut indices = Vec::new();
+            for pat in it.pats() {
+                indices.extend(arm_variants(ctx, &pat, variants)?);
+            }
+            return Some(indices);
+        }
+        ast::Pat::IdentPat(it) => match ctx.sema.resolve_bind_pat_to_const(it)? {
+            hir::ModuleDef::Variant(it) => it,
+            _ => return None,
+        },
+        ast::Pat::PathPat(it) => match ctx.sema.resolve_path(&it.path()?)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it)) => it,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let idx = variants.iter().position(|it| *it == variant)?;
+    Some(vec![idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_min_idiom() {
+        check_assist(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    match a.cmp(&b)$0 {
+        Ordering::Less | Ordering::Equal => a,
+        Ordering::Greater => b,
+    }
+}
+"#,
+            r#"
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    a.min(b)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_max_idiom_with_wildcard() {
+        check_assist(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering::*;
+
+fn larger(a: u32, b: u32) -> u32 {
+    match a.cmp(&b)$0 {
+        Greater => a,
+        _ => b,
+    }
+}
+"#,
+            r#"
+use core::cmp::Ordering::*;
+
+fn larger(a: u32, b: u32) -> u32 {
+    a.max(b)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_swaps_operands_for_ties() {
+        check_assist(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    match a.cmp(&b)$0 {
+        Ordering::Less => a,
+        Ordering::Equal | Ordering::Greater => b,
+    }
+}
+"#,
+            r#"
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    b.min(a)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_parenthesizes_receiver() {
+        check_assist(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32, c: u32) -> u32 {
+    match c.cmp(&(a + b))$0 {
+        Ordering::Less => c,
+        _ => a + b,
+    }
+}
+"#,
+            r#"
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32, c: u32) -> u32 {
+    (a + b).min(c)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_inherent_cmp() {
+        cov_mark::check!(convert_ordering_match_to_min_max_not_ord);
+        check_assist_not_applicable(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+struct Version(u32);
+impl Version {
+    fn cmp(&self, other: &Version) -> Ordering { Ordering::Equal }
+}
+
+fn smaller(a: Version, b: Version) -> Version {
+    match a.cmp(&b)$0 {
+        Ordering::Less | Ordering::Equal => a,
+        Ordering::Greater => b,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_unrelated_arms() {
+        cov_mark::check!(convert_ordering_match_to_min_max_unrelated_arm);
+        check_assist_not_applicable(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    match a.cmp(&b)$0 {
+        Ordering::Less => a,
+        Ordering::Equal => 0,
+        Ordering::Greater => b,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_constant_result() {
+        check_assist_not_applicable(
+            convert_ordering_match_to_min_max,
+            r#"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn first(a: u32, b: u32) -> u32 {
+    match a.cmp(&b)$0 {
+        _ => a,
+    }
+}
+"#,
+        );
+    }
+}
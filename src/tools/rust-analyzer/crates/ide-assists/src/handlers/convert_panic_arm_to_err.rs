@@ -0,0 +1,281 @@
+use hir::{HirDisplay, StructKind};
+use ide_db::{famous_defs::FamousDefs, syntax_helpers::node_ext::for_each_tail_expr};
+use syntax::{
+    ast::{self, AstNode},
+    match_ast,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_panic_arm_to_err
+//
+// Replaces a `panic!` in a match arm with an `Err` of the function's error type.
+//
+// ```
+// # //- minicore: result
+// enum MyError { Unexpected }
+//
+// fn parse(c: char) -> Result<u8, MyError> {
+//     match c {
+//         '0' => Ok(0),
+//         _ => $0panic!("unexpected"),
+//     }
+// }
+// ```
+// ->
+// ```
+// enum MyError { Unexpected }
+//
+// fn parse(c: char) -> Result<u8, MyError> {
+//     match c {
+//         '0' => Ok(0),
+//         _ => Err(${0:MyError::Unexpected}),
+//     }
+// }
+// ```
+pub(crate) fn convert_panic_arm_to_err(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let arm_expr = match_arm.expr()?;
+    let macro_call = match &arm_expr {
+        ast::Expr::MacroExpr(it) => it.macro_call()?,
+        _ => return None,
+    };
+    if macro_call.path()?.segment()?.name_ref()?.text() != "panic" {
+        return None;
+    }
+
+    // Only look at the innermost function, a `panic!` inside of a closure can't
+    // be turned into an early return of the surrounding function.
+    let func = match_arm.syntax().ancestors().find_map(|it| {
+        match_ast! {
+            match it {
+                ast::Fn(func) => Some(Some(func)),
+                ast::ClosureExpr(_) => Some(None),
+                _ => None,
+            }
+        }
+    })??;
+    let ret_ty = func.ret_type()?.ty()?;
+    let ty = ctx.sema.resolve_type(&ret_ty)?;
+    let scope = ctx.sema.scope(ret_ty.syntax())?;
+    let result_enum = FamousDefs(&ctx.sema, scope.krate()).core_result_Result()?;
+    if !matches!(ty.as_adt(), Some(hir::Adt::Enum(it)) if it == result_enum) {
+        cov_mark::hit!(convert_panic_arm_to_err_not_result);
+        return None;
+    }
+    let err_ty = ty.type_arguments().nth(1)?;
+
+    let err_value = match err_ty.as_adt() {
+        Some(hir::Adt::Enum(err_enum)) => {
+            let err_ty_name = err_ty.display_source_code(ctx.db(), scope.module().into()).ok()?;
+            err_enum
+                .variants(ctx.db())
+                .into_iter()
+                .find(|variant| variant.kind(ctx.db()) == StructKind::Unit)
+                .map(|variant| format!("{err_ty_name}::{}", variant.name(ctx.db())))
+        }
+        _ => None,
+    };
+
+    let body = func.body()?;
+    let mut is_tail_expr = false;
+    for_each_tail_expr(&ast::Expr::BlockExpr(body), &mut |expr| {
+        is_tail_expr |= expr.syntax() == arm_expr.syntax()
+    });
+    let return_kw = if is_tail_expr { "" } else { "return " };
+
+    let target = arm_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_panic_arm_to_err", AssistKind::RefactorRewrite),
+        "Convert `panic!` to `Err`",
+        target,
+        |builder| {
+            let err_value = err_value.as_deref().unwrap_or("todo!()");
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.replace_snippet(
+                    cap,
+                    target,
+                    format!("{return_kw}Err(${{0:{err_value}}})"),
+                ),
+                None => builder.replace(target, format!("{return_kw}Err({err_value})")),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_no_snippet_cap, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_tail_panic_arm() {
+        check_assist(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        '1' => Ok(1),
+        _ => panic!$0("unexpected"),
+    }
+}
+"#,
+            r#"
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        '1' => Ok(1),
+        _ => Err(${0:MyError::Unexpected}),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_non_tail_panic_arm_returns() {
+        check_assist(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    let value = match c {
+        '0' => 0,
+        _ => $0panic!(),
+    };
+    Ok(value + 1)
+}
+"#,
+            r#"
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    let value = match c {
+        '0' => 0,
+        _ => return Err(${0:MyError::Unexpected}),
+    };
+    Ok(value + 1)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_panic_arm_picks_unit_variant() {
+        check_assist_no_snippet_cap(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+enum MyError { Io(i32), Invalid }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => $0panic!("unexpected"),
+    }
+}
+"#,
+            r#"
+enum MyError { Io(i32), Invalid }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => Err(MyError::Invalid),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_panic_arm_without_known_error_value() {
+        check_assist(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+struct MyError;
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => $0panic!("unexpected"),
+    }
+}
+"#,
+            r#"
+struct MyError;
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => Err(${0:todo!()}),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_fn_does_not_return_result() {
+        cov_mark::check!(convert_panic_arm_to_err_not_result);
+        check_assist_not_applicable(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: option, result
+fn parse(c: char) -> Option<u8> {
+    match c {
+        '0' => Some(0),
+        _ => $0panic!("unexpected"),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_panic_arm() {
+        check_assist_not_applicable(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => $0unreachable!(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_inside_closure() {
+        check_assist_not_applicable(
+            convert_panic_arm_to_err,
+            r#"
+//- minicore: result
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    let f = |c: char| match c {
+        '0' => 0,
+        _ => $0panic!("unexpected"),
+    };
+    Ok(f(c))
+}
+"#,
+        );
+    }
+}
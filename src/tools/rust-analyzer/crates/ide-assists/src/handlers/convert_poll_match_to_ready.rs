@@ -0,0 +1,275 @@
+use hir::{PathResolution, ScopeDef};
+use ide_db::{
+    famous_defs::FamousDefs,
+    imports::insert_use::{insert_use, ImportScope},
+};
+use syntax::{
+    ast::{self, make, AstNode, HasName},
+    ted,
+};
+
+use crate::{utils::variant_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_poll_match_to_ready
+//
+// Replaces a match on a `Poll` that returns `Poll::Pending` when pending and evaluates to
+// the ready value otherwise with the `ready!` macro.
+//
+// ```
+// # //- minicore: future
+// use core::task::{Context, Poll};
+//
+// fn poll_value(inner: Poll<u32>, cx: &mut Context<'_>) -> Poll<u32> {
+//     let value = $0match inner {
+//         Poll::Ready(v) => v,
+//         Poll::Pending => return Poll::Pending,
+//     };
+//     Poll::Ready(value + 1)
+// }
+// ```
+// ->
+// ```
+// use core::task::{Context, Poll, ready};
+//
+// fn poll_value(inner: Poll<u32>, cx: &mut Context<'_>) -> Poll<u32> {
+//     let value = ready!(inner);
+//     Poll::Ready(value + 1)
+// }
+// ```
+pub(crate) fn convert_poll_match_to_ready(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let famous_defs = FamousDefs(&ctx.sema, scope.krate());
+    let poll = famous_defs.core_task_Poll()?;
+    if ctx.sema.type_of_expr(&scrutinee)?.adjusted().as_adt() != Some(hir::Adt::Enum(poll)) {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = &arms[..] else { return None };
+    if first.guard().is_some() || second.guard().is_some() {
+        return None;
+    }
+    let (ready, pending) = match variant_name(ctx, &first.pat()?)?.as_str() {
+        "Ready" => (first, second),
+        _ => (second, first),
+    };
+    if variant_name(ctx, &ready.pat()?)? != "Ready"
+        || variant_name(ctx, &pending.pat()?)? != "Pending"
+    {
+        return None;
+    }
+
+    let binding = match ready.pat()? {
+        ast::Pat::TupleStructPat(it) => match it.fields().next()? {
+            ast::Pat::IdentPat(it) if it.pat().is_none() => it.name()?,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    if ready.expr()?.syntax().text() != binding.text().as_str() {
+        return None;
+    }
+    if !returns_pending(ctx, &pending.expr()?) {
+        cov_mark::hit!(convert_poll_match_to_ready_pending_does_work);
+        return None;
+    }
+
+    let mut ready_in_scope = false;
+    scope.process_all_names(&mut |name, def| {
+        if name.to_smol_str() == "ready"
+            && matches!(def, ScopeDef::ModuleDef(hir::ModuleDef::Macro(_)))
+        {
+            ready_in_scope = true;
+        }
+    });
+    let ready_path = match famous_defs.std() {
+        Some(_) => "std::task::ready",
+        None => "core::task::ready",
+    };
+    let import_scope = ImportScope::find_insert_use_container(match_expr.syntax(), &ctx.sema)?;
+
+    acc.add(
+        AssistId("convert_poll_match_to_ready", AssistKind::RefactorRewrite),
+        "Convert match to `ready!`",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let match_expr = builder.make_mut(match_expr.clone());
+            let import_scope = match import_scope {
+                ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+            };
+
+            let call = make::expr_macro_call(
+                make::expr_path(make::ext::ident_path("ready")),
+                make::arg_list([scrutinee]),
+            )
+            .clone_for_update();
+            ted::replace(match_expr.syntax(), call.syntax());
+            if !ready_in_scope {
+                insert_use(&import_scope, make::path_from_text(ready_path), &ctx.config.insert_use);
+            }
+        },
+    )
+}
+
+/// Checks that `expr` is `return Poll::Pending`, optionally in a block.
+fn returns_pending(ctx: &AssistContext<'_>, expr: &ast::Expr) -> bool {
+    let expr = match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let Some(stmt_list) = block.stmt_list() else { return false };
+            let mut stmts = stmt_list.statements();
+            match (stmts.next(), stmts.next(), stmt_list.tail_expr()) {
+                (None, None, Some(tail)) => tail,
+                (Some(ast::Stmt::ExprStmt(stmt)), None, None) => match stmt.expr() {
+                    Some(it) => it,
+                    None => return false,
+                },
+                _ => return false,
+            }
+        }
+        _ => expr.clone(),
+    };
+    let value = match expr {
+        ast::Expr::ReturnExpr(it) => it.expr(),
+        _ => None,
+    };
+    match value {
+        Some(ast::Expr::PathExpr(it)) => match it.path() {
+            Some(path) => matches!(
+                ctx.sema.resolve_path(&path),
+                Some(PathResolution::Def(hir::ModuleDef::Variant(it)))
+                    if it.name(ctx.db()).to_smol_str() == "Pending"
+            ),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_propagating_match() {
+        check_assist(
+            convert_poll_match_to_ready,
+            r#"
+//- minicore: future
+use core::task::{Context, Poll};
+
+fn poll_next(inner: &mut u32, cx: &mut Context<'_>) -> Poll<u32> {
+    loop {
+        let n = match$0 poll_inner(inner, cx) {
+            Poll::Pending => {
+                return Poll::Pending;
+            }
+            Poll::Ready(n) => n,
+        };
+        if n > 0 {
+            return Poll::Ready(n);
+        }
+    }
+}
+
+fn poll_inner(inner: &mut u32, cx: &mut Context<'_>) -> Poll<u32> { loop {} }
+"#,
+            r#"
+use core::task::{Context, Poll, ready};
+
+fn poll_next(inner: &mut u32, cx: &mut Context<'_>) -> Poll<u32> {
+    loop {
+        let n = ready!(poll_inner(inner, cx));
+        if n > 0 {
+            return Poll::Ready(n);
+        }
+    }
+}
+
+fn poll_inner(inner: &mut u32, cx: &mut Context<'_>) -> Poll<u32> { loop {} }
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_ready_in_scope() {
+        check_assist(
+            convert_poll_match_to_ready,
+            r#"
+//- minicore: future
+use core::task::Poll;
+
+macro_rules! ready {
+    ($e:expr) => { loop {} };
+}
+
+fn poll_twice(inner: Poll<u8>) -> Poll<u8> {
+    Poll::Ready(match$0 inner {
+        Poll::Ready(v) => v,
+        Poll::Pending => return Poll::Pending,
+    })
+}
+"#,
+            r#"
+use core::task::Poll;
+
+macro_rules! ready {
+    ($e:expr) => { loop {} };
+}
+
+fn poll_twice(inner: Poll<u8>) -> Poll<u8> {
+    Poll::Ready(ready!(inner))
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_pending_does_work() {
+        cov_mark::check!(convert_poll_match_to_ready_pending_does_work);
+        check_assist_not_applicable(
+            convert_poll_match_to_ready,
+            r#"
+//- minicore: future
+use core::task::Poll;
+
+fn poll_counted(inner: Poll<u8>, pending: &mut u32) -> Poll<u8> {
+    let v = match$0 inner {
+        Poll::Ready(v) => v,
+        Poll::Pending => {
+            *pending += 1;
+            return Poll::Pending;
+        }
+    };
+    Poll::Ready(v)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_ready_value_is_transformed() {
+        check_assist_not_applicable(
+            convert_poll_match_to_ready,
+            r#"
+//- minicore: future
+use core::task::Poll;
+
+fn poll_double(inner: Poll<u8>) -> u8 {
+    match$0 inner {
+        Poll::Ready(v) => v * 2,
+        Poll::Pending => return 0,
+    }
+}
+"#,
+        );
+    }
+}
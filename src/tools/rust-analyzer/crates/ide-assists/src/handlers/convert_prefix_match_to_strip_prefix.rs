@@ -0,0 +1,167 @@
+use stdx::format_to;
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_prefix_match_to_strip_prefix
+//
+// Replaces a match whose arms are picked by `starts_with` guards on the scrutinee with an
+// `if let` chain on `strip_prefix`, which makes the rest of the string available to the arms.
+//
+// ```
+// # //- minicore: option
+// fn run(line: &str) -> u32 {
+//     $0match line {
+//         _ if line.starts_with("cmd:") => 1,
+//         _ if line.starts_with("msg:") => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn run(line: &str) -> u32 {
+//     if let Some(rest) = line.strip_prefix("cmd:") {
+//         1
+//     } else if let Some(rest) = line.strip_prefix("msg:") {
+//         2
+//     } else {
+//         0
+//     }
+// }
+// ```
+pub(crate) fn convert_prefix_match_to_strip_prefix(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (fallback, arms) = arms.split_last()?;
+    if !matches!(fallback.pat()?, ast::Pat::WildcardPat(_)) || fallback.guard().is_some() {
+        return None;
+    }
+    if arms.is_empty() {
+        return None;
+    }
+
+    let mut branches = Vec::new();
+    for arm in arms {
+        let Some(prefix) = prefix_of(arm, &scrutinee) else {
+            cov_mark::hit!(convert_prefix_match_to_strip_prefix_not_a_prefix);
+            return None;
+        };
+        branches.push((prefix, arm.expr()?));
+    }
+    let fallback = fallback.expr()?;
+    // `rest` is bound in every branch, so it must not refer to something else in them.
+    let mentions_rest = branches.iter().map(|(_, body)| body).chain([&fallback]).any(|body| {
+        body.syntax().descendants().filter_map(ast::NameRef::cast).any(|it| it.text() == "rest")
+    });
+    if mentions_rest {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_prefix_match_to_strip_prefix", AssistKind::RefactorRewrite),
+        "Convert match to `strip_prefix` chain",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let block = |body: &ast::Expr| match body {
+                // Arms are one level deeper than the branches of the `if`.
+                ast::Expr::BlockExpr(_) => {
+                    body.to_string().replace(&format!("\n{}", IndentLevel(1)), "\n")
+                }
+                _ => format!("{{\n{inner}{body}\n{indent}}}"),
+            };
+            let mut buf = String::new();
+            for (idx, (prefix, body)) in branches.iter().enumerate() {
+                if idx > 0 {
+                    buf.push_str(" else ");
+                }
+                format_to!(buf, "if let Some(rest) = {scrutinee}.strip_prefix({prefix}) ");
+                buf.push_str(&block(body));
+            }
+            format_to!(buf, " else {}", block(&fallback));
+            builder.replace(target, buf);
+        },
+    )
+}
+
+/// Returns the prefix of an arm `_ if scrutinee.starts_with(prefix)`.
+fn prefix_of(arm: &ast::MatchArm, scrutinee: &ast::Expr) -> Option<ast::Expr> {
+    if !matches!(arm.pat()?, ast::Pat::WildcardPat(_)) {
+        return None;
+    }
+    let ast::Expr::MethodCallExpr(call) = arm.guard()?.condition()? else { return None };
+    if call.name_ref()?.text() != "starts_with"
+        || call.receiver()?.syntax().text() != scrutinee.syntax().text()
+    {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    let prefix = args.next()?;
+    args.next().is_none().then_some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_prefixes() {
+        check_assist(
+            convert_prefix_match_to_strip_prefix,
+            r#"
+//- minicore: option
+fn parse(input: &str) -> Option<u32> {
+    let kind = match$0 input {
+        _ if input.starts_with("get ") => {
+            let n = 1;
+            n
+        }
+        _ if input.starts_with('#') => 2,
+        _ => return None,
+    };
+    Some(kind)
+}
+"#,
+            r#"
+fn parse(input: &str) -> Option<u32> {
+    let kind = if let Some(rest) = input.strip_prefix("get ") {
+        let n = 1;
+        n
+    } else if let Some(rest) = input.strip_prefix('#') {
+        2
+    } else {
+        return None
+    };
+    Some(kind)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_guards() {
+        cov_mark::check!(convert_prefix_match_to_strip_prefix_not_a_prefix);
+        check_assist_not_applicable(
+            convert_prefix_match_to_strip_prefix,
+            r#"
+//- minicore: option
+fn parse(input: &str) -> u32 {
+    $0match input {
+        _ if input.starts_with("get ") => 1,
+        _ if input.ends_with(';') => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
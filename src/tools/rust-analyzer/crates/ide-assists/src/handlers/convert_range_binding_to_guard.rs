@@ -0,0 +1,195 @@
+use syntax::{
+    ast::{self, AstNode, HasName},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_range_binding_to_guard
+//
+// Replaces a binding with a range subpattern in a match arm with a plain binding and a
+// `contains` check in the guard.
+//
+// ```
+// fn classify(x: u32) -> &'static str {
+//     match x {
+//         $0n @ 1..=10 => "small",
+//         _ => "big",
+//     }
+// }
+// ```
+// ->
+// ```
+// fn classify(x: u32) -> &'static str {
+//     match x {
+//         n if (1..=10).contains(&n) => "small",
+//         _ => "big",
+//     }
+// }
+// ```
+pub(crate) fn convert_range_binding_to_guard(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let ident_pat = ctx.find_node_at_offset::<ast::IdentPat>()?;
+    ident_pat.at_token()?;
+    let range = match ident_pat.pat()? {
+        ast::Pat::RangePat(it) => it,
+        _ => {
+            cov_mark::hit!(convert_range_binding_to_guard_not_a_range);
+            return None;
+        }
+    };
+    let name = ident_pat.name()?;
+
+    // The guard applies to the whole arm, so the binding must not sit in one alternative
+    // of an or-pattern.
+    let arm = ident_pat.syntax().ancestors().find_map(ast::MatchArm::cast)?;
+    let arm_pat = arm.pat()?;
+    if ident_pat
+        .syntax()
+        .ancestors()
+        .take_while(|it| it != arm_pat.syntax())
+        .chain(Some(arm_pat.syntax().clone()))
+        .any(|it| ast::OrPat::can_cast(it.kind()))
+    {
+        return None;
+    }
+
+    // Matching on a reference binds by reference too, even without `ref`.
+    let is_ref = ident_pat.ref_token().is_some()
+        || ctx.sema.to_def(&ident_pat).map_or(false, |it| it.ty(ctx.db()).is_reference());
+    let reference = if is_ref { "" } else { "&" };
+    let check = format!("({range}).contains({reference}{name})");
+    acc.add(
+        AssistId("convert_range_binding_to_guard", AssistKind::RefactorRewrite),
+        "Convert range binding to guard",
+        ident_pat.syntax().text_range(),
+        |builder| {
+            builder.delete(TextRange::new(
+                name.syntax().text_range().end(),
+                ident_pat.syntax().text_range().end(),
+            ));
+            match arm.guard().and_then(|guard| guard.condition()) {
+                Some(condition) => {
+                    let text = match &condition {
+                        ast::Expr::BinExpr(_) => format!("({condition})"),
+                        _ => condition.to_string(),
+                    };
+                    builder.replace(condition.syntax().text_range(), format!("{check} && {text}"));
+                }
+                None => builder.insert(arm_pat.syntax().text_range().end(), format!(" if {check}")),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_top_level_range_binding() {
+        check_assist(
+            convert_range_binding_to_guard,
+            r#"
+fn classify(x: i32) -> i32 {
+    match x {
+        n $0@ -10..=10 => n,
+        0.. => 1,
+        _ => -1,
+    }
+}
+"#,
+            r#"
+fn classify(x: i32) -> i32 {
+    match x {
+        n if (-10..=10).contains(&n) => n,
+        0.. => 1,
+        _ => -1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_nested_ref_binding_with_guard() {
+        check_assist(
+            convert_range_binding_to_guard,
+            r#"
+fn classify(x: Option<char>, upper: bool) -> bool {
+    match x {
+        Some(ref c$0 @ 'a'..='z') if upper || *c == 'q' => true,
+        _ => false,
+    }
+}
+"#,
+            r#"
+fn classify(x: Option<char>, upper: bool) -> bool {
+    match x {
+        Some(ref c) if ('a'..='z').contains(c) && (upper || *c == 'q') => true,
+        _ => false,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_binding_of_matched_reference() {
+        check_assist(
+            convert_range_binding_to_guard,
+            r#"
+//- minicore: option
+fn classify(x: &Option<u32>) -> u32 {
+    match x {
+        Some(n $0@ 1..=9) => *n,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn classify(x: &Option<u32>) -> u32 {
+    match x {
+        Some(n) if (1..=9).contains(n) => *n,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_range_subpattern() {
+        cov_mark::check!(convert_range_binding_to_guard_not_a_range);
+        check_assist_not_applicable(
+            convert_range_binding_to_guard,
+            r#"
+fn classify(x: Option<u32>) -> u32 {
+    match x {
+        o $0@ Some(_) => 1,
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_or_pattern() {
+        check_assist_not_applicable(
+            convert_range_binding_to_guard,
+            r#"
+fn classify(x: u32) -> u32 {
+    match x {
+        n $0@ 1..=3 | n @ 7..=9 => n,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,326 @@
+use hir::{HirDisplay, PathResolution};
+use ide_db::famous_defs::FamousDefs;
+use stdx::{format_to, to_lower_snake_case, to_upper_snake_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    SyntaxKind, T,
+};
+
+use crate::{
+    utils::{module_item, suggest_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_range_match_to_table
+//
+// Moves a match mapping integer ranges to constant values into a `const` table of ranges and
+// a lookup function that falls back to the value of the `_` arm.
+//
+// ```
+// enum Tier { Low, Mid, High }
+//
+// fn tier(n: u32) -> Tier {
+//     $0match n {
+//         0..=9 => Tier::Low,
+//         10..=99 => Tier::Mid,
+//         _ => Tier::High,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Tier { Low, Mid, High }
+//
+// const TIER_RANGES: [(core::ops::RangeInclusive<u32>, Tier); 2] = [
+//     (0..=9, Tier::Low),
+//     (10..=99, Tier::Mid),
+// ];
+//
+// fn $0tier_for(key: u32) -> Tier {
+//     for (range, value) in TIER_RANGES {
+//         if range.contains(&key) {
+//             return value;
+//         }
+//     }
+//     Tier::High
+// }
+//
+// fn tier(n: u32) -> Tier {
+//     tier_for(n)
+// }
+// ```
+pub(crate) fn convert_range_match_to_table(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let key_ty = ctx.sema.type_of_expr(&scrutinee)?.adjusted();
+    if !key_ty.is_int_or_uint() {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (fallback, arms) = arms.split_last()?;
+    if !matches!(fallback.pat()?, ast::Pat::WildcardPat(_)) || arms.len() < 2 {
+        return None;
+    }
+    let fallback = fallback.expr()?;
+    if !unguarded_consts(ctx, &fallback, arms) {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    for arm in arms {
+        let (range, bounds) = match arm.pat()? {
+            ast::Pat::RangePat(it) => {
+                let inclusive = it.syntax().children_with_tokens().any(|it| it.kind() == T![..=]);
+                if !inclusive {
+                    return None;
+                }
+                let (start, end) = (it.start()?, it.end()?);
+                let bounds = (int_value(&start)?, int_value(&end)?);
+                (format!("{start}..={end}"), bounds)
+            }
+            ast::Pat::LiteralPat(it) => {
+                let value = int_value(&it.clone().into())?;
+                (format!("{it}..={it}"), (value, value))
+            }
+            _ => return None,
+        };
+        entries.push((range, bounds, arm.expr()?));
+    }
+
+    let mut sorted = entries.iter().map(|(_, bounds, _)| *bounds).collect::<Vec<_>>();
+    sorted.sort_unstable();
+    if sorted.windows(2).any(|pair| pair[0].1 >= pair[1].0) {
+        cov_mark::hit!(convert_range_match_to_table_overlapping);
+        return None;
+    }
+
+    let value_ty = ctx.sema.type_of_expr(&fallback)?.adjusted();
+    // The table and the function are named after the type of the values, if it has a name.
+    let stripped = value_ty.strip_references();
+    let base = match (stripped.as_adt(), stripped.as_builtin()) {
+        (Some(adt), _) => adt.name(ctx.db()).to_string(),
+        (None, Some(builtin)) => builtin.name().to_string(),
+        (None, None) => "value".to_owned(),
+    };
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let table = format!("{}_RANGES", to_upper_snake_case(&base));
+    let table = suggest_name::unique_in_scope(&table, &scope, &[]);
+    let lookup = format!("{}_for", to_lower_snake_case(&base));
+    let lookup = suggest_name::unique_in_scope(&lookup, &scope, &[]);
+    let module = scope.module();
+    let key_ty = key_ty.display_source_code(ctx.db(), module.into()).ok()?;
+    // Constant references are `'static`, which the signature of the function has to spell out.
+    let value_ty = match value_ty.remove_ref() {
+        Some(it) => format!("&'static {}", it.display_source_code(ctx.db(), module.into()).ok()?),
+        None => value_ty.display_source_code(ctx.db(), module.into()).ok()?,
+    };
+    let krate = module.krate();
+    let ops = match FamousDefs(&ctx.sema, krate).std() {
+        Some(_) => "std::ops",
+        None => "core::ops",
+    };
+
+    let item = module_item(match_expr.syntax())?;
+
+    acc.add(
+        AssistId("convert_range_match_to_table", AssistKind::RefactorExtract),
+        "Convert match to range table lookup",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(item.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let mut buf = format!(
+                "const {table}: [({ops}::RangeInclusive<{key_ty}>, {value_ty}); {}] = [",
+                entries.len()
+            );
+            for (range, _, value) in &entries {
+                format_to!(buf, "\n{inner}({range}, {value}),");
+            }
+            format_to!(buf, "\n{indent}];\n\n{indent}");
+            match ctx.config.snippet_cap {
+                Some(_) => format_to!(buf, "fn $0{lookup}"),
+                None => format_to!(buf, "fn {lookup}"),
+            }
+            let (loop_body, ret) = (IndentLevel(indent.0 + 2), IndentLevel(indent.0 + 3));
+            format_to!(
+                buf,
+                "(key: {key_ty}) -> {value_ty} {{\n\
+                 {inner}for (range, value) in {table} {{\n\
+                 {loop_body}if range.contains(&key) {{\n\
+                 {ret}return value;\n\
+                 {loop_body}}}\n\
+                 {inner}}}\n\
+                 {inner}{fallback}\n\
+                 {indent}}}\n\n{indent}"
+            );
+
+            builder.replace(match_expr.syntax().text_range(), format!("{lookup}({scrutinee})"));
+            let offset = item.syntax().text_range().start();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, buf),
+                None => builder.insert(offset, buf),
+            }
+        },
+    )
+}
+
+/// Checks that none of the arms has a guard and all of them, as well as `fallback`, evaluate
+/// to values that can live in a `const`.
+fn unguarded_consts(ctx: &AssistContext<'_>, fallback: &ast::Expr, arms: &[ast::MatchArm]) -> bool {
+    let is_const = |expr: &ast::Expr| match expr {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::PathExpr(it) => matches!(
+            it.path().and_then(|path| ctx.sema.resolve_path(&path)),
+            Some(PathResolution::Def(hir::ModuleDef::Variant(_) | hir::ModuleDef::Const(_)))
+        ),
+        _ => false,
+    };
+    is_const(fallback)
+        && arms
+            .iter()
+            .all(|arm| arm.guard().is_none() && arm.expr().map_or(false, |it| is_const(&it)))
+}
+
+fn int_value(pat: &ast::Pat) -> Option<i128> {
+    let ast::Pat::LiteralPat(pat) = pat else { return None };
+    let ast::LiteralKind::IntNumber(number) = pat.literal()?.kind() else { return None };
+    let value = i128::try_from(number.value()?).ok()?;
+    let negative = pat.syntax().first_token().map_or(false, |it| it.kind() == SyntaxKind::MINUS);
+    Some(if negative { -value } else { value })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_range_tiers() {
+        check_assist(
+            convert_range_match_to_table,
+            r#"
+enum Tier { None, Low, Mid, High }
+
+mod scoring {
+    use super::Tier;
+
+    pub fn tier(score: i64) -> Tier {
+        let tier = match$0 score {
+            -9..=-1 => Tier::None,
+            0 => Tier::Low,
+            1..=99 => Tier::Mid,
+            _ => Tier::High,
+        };
+        tier
+    }
+}
+"#,
+            r#"
+enum Tier { None, Low, Mid, High }
+
+mod scoring {
+    use super::Tier;
+
+    const TIER_RANGES: [(core::ops::RangeInclusive<i64>, Tier); 3] = [
+        (-9..=-1, Tier::None),
+        (0..=0, Tier::Low),
+        (1..=99, Tier::Mid),
+    ];
+
+    fn $0tier_for(key: i64) -> Tier {
+        for (range, value) in TIER_RANGES {
+            if range.contains(&key) {
+                return value;
+            }
+        }
+        Tier::High
+    }
+
+    pub fn tier(score: i64) -> Tier {
+        let tier = tier_for(score);
+        tier
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_reference_values_and_taken_name() {
+        check_assist(
+            convert_range_match_to_table,
+            r#"
+fn str_for() {}
+
+fn label(n: u8) -> &'static str {
+    match$0 n {
+        0..=9 => "low",
+        10..=99 => "mid",
+        _ => "high",
+    }
+}
+"#,
+            r#"
+fn str_for() {}
+
+const STR_RANGES: [(core::ops::RangeInclusive<u8>, &'static str); 2] = [
+    (0..=9, "low"),
+    (10..=99, "mid"),
+];
+
+fn $0str_for1(key: u8) -> &'static str {
+    for (range, value) in STR_RANGES {
+        if range.contains(&key) {
+            return value;
+        }
+    }
+    "high"
+}
+
+fn label(n: u8) -> &'static str {
+    str_for1(n)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_overlapping_ranges() {
+        cov_mark::check!(convert_range_match_to_table_overlapping);
+        check_assist_not_applicable(
+            convert_range_match_to_table,
+            r#"
+enum Tier { Low, Mid, High }
+
+fn tier(n: u32) -> Tier {
+    match$0 n {
+        0..=10 => Tier::Low,
+        10..=99 => Tier::Mid,
+        _ => Tier::High,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_computed_values() {
+        check_assist_not_applicable(
+            convert_range_match_to_table,
+            r#"
+fn scale(n: u32) -> u32 {
+    match$0 n {
+        0..=9 => n,
+        10..=99 => 2,
+        _ => 3,
+    }
+}
+"#,
+        );
+    }
+}
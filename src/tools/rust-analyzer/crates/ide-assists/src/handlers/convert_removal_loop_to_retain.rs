@@ -0,0 +1,417 @@
+use ide_db::{defs::Definition, famous_defs::FamousDefs};
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasArgList, HasLoopBody, HasName},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_removal_loop_to_retain
+//
+// Replaces a loop walking a vector by index and removing the elements a match selects with
+// `Vec::retain`.
+//
+// ```
+// # //- minicore: index
+// # //- /main.rs crate:main deps:std,alloc
+// enum Job { Done, Pending(u32) }
+//
+// fn prune(jobs: &mut Vec<Job>) {
+//     let mut i = 0;
+//     $0while i < jobs.len() {
+//         match jobs[i] {
+//             Job::Done => {
+//                 jobs.remove(i);
+//             }
+//             _ => i += 1,
+//         }
+//     }
+// }
+// # //- /std.rs crate:std deps:alloc
+// # pub mod prelude { pub mod rust_2021 { pub use alloc::vec::Vec; } }
+// # //- /alloc.rs crate:alloc
+// # pub mod vec {
+// #     pub struct Vec<T>(T);
+// #     impl<T> Vec<T> {
+// #         pub fn len(&self) -> usize { 0 }
+// #         pub fn remove(&mut self, index: usize) -> T { loop {} }
+// #     }
+// #     impl<T> core::ops::Index<usize> for Vec<T> { type Output = T; }
+// # }
+// ```
+// ->
+// ```
+// enum Job { Done, Pending(u32) }
+//
+// fn prune(jobs: &mut Vec<Job>) {
+//     jobs.retain(|it| !matches!(*it, Job::Done));
+// }
+// ```
+pub(crate) fn convert_removal_loop_to_retain(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let while_expr = ctx.find_node_at_offset::<ast::WhileExpr>()?;
+    if while_expr.label().is_some() {
+        return None;
+    }
+    let ast::Expr::BinExpr(cond) = while_expr.condition()? else { return None };
+    let (Some(index), Some(ast::Expr::MethodCallExpr(len))) = (cond.lhs(), cond.rhs()) else {
+        return None;
+    };
+    let less = ast::CmpOp::Ord { ordering: ast::Ordering::Less, strict: true };
+    if cond.op_kind()? != ast::BinaryOp::CmpOp(less)
+        || len.name_ref()?.text() != "len"
+        || len.arg_list()?.args().next().is_some()
+    {
+        return None;
+    }
+    let vec = len.receiver()?;
+    if !is_vec(ctx, &vec) {
+        return None;
+    }
+    let same = |expr: &ast::Expr, like: &ast::Expr| expr.syntax().text() == like.syntax().text();
+
+    // The index has to start at zero right before the loop, and is gone after it.
+    let stmt = match while_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(it) => it.syntax().clone(),
+        None => while_expr.syntax().clone(),
+    };
+    let let_stmt = stmt.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let ast::Pat::IdentPat(index_pat) = let_stmt.pat()? else { return None };
+    let zero = let_stmt.initializer().map_or(false, |it| it.syntax().text() == "0");
+    if !zero
+        || index_pat.mut_token().is_none()
+        || index.syntax().text() != index_pat.name()?.text().as_str()
+    {
+        return None;
+    }
+    let range = TextRange::new(let_stmt.syntax().text_range().start(), stmt.text_range().end());
+
+    let stmt_list = while_expr.loop_body()?.stmt_list()?;
+    let match_expr =
+        match (stmt_list.statements().collect::<Vec<_>>().as_slice(), stmt_list.tail_expr()) {
+            ([], Some(ast::Expr::MatchExpr(it))) => it,
+            ([ast::Stmt::ExprStmt(it)], None) => match it.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            },
+            _ => return None,
+        };
+    // Each element is looked at once, through a shared reference in the closure.
+    let scrutinee = match match_expr.expr()? {
+        ast::Expr::IndexExpr(it) if same(&it.base()?, &vec) && same(&it.index()?, &index) => "*it",
+        ast::Expr::RefExpr(it) if it.mut_token().is_none() => match it.expr()? {
+            ast::Expr::IndexExpr(it) if same(&it.base()?, &vec) && same(&it.index()?, &index) => {
+                "it"
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let vec_root = vec.syntax().first_token()?;
+    let mut arms = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let keep = match arm.expr()? {
+            it if is_increment(&it, &index) => true,
+            it if is_removal(&it, &vec, &index) => false,
+            _ => {
+                cov_mark::hit!(convert_removal_loop_to_retain_not_filtering);
+                return None;
+            }
+        };
+        let captures_loop_state = arm.guard().map_or(false, |guard| {
+            guard.syntax().descendants().filter_map(ast::PathExpr::cast).any(|it| {
+                let text = it.syntax().text();
+                text == index.syntax().text() || text == vec_root.text()
+            })
+        });
+        if captures_loop_state {
+            return None;
+        }
+        arms.push((arm, keep));
+    }
+    let index_local = ctx.sema.to_def(&index_pat)?;
+    let usages = Definition::Local(index_local).usages(&ctx.sema).all();
+    let index_outlives_loop =
+        usages.iter().flat_map(|(_, refs)| refs).any(|it| !range.contains_range(it.range));
+    if index_outlives_loop {
+        return None;
+    }
+
+    let semicolon = match while_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(it) if it.semicolon_token().is_some() => "",
+        _ => ";",
+    };
+    acc.add(
+        AssistId("convert_removal_loop_to_retain", AssistKind::RefactorRewrite),
+        "Convert loop to `retain`",
+        while_expr.syntax().text_range(),
+        |builder| {
+            let (last, last_keep) = &arms[arms.len() - 1];
+            let others = &arms[..arms.len() - 1];
+            let catch_all = matches!(last.pat(), Some(ast::Pat::WildcardPat(_)))
+                && last.guard().is_none()
+                && !others.is_empty()
+                && others.iter().all(|(arm, keep)| arm.guard().is_none() && keep != last_keep);
+            let filter = if catch_all {
+                let pats = others.iter().filter_map(|(arm, _)| arm.pat()).join(" | ");
+                let negation = if *last_keep { "!" } else { "" };
+                format!("{negation}matches!({scrutinee}, {pats})")
+            } else {
+                let indent = IndentLevel::from_node(while_expr.syntax());
+                let mut buf = format!("match {scrutinee} {{");
+                for (arm, keep) in &arms {
+                    let Some(pat) = arm.pat() else { continue };
+                    format_to!(buf, "\n{}{pat}", indent + 1);
+                    if let Some(guard) = arm.guard() {
+                        format_to!(buf, " {guard}");
+                    }
+                    format_to!(buf, " => {keep},");
+                }
+                format_to!(buf, "\n{indent}}}");
+                buf
+            };
+            builder.replace(range, format!("{vec}.retain(|it| {filter}){semicolon}"));
+        },
+    )
+}
+
+fn is_vec(ctx: &AssistContext<'_>, vec: &ast::Expr) -> bool {
+    let Some(ty) = ctx.sema.type_of_expr(vec) else { return false };
+    let Some(scope) = ctx.sema.scope(vec.syntax()) else { return false };
+    let Some(vec) = FamousDefs(&ctx.sema, scope.krate()).alloc_vec_Vec() else { return false };
+    ty.original.strip_references().as_adt() == Some(hir::Adt::Struct(vec))
+}
+
+/// Returns the only expression of a block doing nothing else, or the expression itself.
+fn only_expr(expr: &ast::Expr) -> Option<ast::Expr> {
+    let ast::Expr::BlockExpr(block) = expr else { return Some(expr.clone()) };
+    if block.modifier().is_some() {
+        return None;
+    }
+    let stmts = block.stmt_list()?;
+    match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+        ([], Some(it)) => Some(it),
+        ([ast::Stmt::ExprStmt(it)], None) => it.expr(),
+        _ => None,
+    }
+}
+
+/// Checks for `index += 1`, which moves on to the next element.
+fn is_increment(expr: &ast::Expr, index: &ast::Expr) -> bool {
+    let Some(ast::Expr::BinExpr(bin)) = only_expr(expr) else { return false };
+    let add = ast::BinaryOp::Assignment { op: Some(ast::ArithOp::Add) };
+    bin.op_kind() == Some(add)
+        && bin.lhs().map_or(false, |it| it.syntax().text() == index.syntax().text())
+        && bin.rhs().map_or(false, |it| it.syntax().text() == "1")
+}
+
+/// Checks for `vec.remove(index);`, which drops the element and leaves the index on the next one.
+fn is_removal(expr: &ast::Expr, vec: &ast::Expr, index: &ast::Expr) -> bool {
+    let ast::Expr::BlockExpr(block) = expr else { return false };
+    let Some(stmts) = block.stmt_list() else { return false };
+    let Some(ast::Expr::MethodCallExpr(call)) = only_expr(expr) else { return false };
+    let Some(args) = call.arg_list() else { return false };
+    stmts.tail_expr().is_none()
+        && call.name_ref().map_or(false, |it| it.text() == "remove")
+        && call.receiver().map_or(false, |it| it.syntax().text() == vec.syntax().text())
+        && args.args().map(|it| it.syntax().text().to_string()).eq([index.syntax().to_string()])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const ALLOC: &str = r#"//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn len(&self) -> usize { 0 }
+        pub fn remove(&mut self, index: usize) -> T { loop {} }
+    }
+    impl<T> core::ops::Index<usize> for Vec<T> { type Output = T; }
+    impl<T> core::ops::IndexMut<usize> for Vec<T> {}
+}
+"#;
+
+    #[test]
+    fn convert_index_based_removal() {
+        check_assist(
+            convert_removal_loop_to_retain,
+            &format!(
+                r#"
+//- minicore: index
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+enum Event {{ Click(u32, u32), Scroll(i32), Close }}
+struct Queue {{ events: Vec<Event> }}
+
+impl Queue {{
+    fn drop_input(&mut self) {{
+        let mut i = 0;
+        while$0 i < self.events.len() {{
+            match &self.events[i] {{
+                Event::Click(..) | Event::Scroll(_) => {{
+                    self.events.remove(i);
+                }}
+                Event::Close => {{
+                    i += 1;
+                }}
+            }}
+        }}
+        self.flush();
+    }}
+    fn flush(&mut self) {{}}
+}}
+{ALLOC}"#
+            ),
+            r#"
+use alloc::vec::Vec;
+
+enum Event { Click(u32, u32), Scroll(i32), Close }
+struct Queue { events: Vec<Event> }
+
+impl Queue {
+    fn drop_input(&mut self) {
+        self.events.retain(|it| match it {
+            Event::Click(..) | Event::Scroll(_) => false,
+            Event::Close => true,
+        });
+        self.flush();
+    }
+    fn flush(&mut self) {}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_removal_with_guard() {
+        check_assist(
+            convert_removal_loop_to_retain,
+            &format!(
+                r#"
+//- minicore: index
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn drop_small(sizes: &mut Vec<u64>, limit: u64) {{
+    let mut i = 0;
+    while i < sizes.len() {{
+        match$0 sizes[i] {{
+            0 => i += 1,
+            n if n < limit => {{
+                sizes.remove(i);
+            }}
+            _ => i += 1,
+        }}
+    }}
+}}
+{ALLOC}"#
+            ),
+            r#"
+use alloc::vec::Vec;
+
+fn drop_small(sizes: &mut Vec<u64>, limit: u64) {
+    sizes.retain(|it| match *it {
+        0 => true,
+        n if n < limit => false,
+        _ => true,
+    });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_elements_are_mutated() {
+        cov_mark::check!(convert_removal_loop_to_retain_not_filtering);
+        check_assist_not_applicable(
+            convert_removal_loop_to_retain,
+            &format!(
+                r#"
+//- minicore: index
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn halve(values: &mut Vec<u32>) {{
+    let mut i = 0;
+    $0while i < values.len() {{
+        match values[i] {{
+            0 => {{
+                values.remove(i);
+            }}
+            n => {{
+                values[i] = n / 2;
+                i += 1;
+            }}
+        }}
+    }}
+}}
+{ALLOC}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_index_is_used_after_loop() {
+        check_assist_not_applicable(
+            convert_removal_loop_to_retain,
+            &format!(
+                r#"
+//- minicore: index
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn prune(values: &mut Vec<u32>) -> usize {{
+    let mut i = 0;
+    $0while i < values.len() {{
+        match values[i] {{
+            0 => {{
+                values.remove(i);
+            }}
+            _ => i += 1,
+        }}
+    }}
+    i
+}}
+{ALLOC}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_vec_types() {
+        check_assist_not_applicable(
+            convert_removal_loop_to_retain,
+            r#"
+//- minicore: index
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn len(&self) -> usize { 0 }
+    fn remove(&mut self, index: usize) -> T { loop {} }
+}
+impl<T> core::ops::Index<usize> for Vec<T> { type Output = T; }
+
+fn prune(values: &mut Vec<u32>) {
+    let mut i = 0;
+    $0while i < values.len() {
+        match values[i] {
+            0 => {
+                values.remove(i);
+            }
+            _ => i += 1,
+        }
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, AstNode, HasArgList, HasName};
+
+use crate::{
+    utils::{has_early_exit, needs_parens_as_receiver},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_result_match_to_map
+//
+// Replaces a match on a `Result` that wraps the value of one variant back into the same
+// variant unchanged and transforms the other with `map` or `map_err`.
+//
+// ```
+// # //- minicore: result
+// fn double(res: Result<u32, ()>) -> Result<u32, ()> {
+//     $0match res {
+//         Ok(v) => Ok(v * 2),
+//         Err(e) => Err(e),
+//     }
+// }
+// ```
+// ->
+// ```
+// fn double(res: Result<u32, ()>) -> Result<u32, ()> {
+//     res.map(|v| v * 2)
+// }
+// ```
+pub(crate) fn convert_result_match_to_map(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let krate = ctx.sema.scope(match_expr.syntax())?.krate();
+    let result_enum = FamousDefs(&ctx.sema, krate).core_result_Result()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    if !matches!(scrutinee_ty.as_adt(), Some(hir::Adt::Enum(it)) if it == result_enum) {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = arms.as_slice() else { return None };
+    let (first, second) =
+        (rewrapped(ctx, result_enum, first)?, rewrapped(ctx, result_enum, second)?);
+    let (ok, err) = match (first.variant.as_str(), second.variant.as_str()) {
+        ("Ok", "Err") => (first, second),
+        ("Err", "Ok") => (second, first),
+        _ => return None,
+    };
+    let (method, mapped) = match (ok.is_identity(), err.is_identity()) {
+        (false, true) => ("map", ok),
+        (true, false) => ("map_err", err),
+        (true, true) => return None,
+        (false, false) => {
+            cov_mark::hit!(convert_result_match_to_map_both_sides);
+            return None;
+        }
+    };
+    if has_early_exit(&mapped.value) {
+        cov_mark::hit!(convert_result_match_to_map_early_exit);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_result_match_to_map", AssistKind::RefactorRewrite),
+        format!("Replace match with `{method}`"),
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(target, format!("{receiver}.{method}({})", mapped.function()));
+        },
+    )
+}
+
+/// An arm `Variant(binding) => Variant(value)`.
+struct Rewrapped {
+    variant: String,
+    binding: String,
+    value: ast::Expr,
+}
+
+impl Rewrapped {
+    fn is_identity(&self) -> bool {
+        matches!(&self.value, ast::Expr::PathExpr(it) if it.syntax().text() == self.binding.as_str())
+    }
+
+    /// Renders the transformation of the binding, without a closure for `f(binding)`.
+    fn function(&self) -> String {
+        if let ast::Expr::CallExpr(call) = &self.value {
+            let mut args = call.arg_list().into_iter().flat_map(|it| it.args());
+            if let (Some(ast::Expr::PathExpr(callee)), Some(arg), None) =
+                (call.expr(), args.next(), args.next())
+            {
+                if arg.syntax().text() == self.binding.as_str() {
+                    return callee.to_string();
+                }
+            }
+        }
+        format!("|{}| {}", self.binding, self.value)
+    }
+}
+
+fn rewrapped(
+    ctx: &AssistContext<'_>,
+    result_enum: hir::Enum,
+    arm: &ast::MatchArm,
+) -> Option<Rewrapped> {
+    if arm.guard().is_some() {
+        return None;
+    }
+    let ast::Pat::TupleStructPat(pat) = arm.pat()? else { return None };
+    let variant = result_variant(ctx, result_enum, &pat.path()?)?;
+    let mut fields = pat.fields();
+    let binding = match (fields.next(), fields.next()) {
+        (Some(ast::Pat::IdentPat(it)), None)
+            if it.ref_token().is_none() && it.mut_token().is_none() && it.pat().is_none() =>
+        {
+            it.name()?.to_string()
+        }
+        _ => return None,
+    };
+
+    let ast::Expr::CallExpr(call) = arm.expr()? else { return None };
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    if result_variant(ctx, result_enum, &callee.path()?)? != variant {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    let value = args.next()?;
+    if args.next().is_some() {
+        return None;
+    }
+    Some(Rewrapped { variant, binding, value })
+}
+
+fn result_variant(
+    ctx: &AssistContext<'_>,
+    result_enum: hir::Enum,
+    path: &ast::Path,
+) -> Option<String> {
+    match ctx.sema.resolve_path(path)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it))
+            if it.parent_enum(ctx.db()) == result_enum =>
+        {
+            Some(it.name(ctx.db()).to_string())
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_to_map() {
+        check_assist(
+            convert_result_match_to_map,
+            r#"
+//- minicore: result
+fn len(s: &str) -> usize { 0 }
+
+fn parse(input: Result<&str, u8>) -> Result<usize, u8> {
+    match$0 input {
+        Err(code) => Err(code),
+        Ok(s) => Ok(len(s)),
+    }
+}
+"#,
+            r#"
+fn len(s: &str) -> usize { 0 }
+
+fn parse(input: Result<&str, u8>) -> Result<usize, u8> {
+    input.map(len)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_to_map_err() {
+        check_assist(
+            convert_result_match_to_map,
+            r#"
+//- minicore: result
+struct Error { code: u8 }
+
+fn read(res: Result<u32, u8>) -> Result<u32, Error> {
+    match$0 res {
+        Ok(n) => Ok(n),
+        Err(code) => Err(Error { code }),
+    }
+}
+"#,
+            r#"
+struct Error { code: u8 }
+
+fn read(res: Result<u32, u8>) -> Result<u32, Error> {
+    res.map_err(|code| Error { code })
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_both_sides_change() {
+        cov_mark::check!(convert_result_match_to_map_both_sides);
+        check_assist_not_applicable(
+            convert_result_match_to_map,
+            r#"
+//- minicore: result
+fn read(res: Result<u32, u8>) -> Result<u64, u16> {
+    match$0 res {
+        Ok(n) => Ok(n as u64),
+        Err(code) => Err(code as u16),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_the_value_returns_early() {
+        cov_mark::check!(convert_result_match_to_map_early_exit);
+        check_assist_not_applicable(
+            convert_result_match_to_map,
+            r#"
+//- minicore: result, try
+fn parse(n: u32) -> Result<u64, ()> { Ok(n as u64) }
+fn read(res: Result<u32, ()>) -> Result<Result<u64, ()>, ()> {
+    Ok(match$0 res {
+        Ok(n) => Ok(parse(n)?),
+        Err(e) => Err(e),
+    })
+}
+"#,
+        );
+    }
+}
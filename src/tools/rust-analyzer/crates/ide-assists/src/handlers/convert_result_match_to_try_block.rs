@@ -0,0 +1,320 @@
+use hir::PathResolution;
+use ide_db::{base_db::Edition, famous_defs::FamousDefs};
+use stdx::format_to;
+use syntax::ast::{
+    self,
+    edit::{AstNodeEdit, IndentLevel},
+    AstNode, HasArgList, HasAttrs,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_result_match_to_try_block
+//
+// Replaces nested matches that only pass `Err`s through with a `try` block using `?`.
+// Requires the `try_blocks` feature.
+//
+// ```
+// # //- minicore: result
+// #![feature(try_blocks)]
+//
+// fn sum(a: Result<u32, ()>, b: Result<u32, ()>) -> Result<u32, ()> {
+//     $0match a {
+//         Ok(x) => match b {
+//             Ok(y) => Ok(x + y),
+//             Err(e) => Err(e),
+//         },
+//         Err(e) => Err(e),
+//     }
+// }
+// ```
+// ->
+// ```
+// #![feature(try_blocks)]
+//
+// fn sum(a: Result<u32, ()>, b: Result<u32, ()>) -> Result<u32, ()> {
+//     try {
+//         let x = a?;
+//         let y = b?;
+//         x + y
+//     }
+// }
+// ```
+pub(crate) fn convert_result_match_to_try_block(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let krate = ctx.sema.scope(match_expr.syntax())?.krate();
+    let result = FamousDefs(&ctx.sema, krate).core_result_Result()?;
+
+    let mut lets = Vec::new();
+    let mut current = match_expr.clone();
+    let tail = loop {
+        let (pat, scrutinee, value) = propagating_parts(ctx, &current, result)?;
+        lets.push((pat, scrutinee));
+        match value {
+            ast::Expr::MatchExpr(inner) if propagating_parts(ctx, &inner, result).is_some() => {
+                current = inner
+            }
+            value => break value,
+        }
+    };
+
+    if krate.edition(ctx.db()) == Edition::Edition2015 || !has_try_blocks_feature(ctx, krate) {
+        cov_mark::hit!(convert_result_match_to_try_block_unavailable);
+        return None;
+    }
+
+    // An `Ok(..)` tail is what the `try` block wraps its value in anyway.
+    let tail = match &tail {
+        ast::Expr::CallExpr(call) if result_ctor(ctx, call, result).as_deref() == Some("Ok") => {
+            call.arg_list()?.args().next()?.reset_indent().to_string()
+        }
+        _ => format!("{}?", wrap_for_try(&tail.reset_indent())),
+    };
+
+    acc.add(
+        AssistId("convert_result_match_to_try_block", AssistKind::RefactorRewrite),
+        "Convert to `try` block",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let mut buf = String::from("try {");
+            for (pat, scrutinee) in &lets {
+                format_to!(buf, "\n{inner}let {pat} = {}?;", wrap_for_try(scrutinee));
+            }
+            format_to!(buf, "\n{inner}{tail}\n{indent}}}");
+            builder.replace(match_expr.syntax().text_range(), buf);
+        },
+    )
+}
+
+/// Splits `match scrutinee { Ok(pat) => value, Err(e) => Err(e) }` into its parts.
+fn propagating_parts(
+    ctx: &AssistContext<'_>,
+    match_expr: &ast::MatchExpr,
+    result: hir::Enum,
+) -> Option<(ast::Pat, ast::Expr, ast::Expr)> {
+    let scrutinee = match_expr.expr()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() != 2 || arms.iter().any(|arm| arm.guard().is_some()) {
+        return None;
+    }
+
+    let mut ok = None;
+    let mut err_passed_through = false;
+    for arm in &arms {
+        let pat = match arm.pat()? {
+            ast::Pat::TupleStructPat(it) => it,
+            _ => return None,
+        };
+        let variant = match ctx.sema.resolve_path(&pat.path()?)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it)) => it,
+            _ => return None,
+        };
+        if variant.parent_enum(ctx.db()) != result {
+            return None;
+        }
+        let field = pat.fields().next()?;
+        match variant.name(ctx.db()).to_smol_str().as_str() {
+            "Ok" => {
+                if !matches!(
+                    field,
+                    ast::Pat::IdentPat(_) | ast::Pat::TuplePat(_) | ast::Pat::WildcardPat(_)
+                ) {
+                    return None;
+                }
+                ok = Some((field, arm.expr()?));
+            }
+            _ => {
+                let ast::Pat::IdentPat(binding) = field else { return None };
+                err_passed_through =
+                    is_err_of(ctx, &arm.expr()?, &binding.syntax().to_string(), result);
+            }
+        }
+    }
+    let (pat, value) = ok?;
+    if !err_passed_through {
+        return None;
+    }
+    let value = match &value {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => match block.stmt_list() {
+            Some(stmts) if stmts.statements().next().is_none() => stmts.tail_expr()?,
+            _ => value,
+        },
+        _ => value,
+    };
+    Some((pat, scrutinee, value))
+}
+
+/// Checks for `Err(binding)` or `Err(binding.into())`, which `?` does as well.
+fn is_err_of(ctx: &AssistContext<'_>, expr: &ast::Expr, binding: &str, result: hir::Enum) -> bool {
+    let ast::Expr::CallExpr(call) = expr else { return false };
+    if result_ctor(ctx, call, result).as_deref() != Some("Err") {
+        return false;
+    }
+    let Some(arg) = call.arg_list().and_then(|it| it.args().next()) else { return false };
+    match arg {
+        ast::Expr::PathExpr(it) => it.syntax().text() == binding,
+        ast::Expr::MethodCallExpr(it) => {
+            it.name_ref().map_or(false, |it| it.text() == "into")
+                && it.receiver().map_or(false, |it| it.syntax().text() == binding)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the name of the `Result` variant constructed by `call`.
+fn result_ctor(ctx: &AssistContext<'_>, call: &ast::CallExpr, result: hir::Enum) -> Option<String> {
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    match ctx.sema.resolve_path(&callee.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) if it.parent_enum(ctx.db()) == result => {
+            Some(it.name(ctx.db()).to_string())
+        }
+        _ => None,
+    }
+}
+
+fn wrap_for_try(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::PathExpr(_)
+        | ast::Expr::CallExpr(_)
+        | ast::Expr::MethodCallExpr(_)
+        | ast::Expr::FieldExpr(_)
+        | ast::Expr::IndexExpr(_)
+        | ast::Expr::ParenExpr(_)
+        | ast::Expr::MacroExpr(_)
+        | ast::Expr::TryExpr(_)
+        | ast::Expr::AwaitExpr(_) => expr.to_string(),
+        _ => format!("({expr})"),
+    }
+}
+
+fn has_try_blocks_feature(ctx: &AssistContext<'_>, krate: hir::Crate) -> bool {
+    let source = krate.root_module(ctx.db()).definition_source(ctx.db()).value;
+    let hir::ModuleSource::SourceFile(file) = source else { return false };
+    file.attrs().filter(|attr| attr.excl_token().is_some()).any(|attr| {
+        matches!(
+            attr.as_simple_call(),
+            Some((name, args)) if name == "feature"
+                && args.syntax().descendants_with_tokens().any(|it| it.to_string() == "try_blocks")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_two_level_propagation() {
+        check_assist(
+            convert_result_match_to_try_block,
+            r#"
+//- minicore: result
+#![feature(never_type, try_blocks)]
+
+fn parse(s: &str) -> Result<u32, u8> { Ok(0) }
+fn check(n: u32) -> Result<(u32, bool), u8> { Ok((n, true)) }
+
+fn run(s: &str) -> Result<u32, u8> {
+    let value = match$0 parse(s) {
+        Err(e) => Err(e.into()),
+        Ok(n) => match check(n + 1) {
+            Ok((m, _)) => {
+                Ok(m * 2)
+            }
+            Err(e) => Err(e),
+        },
+    };
+    value
+}
+"#,
+            r#"
+#![feature(never_type, try_blocks)]
+
+fn parse(s: &str) -> Result<u32, u8> { Ok(0) }
+fn check(n: u32) -> Result<(u32, bool), u8> { Ok((n, true)) }
+
+fn run(s: &str) -> Result<u32, u8> {
+    let value = try {
+        let n = parse(s)?;
+        let (m, _) = check(n + 1)?;
+        m * 2
+    };
+    value
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_feature() {
+        cov_mark::check!(convert_result_match_to_try_block_unavailable);
+        check_assist_not_applicable(
+            convert_result_match_to_try_block,
+            r#"
+//- minicore: result
+fn run(a: Result<u32, ()>) -> Result<u32, ()> {
+    match$0 a {
+        Ok(x) => Ok(x),
+        Err(e) => Err(e),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_edition_2015() {
+        cov_mark::check!(convert_result_match_to_try_block_unavailable);
+        check_assist_not_applicable(
+            convert_result_match_to_try_block,
+            r#"
+//- /main.rs edition:2015 crate:main deps:core
+#![feature(try_blocks)]
+
+fn run(a: core::result::Result<u32, ()>) -> core::result::Result<u32, ()> {
+    match$0 a {
+        Ok(x) => Ok(x),
+        Err(e) => Err(e),
+    }
+}
+//- /core.rs crate:core
+pub mod result {
+    pub enum Result<T, E> {
+        Ok(T),
+        Err(E),
+    }
+}
+pub mod prelude {
+    pub mod rust_2015 {
+        pub use crate::result::Result::{self, Ok, Err};
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_err_is_handled() {
+        check_assist_not_applicable(
+            convert_result_match_to_try_block,
+            r#"
+//- minicore: result
+#![feature(try_blocks)]
+
+fn run(a: Result<u32, ()>) -> Result<u32, ()> {
+    match$0 a {
+        Ok(x) => Ok(x),
+        Err(_) => Ok(0),
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,355 @@
+use hir::HirDisplay;
+use stdx::{format_to, to_upper_snake_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    AstToken,
+};
+
+use crate::{
+    utils::{module_item, suggest_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+/// Below this many keys a plain match is at least as fast as a binary search.
+const MIN_KEYS: usize = 16;
+
+// Assist: convert_str_match_to_sorted_table
+//
+// Replaces a large match of string literals to literal values with a binary search in a
+// sorted `const` table.
+//
+// ```
+// fn code(s: &str) -> u8 {
+//     $0match s {
+//         "p" => 15, "o" => 14, "n" => 13, "m" => 12,
+//         "l" => 11, "k" => 10, "j" => 9, "i" => 8,
+//         "h" => 7, "g" => 6, "f" => 5, "e" => 4,
+//         "d" => 3, "c" => 2, "b" => 1, "a" => 0,
+//         _ => 255,
+//     }
+// }
+// ```
+// ->
+// ```
+// const CODE_TABLE: [(&str, u8); 16] = [
+//     ("a", 0),
+//     ("b", 1),
+//     ("c", 2),
+//     ("d", 3),
+//     ("e", 4),
+//     ("f", 5),
+//     ("g", 6),
+//     ("h", 7),
+//     ("i", 8),
+//     ("j", 9),
+//     ("k", 10),
+//     ("l", 11),
+//     ("m", 12),
+//     ("n", 13),
+//     ("o", 14),
+//     ("p", 15),
+// ];
+//
+// fn code(s: &str) -> u8 {
+//     match CODE_TABLE.binary_search_by_key(&s, |&(key, _)| key) {
+//         Ok(idx) => CODE_TABLE[idx].1,
+//         Err(_) => 255,
+//     }
+// }
+// ```
+pub(crate) fn convert_str_match_to_sorted_table(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    if !matches!(
+        scrutinee,
+        ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_) | ast::Expr::MethodCallExpr(_)
+    ) {
+        return None;
+    }
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    let is_str_ref = scrutinee_ty
+        .as_reference()
+        .and_then(|(inner, _)| inner.as_builtin())
+        .map_or(false, |it| it.is_str());
+    if !is_str_ref {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    let mut fallback = None;
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() || fallback.is_some() {
+            return None;
+        }
+        let expr = arm.expr()?;
+        let pats = match arm.pat()? {
+            ast::Pat::WildcardPat(_) => {
+                fallback = Some(expr);
+                continue;
+            }
+            ast::Pat::OrPat(it) => it.pats().collect(),
+            pat => vec![pat],
+        };
+        // The table is indexed by value, which only works for `Copy` values.
+        if !matches!(expr, ast::Expr::Literal(_)) {
+            cov_mark::hit!(convert_str_match_to_sorted_table_non_literal_value);
+            return None;
+        }
+        for pat in pats {
+            let key = match pat {
+                ast::Pat::LiteralPat(it) => match it.literal()?.kind() {
+                    ast::LiteralKind::String(it) => it,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            entries.push((key.value()?.into_owned(), key.syntax().to_string(), expr.clone()));
+        }
+    }
+    let fallback = fallback?;
+    if entries.len() < MIN_KEYS {
+        cov_mark::hit!(convert_str_match_to_sorted_table_too_small);
+        return None;
+    }
+    entries.sort_by(|(lhs, ..), (rhs, ..)| lhs.cmp(rhs));
+    if entries.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let value_ty = ctx.sema.type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?.original;
+    let value_ty = value_ty.display_source_code(ctx.db(), module.into()).ok()?;
+
+    // The table goes right before the item the match is in.
+    let item = module_item(match_expr.syntax())?;
+    let fn_name = match_expr
+        .syntax()
+        .ancestors()
+        .find_map(ast::Fn::cast)
+        .and_then(|func| func.name())
+        .map(|name| to_upper_snake_case(&name.to_string()));
+    let table_name = match fn_name {
+        Some(name) => format!("{name}_TABLE"),
+        None => "TABLE".to_owned(),
+    };
+    let table_name =
+        suggest_name::unique_in_scope(&table_name, &ctx.sema.scope(match_expr.syntax())?, &[]);
+
+    acc.add(
+        AssistId("convert_str_match_to_sorted_table", AssistKind::RefactorRewrite),
+        "Convert match to sorted table lookup",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(item.syntax());
+            let mut table =
+                format!("const {table_name}: [(&str, {value_ty}); {}] = [", entries.len());
+            for (_, key, value) in &entries {
+                format_to!(table, "\n{indent}    ({key}, {value}),");
+            }
+            format_to!(table, "\n{indent}];\n\n{indent}");
+            builder.insert(item.syntax().text_range().start(), table);
+
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let lookup = format!(
+                "match {table_name}.binary_search_by_key(&{scrutinee}, |&(key, _)| key) {{\n\
+                 {indent}    Ok(idx) => {table_name}[idx].1,\n\
+                 {indent}    Err(_) => {fallback},\n\
+                 {indent}}}"
+            );
+            builder.replace(match_expr.syntax().text_range(), lookup);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_sixteen_keys() {
+        check_assist(
+            convert_str_match_to_sorted_table,
+            r#"
+//- minicore: option
+struct Lexer { word: &'static str }
+
+impl Lexer {
+    fn keyword_id(&self) -> Option<u32> {
+        Some(match$0 self.word {
+            "while" => 1,
+            "use" => 2,
+            "trait" => 3,
+            "struct" => 4,
+            "return" => 5,
+            "pub" => 6,
+            "mut" => 7,
+            "mod" => 8,
+            "match" => 9,
+            "loop" => 10,
+            "let" => 11,
+            "in" | "impl" => 12,
+            "if" => 13,
+            "fn" => 14,
+            "else" => 15,
+            _ => return None,
+        })
+    }
+}
+"#,
+            r#"
+struct Lexer { word: &'static str }
+
+const KEYWORD_ID_TABLE: [(&str, u32); 16] = [
+    ("else", 15),
+    ("fn", 14),
+    ("if", 13),
+    ("impl", 12),
+    ("in", 12),
+    ("let", 11),
+    ("loop", 10),
+    ("match", 9),
+    ("mod", 8),
+    ("mut", 7),
+    ("pub", 6),
+    ("return", 5),
+    ("struct", 4),
+    ("trait", 3),
+    ("use", 2),
+    ("while", 1),
+];
+
+impl Lexer {
+    fn keyword_id(&self) -> Option<u32> {
+        Some(match KEYWORD_ID_TABLE.binary_search_by_key(&self.word, |&(key, _)| key) {
+            Ok(idx) => KEYWORD_ID_TABLE[idx].1,
+            Err(_) => return None,
+        })
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_with_table_name_taken() {
+        check_assist(
+            convert_str_match_to_sorted_table,
+            r#"
+//- minicore: option
+struct Lexer { word: &'static str }
+const KEYWORD_ID_TABLE: u8 = 0;
+
+impl Lexer {
+    fn keyword_id(&self) -> Option<u32> {
+        Some(match$0 self.word {
+            "while" => 1,
+            "use" => 2,
+            "trait" => 3,
+            "struct" => 4,
+            "return" => 5,
+            "pub" => 6,
+            "mut" => 7,
+            "mod" => 8,
+            "match" => 9,
+            "loop" => 10,
+            "let" => 11,
+            "in" | "impl" => 12,
+            "if" => 13,
+            "fn" => 14,
+            "else" => 15,
+            _ => return None,
+        })
+    }
+}
+"#,
+            r#"
+struct Lexer { word: &'static str }
+const KEYWORD_ID_TABLE: u8 = 0;
+
+const KEYWORD_ID_TABLE1: [(&str, u32); 16] = [
+    ("else", 15),
+    ("fn", 14),
+    ("if", 13),
+    ("impl", 12),
+    ("in", 12),
+    ("let", 11),
+    ("loop", 10),
+    ("match", 9),
+    ("mod", 8),
+    ("mut", 7),
+    ("pub", 6),
+    ("return", 5),
+    ("struct", 4),
+    ("trait", 3),
+    ("use", 2),
+    ("while", 1),
+];
+
+impl Lexer {
+    fn keyword_id(&self) -> Option<u32> {
+        Some(match KEYWORD_ID_TABLE1.binary_search_by_key(&self.word, |&(key, _)| key) {
+            Ok(idx) => KEYWORD_ID_TABLE1[idx].1,
+            Err(_) => return None,
+        })
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_few_keys() {
+        cov_mark::check!(convert_str_match_to_sorted_table_too_small);
+        check_assist_not_applicable(
+            convert_str_match_to_sorted_table,
+            r#"
+fn code(s: &str) -> u8 {
+    match$0 s {
+        "a" => 0,
+        "b" => 1,
+        _ => 255,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_literal_values() {
+        cov_mark::check!(convert_str_match_to_sorted_table_non_literal_value);
+        check_assist_not_applicable(
+            convert_str_match_to_sorted_table,
+            r#"
+fn code(s: &str, base: u8) -> u8 {
+    match$0 s {
+        "a" => base,
+        _ => 255,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_fallback() {
+        check_assist_not_applicable(
+            convert_str_match_to_sorted_table,
+            r#"
+fn code(s: &'static str) -> u8 {
+    match$0 s {
+        "a" => 0, "b" => 1, "c" => 2, "d" => 3,
+        "e" => 4, "f" => 5, "g" => 6, "h" => 7,
+        "i" => 8, "j" => 9, "k" => 10, "l" => 11,
+        "m" => 12, "n" => 13, "o" => 14, "p" => 15,
+        other => other.len() as u8,
+    }
+}
+"#,
+        );
+    }
+}
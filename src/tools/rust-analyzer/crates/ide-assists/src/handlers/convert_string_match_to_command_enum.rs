@@ -0,0 +1,291 @@
+use stdx::{format_to, to_upper_camel_case};
+use syntax::ast::{self, edit::IndentLevel, AstNode};
+
+use crate::{
+    utils::{module_item, needs_parens_as_receiver},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: convert_string_match_to_command_enum
+//
+// Generates an enum with a variant for each string matched on, and a `FromStr` implementation
+// parsing them, and matches on the parsed enum instead.
+//
+// ```
+// fn run(cmd: &str) -> u32 {
+//     $0match cmd {
+//         "start" => 1,
+//         "stop" | "halt" => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// enum $0Command {
+//     Start,
+//     Stop,
+// }
+//
+// impl core::str::FromStr for Command {
+//     type Err = ();
+//
+//     fn from_str(s: &str) -> Result<Self, Self::Err> {
+//         match s {
+//             "start" => Ok(Self::Start),
+//             "stop" | "halt" => Ok(Self::Stop),
+//             _ => Err(()),
+//         }
+//     }
+// }
+//
+// fn run(cmd: &str) -> u32 {
+//     match cmd.parse::<Command>() {
+//         Ok(Command::Start) => 1,
+//         Ok(Command::Stop) => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+pub(crate) fn convert_string_match_to_command_enum(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let mut arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    match arms.pop()?.pat()? {
+        ast::Pat::WildcardPat(_) => (),
+        _ => return None,
+    }
+
+    let mut commands: Vec<CommandArm> = Vec::new();
+    for arm in &arms {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let pat = arm.pat()?;
+        let Some(strings) = string_literals(&pat) else {
+            cov_mark::hit!(convert_string_match_to_command_enum_non_literal);
+            return None;
+        };
+        let variant = command_variant(&strings[0])?;
+        if commands.iter().any(|it| it.variant == variant) {
+            return None;
+        }
+        commands.push(CommandArm { variant, pat });
+    }
+    if commands.is_empty() {
+        return None;
+    }
+
+    // The enum goes in front of the item containing the match.
+    let item = module_item(match_expr.syntax())?;
+    let scope = ctx.sema.scope(item.syntax())?;
+    let mut taken = false;
+    scope.process_all_names(&mut |name, _| taken |= name.to_smol_str() == ENUM_NAME);
+    if taken {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_string_match_to_command_enum", AssistKind::RefactorRewrite),
+        "Convert to match on a command enum",
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(
+                scrutinee.syntax().text_range(),
+                format!("{receiver}.parse::<{ENUM_NAME}>()"),
+            );
+            for command in &commands {
+                builder.replace(
+                    command.pat.syntax().text_range(),
+                    format!("Ok({ENUM_NAME}::{})", command.variant),
+                );
+            }
+
+            let indent = IndentLevel::from_node(item.syntax());
+            let name = match ctx.config.snippet_cap {
+                Some(_) => format!("$0{ENUM_NAME}"),
+                None => ENUM_NAME.to_string(),
+            };
+            let mut buf = String::from("#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+            format_to!(buf, "\n{indent}enum {name} {{");
+            for command in &commands {
+                format_to!(buf, "\n{indent}    {},", command.variant);
+            }
+            format_to!(
+                buf,
+                "\n{indent}}}\n\n{indent}impl core::str::FromStr for {ENUM_NAME} {{\n\
+                 {indent}    type Err = ();\n\n\
+                 {indent}    fn from_str(s: &str) -> Result<Self, Self::Err> {{\n\
+                 {indent}        match s {{"
+            );
+            for command in &commands {
+                let (pat, variant) = (&command.pat, &command.variant);
+                format_to!(buf, "\n{indent}            {pat} => Ok(Self::{variant}),");
+            }
+            format_to!(
+                buf,
+                "\n{indent}            _ => Err(()),\n\
+                 {indent}        }}\n\
+                 {indent}    }}\n\
+                 {indent}}}\n\n{indent}"
+            );
+            let offset = item.syntax().text_range().start();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, buf),
+                None => builder.insert(offset, buf),
+            }
+        },
+    )
+}
+
+const ENUM_NAME: &str = "Command";
+
+/// An arm of the string match, which becomes a variant of the enum.
+struct CommandArm {
+    variant: String,
+    pat: ast::Pat,
+}
+
+/// Returns the strings matched by `pat`, if it consists of string literals only.
+fn string_literals(pat: &ast::Pat) -> Option<Vec<String>> {
+    match pat {
+        ast::Pat::OrPat(it) => it
+            .pats()
+            .map(|it| string_literals(&it))
+            .collect::<Option<Vec<_>>>()
+            .map(|it| it.concat()),
+        ast::Pat::LiteralPat(it) => match it.literal()?.kind() {
+            ast::LiteralKind::String(it) => Some(vec![it.value()?.into_owned()]),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Turns a command like `dry-run` into a variant name like `DryRun`.
+fn command_variant(command: &str) -> Option<String> {
+    if !command.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ' ')) {
+        return None;
+    }
+    let name = to_upper_camel_case(command);
+    name.starts_with(|c: char| c.is_ascii_alphabetic()).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_commands() {
+        check_assist(
+            convert_string_match_to_command_enum,
+            r#"
+struct Server;
+impl Server {
+    fn start(&self) {}
+    fn stop(&self) {}
+}
+
+mod cli {
+    fn dispatch(server: &super::Server, line: String) {
+        match$0 line.trim() {
+            "start" => server.start(),
+            "stop" | "quit" => server.stop(),
+            "dry-run" => {
+                server.start();
+                server.stop();
+            }
+            _ => (),
+        }
+    }
+}
+"#,
+            r#"
+struct Server;
+impl Server {
+    fn start(&self) {}
+    fn stop(&self) {}
+}
+
+mod cli {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum $0Command {
+        Start,
+        Stop,
+        DryRun,
+    }
+
+    impl core::str::FromStr for Command {
+        type Err = ();
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "start" => Ok(Self::Start),
+                "stop" | "quit" => Ok(Self::Stop),
+                "dry-run" => Ok(Self::DryRun),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn dispatch(server: &super::Server, line: String) {
+        match line.trim().parse::<Command>() {
+            Ok(Command::Start) => server.start(),
+            Ok(Command::Stop) => server.stop(),
+            Ok(Command::DryRun) => {
+                server.start();
+                server.stop();
+            }
+            _ => (),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_literal_pattern() {
+        cov_mark::check!(convert_string_match_to_command_enum_non_literal);
+        check_assist_not_applicable(
+            convert_string_match_to_command_enum,
+            r#"
+const STOP: &str = "stop";
+
+fn run(cmd: &str) -> u32 {
+    match$0 cmd {
+        "start" => 1,
+        STOP => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_wildcard() {
+        check_assist_not_applicable(
+            convert_string_match_to_command_enum,
+            r#"
+fn run(cmd: &str) -> u32 {
+    match$0 cmd {
+        "start" => 1,
+        other => other.len() as u32,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasArgList, HasLoopBody},
+    T,
+};
+
+use crate::{utils::new_vec_binding, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_try_loop_to_collect
+//
+// Replaces a loop that pushes a value from a match on each item into a new vector, and
+// returns the first error it runs into, with a `collect` into a `Result`.
+//
+// ```
+// # //- minicore: iterator, option, result
+// # struct Vec<T>(T);
+// # impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+// fn parse(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+//     let mut out = Vec::new();
+//     $0for item in items {
+//         match item {
+//             Some(b) => out.push(b),
+//             None => return Err(()),
+//         }
+//     }
+//     Ok(())
+// }
+// ```
+// ->
+// ```
+// # struct Vec<T>(T);
+// # impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+// fn parse(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+//     let out = items.map(|item| match item {
+//         Some(b) => Ok(b),
+//         None => Err(()),
+//     }).collect::<Result<Vec<_>, _>>()?;
+//     Ok(())
+// }
+// ```
+pub(crate) fn convert_try_loop_to_collect(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_expr = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let loop_node = match for_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(stmt) => stmt.syntax().clone(),
+        None => for_expr.syntax().clone(),
+    };
+    let let_stmt = loop_node.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let target = let_stmt.syntax().text_range().cover(loop_node.text_range());
+    let (out, keep_mut) = new_vec_binding(ctx, &let_stmt, target)?;
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+    let match_expr = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::MatchExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let mut arms = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let expr = single_expr(&arm.expr()?)?;
+        let value = match pushed(&expr, &out) {
+            Some(value) => Ok(value),
+            None => Err(returned_err(ctx, &expr)?),
+        };
+        arms.push((arm, value));
+    }
+    if !arms.iter().any(|(_, value)| value.is_err()) {
+        cov_mark::hit!(convert_try_loop_to_collect_no_early_return);
+        return None;
+    }
+
+    let iterable = for_expr.iterable()?;
+    let krate = ctx.sema.scope(for_expr.syntax())?.krate();
+    let iterator = FamousDefs(&ctx.sema, krate).core_iter_Iterator()?;
+    let is_iterator =
+        ctx.sema.type_of_expr(&iterable)?.original.impls_trait(ctx.db(), iterator, &[]);
+    let pat = for_expr.pat()?;
+    let scrutinee = match_expr.expr()?;
+
+    let out = if keep_mut { format!("mut {out}") } else { out };
+
+    acc.add(
+        AssistId("convert_try_loop_to_collect", AssistKind::RefactorRewrite),
+        "Convert loop to `collect` into `Result`",
+        target,
+        |builder| {
+            let iter = match (&iterable, is_iterator) {
+                (_, true) => iterable.to_string(),
+                (ast::Expr::PathExpr(_) | ast::Expr::MethodCallExpr(_), false) => {
+                    format!("{iterable}.into_iter()")
+                }
+                (_, false) => format!("({iterable}).into_iter()"),
+            };
+            let indent = IndentLevel::from_node(let_stmt.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let mut buf = format!("let {out} = {iter}.map(|{pat}| match {scrutinee} {{");
+            for (arm, value) in &arms {
+                let pat = arm.pat().map(|it| it.to_string()).unwrap_or_default();
+                let guard = arm.guard().map(|it| format!(" {it}")).unwrap_or_default();
+                let value = match value {
+                    Ok(it) => format!("Ok({it})"),
+                    Err(it) => format!("Err({it})"),
+                };
+                format_to!(buf, "\n{inner}{pat}{guard} => {value},");
+            }
+            format_to!(buf, "\n{indent}}}).collect::<Result<Vec<_>, _>>()?;");
+            builder.replace(target, buf);
+        },
+    )
+}
+
+/// Unwraps a block that only contains one expression.
+fn single_expr(expr: &ast::Expr) -> Option<ast::Expr> {
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmts = block.stmt_list()?;
+            match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+                ([], Some(tail)) => Some(tail),
+                ([ast::Stmt::ExprStmt(stmt)], None) => stmt.expr(),
+                _ => None,
+            }
+        }
+        _ => Some(expr.clone()),
+    }
+}
+
+/// Returns the value of `out.push(value)`.
+fn pushed(expr: &ast::Expr, out: &str) -> Option<ast::Expr> {
+    let ast::Expr::MethodCallExpr(call) = expr else { return None };
+    if call.name_ref()?.text() != "push" || call.receiver()?.syntax().text() != out {
+        return None;
+    }
+    let mut args = call.arg_list()?.args();
+    let value = args.next()?;
+    args.next().is_none().then_some(value)
+}
+
+/// Returns the error of `return Err(error)`.
+fn returned_err(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<ast::Expr> {
+    let ast::Expr::ReturnExpr(ret) = expr else { return None };
+    let ast::Expr::CallExpr(call) = ret.expr()? else { return None };
+    let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+    match ctx.sema.resolve_path(&callee.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it))
+            if it.name(ctx.db()).to_smol_str() == "Err" => {}
+        _ => return None,
+    }
+    let mut args = call.arg_list()?.args();
+    let error = args.next()?;
+    args.next().is_none().then_some(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_try_collect_loop() {
+        check_assist(
+            convert_try_loop_to_collect,
+            r#"
+//- minicore: iterator, option, result
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+enum Field { Num(u32), Text, Missing }
+
+fn sum(fields: &[Field]) -> Result<u32, &'static str> {
+    let mut nums = Vec::new();
+    f$0or field in fields {
+        match field {
+            Field::Num(n) if *n > 0 => nums.push(*n),
+            Field::Num(_) | Field::Missing => nums.push(0),
+            Field::Text => {
+                return Err("text");
+            }
+        }
+    }
+    nums.push(1);
+    Ok(0)
+}
+"#,
+            r#"
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+enum Field { Num(u32), Text, Missing }
+
+fn sum(fields: &[Field]) -> Result<u32, &'static str> {
+    let mut nums = fields.into_iter().map(|field| match field {
+        Field::Num(n) if *n > 0 => Ok(*n),
+        Field::Num(_) | Field::Missing => Ok(0),
+        Field::Text => Err("text"),
+    }).collect::<Result<Vec<_>, _>>()?;
+    nums.push(1);
+    Ok(0)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_early_return() {
+        cov_mark::check!(convert_try_loop_to_collect_no_early_return);
+        check_assist_not_applicable(
+            convert_try_loop_to_collect,
+            r#"
+//- minicore: iterator, option, result
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+
+fn all(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+    let mut out = Vec::new();
+    $0for item in items {
+        match item {
+            Some(b) => out.push(b),
+            None => out.push(0),
+        }
+    }
+    Ok(())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_errors_are_skipped() {
+        check_assist_not_applicable(
+            convert_try_loop_to_collect,
+            r#"
+//- minicore: iterator, option, result
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+    fn push(&mut self, x: T) {}
+}
+
+fn all(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+    let mut out = Vec::new();
+    $0for item in items {
+        match item {
+            Some(b) => out.push(b),
+            None => continue,
+        }
+    }
+    Ok(())
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,223 @@
+use ide_db::defs::Definition;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    SyntaxNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_tuple_match_to_if_chain
+//
+// Replaces a match on a tuple of variables, whose arms only bind or compare the elements
+// against literals, with an `if` chain on the variables themselves.
+//
+// ```
+// fn compare(a: u32, b: u32) -> &'static str {
+//     $0match (a, b) {
+//         (0, _) => "zero",
+//         (x, y) if x == y => "same",
+//         _ => "different",
+//     }
+// }
+// ```
+// ->
+// ```
+// fn compare(a: u32, b: u32) -> &'static str {
+//     if a == 0 {
+//         "zero"
+//     } else if a == b {
+//         "same"
+//     } else {
+//         "different"
+//     }
+// }
+// ```
+pub(crate) fn convert_tuple_match_to_if_chain(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let ast::Expr::TupleExpr(tuple) = match_expr.expr()? else { return None };
+    // The elements are mentioned once per arm instead of evaluated once, so they have to be
+    // plain variables.
+    let elements = tuple
+        .fields()
+        .map(|field| match field {
+            ast::Expr::PathExpr(it) => it.path().filter(|it| it.qualifier().is_none()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let mut branches = Vec::new();
+    for (idx, arm) in arms.iter().enumerate() {
+        let pats = match arm.pat()? {
+            ast::Pat::TuplePat(it) => it.fields().collect::<Vec<_>>(),
+            ast::Pat::WildcardPat(_) => Vec::new(),
+            _ => {
+                cov_mark::hit!(convert_tuple_match_to_if_chain_complex_pattern);
+                return None;
+            }
+        };
+        if !pats.is_empty() && pats.len() != elements.len() {
+            return None;
+        }
+
+        let mut conds = Vec::new();
+        let mut renames = Vec::new();
+        for (pat, element) in pats.iter().zip(&elements) {
+            match pat {
+                ast::Pat::WildcardPat(_) => {}
+                ast::Pat::LiteralPat(it) => conds.push(format!("{element} == {it}")),
+                ast::Pat::IdentPat(it)
+                    if it.ref_token().is_none()
+                        && it.mut_token().is_none()
+                        && it.pat().is_none() =>
+                {
+                    let local = ctx.sema.to_def(it)?;
+                    let usages = Definition::Local(local).usages(&ctx.sema).all();
+                    let ranges = usages.iter().flat_map(|(_, refs)| refs.iter().map(|it| it.range));
+                    renames.extend(ranges.map(|range| (range, element.to_string())));
+                }
+                _ => {
+                    cov_mark::hit!(convert_tuple_match_to_if_chain_complex_pattern);
+                    return None;
+                }
+            }
+        }
+        if let Some(guard) = arm.guard().and_then(|it| it.condition()) {
+            conds.push(replaced(guard.syntax(), &renames));
+        }
+        // An arm that matches everything has to be the last one, and the last one has to be it.
+        if conds.is_empty() != (idx == arms.len() - 1) {
+            return None;
+        }
+        branches.push((conds.join(" && "), arm.expr()?, renames));
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_tuple_match_to_if_chain", AssistKind::RefactorRewrite),
+        "Convert tuple match to `if` chain",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let mut buf = String::new();
+            for (idx, (cond, body, renames)) in branches.iter().enumerate() {
+                match (idx, cond.is_empty()) {
+                    (0, _) => format_to!(buf, "if {cond} "),
+                    (_, false) => format_to!(buf, " else if {cond} "),
+                    (_, true) => buf.push_str(" else "),
+                }
+                let text = replaced(body.syntax(), renames);
+                match body {
+                    // Arms are one level deeper than the branches of the `if`.
+                    ast::Expr::BlockExpr(_) => {
+                        buf.push_str(&text.replace(&format!("\n{}", IndentLevel(1)), "\n"))
+                    }
+                    _ => format_to!(buf, "{{\n{inner}{text}\n{indent}}}"),
+                }
+            }
+            builder.replace(target, buf);
+        },
+    )
+}
+
+/// Returns the text of `node` with the ranges in `renames` replaced.
+fn replaced(node: &SyntaxNode, renames: &[(TextRange, String)]) -> String {
+    let range = node.text_range();
+    let mut renames =
+        renames.iter().filter(|(it, _)| range.contains_range(*it)).collect::<Vec<_>>();
+    renames.sort_by_key(|(it, _)| it.start());
+
+    let text = node.to_string();
+    let mut buf = String::new();
+    let mut last = 0;
+    for (rename, replacement) in renames {
+        let start = usize::from(rename.start() - range.start());
+        buf.push_str(&text[last..start]);
+        buf.push_str(replacement);
+        last = usize::from(rename.end() - range.start());
+    }
+    buf.push_str(&text[last..]);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_pair_match() {
+        check_assist(
+            convert_tuple_match_to_if_chain,
+            r#"
+fn step(x: i32, y: i32) -> i32 {
+    let step = match$0 (x, y) {
+        (0, 0) => 0,
+        (dx, dy) if dx > dy => {
+            let d = dx - dy;
+            d * 2
+        }
+        (_, dy) if dy < 0 => -dy,
+        _ => 1,
+    };
+    step
+}
+"#,
+            r#"
+fn step(x: i32, y: i32) -> i32 {
+    let step = if x == 0 && y == 0 {
+        0
+    } else if x > y {
+        let d = x - y;
+        d * 2
+    } else if y < 0 {
+        -y
+    } else {
+        1
+    };
+    step
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_nested_patterns() {
+        cov_mark::check!(convert_tuple_match_to_if_chain_complex_pattern);
+        check_assist_not_applicable(
+            convert_tuple_match_to_if_chain,
+            r#"
+//- minicore: option
+fn pick(a: Option<u8>, b: u8) -> u8 {
+    $0match (a, b) {
+        (Some(x), _) => x,
+        _ => b,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_computed_elements() {
+        check_assist_not_applicable(
+            convert_tuple_match_to_if_chain,
+            r#"
+fn f() -> u8 { 0 }
+
+fn pick(b: u8) -> u8 {
+    $0match (f(), b) {
+        (0, _) => 1,
+        _ => b,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use stdx::format_to;
+use syntax::ast::{
+    self,
+    edit::{AstNodeEdit, IndentLevel},
+    AstNode,
+};
+
+use crate::{utils::variants, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_tuple_match_to_nested_match
+//
+// Replaces a match on a pair with a match on the first element, whose arms match on the
+// second one.
+//
+// ```
+// enum Dir { N, E, S }
+//
+// fn turn(from: Dir, to: Dir) -> i32 {
+//     $0match (from, to) {
+//         (Dir::N, Dir::E) => 1,
+//         (Dir::E, Dir::N) => -1,
+//         (Dir::N, _) => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Dir { N, E, S }
+//
+// fn turn(from: Dir, to: Dir) -> i32 {
+//     match from {
+//         Dir::N => match to {
+//             Dir::E => 1,
+//             _ => 2,
+//         },
+//         Dir::E => match to {
+//             Dir::N => -1,
+//             _ => 0,
+//         },
+//         _ => 0,
+//     }
+// }
+// ```
+pub(crate) fn convert_tuple_match_to_nested_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let ast::Expr::TupleExpr(tuple) = match_expr.expr()? else { return None };
+    let [first, second]: [ast::Expr; 2] = tuple.fields().collect::<Vec<_>>().try_into().ok()?;
+    // The second element is only looked at once the first one is matched.
+    if !matches!(second, ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_)) {
+        return None;
+    }
+
+    let mut groups: Vec<Group> = Vec::new();
+    let mut fallback: Vec<(ast::Pat, ast::Expr)> = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let (outer, inner) = match arm.pat()? {
+            ast::Pat::TuplePat(pat) => {
+                let [outer, inner]: [ast::Pat; 2] =
+                    pat.fields().collect::<Vec<_>>().try_into().ok()?;
+                (outer, inner)
+            }
+            ast::Pat::WildcardPat(it) => (it.clone().into(), it.into()),
+            _ => return None,
+        };
+        let body = arm.expr()?;
+        if matches!(outer, ast::Pat::WildcardPat(_)) {
+            fallback.push((inner, body));
+            continue;
+        }
+        // Arms after one matching any first element may only be reached through it.
+        if !fallback.is_empty() {
+            cov_mark::hit!(convert_tuple_match_to_nested_match_overlapping);
+            return None;
+        }
+        let key = outer.to_string();
+        match groups.iter_mut().find(|it| it.key == key) {
+            Some(group) => group.arms.push((inner, body)),
+            None => {
+                let variants = variants(ctx, &outer)?;
+                if groups.iter().any(|it| it.variants.iter().any(|v| variants.contains(v))) {
+                    cov_mark::hit!(convert_tuple_match_to_nested_match_overlapping);
+                    return None;
+                }
+                groups.push(Group { key, pat: outer, variants, arms: vec![(inner, body)] });
+            }
+        }
+    }
+    if groups.is_empty() {
+        return None;
+    }
+    // The arms for any first element are copied into the groups, where bindings of the group
+    // would be in scope for them.
+    let binds = |pat: &ast::Pat| {
+        pat.syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .any(|it| ctx.sema.resolve_bind_pat_to_const(&it).is_none() || it.pat().is_some())
+    };
+    if !fallback.is_empty() && groups.iter().any(|it| binds(&it.pat)) {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_tuple_match_to_nested_match", AssistKind::RefactorRewrite),
+        "Convert to nested match",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let arm_indent = IndentLevel(indent.0 + 1);
+            let mut buf = format!("match {first} {{");
+            for group in &groups {
+                let mut arms = group.arms.clone();
+                if !arms.last().map_or(false, |(pat, _)| is_catch_all(pat)) {
+                    // Leave out what the group already handles, it would be unreachable.
+                    let handled = arms.iter().map(|(pat, _)| pat.to_string()).collect::<Vec<_>>();
+                    let rest =
+                        fallback.iter().filter(|(pat, _)| !handled.contains(&pat.to_string()));
+                    arms.extend(rest.cloned());
+                }
+                format_to!(buf, "\n{arm_indent}{} => ", group.pat);
+                render_inner(&mut buf, &second, &arms, arm_indent);
+            }
+            if !fallback.is_empty() {
+                format_to!(buf, "\n{arm_indent}_ => ");
+                render_inner(&mut buf, &second, &fallback, arm_indent);
+            }
+            format_to!(buf, "\n{indent}}}");
+            builder.replace(target, buf);
+        },
+    )
+}
+
+/// The arms of the pair match sharing the pattern of the first element.
+struct Group {
+    key: String,
+    pat: ast::Pat,
+    variants: Vec<hir::Variant>,
+    arms: Vec<(ast::Pat, ast::Expr)>,
+}
+
+fn is_catch_all(pat: &ast::Pat) -> bool {
+    matches!(pat, ast::Pat::WildcardPat(_))
+}
+
+/// Renders the body of an outer arm, which only needs a match if it distinguishes anything.
+fn render_inner(
+    buf: &mut String,
+    scrutinee: &ast::Expr,
+    arms: &[(ast::Pat, ast::Expr)],
+    indent: IndentLevel,
+) {
+    if let [(pat, body)] = arms {
+        if is_catch_all(pat) {
+            let body = body.reset_indent().indent(indent);
+            let comma = if matches!(body, ast::Expr::BlockExpr(_)) { "" } else { "," };
+            format_to!(buf, "{body}{comma}");
+            return;
+        }
+    }
+    let inner = IndentLevel(indent.0 + 1);
+    format_to!(buf, "match {scrutinee} {{");
+    for (pat, body) in arms {
+        let body = body.reset_indent().indent(inner);
+        let comma = if matches!(body, ast::Expr::BlockExpr(_)) { "" } else { "," };
+        format_to!(buf, "\n{inner}{pat} => {body}{comma}");
+    }
+    format_to!(buf, "\n{indent}}},");
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn nest_sparse_match() {
+        check_assist(
+            convert_tuple_match_to_nested_match,
+            r#"
+enum Color { Red, Green, Blue }
+struct Tile { color: Color }
+
+fn mix(a: Color, tile: Tile) -> u8 {
+    match$0 (a, tile.color) {
+        (Color::Red, Color::Green) => 1,
+        (Color::Blue, Color::Red | Color::Green) => {
+            let x = 2;
+            x
+        }
+        (Color::Red, Color::Blue) => 3,
+        (Color::Green, _) => 4,
+        (_, Color::Blue) => 5,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+enum Color { Red, Green, Blue }
+struct Tile { color: Color }
+
+fn mix(a: Color, tile: Tile) -> u8 {
+    match a {
+        Color::Red => match tile.color {
+            Color::Green => 1,
+            Color::Blue => 3,
+            _ => 0,
+        },
+        Color::Blue => match tile.color {
+            Color::Red | Color::Green => {
+                let x = 2;
+                x
+            }
+            Color::Blue => 5,
+            _ => 0,
+        },
+        Color::Green => 4,
+        _ => match tile.color {
+            Color::Blue => 5,
+            _ => 0,
+        },
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_wildcard_comes_first() {
+        cov_mark::check!(convert_tuple_match_to_nested_match_overlapping);
+        check_assist_not_applicable(
+            convert_tuple_match_to_nested_match,
+            r#"
+enum Color { Red, Green }
+
+fn mix(a: Color, b: Color) -> u8 {
+    match$0 (a, b) {
+        (_, Color::Green) => 1,
+        (Color::Red, _) => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
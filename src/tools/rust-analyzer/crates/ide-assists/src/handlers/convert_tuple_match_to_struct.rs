@@ -0,0 +1,288 @@
+use hir::{db::HirDatabase, HirDisplay};
+use itertools::Itertools;
+use stdx::{format_to, to_upper_camel_case};
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasName};
+
+use crate::{utils::module_item, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_tuple_match_to_struct
+//
+// Replaces the tuples produced by the arms of a match that is destructured by a `let` with
+// a new struct whose fields are named after the bindings.
+//
+// ```
+// fn parse_header(tag: u8) {
+//     let (len, signed) = $0match tag {
+//         0 => (1, false),
+//         _ => (4, true),
+//     };
+// }
+// ```
+// ->
+// ```
+// struct $0ParseHeader {
+//     len: i32,
+//     signed: bool,
+// }
+//
+// fn parse_header(tag: u8) {
+//     let ParseHeader { len, signed } = match tag {
+//         0 => ParseHeader { len: 1, signed: false },
+//         _ => ParseHeader { len: 4, signed: true },
+//     };
+// }
+// ```
+pub(crate) fn convert_tuple_match_to_struct(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let let_stmt = match_expr.syntax().parent().and_then(ast::LetStmt::cast)?;
+    if let_stmt.initializer()?.syntax() != match_expr.syntax() || let_stmt.ty().is_some() {
+        return None;
+    }
+    let tuple_pat = match let_stmt.pat()? {
+        ast::Pat::TuplePat(it) => it,
+        _ => return None,
+    };
+    let bindings = tuple_pat
+        .fields()
+        .map(|pat| match pat {
+            ast::Pat::IdentPat(it) if it.pat().is_none() => Some(it),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let field_names =
+        bindings.iter().map(|it| Some(it.name()?.to_string())).collect::<Option<Vec<_>>>()?;
+
+    let tuples = match_expr
+        .match_arm_list()?
+        .arms()
+        .map(|arm| match arm.expr()? {
+            ast::Expr::TupleExpr(it) => Some(it),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if tuples.iter().any(|tuple| tuple.fields().count() != field_names.len()) {
+        cov_mark::hit!(convert_tuple_match_to_struct_arity_mismatch);
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let ty = ctx.sema.type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?.original;
+    let field_tys = ty.tuple_fields(ctx.db());
+    // The struct would need the lifetime and type parameters of the fields.
+    if field_tys.iter().any(|ty| needs_generics(ctx.db(), ty)) {
+        cov_mark::hit!(convert_tuple_match_to_struct_needs_generics);
+        return None;
+    }
+    let field_tys = field_tys
+        .iter()
+        .map(|ty| ty.display_source_code(ctx.db(), module.into()).ok())
+        .collect::<Option<Vec<_>>>()?;
+    if field_tys.len() != field_names.len() {
+        return None;
+    }
+
+    let func = match_expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let struct_name = to_upper_camel_case(&func.name()?.to_string());
+    // The struct goes right before the item the match is in.
+    let item = module_item(func.syntax())?;
+
+    acc.add(
+        AssistId("convert_tuple_match_to_struct", AssistKind::RefactorRewrite),
+        "Convert tuples to a struct",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(item.syntax());
+            let mut def = String::from("struct ");
+            if ctx.config.snippet_cap.is_some() {
+                def.push_str("$0");
+            }
+            format_to!(def, "{struct_name} {{");
+            for (name, ty) in field_names.iter().zip(&field_tys) {
+                format_to!(def, "\n{indent}    {name}: {ty},");
+            }
+            format_to!(def, "\n{indent}}}\n\n{indent}");
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, item.syntax().text_range().start(), def),
+                None => builder.insert(item.syntax().text_range().start(), def),
+            }
+
+            builder.replace(
+                tuple_pat.syntax().text_range(),
+                format!("{struct_name} {{ {} }}", bindings.iter().join(", ")),
+            );
+            for tuple in &tuples {
+                let fields = field_names
+                    .iter()
+                    .zip(tuple.fields())
+                    .map(|(name, value)| {
+                        if value.syntax().text() == name.as_str() {
+                            name.clone()
+                        } else {
+                            format!("{name}: {value}")
+                        }
+                    })
+                    .join(", ");
+                builder
+                    .replace(tuple.syntax().text_range(), format!("{struct_name} {{ {fields} }}"));
+            }
+        },
+    )
+}
+
+fn needs_generics(db: &dyn HirDatabase, ty: &hir::Type) -> bool {
+    let has_lifetime = ty.as_adt().map_or(false, |adt| {
+        let params = hir::GenericDef::from(adt).params(db);
+        params.iter().any(|it| matches!(it, hir::GenericParam::LifetimeParam(_)))
+    });
+    ty.is_reference()
+        || has_lifetime
+        || !ty.generic_params(db).is_empty()
+        || ty.type_arguments().any(|it| needs_generics(db, &it))
+        || ty.tuple_fields(db).iter().any(|it| needs_generics(db, it))
+        || ty.as_array(db).map_or(false, |(it, _)| needs_generics(db, &it))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_no_snippet_cap, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_element_tuples() {
+        check_assist(
+            convert_tuple_match_to_struct,
+            r#"
+enum Shape { Square(u32), Rect(u32, u32) }
+
+mod geometry {
+    fn measure(shape: super::Shape) -> u32 {
+        let (width, height, mut area) = match$0 shape {
+            super::Shape::Square(width) => (width, width, width * width),
+            super::Shape::Rect(w, h) => (w, h, w * h),
+        };
+        area += width + height;
+        area
+    }
+}
+"#,
+            r#"
+enum Shape { Square(u32), Rect(u32, u32) }
+
+mod geometry {
+    struct $0Measure {
+        width: u32,
+        height: u32,
+        area: u32,
+    }
+
+    fn measure(shape: super::Shape) -> u32 {
+        let Measure { width, height, mut area } = match shape {
+            super::Shape::Square(width) => Measure { width, height: width, area: width * width },
+            super::Shape::Rect(w, h) => Measure { width: w, height: h, area: w * h },
+        };
+        area += width + height;
+        area
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_without_snippet_cap() {
+        check_assist_no_snippet_cap(
+            convert_tuple_match_to_struct,
+            r#"
+fn split(flag: bool) {
+    let (a, b) = match$0 flag {
+        true => (1u8, 'a'),
+        false => (2, 'b'),
+    };
+}
+"#,
+            r#"
+struct Split {
+    a: u8,
+    b: char,
+}
+
+fn split(flag: bool) {
+    let Split { a, b } = match flag {
+        true => Split { a: 1u8, b: 'a' },
+        false => Split { a: 2, b: 'b' },
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_arities() {
+        cov_mark::check!(convert_tuple_match_to_struct_arity_mismatch);
+        check_assist_not_applicable(
+            convert_tuple_match_to_struct,
+            r#"
+fn split(flag: bool) {
+    let (a, b) = match$0 flag {
+        true => (1, 2),
+        false => (1, 2, 3),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_destructuring_let() {
+        check_assist_not_applicable(
+            convert_tuple_match_to_struct,
+            r#"
+fn split(flag: bool) -> (u8, u8) {
+    match$0 flag {
+        true => (1, 2),
+        false => (3, 4),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_borrowed_fields() {
+        cov_mark::check!(convert_tuple_match_to_struct_needs_generics);
+        check_assist_not_applicable(
+            convert_tuple_match_to_struct,
+            r#"
+fn split(flag: bool, text: &str) {
+    let (head, len) = match$0 flag {
+        true => (text, 1),
+        false => (text, 2),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_fields_with_lifetimes() {
+        cov_mark::check!(convert_tuple_match_to_struct_needs_generics);
+        check_assist_not_applicable(
+            convert_tuple_match_to_struct,
+            r#"
+//- minicore: option
+struct Token<'a>(&'a str);
+
+fn split(flag: bool, token: Token<'_>) {
+    let (head, len) = match$0 flag {
+        true => (Some(token), 1),
+        false => (None, 2),
+    };
+}
+"#,
+        );
+    }
+}
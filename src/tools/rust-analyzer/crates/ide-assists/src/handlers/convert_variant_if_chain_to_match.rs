@@ -0,0 +1,240 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode,
+    },
+    T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_variant_if_chain_to_match
+//
+// Converts an `if`-`else if`-`else` chain whose branches each produce a variant of the same
+// enum into a match with one guarded arm per condition.
+//
+// ```
+// enum Size { Small, Medium, Large }
+//
+// fn size(n: u32) -> Size {
+//     $0if n < 10 {
+//         Size::Small
+//     } else if n < 100 {
+//         Size::Medium
+//     } else {
+//         Size::Large
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Size { Small, Medium, Large }
+//
+// fn size(n: u32) -> Size {
+//     match () {
+//         _ if n < 10 => Size::Small,
+//         _ if n < 100 => Size::Medium,
+//         _ => Size::Large,
+//     }
+// }
+// ```
+pub(crate) fn convert_variant_if_chain_to_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let if_kw = ctx.find_token_syntax_at_offset(T![if])?;
+    let if_expr = if_kw.parent().and_then(ast::IfExpr::cast)?;
+    // Only offer on the head of the chain.
+    if if_expr.syntax().parent().and_then(ast::IfExpr::cast).is_some() {
+        return None;
+    }
+
+    let mut branches = Vec::new();
+    let mut current = if_expr.clone();
+    let fallback = loop {
+        let condition = current.condition()?;
+        if matches!(condition, ast::Expr::LetExpr(_)) {
+            return None;
+        }
+        branches.push((condition, current.then_branch()?));
+        match current.else_branch()? {
+            ast::ElseBranch::IfExpr(it) => current = it,
+            ast::ElseBranch::Block(it) => break it,
+        }
+    };
+
+    let mut enum_ = None;
+    for block in branches.iter().map(|(_, block)| block).chain(Some(&fallback)) {
+        let variant = constructed_variant(ctx, &block.tail_expr()?)?;
+        match enum_ {
+            None => enum_ = Some(variant.parent_enum(ctx.db())),
+            Some(it) if it == variant.parent_enum(ctx.db()) => (),
+            Some(_) => return None,
+        }
+    }
+    // A repeated condition makes the later arm unreachable, which is better left to the user.
+    for (i, (condition, _)) in branches.iter().enumerate() {
+        if branches[..i].iter().any(|(it, _)| it.syntax().text() == condition.syntax().text()) {
+            cov_mark::hit!(convert_variant_if_chain_to_match_overlapping_conditions);
+            return None;
+        }
+    }
+
+    let target = if_expr.syntax().text_range();
+    acc.add(
+        AssistId("convert_variant_if_chain_to_match", AssistKind::RefactorRewrite),
+        "Convert `if` chain to `match`",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(if_expr.syntax());
+            let body = |block: &ast::BlockExpr| -> ast::Expr {
+                match block.stmt_list() {
+                    Some(stmts) if stmts.statements().next().is_none() => {
+                        if let Some(tail) = stmts.tail_expr() {
+                            return tail.reset_indent();
+                        }
+                    }
+                    _ => (),
+                }
+                // Bodies with statements move one level deeper, into the arms.
+                let block: ast::Expr = block.clone().into();
+                block.dedent(indent).indent(IndentLevel(1))
+            };
+            let arms = branches
+                .iter()
+                .map(|(condition, block)| {
+                    make::match_arm(
+                        [make::wildcard_pat().into()],
+                        Some(condition.clone()),
+                        body(block),
+                    )
+                })
+                .chain(Some(make::match_arm([make::wildcard_pat().into()], None, body(&fallback))));
+            let match_expr =
+                make::expr_match(make::expr_unit(), make::match_arm_list(arms)).indent(indent);
+            builder.replace(target, match_expr.to_string());
+        },
+    )
+}
+
+fn constructed_variant(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<hir::Variant> {
+    let path = match expr {
+        ast::Expr::PathExpr(it) => it.path()?,
+        ast::Expr::CallExpr(it) => match it.expr()? {
+            ast::Expr::PathExpr(it) => it.path()?,
+            _ => return None,
+        },
+        ast::Expr::RecordExpr(it) => it.path()?,
+        _ => return None,
+    };
+    match ctx.sema.resolve_path(&path)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn convert_three_branch_ladder() {
+        check_assist(
+            convert_variant_if_chain_to_match,
+            r#"
+enum Shape { Dot, Line(u32), Rect { w: u32, h: u32 } }
+
+fn shape(w: u32, h: u32) -> Shape {
+    let shape = i$0f w == 0 && h == 0 {
+        Shape::Dot
+    } else if h == 0 {
+        Shape::Line(w)
+    } else {
+        let w = w.max(1);
+        Shape::Rect { w, h }
+    };
+    shape
+}
+"#,
+            r#"
+enum Shape { Dot, Line(u32), Rect { w: u32, h: u32 } }
+
+fn shape(w: u32, h: u32) -> Shape {
+    let shape = match () {
+        _ if w == 0 && h == 0 => Shape::Dot,
+        _ if h == 0 => Shape::Line(w),
+        _ => {
+            let w = w.max(1);
+            Shape::Rect { w, h }
+        }
+    };
+    shape
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_repeated_condition() {
+        cov_mark::check!(convert_variant_if_chain_to_match_overlapping_conditions);
+        check_assist_not_applicable(
+            convert_variant_if_chain_to_match,
+            r#"
+enum Size { Small, Medium, Large }
+
+fn size(n: u32) -> Size {
+    $0if n < 10 {
+        Size::Small
+    } else if n < 10 {
+        Size::Medium
+    } else {
+        Size::Large
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_mixed_enums() {
+        check_assist_not_applicable(
+            convert_variant_if_chain_to_match,
+            r#"
+enum Size { Small, Large }
+enum Other { Small }
+
+fn size(n: u32) -> Size {
+    $0if n < 10 {
+        Size::Small
+    } else if n < 20 {
+        Other::Small
+    } else {
+        Size::Large
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_else() {
+        check_assist_not_applicable(
+            convert_variant_if_chain_to_match,
+            r#"
+enum Size { Small, Large }
+
+fn size(n: u32) {
+    $0if n < 10 {
+        Size::Small;
+    } else if n < 20 {
+        Size::Large;
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use itertools::Itertools;
+use syntax::ast::{self, AstNode, HasArgList, HasName};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: convert_vec_pushes_to_vec_macro
+//
+// Replaces a match arm body that creates a vector, pushes a fixed number of elements into it
+// and returns it with a `vec!` invocation.
+//
+// ```
+// # //- /main.rs crate:main deps:std,alloc
+// fn digits(even: bool) -> Vec<u8> {
+//     match even {
+//         true => {$0
+//             let mut v = Vec::new();
+//             v.push(0);
+//             v.push(2);
+//             v
+//         }
+//         false => vec![1, 3],
+//     }
+// }
+// # //- /std.rs crate:std deps:alloc
+// # pub mod prelude { pub mod rust_2021 { pub use alloc::vec::Vec; } }
+// # //- /alloc.rs crate:alloc
+// # pub mod vec {
+// #     pub struct Vec<T>(T);
+// #     impl<T> Vec<T> { pub fn new() -> Self { loop {} } pub fn push(&mut self, value: T) {} }
+// # }
+// ```
+// ->
+// ```
+// fn digits(even: bool) -> Vec<u8> {
+//     match even {
+//         true => vec![0, 2],
+//         false => vec![1, 3],
+//     }
+// }
+// ```
+pub(crate) fn convert_vec_pushes_to_vec_macro(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let block = ctx.find_node_at_offset::<ast::BlockExpr>()?;
+    if block.modifier().is_some() {
+        return None;
+    }
+    let arm = block.syntax().parent().and_then(ast::MatchArm::cast)?;
+    if arm.expr()?.syntax() != block.syntax() {
+        return None;
+    }
+    let stmt_list = block.stmt_list()?;
+    let mut stmts = stmt_list.statements();
+
+    let let_stmt = match stmts.next()? {
+        ast::Stmt::LetStmt(it) => it,
+        _ => return None,
+    };
+    // Without the annotation the element type may be inferred differently.
+    if let_stmt.ty().is_some() {
+        return None;
+    }
+    let ident_pat = match let_stmt.pat()? {
+        ast::Pat::IdentPat(it) if it.mut_token().is_some() && it.ref_token().is_none() => it,
+        _ => return None,
+    };
+    if !is_new_vec(ctx, &let_stmt.initializer()?) {
+        return None;
+    }
+    let local = ctx.sema.to_def(&ident_pat)?;
+    let name = ident_pat.name()?.to_string();
+    let refers_to_vec = |expr: &ast::Expr| {
+        expr.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+            matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if it == local)
+        })
+    };
+
+    let elements = stmts
+        .map(|stmt| {
+            let call = match stmt {
+                ast::Stmt::ExprStmt(it) => match it.expr()? {
+                    ast::Expr::MethodCallExpr(it) => it,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            if call.name_ref()?.text() != "push"
+                || call.receiver()?.syntax().text() != name.as_str()
+            {
+                return None;
+            }
+            let value = call.arg_list()?.args().exactly_one().ok()?;
+            if refers_to_vec(&value) {
+                return None;
+            }
+            Some(value)
+        })
+        .collect::<Option<Vec<_>>>();
+    let elements = match elements {
+        Some(it) if !it.is_empty() => it,
+        _ => {
+            cov_mark::hit!(convert_vec_pushes_to_vec_macro_dynamic_fill);
+            return None;
+        }
+    };
+    if stmt_list.tail_expr()?.syntax().text() != name.as_str() {
+        return None;
+    }
+
+    let target = block.syntax().text_range();
+    acc.add(
+        AssistId("convert_vec_pushes_to_vec_macro", AssistKind::RefactorRewrite),
+        "Convert to `vec!`",
+        target,
+        |builder| {
+            // Block bodies don't need a comma, but the macro call does.
+            let comma = if arm.comma_token().is_none() { "," } else { "" };
+            builder.replace(target, format!("vec![{}]{comma}", elements.iter().join(", ")));
+        },
+    )
+}
+
+fn is_new_vec(ctx: &AssistContext<'_>, expr: &ast::Expr) -> bool {
+    let call = match expr {
+        ast::Expr::CallExpr(it) if it.arg_list().map_or(false, |it| it.args().next().is_none()) => {
+            it
+        }
+        _ => return false,
+    };
+    let path = match call.expr() {
+        Some(ast::Expr::PathExpr(it)) => it.path(),
+        _ => None,
+    };
+    let func = match path.and_then(|path| ctx.sema.resolve_path(&path)) {
+        Some(PathResolution::Def(hir::ModuleDef::Function(it))) => it,
+        _ => return false,
+    };
+    let Some(scope) = ctx.sema.scope(call.syntax()) else { return false };
+    let vec = FamousDefs(&ctx.sema, scope.krate()).alloc_vec_Vec();
+    func.name(ctx.db()).to_smol_str() == "new"
+        && vec.is_some()
+        && func.ret_type(ctx.db()).as_adt() == vec.map(hir::Adt::Struct)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const ALLOC: &str = r#"//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+        pub fn push(&mut self, value: T) {}
+        pub fn len(&self) -> usize { 0 }
+    }
+}
+"#;
+
+    #[test]
+    fn convert_two_pushes() {
+        check_assist(
+            convert_vec_pushes_to_vec_macro,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn names(short: bool) -> Vec<&'static str> {{
+    match short {{
+        true => {{
+            let mut names = Vec::new();$0
+            names.push("a");
+            names.push("b");
+            names
+        }}
+        false => Vec::new(),
+    }}
+}}
+{ALLOC}"#
+            ),
+            r#"
+use alloc::vec::Vec;
+
+fn names(short: bool) -> Vec<&'static str> {
+    match short {
+        true => vec!["a", "b"],
+        false => Vec::new(),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_dynamic_fill() {
+        cov_mark::check!(convert_vec_pushes_to_vec_macro_dynamic_fill);
+        check_assist_not_applicable(
+            convert_vec_pushes_to_vec_macro,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn names(short: bool) -> Vec<u32> {{
+    match short {{
+        true => {{
+            let mut v = Vec::new();$0
+            for i in 0..3 {{
+                v.push(i);
+            }}
+            v
+        }}
+        false => Vec::new(),
+    }}
+}}
+{ALLOC}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_vec_is_used_in_push() {
+        check_assist_not_applicable(
+            convert_vec_pushes_to_vec_macro,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn sizes(short: bool) -> Vec<usize> {{
+    match short {{
+        true => {{
+            let mut v = Vec::new();$0
+            v.push(0);
+            v.push(v.len());
+            v
+        }}
+        false => Vec::new(),
+    }}
+}}
+{ALLOC}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_of_match_arm() {
+        check_assist_not_applicable(
+            convert_vec_pushes_to_vec_macro,
+            &format!(
+                r#"
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn names() -> Vec<&'static str> {{
+    let mut names = Vec::new();$0
+    names.push("a");
+    names
+}}
+{ALLOC}"#
+            ),
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_vec_types() {
+        check_assist_not_applicable(
+            convert_vec_pushes_to_vec_macro,
+            r#"
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, value: T) {} }
+
+fn names(short: bool) -> Vec<&'static str> {
+    match short {
+        true => {
+            let mut names = Vec::new();$0
+            names.push("a");
+            names
+        }
+        false => Vec::new(),
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,510 @@
+use hir::{HirDisplay, ScopeDef};
+use itertools::Itertools;
+use stdx::{format_to, to_upper_camel_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, AstToken, HasName},
+    NodeOrToken, SyntaxKind, SyntaxToken,
+};
+
+use crate::{
+    utils::{module_item, suggest_name, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: extract_event_enum_from_log_match
+//
+// Extracts an `Event` enum with a variant for each arm of a match logging a different message,
+// holding the values the message formats. The match builds the event, which is logged once,
+// and the messages move into the `Display` impl of the enum.
+//
+// ```
+// # macro_rules! info { ($($t:tt)*) => {} }
+// enum State { Idle, Busy(u32) }
+//
+// fn report(state: State) {
+//     $0match state {
+//         State::Idle => info!("idle"),
+//         State::Busy(jobs) => info!("busy with {jobs} jobs"),
+//     }
+// }
+// ```
+// ->
+// ```
+// # macro_rules! info { ($($t:tt)*) => {} }
+// enum State { Idle, Busy(u32) }
+//
+// enum Event {
+//     Idle,
+//     Busy { jobs: u32 },
+// }
+//
+// impl std::fmt::Display for Event {
+//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+//         match self {
+//             Event::Idle => write!(f, "idle"),
+//             Event::Busy { jobs } => write!(f, "busy with {jobs} jobs"),
+//         }
+//     }
+// }
+//
+// fn report(state: State) {
+//     let $0event = match state {
+//         State::Idle => Event::Idle,
+//         State::Busy(jobs) => Event::Busy { jobs },
+//     };
+//     info!("{event}");
+// }
+// ```
+pub(crate) fn extract_event_enum_from_log_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let parent = match_expr.syntax().parent()?;
+    if !ast::ExprStmt::can_cast(parent.kind()) && !ast::StmtList::can_cast(parent.kind()) {
+        return None;
+    }
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() < 2 {
+        return None;
+    }
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let mut taken = false;
+    scope.process_all_names(&mut |name, _| {
+        taken |= name.to_smol_str() == "Event";
+    });
+    if taken {
+        return None;
+    }
+    let name = suggest_name::unique_in_scope("event", &scope, &[]);
+
+    let mut macro_path = None::<ast::Path>;
+    let mut events = Vec::with_capacity(arms.len());
+    for arm in &arms {
+        let Some(event) = log_event(ctx, arm) else {
+            cov_mark::hit!(extract_event_enum_from_log_match_incompatible_calls);
+            return None;
+        };
+        let path = macro_path.get_or_insert_with(|| event.macro_path.clone());
+        if path.syntax().text() != event.macro_path.syntax().text() {
+            cov_mark::hit!(extract_event_enum_from_log_match_incompatible_calls);
+            return None;
+        }
+        if events.iter().any(|it: &LogEvent| it.variant == event.variant) {
+            return None;
+        }
+        events.push(event);
+    }
+    let macro_path = macro_path?;
+
+    let item = module_item(match_expr.syntax())?;
+    let semicolon = match ast::ExprStmt::cast(parent).and_then(|it| it.semicolon_token()) {
+        Some(_) => "",
+        None => ";",
+    };
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("extract_event_enum_from_log_match", AssistKind::RefactorExtract),
+        "Extract event enum from log calls",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(item.syntax());
+            let mut enum_def = String::from("enum Event {");
+            let mut display = format!(
+                "impl std::fmt::Display for Event {{\n\
+                 {indent}    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{\n\
+                 {indent}        match self {{"
+            );
+            for LogEvent { variant, fields, format, args, .. } in &events {
+                let names = fields.iter().map(|(name, _)| name).join(", ");
+                match fields.is_empty() {
+                    true => format_to!(enum_def, "\n{indent}    {variant},"),
+                    false => {
+                        let fields =
+                            fields.iter().map(|(name, ty)| format!("{name}: {ty}")).join(", ");
+                        format_to!(enum_def, "\n{indent}    {variant} {{ {fields} }},")
+                    }
+                }
+                let pat = match fields.is_empty() {
+                    true => format!("Event::{variant}"),
+                    false => format!("Event::{variant} {{ {names} }}"),
+                };
+                let args = args.iter().map(|it| format!(", {it}")).collect::<String>();
+                format_to!(display, "\n{indent}            {pat} => write!(f, {format}{args}),");
+            }
+            format_to!(enum_def, "\n{indent}}}\n\n{indent}");
+            format_to!(display, "\n{indent}        }}\n{indent}    }}\n{indent}}}\n\n{indent}");
+            let start = item.syntax().text_range().start();
+            builder.insert(start, enum_def);
+            builder.insert(start, display);
+
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, target.start(), format!("let $0{name} = ")),
+                None => builder.insert(target.start(), format!("let {name} = ")),
+            }
+            for (arm, LogEvent { variant, fields, .. }) in arms.iter().zip(&events) {
+                let Some(expr) = arm.expr() else { continue };
+                let value = match fields.is_empty() {
+                    true => format!("Event::{variant}"),
+                    false => {
+                        let names = fields.iter().map(|(name, _)| name).join(", ");
+                        format!("Event::{variant} {{ {names} }}")
+                    }
+                };
+                let comma = if arm.comma_token().is_some() { "" } else { "," };
+                builder.replace(expr.syntax().text_range(), format!("{value}{comma}"));
+            }
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let log = format!(";\n{indent}{macro_path}!(\"{{{name}}}\"){semicolon}");
+            builder.insert(target.end(), log);
+        },
+    )
+}
+
+struct LogEvent {
+    macro_path: ast::Path,
+    variant: String,
+    /// The locals the message formats, with their types, in the order they appear.
+    fields: Vec<(String, String)>,
+    format: ast::String,
+    /// The positional arguments of the message.
+    args: Vec<String>,
+}
+
+/// Takes apart the arm `pat => log!("message {}", local)`, which logs locals only.
+fn log_event(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<LogEvent> {
+    let expr = match arm.expr()? {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmts = block.stmt_list()?;
+            match (stmts.statements().collect::<Vec<_>>().as_slice(), stmts.tail_expr()) {
+                ([], Some(it)) => it,
+                ([ast::Stmt::ExprStmt(it)], None) => it.expr()?,
+                _ => return None,
+            }
+        }
+        it => it,
+    };
+    let ast::Expr::MacroExpr(macro_expr) = expr else { return None };
+    let call = macro_expr.macro_call()?;
+    let tokens = call.token_tree()?.token_trees_and_tokens().collect::<Vec<_>>();
+    let [_, NodeOrToken::Token(format), rest @ .., _] = tokens.as_slice() else { return None };
+    let format = ast::String::cast(format.clone())?;
+
+    // Every argument is a single identifier, following a comma.
+    let mut args = Vec::new();
+    let is_token = |it: &NodeOrToken<_, SyntaxToken>, kind| matches!(it, NodeOrToken::Token(it) if it.kind() == kind);
+    let mut rest = rest.iter().filter(|it| !is_token(it, SyntaxKind::WHITESPACE));
+    while let Some(comma) = rest.next() {
+        if !is_token(comma, SyntaxKind::COMMA) {
+            return None;
+        }
+        match rest.next() {
+            Some(NodeOrToken::Token(it)) if it.kind() == SyntaxKind::IDENT => {
+                args.push(it.text().to_owned())
+            }
+            None => break,
+            _ => return None,
+        }
+    }
+    let placeholders = placeholders(format.text())?;
+    if placeholders.iter().filter(|it| it.is_none()).count() != args.len() {
+        return None;
+    }
+    let mut positional = args.iter();
+    let mut names = Vec::new();
+    for placeholder in placeholders {
+        let name = match placeholder {
+            Some(it) => it,
+            None => positional.next()?.clone(),
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+
+    // The event takes the values, which only the bindings of the arm can give away.
+    let pat = arm.pat()?;
+    let bound = pat
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| Some(it.name()?.to_string()))
+        .collect::<Vec<_>>();
+    let scope = ctx.sema.scope(call.syntax())?;
+    let mut locals = Vec::new();
+    scope.process_all_names(&mut |name, def| {
+        if let ScopeDef::Local(local) = def {
+            locals.push((name.to_smol_str(), local));
+        }
+    });
+    let mut fields = Vec::with_capacity(names.len());
+    for name in names {
+        let (_, local) = locals.iter().find(|(it, _)| *it == name)?;
+        let ty = local.ty(ctx.db());
+        if name == "f" || ty.is_reference() || !(bound.contains(&name) || ty.is_copy(ctx.db())) {
+            return None;
+        }
+        let ty = ty.display_source_code(ctx.db(), scope.module().into()).ok()?;
+        fields.push((name, ty));
+    }
+
+    let variant = match variants(ctx, &pat).as_deref() {
+        Some([variant]) => variant.name(ctx.db()).to_string(),
+        _ => message_name(format.text())?,
+    };
+    Some(LogEvent { macro_path: call.path()?, variant, fields, format, args })
+}
+
+/// Lists the placeholders of a format string, with the name of the local each one captures.
+fn placeholders(text: &str) -> Option<Vec<Option<String>>> {
+    let mut placeholders = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let inner = chars.by_ref().take_while(|&c| c != '}').collect::<String>();
+        let (name, spec) = inner.split_once(':').unwrap_or((&inner, ""));
+        // `{:width$}` would format another value, and `{0}` repeat a positional one.
+        let is_ident = name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if spec.contains('$') || !(name.is_empty() || is_ident) {
+            return None;
+        }
+        placeholders.push((!name.is_empty()).then(|| name.to_owned()));
+    }
+    Some(placeholders)
+}
+
+/// Names a variant after the first words of its message.
+fn message_name(text: &str) -> Option<String> {
+    let mut message = String::new();
+    let mut depth = 0;
+    for c in text.trim_matches(|c| c == '"' || c == 'r' || c == '#').chars() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            c if depth == 0 => message.push(c),
+            _ => (),
+        }
+    }
+    let words =
+        message.split(|c: char| !c.is_alphanumeric()).filter(|it| !it.is_empty()).take(2).join("_");
+    let name = to_upper_camel_case(&words);
+    name.starts_with(char::is_alphabetic).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_three_messages() {
+        check_assist(
+            extract_event_enum_from_log_match,
+            r#"
+//- minicore: copy
+macro_rules! warn { ($($t:tt)*) => {} }
+enum Conn { Open(u32), Retry { attempt: u8, delay: u64 }, Closed }
+
+mod net {
+    use super::*;
+
+    pub fn log_state(conn: Conn, port: u16) {
+        match$0 conn {
+            Conn::Open(fd) => warn!("opened fd {} on port {port}", fd),
+            Conn::Retry { attempt, delay } => {
+                warn!("retry {attempt} in {delay}ms");
+            }
+            Conn::Closed => warn!("closed, {{port {}}} free", port),
+        };
+    }
+}
+"#,
+            r#"
+macro_rules! warn { ($($t:tt)*) => {} }
+enum Conn { Open(u32), Retry { attempt: u8, delay: u64 }, Closed }
+
+mod net {
+    use super::*;
+
+    enum Event {
+        Open { fd: u32, port: u16 },
+        Retry { attempt: u8, delay: u64 },
+        Closed { port: u16 },
+    }
+
+    impl std::fmt::Display for Event {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Event::Open { fd, port } => write!(f, "opened fd {} on port {port}", fd),
+                Event::Retry { attempt, delay } => write!(f, "retry {attempt} in {delay}ms"),
+                Event::Closed { port } => write!(f, "closed, {{port {}}} free", port),
+            }
+        }
+    }
+
+    pub fn log_state(conn: Conn, port: u16) {
+        let $0event = match conn {
+            Conn::Open(fd) => Event::Open { fd, port },
+            Conn::Retry { attempt, delay } => Event::Retry { attempt, delay },
+            Conn::Closed => Event::Closed { port },
+        };
+        warn!("{event}");
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_names_variants_after_messages() {
+        check_assist(
+            extract_event_enum_from_log_match,
+            r#"
+//- minicore: copy
+macro_rules! info { ($($t:tt)*) => {} }
+
+fn progress(done: u32, total: u32) {
+    match$0 done {
+        0 => info!("not started"),
+        n if n == total => info!("all {n} done"),
+        n => info!("done {}/{}", n, total),
+    }
+}
+"#,
+            r#"
+macro_rules! info { ($($t:tt)*) => {} }
+
+enum Event {
+    NotStarted,
+    AllDone { n: u32 },
+    Done { n: u32, total: u32 },
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::NotStarted => write!(f, "not started"),
+            Event::AllDone { n } => write!(f, "all {n} done"),
+            Event::Done { n, total } => write!(f, "done {}/{}", n, total),
+        }
+    }
+}
+
+fn progress(done: u32, total: u32) {
+    let $0event = match done {
+        0 => Event::NotStarted,
+        n if n == total => Event::AllDone { n },
+        n => Event::Done { n, total },
+    };
+    info!("{event}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_with_event_in_scope() {
+        check_assist(
+            extract_event_enum_from_log_match,
+            r#"
+macro_rules! info { ($($t:tt)*) => {} }
+
+fn report(ok: bool, event: u8) {
+    match$0 ok {
+        true => info!("fine"),
+        false => info!("failed"),
+    }
+}
+"#,
+            r#"
+macro_rules! info { ($($t:tt)*) => {} }
+
+enum Event {
+    Fine,
+    Failed,
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Fine => write!(f, "fine"),
+            Event::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+fn report(ok: bool, event: u8) {
+    let $0event1 = match ok {
+        true => Event::Fine,
+        false => Event::Failed,
+    };
+    info!("{event1}");
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_macros() {
+        cov_mark::check!(extract_event_enum_from_log_match_incompatible_calls);
+        check_assist_not_applicable(
+            extract_event_enum_from_log_match,
+            r#"
+macro_rules! info { ($($t:tt)*) => {} }
+macro_rules! error { ($($t:tt)*) => {} }
+
+fn report(ok: bool) {
+    match$0 ok {
+        true => info!("fine"),
+        false => error!("failed"),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_computed_arguments() {
+        cov_mark::check!(extract_event_enum_from_log_match_incompatible_calls);
+        check_assist_not_applicable(
+            extract_event_enum_from_log_match,
+            r#"
+macro_rules! info { ($($t:tt)*) => {} }
+
+fn report(count: u32) {
+    match$0 count {
+        0 => info!("empty"),
+        n => info!("{} left", n - 1),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_outer_value_that_is_not_copy() {
+        check_assist_not_applicable(
+            extract_event_enum_from_log_match,
+            r#"
+//- minicore: copy
+macro_rules! info { ($($t:tt)*) => {} }
+struct Name;
+
+fn report(count: u32, name: Name) {
+    match$0 count {
+        0 => info!("empty"),
+        _ => info!("{name} is busy"),
+    }
+}
+"#,
+        );
+    }
+}
@@ -31,7 +31,7 @@
 
 use crate::{
     assist_context::{AssistContext, Assists, TreeMutator},
-    utils::generate_impl_text,
+    utils::{expr_require_exclusive_access, generate_impl_text},
     AssistId,
 };
 
@@ -1090,41 +1090,6 @@ fn reference_is_exclusive(
     expr_require_exclusive_access(ctx, &path).unwrap_or(false)
 }
 
-/// checks if this expr requires `&mut` access, recurses on field access
-fn expr_require_exclusive_access(ctx: &AssistContext<'_>, expr: &ast::Expr) -> Option<bool> {
-    if let ast::Expr::MacroExpr(_) = expr {
-        // FIXME: expand macro and check output for mutable usages of the variable?
-        return None;
-    }
-
-    let parent = expr.syntax().parent()?;
-
-    if let Some(bin_expr) = ast::BinExpr::cast(parent.clone()) {
-        if matches!(bin_expr.op_kind()?, ast::BinaryOp::Assignment { .. }) {
-            return Some(bin_expr.lhs()?.syntax() == expr.syntax());
-        }
-        return Some(false);
-    }
-
-    if let Some(ref_expr) = ast::RefExpr::cast(parent.clone()) {
-        return Some(ref_expr.mut_token().is_some());
-    }
-
-    if let Some(method_call) = ast::MethodCallExpr::cast(parent.clone()) {
-        let func = ctx.sema.resolve_method_call(&method_call)?;
-        let self_param = func.self_param(ctx.db())?;
-        let access = self_param.access(ctx.db());
-
-        return Some(matches!(access, hir::Access::Exclusive));
-    }
-
-    if let Some(field) = ast::FieldExpr::cast(parent) {
-        return expr_require_exclusive_access(ctx, &field.into());
-    }
-
-    Some(false)
-}
-
 trait HasTokenAtOffset {
     fn token_at_offset(&self, offset: TextSize) -> TokenAtOffset<SyntaxToken>;
 }
@@ -2654,14 +2619,12 @@ fn does_not_add_extra_whitespace() {
             r#"
 fn foo() -> u32 {
 
-
     $0return 2 + 2$0;
 }
 "#,
             r#"
 fn foo() -> u32 {
 
-
     return fun_name();
 }
 
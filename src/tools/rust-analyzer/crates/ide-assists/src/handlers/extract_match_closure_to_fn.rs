@@ -0,0 +1,314 @@
+use hir::{HirDisplay, PathResolution};
+use stdx::format_to;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasGenericParams,
+    },
+    match_ast,
+};
+
+use crate::{utils::module_item, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_match_closure_to_fn
+//
+// Moves a closure whose body is just a match into a free function, and replaces the closure
+// with the function's path.
+//
+// ```
+// # //- minicore: fn
+// fn apply(f: impl Fn(u8) -> bool) {}
+//
+// fn run() {
+//     apply($0|b| match b {
+//         b'0'..=b'9' => true,
+//         _ => false,
+//     });
+// }
+// ```
+// ->
+// ```
+// fn apply(f: impl Fn(u8) -> bool) {}
+//
+// fn run() {
+//     apply(fun_name);
+// }
+//
+// fn $0fun_name(b: u8) -> bool {
+//     match b {
+//         b'0'..=b'9' => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn extract_match_closure_to_fn(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let closure = ctx.find_node_at_offset::<ast::ClosureExpr>()?;
+    if closure.async_token().is_some() || closure.generic_param_list().is_some() {
+        return None;
+    }
+    let match_expr = match closure.body()? {
+        ast::Expr::MatchExpr(it) => it,
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            let stmt_list = block.stmt_list()?;
+            if stmt_list.statements().next().is_some() {
+                return None;
+            }
+            match stmt_list.tail_expr()? {
+                ast::Expr::MatchExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+
+    // A free function has no access to the closure's environment.
+    let own_locals = closure
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| ctx.sema.to_def(&it))
+        .collect::<Vec<_>>();
+    let captures = match_expr.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        match ctx.sema.resolve_path(&path) {
+            Some(PathResolution::Local(it)) => !own_locals.contains(&it),
+            Some(PathResolution::SelfType(_)) => true,
+            _ => false,
+        }
+    });
+    if captures {
+        cov_mark::hit!(extract_match_closure_to_fn_captures);
+        return None;
+    }
+    // Nor to the generic parameters of the items around the closure.
+    let in_generic_item = closure.syntax().ancestors().any(|it| {
+        let generics = match_ast! {
+            match it {
+                ast::Fn(it) => it.generic_param_list(),
+                ast::Impl(it) => it.generic_param_list(),
+                ast::Trait(it) => it.generic_param_list(),
+                _ => None,
+            }
+        };
+        generics.map_or(false, |it| it.generic_params().next().is_some())
+    });
+    if in_generic_item {
+        cov_mark::hit!(extract_match_closure_to_fn_generic_parent);
+        return None;
+    }
+
+    let module = ctx.sema.scope(closure.syntax())?.module();
+    let params = closure
+        .param_list()?
+        .params()
+        .map(|param| {
+            let pat = param.pat()?;
+            let ty = match param.ty() {
+                Some(ty) => ty.to_string(),
+                None => ctx
+                    .sema
+                    .type_of_pat(&pat)?
+                    .original
+                    .display_source_code(ctx.db(), module.into())
+                    .ok()?,
+            };
+            Some(format!("{pat}: {ty}"))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let ret_ty = match closure.ret_type().and_then(|it| it.ty()) {
+        Some(ty) => Some(ty.to_string()),
+        None => {
+            let ty = ctx.sema.type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?.original;
+            if ty.is_unit() {
+                None
+            } else {
+                Some(ty.display_source_code(ctx.db(), module.into()).ok()?)
+            }
+        }
+    };
+
+    // The function goes right after the item the closure is in.
+    let item = module_item(closure.syntax())?;
+
+    let target = closure.syntax().text_range();
+    acc.add(
+        AssistId("extract_match_closure_to_fn", AssistKind::RefactorExtract),
+        "Extract closure into function",
+        target,
+        |builder| {
+            let name = "fun_name";
+            builder.replace(target, name);
+
+            let indent = IndentLevel::from_node(item.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let body = match_expr.dedent(match_expr.indent_level()).indent(inner);
+            let mut fn_def = format!("\n\n{indent}fn ");
+            if ctx.config.snippet_cap.is_some() {
+                fn_def.push_str("$0");
+            }
+            format_to!(fn_def, "{name}({})", params.join(", "));
+            if let Some(ret_ty) = &ret_ty {
+                format_to!(fn_def, " -> {ret_ty}");
+            }
+            format_to!(fn_def, " {{\n{inner}{body}\n{indent}}}");
+            let offset = item.syntax().text_range().end();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, fn_def),
+                None => builder.insert(offset, fn_def),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_non_capturing_closure() {
+        check_assist(
+            extract_match_closure_to_fn,
+            r#"
+//- minicore: option
+enum Token { Num(u32), Plus }
+
+mod lexer {
+    use super::Token;
+
+    fn values(tokens: &[Token]) -> Option<u32> {
+        let value = $0|token: &Token| -> Option<u32> {
+            match token {
+                Token::Num(n) => Some(*n),
+                Token::Plus => None,
+            }
+        };
+        value(&tokens[0])
+    }
+}
+"#,
+            r#"
+enum Token { Num(u32), Plus }
+
+mod lexer {
+    use super::Token;
+
+    fn values(tokens: &[Token]) -> Option<u32> {
+        let value = fun_name;
+        value(&tokens[0])
+    }
+
+    fn $0fun_name(token: &Token) -> Option<u32> {
+        match token {
+            Token::Num(n) => Some(*n),
+            Token::Plus => None,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_closure_with_inferred_types() {
+        check_assist(
+            extract_match_closure_to_fn,
+            r#"
+//- minicore: fn
+fn apply(f: impl Fn(bool, u8) -> u8) {}
+
+fn run() {
+    apply($0|flag, n| match (flag, n) {
+        (true, n) => n,
+        (false, _) => 0,
+    });
+}
+"#,
+            r#"
+fn apply(f: impl Fn(bool, u8) -> u8) {}
+
+fn run() {
+    apply(fun_name);
+}
+
+fn $0fun_name(flag: bool, n: u8) -> u8 {
+    match (flag, n) {
+        (true, n) => n,
+        (false, _) => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_capturing_closure() {
+        cov_mark::check!(extract_match_closure_to_fn_captures);
+        check_assist_not_applicable(
+            extract_match_closure_to_fn,
+            r#"
+fn apply(f: impl Fn(bool) -> u8) {}
+
+fn run(fallback: u8) {
+    apply($0|flag| match flag {
+        true => 1,
+        false => fallback,
+    });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_body_is_not_a_match() {
+        check_assist_not_applicable(
+            extract_match_closure_to_fn,
+            r#"
+fn apply(f: impl Fn(bool) -> u8) {}
+
+fn run() {
+    apply($0|flag| if flag { 1 } else { 0 });
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_generic_fn() {
+        cov_mark::check!(extract_match_closure_to_fn_generic_parent);
+        check_assist_not_applicable(
+            extract_match_closure_to_fn,
+            r#"
+fn run<T: Copy>(items: &[T]) {
+    let f = $0|x: T| match 0 {
+        _ => x,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_generic_impl() {
+        cov_mark::check!(extract_match_closure_to_fn_generic_parent);
+        check_assist_not_applicable(
+            extract_match_closure_to_fn,
+            r#"
+//- minicore: option
+struct Wrapper<T>(T);
+impl<T> Wrapper<T> {
+    fn run() {
+        let f = $0|x: Option<T>| match x {
+            Some(_) => 1,
+            None => 0,
+        };
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,385 @@
+use hir::{Access, HirDisplay};
+use ide_db::famous_defs::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasGenericParams, HasName},
+    T,
+};
+
+use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_match_to_from_impl
+//
+// Moves a method whose body is a match on `self` into a `From` implementation of the
+// returned type, or a `TryFrom` implementation if the method returns a `Result`.
+//
+// ```
+// enum Level { Low, High }
+//
+// impl Level {
+//     fn to_code(self) -> u8 {
+//         $0match self {
+//             Self::Low => 0,
+//             Self::High => 1,
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Level { Low, High }
+//
+// impl Level {
+//     fn to_code(self) -> u8 {
+//         self.into()
+//     }
+// }
+//
+// impl From<Level> for u8 {
+//     fn from(value: Level) -> Self {
+//         match value {
+//             Level::Low => 0,
+//             Level::High => 1,
+//         }
+//     }
+// }
+// ```
+pub(crate) fn extract_match_to_from_impl(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    if match_expr.expr()?.syntax().text() != "self" {
+        return None;
+    }
+    let func = match_expr.syntax().ancestors().find_map(ast::Fn::cast)?;
+    let body = func.body()?;
+    let stmt_list = body.stmt_list()?;
+    // Only move methods that consist of nothing but the match.
+    if stmt_list.statements().next().is_some()
+        || stmt_list.tail_expr()?.syntax() != match_expr.syntax()
+    {
+        cov_mark::hit!(extract_match_to_from_impl_other_statements);
+        return None;
+    }
+    let impl_ = ast::Impl::cast(func.syntax().parent()?.parent()?)?;
+    if impl_.trait_().is_some() {
+        return None;
+    }
+    // The parameters of the impl would have to be carried over to the new one.
+    if impl_.generic_param_list().is_some() {
+        cov_mark::hit!(extract_match_to_from_impl_generic_impl);
+        return None;
+    }
+
+    let function = ctx.sema.to_def(&func)?;
+    let source_ty_ref = match function.self_param(ctx.db())?.access(ctx.db()) {
+        Access::Owned => "",
+        Access::Shared => "&",
+        Access::Exclusive => return None,
+    };
+    if !function.params_without_self(ctx.db()).is_empty() || func.generic_param_list().is_some() {
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let self_ty = ctx.sema.to_def(&impl_)?.self_ty(ctx.db());
+    let ret_ty = function.ret_type(ctx.db());
+    if ret_ty.is_unit() || ret_ty == self_ty {
+        return None;
+    }
+    let self_name = self_ty.display_source_code(ctx.db(), module.into()).ok()?;
+
+    let result_enum = FamousDefs(&ctx.sema, module.krate()).core_result_Result();
+    let fallible = match (ret_ty.as_adt(), result_enum) {
+        (Some(hir::Adt::Enum(ret_enum)), Some(result_enum)) if ret_enum == result_enum => {
+            let mut args = ret_ty.type_arguments();
+            let ok = args.next()?.display_source_code(ctx.db(), module.into()).ok()?;
+            let err = args.next()?.display_source_code(ctx.db(), module.into()).ok()?;
+            Some((ok, err))
+        }
+        _ => None,
+    };
+    let ret_name = ret_ty.display_source_code(ctx.db(), module.into()).ok()?;
+
+    let bindings = match_expr
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| Some(it.name()?.to_string()))
+        .collect::<Vec<_>>();
+    let param =
+        suggest_name::unique_in_scope("value", &ctx.sema.scope(match_expr.syntax())?, &bindings);
+
+    // `self` becomes the argument of the conversion function, and `Self` stops referring
+    // to the type that is converted from.
+    let match_text = match_expr
+        .syntax()
+        .descendants_with_tokens()
+        .filter_map(|it| it.into_token())
+        .map(|token| match token.kind() {
+            T![self] => param.clone(),
+            T![Self] => self_name.clone(),
+            _ => token.text().to_owned(),
+        })
+        .collect::<String>();
+
+    let (label, call) = match fallible {
+        Some(_) => ("Extract match into `TryFrom` impl", "self.try_into()"),
+        None => ("Extract match into `From` impl", "self.into()"),
+    };
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("extract_match_to_from_impl", AssistKind::RefactorExtract),
+        label,
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(impl_.syntax());
+            let source = format!("{source_ty_ref}{self_name}");
+            let mut buf = String::new();
+            match &fallible {
+                Some((ok, err)) => {
+                    format_to!(buf, "\n\n{indent}impl TryFrom<{source}> for {ok} {{");
+                    format_to!(buf, "\n{indent}    type Error = {err};\n");
+                    format_to!(
+                        buf,
+                        "\n{indent}    fn try_from({param}: {source}) -> Result<Self, Self::Error> {{"
+                    );
+                }
+                None => {
+                    format_to!(buf, "\n\n{indent}impl From<{source}> for {ret_name} {{");
+                    format_to!(buf, "\n{indent}    fn from({param}: {source}) -> Self {{");
+                }
+            }
+            format_to!(buf, "\n{indent}        {match_text}\n{indent}    }}\n{indent}}}");
+
+            builder.replace(target, call);
+            builder.insert(impl_.syntax().text_range().end(), buf);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_infallible_from() {
+        check_assist(
+            extract_match_to_from_impl,
+            r#"
+enum Level { Low, Mid, High }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        match self$0 {
+            Self::Low => 0,
+            Self::Mid => 1,
+            Level::High => 2,
+        }
+    }
+}
+"#,
+            r#"
+enum Level { Low, Mid, High }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        self.into()
+    }
+}
+
+impl From<Level> for u8 {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Low => 0,
+            Level::Mid => 1,
+            Level::High => 2,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_from_reference() {
+        check_assist(
+            extract_match_to_from_impl,
+            r#"
+struct Name(&'static str);
+enum Level { Low, High }
+
+impl Level {
+    fn name(&self) -> Name {
+        match self$0 {
+            Self::Low => Name("low"),
+            Self::High => Name("high"),
+        }
+    }
+}
+"#,
+            r#"
+struct Name(&'static str);
+enum Level { Low, High }
+
+impl Level {
+    fn name(&self) -> Name {
+        self.into()
+    }
+}
+
+impl From<&Level> for Name {
+    fn from(value: &Level) -> Self {
+        match value {
+            Level::Low => Name("low"),
+            Level::High => Name("high"),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_fallible_try_from() {
+        check_assist(
+            extract_match_to_from_impl,
+            r#"
+//- minicore: result
+struct Unsupported;
+enum Level { Low, High, Unknown }
+
+impl Level {
+    fn to_code(self) -> Result<u8, Unsupported> {
+        match self$0 {
+            Self::Low => Ok(0),
+            Self::High => Ok(1),
+            Self::Unknown => Err(Unsupported),
+        }
+    }
+}
+"#,
+            r#"
+struct Unsupported;
+enum Level { Low, High, Unknown }
+
+impl Level {
+    fn to_code(self) -> Result<u8, Unsupported> {
+        self.try_into()
+    }
+}
+
+impl TryFrom<Level> for u8 {
+    type Error = Unsupported;
+
+    fn try_from(value: Level) -> Result<Self, Self::Error> {
+        match value {
+            Level::Low => Ok(0),
+            Level::High => Ok(1),
+            Level::Unknown => Err(Unsupported),
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_other_statements() {
+        cov_mark::check!(extract_match_to_from_impl_other_statements);
+        check_assist_not_applicable(
+            extract_match_to_from_impl,
+            r#"
+enum Level { Low, High }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        log();
+        match self$0 {
+            Self::Low => 0,
+            Self::High => 1,
+        }
+    }
+}
+
+fn log() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_extra_params() {
+        check_assist_not_applicable(
+            extract_match_to_from_impl,
+            r#"
+enum Level { Low, High }
+
+impl Level {
+    fn to_code(self, offset: u8) -> u8 {
+        match self$0 {
+            Self::Low => offset,
+            Self::High => offset + 1,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_with_value_binding() {
+        check_assist(
+            extract_match_to_from_impl,
+            r#"
+enum Level { Low, High(u8) }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        match self$0 {
+            Self::Low => 0,
+            Self::High(value) => value,
+        }
+    }
+}
+"#,
+            r#"
+enum Level { Low, High(u8) }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        self.into()
+    }
+}
+
+impl From<Level> for u8 {
+    fn from(value1: Level) -> Self {
+        match value1 {
+            Level::Low => 0,
+            Level::High(value) => value,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_generic_impl() {
+        cov_mark::check!(extract_match_to_from_impl_generic_impl);
+        check_assist_not_applicable(
+            extract_match_to_from_impl,
+            r#"
+enum Level<T> { Low(T), High }
+
+impl<T> Level<T> {
+    fn to_code(self) -> u8 {
+        match self$0 {
+            Self::Low(_) => 0,
+            Self::High => 1,
+        }
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    SyntaxKind::{CALL_EXPR, MACRO_EXPR, METHOD_CALL_EXPR},
+};
+
+use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_shared_match_guard
+//
+// Moves a guard condition that several arms of a match share into a variable before the
+// match, so it is written and evaluated only once. As the condition is then evaluated before
+// any pattern is tried, it must not contain calls or macros.
+//
+// ```
+// fn exit_code(status: u8, verbose: bool) -> i32 {
+//     match status {
+//         0 if $0verbose && status < 10 => 0,
+//         1 if verbose && status < 10 => 1,
+//         _ => 2,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn exit_code(status: u8, verbose: bool) -> i32 {
+//     let $0cond = verbose && status < 10;
+//     match status {
+//         0 if cond => 0,
+//         1 if cond => 1,
+//         _ => 2,
+//     }
+// }
+// ```
+pub(crate) fn extract_shared_match_guard(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let guard = ctx.find_node_at_offset::<ast::MatchGuard>()?;
+    let cond = guard.condition()?;
+    let match_expr = guard.syntax().ancestors().find_map(ast::MatchExpr::cast)?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let shared = arms
+        .iter()
+        .filter_map(|arm| arm.guard()?.condition())
+        .filter(|it| it.syntax().text() == cond.syntax().text())
+        .collect::<Vec<_>>();
+    if shared.len() < 2 {
+        return None;
+    }
+    let has_side_effects = cond
+        .syntax()
+        .descendants()
+        .any(|it| matches!(it.kind(), CALL_EXPR | METHOD_CALL_EXPR | MACRO_EXPR));
+    if has_side_effects {
+        cov_mark::hit!(extract_shared_match_guard_side_effects);
+        return None;
+    }
+
+    // Outside the match, the bindings of the patterns don't exist.
+    let bindings = arms
+        .iter()
+        .filter_map(|arm| arm.pat())
+        .flat_map(|pat| pat.syntax().descendants().filter_map(ast::IdentPat::cast))
+        .filter_map(|it| ctx.sema.to_def(&it))
+        .collect::<Vec<_>>();
+    let uses_binding = cond.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if bindings.contains(&it))
+    });
+    if uses_binding {
+        cov_mark::hit!(extract_shared_match_guard_uses_binding);
+        return None;
+    }
+
+    // The variable must neither clash with a name in scope nor be shadowed by a binding in the arms.
+    let taken = bindings.iter().map(|it| it.name(ctx.db()).to_string()).collect::<Vec<_>>();
+    let name = suggest_name::unique_in_scope("cond", &ctx.sema.scope(match_expr.syntax())?, &taken);
+
+    // The variable is declared in a new statement, so nothing may be evaluated before the match.
+    let stmt = match_expr.syntax().parent()?;
+    let is_whole_stmt = ast::StmtList::can_cast(stmt.kind())
+        || ast::ExprStmt::can_cast(stmt.kind())
+        || ast::LetStmt::cast(stmt.clone()).map_or(false, |it| {
+            it.initializer().map_or(false, |init| init.syntax() == match_expr.syntax())
+        });
+    if !is_whole_stmt {
+        return None;
+    }
+    let insert_at = match ast::StmtList::can_cast(stmt.kind()) {
+        true => match_expr.syntax().text_range().start(),
+        false => stmt.text_range().start(),
+    };
+
+    acc.add(
+        AssistId("extract_shared_match_guard", AssistKind::RefactorExtract),
+        "Extract shared guard into a variable",
+        cond.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            for it in &shared {
+                builder.replace(it.syntax().text_range(), name.clone());
+            }
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(
+                    cap,
+                    insert_at,
+                    format!("let $0{name} = {cond};\n{indent}"),
+                ),
+                None => builder.insert(insert_at, format!("let {name} = {cond};\n{indent}")),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_guard_shared_by_three_arms() {
+        check_assist(
+            extract_shared_match_guard,
+            r#"
+struct Config { strict: bool, level: u8 }
+
+fn check(config: &Config, input: Option<u8>) -> u8 {
+    let result = match input {
+        Some(0) if config.strict && config.level > 1 => 10,
+        Some(1) if config.strict && config.level > 1 => 11,
+        Some(_) if config.level > 1 => 12,
+        None if config.strict && config.level > 1$0 => 13,
+        _ => 0,
+    };
+    result
+}
+"#,
+            r#"
+struct Config { strict: bool, level: u8 }
+
+fn check(config: &Config, input: Option<u8>) -> u8 {
+    let $0cond = config.strict && config.level > 1;
+    let result = match input {
+        Some(0) if cond => 10,
+        Some(1) if cond => 11,
+        Some(_) if config.level > 1 => 12,
+        None if cond => 13,
+        _ => 0,
+    };
+    result
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_guard_uses_binding() {
+        cov_mark::check!(extract_shared_match_guard_uses_binding);
+        check_assist_not_applicable(
+            extract_shared_match_guard,
+            r#"
+fn check(input: (u8, u8)) -> u8 {
+    match input {
+        (0, n) if n > 3 => 10,
+        (1, n) if n > 3$0 => 11,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_guard_with_fresh_name() {
+        check_assist(
+            extract_shared_match_guard,
+            r#"
+fn check(input: Option<u8>, cond: bool, limit: u8) -> u8 {
+    match input {
+        Some(cond1) if limit > 3$0 && cond => cond1,
+        None if limit > 3 && cond => 1,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn check(input: Option<u8>, cond: bool, limit: u8) -> u8 {
+    let $0cond2 = limit > 3 && cond;
+    match input {
+        Some(cond1) if cond2 => cond1,
+        None if cond2 => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_guard_has_side_effects() {
+        cov_mark::check!(extract_shared_match_guard_side_effects);
+        check_assist_not_applicable(
+            extract_shared_match_guard,
+            r#"
+fn ready(id: u8) -> bool { id > 0 }
+
+fn check(input: u8, id: u8) -> u8 {
+    match input {
+        0 if ready(id)$0 => 10,
+        1 if ready(id) => 11,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use hir::{HirDisplay, PathResolution, StructKind};
+use ide_db::famous_defs::FamousDefs;
+use stdx::format_to;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode,
+    },
+    SyntaxKind,
+};
+
+use crate::{utils::module_item, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: extract_transition_table_from_match
+//
+// Extracts the transitions of a state machine stepped by a match on the state and the input in
+// a loop into a table, which the loop looks the next state and the action to run up in.
+//
+// ```
+// # //- minicore: copy
+// enum Mode { Normal, Insert }
+// # impl Clone for Mode { fn clone(&self) -> Self { *self } }
+// # impl Copy for Mode {}
+// # struct Keys;
+// # impl Keys { fn next(&mut self) -> char { 'i' } fn flush(&mut self) {} }
+//
+// fn run(keys: &mut Keys) {
+//     let mut mode = Mode::Normal;
+//     loop {
+//         let key = keys.next();
+//         mode = $0match (mode, key) {
+//             (Mode::Normal, 'i') => Mode::Insert,
+//             (Mode::Normal, _) => Mode::Normal,
+//             (Mode::Insert, 'q') => {
+//                 keys.flush();
+//                 Mode::Normal
+//             }
+//             (Mode::Insert, _) => Mode::Insert,
+//         };
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Mode { Normal, Insert }
+// # impl Clone for Mode { fn clone(&self) -> Self { *self } }
+// # impl Copy for Mode {}
+// # struct Keys;
+// # impl Keys { fn next(&mut self) -> char { 'i' } fn flush(&mut self) {} }
+//
+// enum Action {
+//     None,
+//     Action1,
+// }
+//
+// const TRANSITIONS: &[(fn(Mode, char) -> bool, Action, Mode)] = &[
+//     (|state, input| matches!((state, input), (Mode::Normal, 'i')), Action::None, Mode::Insert),
+//     (|state, input| matches!((state, input), (Mode::Normal, _)), Action::None, Mode::Normal),
+//     (|state, input| matches!((state, input), (Mode::Insert, 'q')), Action::Action1, Mode::Normal),
+//     (|state, input| matches!((state, input), (Mode::Insert, _)), Action::None, Mode::Insert),
+// ];
+//
+// fn run(keys: &mut Keys) {
+//     let mut mode = Mode::Normal;
+//     loop {
+//         let key = keys.next();
+//         let (_, action, next) = TRANSITIONS.iter().find(|(accepts, ..)| accepts(mode, key)).unwrap();
+//         match action {
+//             Action::None => {}
+//             Action::Action1 => {
+//                 keys.flush();
+//             }
+//         }
+//         mode = *next;
+//     }
+// }
+// ```
+pub(crate) fn extract_transition_table_from_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let assign = match_expr.syntax().parent().and_then(ast::BinExpr::cast)?;
+    if assign.op_kind()? != (ast::BinaryOp::Assignment { op: None })
+        || assign.rhs()?.syntax() != match_expr.syntax()
+    {
+        return None;
+    }
+    let stmt = assign.syntax().parent().and_then(ast::ExprStmt::cast)?;
+    let in_loop =
+        stmt.syntax().ancestors().take_while(|it| !ast::Fn::can_cast(it.kind())).any(|it| {
+            matches!(
+                it.kind(),
+                SyntaxKind::LOOP_EXPR | SyntaxKind::WHILE_EXPR | SyntaxKind::FOR_EXPR
+            )
+        });
+    if !in_loop {
+        return None;
+    }
+
+    // The state and the input are looked at for each row of the table, so they must be locals.
+    let ast::Expr::TupleExpr(scrutinee) = match_expr.expr()? else { return None };
+    let [state, input]: [ast::Expr; 2] = scrutinee.fields().collect::<Vec<_>>().try_into().ok()?;
+    let (ast::Expr::PathExpr(_), ast::Expr::PathExpr(_)) = (&state, &input) else { return None };
+    if assign.lhs()?.syntax().text() != state.syntax().text() {
+        return None;
+    }
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let state_ty = ctx.sema.type_of_expr(&state)?.original;
+    let input_ty = ctx.sema.type_of_expr(&input)?.original;
+    let enum_ = match state_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    if enum_.variants(ctx.db()).iter().any(|it| it.kind(ctx.db()) != StructKind::Unit) {
+        return None;
+    }
+    let copy = FamousDefs(&ctx.sema, module.krate()).core_marker_Copy()?;
+    if !state_ty.impls_trait(ctx.db(), copy, &[]) || !input_ty.impls_trait(ctx.db(), copy, &[]) {
+        return None;
+    }
+    let state_ty = state_ty.display_source_code(ctx.db(), module.into()).ok()?;
+    let input_ty = input_ty.display_source_code(ctx.db(), module.into()).ok()?;
+
+    let mut transitions = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        // The patterns end up in functions, where the locals they would bind are not used.
+        let pat = arm.pat()?;
+        let binds = pat
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .any(|it| ctx.sema.resolve_bind_pat_to_const(&it).is_none());
+        if binds {
+            return None;
+        }
+        let body = arm.expr()?;
+        let diverges = body.syntax().descendants().any(|it| {
+            matches!(
+                it.kind(),
+                SyntaxKind::RETURN_EXPR
+                    | SyntaxKind::BREAK_EXPR
+                    | SyntaxKind::CONTINUE_EXPR
+                    | SyntaxKind::TRY_EXPR
+                    | SyntaxKind::AWAIT_EXPR
+            )
+        });
+        if diverges {
+            cov_mark::hit!(extract_transition_table_from_match_divergent_action);
+            return None;
+        }
+        let (action, next) = match body {
+            ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+                let stmts = block.stmt_list()?;
+                (stmts.statements().collect::<Vec<_>>(), stmts.tail_expr()?)
+            }
+            it => (Vec::new(), it),
+        };
+        let ast::Expr::PathExpr(next_path) = &next else { return None };
+        let variant = variant_of(ctx, next_path)?;
+        if variant.parent_enum(ctx.db()) != enum_ {
+            return None;
+        }
+        transitions.push(Transition { pat, action, next });
+    }
+    if transitions.is_empty() {
+        return None;
+    }
+
+    // Both go in front of the item containing the loop.
+    let item = module_item(match_expr.syntax())?;
+    let scope = ctx.sema.scope(item.syntax())?;
+    let mut taken = false;
+    scope.process_all_names(&mut |name, _| {
+        taken |= name.to_smol_str() == ACTION_ENUM || name.to_smol_str() == TABLE
+    });
+    if taken {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("extract_transition_table_from_match", AssistKind::RefactorExtract),
+        "Extract transition table",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(stmt.syntax());
+            let action_indent = indent + 2;
+            // Arms doing the same thing share an action.
+            let mut actions: Vec<String> = Vec::new();
+            let mut rows = Vec::new();
+            for Transition { pat, action, next } in &transitions {
+                let mut body = String::new();
+                for stmt in action {
+                    format_to!(
+                        body,
+                        "\n{action_indent}{}",
+                        stmt.reset_indent().indent(action_indent)
+                    );
+                }
+                let action = match body.is_empty() {
+                    true => String::from("None"),
+                    false => {
+                        let idx = actions.iter().position(|it| *it == body).unwrap_or_else(|| {
+                            actions.push(body);
+                            actions.len() - 1
+                        });
+                        format!("Action{}", idx + 1)
+                    }
+                };
+                rows.push((pat, action, next));
+            }
+            let has_none = rows.iter().any(|(_, action, _)| action == "None");
+
+            let item_indent = IndentLevel::from_node(item.syntax());
+            let mut buf = String::new();
+            let accepts =
+                |pat: &ast::Pat| format!("|state, input| matches!((state, input), {pat})");
+            if actions.is_empty() {
+                format_to!(
+                    buf,
+                    "const {TABLE}: &[(fn({state_ty}, {input_ty}) -> bool, {state_ty})] = &["
+                );
+                for (pat, _, next) in &rows {
+                    format_to!(buf, "\n{item_indent}    ({}, {next}),", accepts(pat));
+                }
+            } else {
+                format_to!(buf, "enum {ACTION_ENUM} {{");
+                if has_none {
+                    format_to!(buf, "\n{item_indent}    None,");
+                }
+                for idx in 1..=actions.len() {
+                    format_to!(buf, "\n{item_indent}    Action{idx},");
+                }
+                format_to!(
+                    buf,
+                    "\n{item_indent}}}\n\n{item_indent}const {TABLE}: \
+                     &[(fn({state_ty}, {input_ty}) -> bool, {ACTION_ENUM}, {state_ty})] = &["
+                );
+                for (pat, action, next) in &rows {
+                    let accepts = accepts(pat);
+                    format_to!(
+                        buf,
+                        "\n{item_indent}    ({accepts}, {ACTION_ENUM}::{action}, {next}),"
+                    );
+                }
+            }
+            format_to!(buf, "\n{item_indent}];\n\n{item_indent}");
+            builder.insert(item.syntax().text_range().start(), buf);
+
+            let find = format!("{TABLE}.iter().find(|(accepts, ..)| accepts({state}, {input}))");
+            let driver = if actions.is_empty() {
+                format!("{state} = {find}.unwrap().1;")
+            } else {
+                let mut buf = format!("let (_, action, next) = {find}.unwrap();");
+                format_to!(buf, "\n{indent}match action {{");
+                if has_none {
+                    format_to!(buf, "\n{indent}    {ACTION_ENUM}::None => {{}}");
+                }
+                for (idx, body) in actions.iter().enumerate() {
+                    let idx = idx + 1;
+                    format_to!(buf, "\n{indent}    {ACTION_ENUM}::Action{idx} => {{{body}");
+                    format_to!(buf, "\n{indent}    }}");
+                }
+                format_to!(buf, "\n{indent}}}\n{indent}{state} = *next;");
+                buf
+            };
+            builder.replace(stmt.syntax().text_range(), driver);
+        },
+    )
+}
+
+const ACTION_ENUM: &str = "Action";
+const TABLE: &str = "TRANSITIONS";
+
+/// An arm of the match, which becomes a row of the table.
+struct Transition {
+    pat: ast::Pat,
+    action: Vec<ast::Stmt>,
+    next: ast::Expr,
+}
+
+fn variant_of(ctx: &AssistContext<'_>, path: &ast::PathExpr) -> Option<hir::Variant> {
+    match ctx.sema.resolve_path(&path.path()?)? {
+        PathResolution::Def(hir::ModuleDef::Variant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_two_state_transitions() {
+        check_assist(
+            extract_transition_table_from_match,
+            r#"
+//- minicore: copy
+enum State { Start, Word }
+impl Clone for State { fn clone(&self) -> Self { *self } }
+impl Copy for State {}
+struct Words;
+impl Words {
+    fn push(&mut self, c: char) {}
+    fn finish(&mut self) {}
+}
+
+fn split(text: &Text, words: &mut Words) {
+    let mut state = State::Start;
+    loop {
+        let c = text.next();
+        state = match$0 (state, c) {
+            (State::Start, ' ') => State::Start,
+            (State::Start, _) => {
+                words.push(c);
+                State::Word
+            }
+            (State::Word, ' ') => {
+                words.finish();
+                State::Start
+            }
+            (State::Word, _) => {
+                words.push(c);
+                State::Word
+            }
+        };
+    }
+}
+struct Text;
+impl Text { fn next(&self) -> char { ' ' } }
+"#,
+            r#"
+enum State { Start, Word }
+impl Clone for State { fn clone(&self) -> Self { *self } }
+impl Copy for State {}
+struct Words;
+impl Words {
+    fn push(&mut self, c: char) {}
+    fn finish(&mut self) {}
+}
+
+enum Action {
+    None,
+    Action1,
+    Action2,
+}
+
+const TRANSITIONS: &[(fn(State, char) -> bool, Action, State)] = &[
+    (|state, input| matches!((state, input), (State::Start, ' ')), Action::None, State::Start),
+    (|state, input| matches!((state, input), (State::Start, _)), Action::Action1, State::Word),
+    (|state, input| matches!((state, input), (State::Word, ' ')), Action::Action2, State::Start),
+    (|state, input| matches!((state, input), (State::Word, _)), Action::Action1, State::Word),
+];
+
+fn split(text: &Text, words: &mut Words) {
+    let mut state = State::Start;
+    loop {
+        let c = text.next();
+        let (_, action, next) = TRANSITIONS.iter().find(|(accepts, ..)| accepts(state, c)).unwrap();
+        match action {
+            Action::None => {}
+            Action::Action1 => {
+                words.push(c);
+            }
+            Action::Action2 => {
+                words.finish();
+            }
+        }
+        state = *next;
+    }
+}
+struct Text;
+impl Text { fn next(&self) -> char { ' ' } }
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_plain_transitions() {
+        check_assist(
+            extract_transition_table_from_match,
+            r#"
+//- minicore: copy
+enum Parity { Even, Odd }
+impl Clone for Parity { fn clone(&self) -> Self { *self } }
+impl Copy for Parity {}
+
+fn parity(bits: u32) -> Parity {
+    let mut parity = Parity::Even;
+    let mut rest = bits;
+    while rest != 0 {
+        let bit = rest & 1 == 1;
+        parity = match$0 (parity, bit) {
+            (Parity::Even, true) => Parity::Odd,
+            (Parity::Odd, true) => Parity::Even,
+            (Parity::Even, false) => Parity::Even,
+            (Parity::Odd, false) => Parity::Odd,
+        };
+        rest >>= 1;
+    }
+    parity
+}
+"#,
+            r#"
+enum Parity { Even, Odd }
+impl Clone for Parity { fn clone(&self) -> Self { *self } }
+impl Copy for Parity {}
+
+const TRANSITIONS: &[(fn(Parity, bool) -> bool, Parity)] = &[
+    (|state, input| matches!((state, input), (Parity::Even, true)), Parity::Odd),
+    (|state, input| matches!((state, input), (Parity::Odd, true)), Parity::Even),
+    (|state, input| matches!((state, input), (Parity::Even, false)), Parity::Even),
+    (|state, input| matches!((state, input), (Parity::Odd, false)), Parity::Odd),
+];
+
+fn parity(bits: u32) -> Parity {
+    let mut parity = Parity::Even;
+    let mut rest = bits;
+    while rest != 0 {
+        let bit = rest & 1 == 1;
+        parity = TRANSITIONS.iter().find(|(accepts, ..)| accepts(parity, bit)).unwrap().1;
+        rest >>= 1;
+    }
+    parity
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_divergent_action() {
+        cov_mark::check!(extract_transition_table_from_match_divergent_action);
+        check_assist_not_applicable(
+            extract_transition_table_from_match,
+            r#"
+//- minicore: copy
+enum State { Start, Word }
+impl Clone for State { fn clone(&self) -> Self { *self } }
+impl Copy for State {}
+
+fn count(text: &[char]) -> u32 {
+    let mut state = State::Start;
+    let mut words = 0;
+    let mut i = 0;
+    loop {
+        let c = text[i];
+        state = match$0 (state, c) {
+            (State::Start, '.') => break,
+            (State::Start, _) => State::Word,
+            (State::Word, ' ') => {
+                words += 1;
+                State::Start
+            }
+            (State::Word, _) => {
+                if c == '.' {
+                    return words + 1;
+                }
+                State::Word
+            }
+        };
+        i += 1;
+    }
+    words
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use hir::{HirDisplay, PathResolution};
+use stdx::format_to;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasLoopBody, HasName,
+    },
+    T,
+};
+
+use crate::{
+    utils::{generate_impl_text, is_mutated_outside, iterator_of},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: extract_variant_weight_method
+//
+// Moves a match adding a weight for each variant of an enum to a counter in a loop into a
+// `weight` method on the enum, and replaces the loop with a sum of the weights.
+//
+// ```
+// # //- minicore: iterator, add
+// enum Coin { Penny, Dime, Quarter }
+//
+// fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+//     let mut sum = 0;
+//     $0for coin in coins {
+//         sum += match coin {
+//             Coin::Penny => 1,
+//             Coin::Dime => 10,
+//             Coin::Quarter => 25,
+//         };
+//     }
+//     sum
+// }
+// ```
+// ->
+// ```
+// enum Coin { Penny, Dime, Quarter }
+//
+// impl Coin {
+//     fn $0weight(&self) -> u32 {
+//         match self {
+//             Coin::Penny => 1,
+//             Coin::Dime => 10,
+//             Coin::Quarter => 25,
+//         }
+//     }
+// }
+//
+// fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+//     let sum: u32 = coins.map(Coin::weight).sum();
+//     sum
+// }
+// ```
+pub(crate) fn extract_variant_weight_method(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let for_kw = ctx.find_token_syntax_at_offset(T![for])?;
+    let for_expr = for_kw.parent().and_then(ast::ForExpr::cast)?;
+    let loop_node = match for_expr.syntax().parent().and_then(ast::ExprStmt::cast) {
+        Some(stmt) => stmt.syntax().clone(),
+        None => for_expr.syntax().clone(),
+    };
+    let let_stmt = loop_node.prev_sibling().and_then(ast::LetStmt::cast)?;
+    let ast::Pat::IdentPat(counter_pat) = let_stmt.pat()? else { return None };
+    let counter = counter_pat.name()?.text().to_string();
+
+    let body = for_expr.loop_body()?.stmt_list()?;
+    let update = match (body.statements().next(), body.tail_expr()) {
+        (None, Some(ast::Expr::BinExpr(it))) => it,
+        (Some(ast::Stmt::ExprStmt(stmt)), None) if body.statements().nth(1).is_none() => {
+            match stmt.expr()? {
+                ast::Expr::BinExpr(it) => it,
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    let ast::Expr::MatchExpr(match_expr) = update.rhs()? else { return None };
+    let is_sum = update.op_kind()? == ast::BinaryOp::Assignment { op: Some(ast::ArithOp::Add) }
+        && update.lhs()?.syntax().text() == counter.as_str()
+        && let_stmt.initializer()?.syntax().text() == "0";
+    if !is_sum {
+        cov_mark::hit!(extract_variant_weight_method_not_a_sum);
+        return None;
+    }
+
+    // The items have to be references to the enum to be passed to the method directly.
+    let ast::Pat::IdentPat(item) = for_expr.pat()? else { return None };
+    let item = ctx.sema.to_def(&item)?;
+    let (item_ty, _) = item.ty(ctx.db()).as_reference()?;
+    let enum_ = match item_ty.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let ast::Expr::PathExpr(scrutinee) = match_expr.expr()? else { return None };
+    if ctx.sema.resolve_path(&scrutinee.path()?)? != PathResolution::Local(item) {
+        return None;
+    }
+    // The arms move into the method, so they may only use what their patterns bind.
+    let match_range = match_expr.syntax().text_range();
+    let uses_outer_local =
+        match_expr.match_arm_list()?.syntax().descendants().filter_map(ast::Path::cast).any(
+            |path| {
+                let Some(PathResolution::Local(local)) = ctx.sema.resolve_path(&path) else {
+                    return false;
+                };
+                !match_range.contains_range(local.source(ctx.db()).value.syntax().text_range())
+            },
+        );
+    if uses_outer_local {
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let ret_ty = ctx
+        .sema
+        .type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?
+        .original
+        .display_source_code(ctx.db(), module.into())
+        .ok()?;
+    let enum_src = ctx.sema.source(enum_)?;
+    if enum_src.file_id.is_macro() || enum_src.file_id.original_file(ctx.db()) != ctx.file_id() {
+        return None;
+    }
+    let enum_ast = enum_src.value;
+    let has_weight = hir::Impl::all_for_type(ctx.db(), enum_.ty(ctx.db()))
+        .into_iter()
+        .flat_map(|it| it.items(ctx.db()))
+        .any(|it| matches!(it, hir::AssocItem::Function(f) if f.name(ctx.db()).to_smol_str() == "weight"));
+    if has_weight {
+        cov_mark::hit!(extract_variant_weight_method_name_taken);
+        return None;
+    }
+    let iterable = for_expr.iterable()?;
+
+    let target = let_stmt.syntax().text_range().cover(loop_node.text_range());
+    let counter_local = ctx.sema.to_def(&counter_pat)?;
+    let binding = match is_mutated_outside(ctx, counter_local, target) {
+        true => format!("mut {counter}"),
+        false => counter,
+    };
+    acc.add(
+        AssistId("extract_variant_weight_method", AssistKind::RefactorExtract),
+        "Extract `weight` method and sum the weights",
+        target,
+        |builder| {
+            let enum_name = enum_.name(ctx.db());
+            let iter = iterator_of(&ctx.sema, &iterable);
+            builder.replace(
+                target,
+                format!("let {binding}: {ret_ty} = {iter}.map({enum_name}::weight).sum();"),
+            );
+
+            let name = if ctx.config.snippet_cap.is_some() { "$0weight" } else { "weight" };
+            let mut method = format!("    fn {name}(&self) -> {ret_ty} {{\n        match self {{");
+            let arm_indent = IndentLevel(3);
+            for arm in match_expr.match_arm_list().into_iter().flat_map(|it| it.arms()) {
+                let needs_comma = arm.comma_token().is_none()
+                    && !matches!(arm.expr(), Some(ast::Expr::BlockExpr(_)));
+                let comma = if needs_comma { "," } else { "" };
+                format_to!(
+                    method,
+                    "\n{arm_indent}{}{comma}",
+                    arm.reset_indent().indent(arm_indent)
+                );
+            }
+            method.push_str("\n        }\n    }");
+            let impl_def = generate_impl_text(&ast::Adt::Enum(enum_ast.clone()), &method);
+            let offset = enum_ast.syntax().text_range().end();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, impl_def),
+                None => builder.insert(offset, impl_def),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn extract_weight_from_sum_loop() {
+        check_assist(
+            extract_variant_weight_method,
+            r#"
+//- minicore: iterator, option, add
+struct Iter<'a, T>(&'a T);
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> { None }
+}
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn iter(&self) -> Iter<'_, T> { loop {} }
+}
+enum Piece { Pawn, Knight(bool), Queen { promoted: bool } }
+
+fn material(pieces: &Vec<Piece>) -> u64 {
+    let mut score = 0;
+    $0for piece in pieces.iter() {
+        score += match piece {
+            Piece::Pawn => 1,
+            Piece::Knight(_) => 3,
+            Piece::Queen { promoted } => {
+                let base = 9;
+                if *promoted { base - 1 } else { base }
+            }
+        }
+    }
+    score * 2
+}
+"#,
+            r#"
+struct Iter<'a, T>(&'a T);
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> { None }
+}
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn iter(&self) -> Iter<'_, T> { loop {} }
+}
+enum Piece { Pawn, Knight(bool), Queen { promoted: bool } }
+
+impl Piece {
+    fn $0weight(&self) -> u64 {
+        match self {
+            Piece::Pawn => 1,
+            Piece::Knight(_) => 3,
+            Piece::Queen { promoted } => {
+                let base = 9;
+                if *promoted { base - 1 } else { base }
+            }
+        }
+    }
+}
+
+fn material(pieces: &Vec<Piece>) -> u64 {
+    let score: u64 = pieces.iter().map(Piece::weight).sum();
+    score * 2
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_accumulation() {
+        cov_mark::check!(extract_variant_weight_method_not_a_sum);
+        check_assist_not_applicable(
+            extract_variant_weight_method,
+            r#"
+//- minicore: iterator
+enum Coin { Penny, Dime }
+
+fn product<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let mut acc = 1;
+    $0for coin in coins {
+        acc *= match coin {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+        };
+    }
+    acc
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_arms_use_outer_locals() {
+        check_assist_not_applicable(
+            extract_variant_weight_method,
+            r#"
+//- minicore: iterator, add
+enum Coin { Penny, Dime }
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>, bonus: u32) -> u32 {
+    let mut sum = 0;
+    $0for coin in coins {
+        sum += match coin {
+            Coin::Penny => 1,
+            Coin::Dime => 10 + bonus,
+        };
+    }
+    sum
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn keep_counter_mutable_if_changed_after_the_loop() {
+        check_assist(
+            extract_variant_weight_method,
+            r#"
+//- minicore: iterator, add
+enum Coin { Penny, Dime }
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let mut sum = 0;
+    $0for coin in coins {
+        sum += match coin {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+        };
+    }
+    sum += 5;
+    sum
+}
+"#,
+            r#"
+enum Coin { Penny, Dime }
+
+impl Coin {
+    fn $0weight(&self) -> u32 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+        }
+    }
+}
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let mut sum: u32 = coins.map(Coin::weight).sum();
+    sum += 5;
+    sum
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_if_weight_exists() {
+        cov_mark::check!(extract_variant_weight_method_name_taken);
+        check_assist_not_applicable(
+            extract_variant_weight_method,
+            r#"
+//- minicore: iterator, add
+enum Coin { Penny, Dime }
+impl Coin {
+    fn weight(&self) -> f32 { 0.5 }
+}
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let mut sum = 0;
+    $0for coin in coins {
+        sum += match coin {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+        };
+    }
+    sum
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use hir::{HirDisplay, StructKind};
+use stdx::format_to;
+use syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::{generate_impl_text, needs_parens_as_receiver, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_code_conversions_from_match
+//
+// Moves a match mapping each variant of a fieldless enum to a distinct integer into a `code`
+// method on the enum, next to a `from_code` function mapping the integers back.
+//
+// ```
+// enum Status { Ok, NotFound }
+//
+// fn send(status: Status) -> u16 {
+//     $0match status {
+//         Status::Ok => 200,
+//         Status::NotFound => 404,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Status { Ok, NotFound }
+//
+// impl Status {
+//     fn $0code(&self) -> u16 {
+//         match self {
+//             Self::Ok => 200,
+//             Self::NotFound => 404,
+//         }
+//     }
+//
+//     fn from_code(code: u16) -> Option<Self> {
+//         match code {
+//             200 => Some(Self::Ok),
+//             404 => Some(Self::NotFound),
+//             _ => None,
+//         }
+//     }
+// }
+//
+// fn send(status: Status) -> u16 {
+//     status.code()
+// }
+// ```
+pub(crate) fn generate_code_conversions_from_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let enum_ = match ctx.sema.type_of_expr(&scrutinee)?.original.as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let all_variants = enum_.variants(ctx.db());
+
+    let mut mapping: Vec<(hir::Variant, ast::Literal, u128)> = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() {
+            return None;
+        }
+        let [variant]: [hir::Variant; 1] = variants(ctx, &arm.pat()?)?.try_into().ok()?;
+        if variant.kind(ctx.db()) != StructKind::Unit {
+            return None;
+        }
+        let ast::Expr::Literal(lit) = arm.expr()? else { return None };
+        let ast::LiteralKind::IntNumber(code) = lit.kind() else { return None };
+        let code = code.value()?;
+        if mapping.iter().any(|(v, _, c)| *v == variant || *c == code) {
+            cov_mark::hit!(generate_code_conversions_from_match_not_bijective);
+            return None;
+        }
+        mapping.push((variant, lit, code));
+    }
+    if mapping.len() != all_variants.len() {
+        cov_mark::hit!(generate_code_conversions_from_match_not_bijective);
+        return None;
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let code_ty = ctx
+        .sema
+        .type_of_expr(&ast::Expr::MatchExpr(match_expr.clone()))?
+        .original
+        .display_source_code(ctx.db(), module.into())
+        .ok()?;
+    let enum_src = ctx.sema.source(enum_)?;
+    if enum_src.file_id.is_macro() || enum_src.file_id.original_file(ctx.db()) != ctx.file_id() {
+        return None;
+    }
+    let enum_ast = enum_src.value;
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("generate_code_conversions_from_match", AssistKind::RefactorExtract),
+        "Generate `code` and `from_code`",
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(target, format!("{receiver}.code()"));
+
+            let name = if ctx.config.snippet_cap.is_some() { "$0code" } else { "code" };
+            let mut methods = format!("    fn {name}(&self) -> {code_ty} {{");
+            methods.push_str("\n        match self {");
+            for (variant, lit, _) in &mapping {
+                let variant = variant.name(ctx.db());
+                format_to!(methods, "\n            Self::{variant} => {lit},");
+            }
+            methods.push_str("\n        }\n    }\n");
+            format_to!(methods, "\n    fn from_code(code: {code_ty}) -> Option<Self> {{");
+            methods.push_str("\n        match code {");
+            for (variant, lit, _) in &mapping {
+                let variant = variant.name(ctx.db());
+                format_to!(methods, "\n            {lit} => Some(Self::{variant}),");
+            }
+            methods.push_str("\n            _ => None,\n        }\n    }");
+            let impl_def = generate_impl_text(&ast::Adt::Enum(enum_ast.clone()), &methods);
+            let offset = enum_ast.syntax().text_range().end();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, impl_def),
+                None => builder.insert(offset, impl_def),
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_for_three_variants() {
+        check_assist(
+            generate_code_conversions_from_match,
+            r#"
+//- minicore: option
+enum Status { Ok, NotFound, Teapot }
+struct Response { status: Status }
+
+fn line(res: &Response) -> u16 {
+    let code = match$0 res.status {
+        Status::NotFound => 404,
+        Status::Ok => 200,
+        Status::Teapot => 418,
+    };
+    code
+}
+"#,
+            r#"
+enum Status { Ok, NotFound, Teapot }
+
+impl Status {
+    fn $0code(&self) -> u16 {
+        match self {
+            Self::NotFound => 404,
+            Self::Ok => 200,
+            Self::Teapot => 418,
+        }
+    }
+
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            404 => Some(Self::NotFound),
+            200 => Some(Self::Ok),
+            418 => Some(Self::Teapot),
+            _ => None,
+        }
+    }
+}
+struct Response { status: Status }
+
+fn line(res: &Response) -> u16 {
+    let code = res.status.code();
+    code
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_shared_code() {
+        cov_mark::check!(generate_code_conversions_from_match_not_bijective);
+        check_assist_not_applicable(
+            generate_code_conversions_from_match,
+            r#"
+enum Status { Ok, Created, NotFound }
+
+fn code(status: Status) -> u16 {
+    match$0 status {
+        Status::Ok => 200,
+        Status::Created => 200,
+        Status::NotFound => 404,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_wildcard() {
+        check_assist_not_applicable(
+            generate_code_conversions_from_match,
+            r#"
+enum Status { Ok, Created, NotFound }
+
+fn code(status: Status) -> u16 {
+    match$0 status {
+        Status::NotFound => 404,
+        _ => 200,
+    }
+}
+"#,
+        );
+    }
+}
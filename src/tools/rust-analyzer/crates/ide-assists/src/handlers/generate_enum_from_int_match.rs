@@ -0,0 +1,318 @@
+use hir::HirDisplay;
+use ide_db::base_db::Edition;
+use stdx::{format_to, to_upper_camel_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    SyntaxKind,
+};
+
+use crate::{
+    utils::{int_literal, module_item},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: generate_enum_from_int_match
+//
+// Generates a fieldless `#[repr]` enum with a variant for each integer constant of a match,
+// together with a `TryFrom` impl, and rewrites the match to go through it.
+//
+// ```
+// fn run(opcode: u8) -> u32 {
+//     $0match opcode {
+//         1 => 10,
+//         2 | 3 => 20,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+// #[repr(u8)]
+// enum $0Opcode {
+//     V1 = 1,
+//     V2 = 2,
+//     V3 = 3,
+// }
+//
+// impl TryFrom<u8> for Opcode {
+//     type Error = u8;
+//
+//     fn try_from(value: u8) -> Result<Self, Self::Error> {
+//         match value {
+//             1 => Ok(Self::V1),
+//             2 => Ok(Self::V2),
+//             3 => Ok(Self::V3),
+//             _ => Err(value),
+//         }
+//     }
+// }
+//
+// fn run(opcode: u8) -> u32 {
+//     match Opcode::try_from(opcode) {
+//         Ok(Opcode::V1) => 10,
+//         Ok(Opcode::V2) | Ok(Opcode::V3) => 20,
+//         Err(_) => 0,
+//     }
+// }
+// ```
+pub(crate) fn generate_enum_from_int_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let repr_ty = ctx.sema.type_of_expr(&scrutinee)?.adjusted();
+    if !repr_ty.is_int_or_uint() {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (fallback, arms) = arms.split_last()?;
+    // Whatever the enum doesn't cover ends up in the error of the conversion.
+    let fallback_pat = match fallback.pat()? {
+        ast::Pat::WildcardPat(it) => it.to_string(),
+        ast::Pat::IdentPat(it) if it.pat().is_none() && it.ref_token().is_none() => {
+            it.name()?.to_string()
+        }
+        _ => return None,
+    };
+    if fallback.guard().is_some() || arms.is_empty() {
+        return None;
+    }
+
+    let mut variants: Vec<(u128, String)> = Vec::new();
+    let mut arm_values = Vec::new();
+    for arm in arms {
+        let pat = arm.pat()?;
+        let alternatives = match &pat {
+            ast::Pat::OrPat(it) => it.pats().collect(),
+            _ => vec![pat.clone()],
+        };
+        let mut values = Vec::new();
+        for alternative in alternatives {
+            let Some((value, text)) = int_pattern(&alternative) else {
+                cov_mark::hit!(generate_enum_from_int_match_not_a_constant);
+                return None;
+            };
+            if variants.iter().any(|(it, _)| *it == value) {
+                return None;
+            }
+            variants.push((value, text));
+            values.push(value);
+        }
+        arm_values.push((pat, values));
+    }
+
+    let module = ctx.sema.scope(match_expr.syntax())?.module();
+    let repr = repr_ty.display_source_code(ctx.db(), module.into()).ok()?;
+    let name = match &scrutinee {
+        ast::Expr::PathExpr(it) => it.path()?.segment()?.name_ref()?.to_string(),
+        ast::Expr::FieldExpr(it) => it.name_ref()?.to_string(),
+        _ => "value".to_string(),
+    };
+    let name = to_upper_camel_case(&name);
+
+    let item = module_item(match_expr.syntax())?;
+    let scope = ctx.sema.scope(item.syntax())?;
+    // `TryFrom` is only in the prelude since the 2021 edition.
+    if scope.krate().edition(ctx.db()) < Edition::Edition2021 {
+        cov_mark::hit!(generate_enum_from_int_match_old_edition);
+        return None;
+    }
+    let mut taken = false;
+    scope.process_all_names(&mut |it, _| taken |= it.to_smol_str() == name);
+    if taken {
+        cov_mark::hit!(generate_enum_from_int_match_name_taken);
+        return None;
+    }
+
+    acc.add(
+        AssistId("generate_enum_from_int_match", AssistKind::Generate),
+        format!("Generate `{name}` enum from integer match"),
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(item.syntax());
+            let inner = IndentLevel(indent.0 + 1);
+            let (arm_indent, case) = (IndentLevel(indent.0 + 2), IndentLevel(indent.0 + 3));
+
+            let mut buf = format!("#[derive(Clone, Copy, Debug, PartialEq, Eq)]\n{indent}");
+            format_to!(buf, "#[repr({repr})]\n{indent}");
+            match ctx.config.snippet_cap {
+                Some(_) => format_to!(buf, "enum $0{name} {{"),
+                None => format_to!(buf, "enum {name} {{"),
+            }
+            for (value, text) in &variants {
+                format_to!(buf, "\n{inner}V{value} = {text},");
+            }
+            format_to!(buf, "\n{indent}}}\n\n{indent}impl TryFrom<{repr}> for {name} {{");
+            format_to!(buf, "\n{inner}type Error = {repr};\n");
+            format_to!(buf, "\n{inner}fn try_from(value: {repr}) -> Result<Self, Self::Error> {{");
+            format_to!(buf, "\n{arm_indent}match value {{");
+            for (value, text) in &variants {
+                format_to!(buf, "\n{case}{text} => Ok(Self::V{value}),");
+            }
+            format_to!(buf, "\n{case}_ => Err(value),");
+            format_to!(buf, "\n{arm_indent}}}\n{inner}}}\n{indent}}}\n\n{indent}");
+
+            builder
+                .replace(scrutinee.syntax().text_range(), format!("{name}::try_from({scrutinee})"));
+            for (pat, values) in &arm_values {
+                let pat_text = values
+                    .iter()
+                    .map(|value| format!("Ok({name}::V{value})"))
+                    .collect::<Vec<_>>()
+                    .join(" | ");
+                builder.replace(pat.syntax().text_range(), pat_text);
+            }
+            if let Some(pat) = fallback.pat() {
+                builder.replace(pat.syntax().text_range(), format!("Err({fallback_pat})"));
+            }
+            let offset = item.syntax().text_range().start();
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, buf),
+                None => builder.insert(offset, buf),
+            }
+        },
+    )
+}
+
+/// Returns the value of a non-negative integer literal pattern, along with its text.
+fn int_pattern(pat: &ast::Pat) -> Option<(u128, String)> {
+    let ast::Pat::LiteralPat(pat) = pat else { return None };
+    if pat.syntax().first_token().map_or(false, |it| it.kind() == SyntaxKind::MINUS) {
+        return None;
+    }
+    let literal = pat.literal()?;
+    Some((int_literal(&literal.clone().into())?, literal.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_enum_for_three_constants() {
+        check_assist(
+            generate_enum_from_int_match,
+            r#"
+struct Packet { kind: u16 }
+
+impl Packet {
+    fn describe(&self) -> &'static str {
+        match$0 self.kind {
+            0x01 => "ping",
+            0x02 if true => "pong",
+            0x10 => "data",
+            other => "unknown",
+        }
+    }
+}
+"#,
+            r#"
+struct Packet { kind: u16 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u16)]
+enum $0Kind {
+    V1 = 0x01,
+    V2 = 0x02,
+    V16 = 0x10,
+}
+
+impl TryFrom<u16> for Kind {
+    type Error = u16;
+
+    fn try_from(value: u16) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(Self::V1),
+            0x02 => Ok(Self::V2),
+            0x10 => Ok(Self::V16),
+            _ => Err(value),
+        }
+    }
+}
+
+impl Packet {
+    fn describe(&self) -> &'static str {
+        match Kind::try_from(self.kind) {
+            Ok(Kind::V1) => "ping",
+            Ok(Kind::V2) if true => "pong",
+            Ok(Kind::V16) => "data",
+            Err(other) => "unknown",
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_ranges() {
+        cov_mark::check!(generate_enum_from_int_match_not_a_constant);
+        check_assist_not_applicable(
+            generate_enum_from_int_match,
+            r#"
+fn run(opcode: u8) -> u32 {
+    $0match opcode {
+        1 => 10,
+        2..=9 => 20,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_integers() {
+        check_assist_not_applicable(
+            generate_enum_from_int_match,
+            r#"
+fn run(flag: bool) -> u32 {
+    $0match flag {
+        true => 10,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_name_is_taken() {
+        cov_mark::check!(generate_enum_from_int_match_name_taken);
+        check_assist_not_applicable(
+            generate_enum_from_int_match,
+            r#"
+struct Opcode;
+
+fn run(opcode: u8) -> u32 {
+    $0match opcode {
+        1 => 10,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_before_edition_2021() {
+        cov_mark::check!(generate_enum_from_int_match_old_edition);
+        check_assist_not_applicable(
+            generate_enum_from_int_match,
+            r#"
+//- /main.rs edition:2018 crate:main
+fn run(opcode: u8) -> u32 {
+    $0match opcode {
+        1 => 10,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
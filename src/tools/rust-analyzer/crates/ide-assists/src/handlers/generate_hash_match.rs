@@ -0,0 +1,211 @@
+use hir::StructKind;
+use ide_db::famous_defs::FamousDefs;
+use itertools::Itertools;
+use stdx::format_to;
+use syntax::ast::{self, edit::IndentLevel, AstNode, HasName};
+
+use crate::{utils::is_stub, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_hash_match
+//
+// Fills in the body of a manual `Hash::hash` for an enum with a match hashing the
+// discriminant and then every field of the variant.
+//
+// ```
+// # //- minicore: hash
+// use core::hash::{Hash, Hasher};
+//
+// enum Shape { Dot, Circle(u32) }
+//
+// impl Hash for Shape {
+//     fn hash<H: Hasher>(&self, state: &mut H) {$0}
+// }
+// ```
+// ->
+// ```
+// use core::hash::{Hash, Hasher};
+//
+// enum Shape { Dot, Circle(u32) }
+//
+// impl Hash for Shape {
+//     fn hash<H: Hasher>(&self, state: &mut H) {
+//         core::mem::discriminant(self).hash(state);
+//         match self {
+//             Self::Dot => {}
+//             Self::Circle(arg0) => {
+//                 arg0.hash(state);
+//             }
+//         }
+//     }
+// }
+// ```
+pub(crate) fn generate_hash_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let func = ctx.find_node_at_offset::<ast::Fn>()?;
+    if func.name()?.text() != "hash" {
+        return None;
+    }
+    let impl_ = func.syntax().parent()?.parent().and_then(ast::Impl::cast)?;
+    let impl_def = ctx.sema.to_def(&impl_)?;
+    let hash = FamousDefs(&ctx.sema, impl_def.module(ctx.db()).krate()).core_hash_Hash()?;
+    if impl_def.trait_(ctx.db())? != hash {
+        return None;
+    }
+    let enum_ = match impl_def.self_ty(ctx.db()).as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let body = func.body()?;
+    if !is_stub(&body) {
+        cov_mark::hit!(generate_hash_match_body_not_empty);
+        return None;
+    }
+    let state = match func.param_list()?.params().next()?.pat()? {
+        ast::Pat::IdentPat(it) => it.name()?.to_string(),
+        _ => return None,
+    };
+
+    acc.add(
+        AssistId("generate_hash_match", AssistKind::Generate),
+        "Generate `hash` match over the variants",
+        body.syntax().text_range(),
+        |builder| {
+            let db = ctx.db();
+            let indent = IndentLevel::from_node(func.syntax());
+            let (stmt, arm, field) =
+                (IndentLevel(indent.0 + 1), IndentLevel(indent.0 + 2), IndentLevel(indent.0 + 3));
+            // Hashing the discriminant first keeps variants with equal fields apart.
+            let mut buf = format!(
+                "{{\n{stmt}core::mem::discriminant(self).hash({state});\n{stmt}match self {{"
+            );
+            for variant in enum_.variants(db) {
+                let fields = variant.fields(db);
+                let names = match variant.kind(db) {
+                    StructKind::Record => {
+                        fields.iter().map(|it| it.name(db).to_string()).collect::<Vec<_>>()
+                    }
+                    _ => (0..fields.len()).map(|i| format!("arg{i}")).collect(),
+                };
+                format_to!(buf, "\n{arm}Self::{}", variant.name(db));
+                match variant.kind(db) {
+                    StructKind::Record => format_to!(buf, " {{ {} }}", names.iter().join(", ")),
+                    StructKind::Tuple => format_to!(buf, "({})", names.iter().join(", ")),
+                    StructKind::Unit => (),
+                }
+                if names.is_empty() {
+                    buf.push_str(" => {}");
+                    continue;
+                }
+                buf.push_str(" => {");
+                for name in &names {
+                    format_to!(buf, "\n{field}{name}.hash({state});");
+                }
+                format_to!(buf, "\n{arm}}}");
+            }
+            format_to!(buf, "\n{stmt}}}\n{indent}}}");
+            builder.replace(body.syntax().text_range(), buf);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_for_unit_and_data_variants() {
+        check_assist(
+            generate_hash_match,
+            r#"
+//- minicore: hash
+use core::hash::{Hash, Hasher};
+
+enum Token { Eof, Number(u64, u8), Ident { name: u32 } }
+
+impl Hash for Token {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        unimplemented!()$0
+    }
+}
+"#,
+            r#"
+use core::hash::{Hash, Hasher};
+
+enum Token { Eof, Number(u64, u8), Ident { name: u32 } }
+
+impl Hash for Token {
+    fn hash<H: Hasher>(&self, hasher: &mut H) {
+        core::mem::discriminant(self).hash(hasher);
+        match self {
+            Self::Eof => {}
+            Self::Number(arg0, arg1) => {
+                arg0.hash(hasher);
+                arg1.hash(hasher);
+            }
+            Self::Ident { name } => {
+                name.hash(hasher);
+            }
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_existing_body() {
+        cov_mark::check!(generate_hash_match_body_not_empty);
+        check_assist_not_applicable(
+            generate_hash_match,
+            r#"
+//- minicore: hash
+use core::hash::{Hash, Hasher};
+
+enum Shape { Dot }
+
+impl Hash for Shape {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        0.hash(state);$0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_structs() {
+        check_assist_not_applicable(
+            generate_hash_match,
+            r#"
+//- minicore: hash
+use core::hash::{Hash, Hasher};
+
+struct Point { x: u32 }
+
+impl Hash for Point {
+    fn hash<H: Hasher>(&self, state: &mut H) {$0}
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_hash_traits() {
+        check_assist_not_applicable(
+            generate_hash_match,
+            r#"
+//- minicore: hash
+trait Hash {
+    fn hash(&self, state: &mut u64);
+}
+
+enum Shape { Dot, Circle(u32) }
+
+impl Hash for Shape {
+    fn hash(&self, state: &mut u64) {$0}
+}
+"#,
+        );
+    }
+}
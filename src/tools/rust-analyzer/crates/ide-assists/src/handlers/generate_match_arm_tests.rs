@@ -0,0 +1,370 @@
+use hir::StructKind;
+use ide_db::famous_defs::FamousDefs;
+use itertools::Itertools;
+use stdx::{format_to, to_lower_snake_case};
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasModuleItem, HasName},
+    TextSize,
+};
+
+use crate::{utils::variants, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_match_arm_tests
+//
+// Generates a test for each arm of the match a function consists of, calling the function with
+// a value matching the pattern of the arm.
+//
+// ```
+// enum Shape { Circle(u32), Empty }
+//
+// fn area(shape: Shape) -> u32 {
+//     $0match shape {
+//         Shape::Circle(r) => 3 * r * r,
+//         Shape::Empty => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Shape { Circle(u32), Empty }
+//
+// fn area(shape: Shape) -> u32 {
+//     match shape {
+//         Shape::Circle(r) => 3 * r * r,
+//         Shape::Empty => 0,
+//     }
+// }
+//
+// #[cfg(test)]
+// mod tests {
+//     use super::*;
+//
+//     #[test]
+//     fn area_circle() {
+//         assert_eq!(area(Shape::Circle(todo!())), ${0:todo!()});
+//     }
+//
+//     #[test]
+//     fn area_empty() {
+//         assert_eq!(area(Shape::Empty), todo!());
+//     }
+// }
+// ```
+pub(crate) fn generate_match_arm_tests(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let stmt_list = match_expr.syntax().parent().and_then(ast::StmtList::cast)?;
+    let fn_ = stmt_list.syntax().parent()?.parent().and_then(ast::Fn::cast)?;
+    let param_list = fn_.param_list()?;
+    if param_list.self_param().is_some() {
+        return None;
+    }
+    let fn_name = fn_.name()?;
+    let ast::Expr::PathExpr(scrutinee) = match_expr.expr()? else { return None };
+    let params = param_list.params().map(|it| it.pat()).collect::<Option<Vec<_>>>()?;
+    let scrutinee_param = params.iter().position(|it| match it {
+        ast::Pat::IdentPat(it) => it.syntax().text() == scrutinee.syntax().text(),
+        _ => false,
+    })?;
+
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(fn_.syntax())?.krate());
+    let default_trait = famous_defs.core_default_Default();
+    let sample = |ty: &hir::Type| match default_trait {
+        Some(it) if ty.impls_trait(ctx.db(), it, &[]) => "Default::default()",
+        _ => "todo!()",
+    };
+    let mut args = Vec::with_capacity(params.len());
+    for pat in &params {
+        args.push(sample(&ctx.sema.type_of_pat(pat)?.original));
+    }
+
+    let mut tests = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let pat = match arm.pat()? {
+            ast::Pat::OrPat(it) => it.pats().next()?,
+            it => it,
+        };
+        let Some(value) = sample_value(ctx, &pat, &sample) else {
+            cov_mark::hit!(generate_match_arm_tests_unconstructible);
+            return None;
+        };
+        let [variant]: [hir::Variant; 1] = variants(ctx, &pat)?.try_into().ok()?;
+        let name =
+            format!("{fn_name}_{}", to_lower_snake_case(&variant.name(ctx.db()).to_smol_str()));
+        if tests.iter().any(|(it, _)| *it == name) {
+            return None;
+        }
+        tests.push((name, value));
+    }
+    let returns_value = fn_.ret_type().map_or(false, |it| {
+        it.ty().map_or(
+            false,
+            |ty| !matches!(ty, ast::Type::TupleType(it) if it.fields().next().is_none()),
+        )
+    });
+
+    let parent = fn_.syntax().parent()?;
+    let items = parent.children().filter_map(ast::Item::cast).collect::<Vec<_>>();
+    let existing = items.iter().find_map(|it| match it {
+        ast::Item::Module(it) if it.name().map_or(false, |it| it.text() == "tests") => {
+            it.item_list()
+        }
+        _ => None,
+    });
+    let last_item = items.last()?.clone();
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("generate_match_arm_tests", AssistKind::Generate),
+        "Generate tests for match arms",
+        target,
+        |builder| {
+            let indent = match &existing {
+                Some(it) => IndentLevel::from_node(it.syntax()) + 1,
+                None => IndentLevel::from_node(fn_.syntax()) + 1,
+            };
+            let snippet = ctx.config.snippet_cap.is_some();
+            let mut buf = String::new();
+            for (idx, (name, value)) in tests.iter().enumerate() {
+                let mut args = args.clone();
+                args[scrutinee_param] = value;
+                let call = format!("{fn_name}({})", args.iter().join(", "));
+                let first = snippet && idx == 0;
+                let name = if first && !returns_value { format!("$0{name}") } else { name.clone() };
+                format_to!(buf, "\n\n{indent}#[test]\n{indent}fn {name}() {{");
+                match returns_value {
+                    true => {
+                        let expected = if first { "${0:todo!()}" } else { "todo!()" };
+                        format_to!(buf, "\n{indent}    assert_eq!({call}, {expected});")
+                    }
+                    false => format_to!(buf, "\n{indent}    {call};"),
+                }
+                format_to!(buf, "\n{indent}}}");
+            }
+            let (offset, text) = match existing {
+                Some(item_list) => {
+                    let offset = match item_list.items().last() {
+                        Some(it) => it.syntax().text_range().end(),
+                        None => item_list.syntax().text_range().start() + TextSize::of('{'),
+                    };
+                    (offset, buf)
+                }
+                None => {
+                    let outer = IndentLevel::from_node(fn_.syntax());
+                    let mut module = format!("\n\n{outer}#[cfg(test)]\n{outer}mod tests {{");
+                    format_to!(module, "\n{indent}use super::*;{buf}\n{outer}}}");
+                    (last_item.syntax().text_range().end(), module)
+                }
+            };
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(cap, offset, text),
+                None => builder.insert(offset, text),
+            }
+        },
+    )
+}
+
+/// Builds a value of the variant `pat` matches, which also matches the patterns of its fields.
+fn sample_value(
+    ctx: &AssistContext<'_>,
+    pat: &ast::Pat,
+    sample: &dyn Fn(&hir::Type) -> &'static str,
+) -> Option<String> {
+    let [variant]: [hir::Variant; 1] = variants(ctx, pat)?.try_into().ok()?;
+    let fields = variant.fields(ctx.db());
+    let field_value = |field: &hir::Field, pat: Option<ast::Pat>| -> Option<String> {
+        match pat {
+            None | Some(ast::Pat::WildcardPat(_)) => Some(sample(&field.ty(ctx.db())).to_owned()),
+            Some(ast::Pat::IdentPat(it)) if it.pat().is_none() => {
+                match ctx.sema.resolve_bind_pat_to_const(&it) {
+                    Some(_) => Some(it.to_string()),
+                    None => Some(sample(&field.ty(ctx.db())).to_owned()),
+                }
+            }
+            Some(ast::Pat::LiteralPat(it)) => Some(it.to_string()),
+            _ => None,
+        }
+    };
+    match pat {
+        ast::Pat::PathPat(it) => Some(it.to_string()),
+        ast::Pat::IdentPat(it) => Some(it.to_string()),
+        ast::Pat::TupleStructPat(it) if variant.kind(ctx.db()) == StructKind::Tuple => {
+            let pats = it.fields().collect::<Vec<_>>();
+            let rest = pats.iter().position(|it| matches!(it, ast::Pat::RestPat(_)));
+            let (prefix, suffix) = match rest {
+                Some(idx) => (&pats[..idx], &pats[idx + 1..]),
+                None => (&pats[..], &pats[pats.len()..]),
+            };
+            if rest.is_none() && pats.len() != fields.len() {
+                return None;
+            }
+            let values = fields
+                .iter()
+                .enumerate()
+                .map(|(idx, field)| {
+                    let pat = if idx < prefix.len() {
+                        Some(prefix[idx].clone())
+                    } else {
+                        (idx + suffix.len()).checked_sub(fields.len()).map(|it| suffix[it].clone())
+                    };
+                    field_value(field, pat)
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("{}({})", it.path()?, values.join(", ")))
+        }
+        ast::Pat::RecordPat(it) if variant.kind(ctx.db()) == StructKind::Record => {
+            let list = it.record_pat_field_list()?;
+            let pats = list
+                .fields()
+                .map(|it| Some((it.field_name()?.to_string(), it.pat())))
+                .collect::<Option<Vec<_>>>()?;
+            let values = fields
+                .iter()
+                .map(|field| {
+                    let name = field.name(ctx.db()).to_string();
+                    let pat =
+                        pats.iter().find(|(it, _)| *it == name).and_then(|(_, it)| it.clone());
+                    // A shorthand field binds the field, which any value does.
+                    Some(format!("{name}: {}", field_value(field, pat)?))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(format!("{} {{ {} }}", it.path()?, values.join(", ")))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_tests_for_three_variants() {
+        check_assist(
+            generate_match_arm_tests,
+            r#"
+//- minicore: default
+#[derive(PartialEq, Debug)]
+struct Name;
+impl Default for Name {
+    fn default() -> Self { Name }
+}
+enum Command { Greet(Name, u8), Move { x: i32, y: i32 }, Quit }
+
+fn describe(cmd: Command, verbose: bool) -> u8 {
+    match$0 cmd {
+        Command::Greet(_, 3) => 1,
+        Command::Move { x, .. } => 2,
+        Command::Quit => 3,
+    }
+}
+"#,
+            r#"
+#[derive(PartialEq, Debug)]
+struct Name;
+impl Default for Name {
+    fn default() -> Self { Name }
+}
+enum Command { Greet(Name, u8), Move { x: i32, y: i32 }, Quit }
+
+fn describe(cmd: Command, verbose: bool) -> u8 {
+    match cmd {
+        Command::Greet(_, 3) => 1,
+        Command::Move { x, .. } => 2,
+        Command::Quit => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_greet() {
+        assert_eq!(describe(Command::Greet(Default::default(), 3), todo!()), ${0:todo!()});
+    }
+
+    #[test]
+    fn describe_move() {
+        assert_eq!(describe(Command::Move { x: todo!(), y: todo!() }, todo!()), todo!());
+    }
+
+    #[test]
+    fn describe_quit() {
+        assert_eq!(describe(Command::Quit, todo!()), todo!());
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn generate_tests_into_existing_module() {
+        check_assist(
+            generate_match_arm_tests,
+            r#"
+enum Event { Open, Close }
+struct Log;
+
+fn handle(event: Event, log: &mut Log) {
+    match$0 event {
+        Event::Open => (),
+        Event::Close => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn smoke() {}
+}
+"#,
+            r#"
+enum Event { Open, Close }
+struct Log;
+
+fn handle(event: Event, log: &mut Log) {
+    match event {
+        Event::Open => (),
+        Event::Close => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn smoke() {}
+
+    #[test]
+    fn $0handle_open() {
+        handle(Event::Open, todo!());
+    }
+
+    #[test]
+    fn handle_close() {
+        handle(Event::Close, todo!());
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_wildcard_arm() {
+        cov_mark::check!(generate_match_arm_tests_unconstructible);
+        check_assist_not_applicable(
+            generate_match_arm_tests,
+            r#"
+enum Event { Open, Close, Reset }
+
+fn handle(event: Event) -> u8 {
+    match$0 event {
+        Event::Open => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
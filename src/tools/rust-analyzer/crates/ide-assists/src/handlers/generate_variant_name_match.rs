@@ -0,0 +1,151 @@
+use hir::StructKind;
+use stdx::format_to;
+use syntax::ast::{self, edit::IndentLevel, AstNode};
+
+use crate::{utils::is_stub, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: generate_variant_name_match
+//
+// Fills in the body of a method of an enum returning a `&str` with a match returning the
+// name of each variant.
+//
+// ```
+// enum Shape { Dot, Circle(u32), Rect { w: u32, h: u32 } }
+//
+// impl Shape {
+//     fn name(&self) -> &'static str {$0}
+// }
+// ```
+// ->
+// ```
+// enum Shape { Dot, Circle(u32), Rect { w: u32, h: u32 } }
+//
+// impl Shape {
+//     fn name(&self) -> &'static str {
+//         match self {
+//             Self::Dot => "Dot",
+//             Self::Circle(..) => "Circle",
+//             Self::Rect { .. } => "Rect",
+//         }
+//     }
+// }
+// ```
+pub(crate) fn generate_variant_name_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let func = ctx.find_node_at_offset::<ast::Fn>()?;
+    func.param_list()?.self_param()?;
+    if !returns_str(&func.ret_type()?.ty()?) {
+        return None;
+    }
+    let impl_ = func.syntax().parent()?.parent().and_then(ast::Impl::cast)?;
+    let enum_ = match ctx.sema.to_def(&impl_)?.self_ty(ctx.db()).as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let body = func.body()?;
+    if !is_stub(&body) {
+        cov_mark::hit!(generate_variant_name_match_body_not_empty);
+        return None;
+    }
+
+    acc.add(
+        AssistId("generate_variant_name_match", AssistKind::Generate),
+        "Generate match returning the variant names",
+        body.syntax().text_range(),
+        |builder| {
+            let db = ctx.db();
+            let indent = IndentLevel::from_node(func.syntax());
+            let (stmt, arm) = (IndentLevel(indent.0 + 1), IndentLevel(indent.0 + 2));
+            let mut buf = format!("{{\n{stmt}match self {{");
+            for variant in enum_.variants(db) {
+                let name = variant.name(db);
+                let fields = match variant.kind(db) {
+                    StructKind::Record => " { .. }",
+                    StructKind::Tuple => "(..)",
+                    StructKind::Unit => "",
+                };
+                format_to!(buf, "\n{arm}Self::{name}{fields} => \"{name}\",");
+            }
+            format_to!(buf, "\n{stmt}}}\n{indent}}}");
+            builder.replace(body.syntax().text_range(), buf);
+        },
+    )
+}
+
+/// Checks for `&str`, with or without a lifetime.
+fn returns_str(ty: &ast::Type) -> bool {
+    let ast::Type::RefType(ref_ty) = ty else { return false };
+    if ref_ty.mut_token().is_some() {
+        return false;
+    }
+    matches!(ref_ty.ty(), Some(ast::Type::PathType(it)) if it.syntax().text() == "str")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn generate_for_all_variant_kinds() {
+        check_assist(
+            generate_variant_name_match,
+            r#"
+enum Token { Eof, Number(u64, u8), Ident { name: u32 } }
+
+impl Token {
+    pub fn kind<'a>(&'a self) -> &'a str {
+        unimplemented!()$0
+    }
+}
+"#,
+            r#"
+enum Token { Eof, Number(u64, u8), Ident { name: u32 } }
+
+impl Token {
+    pub fn kind<'a>(&'a self) -> &'a str {
+        match self {
+            Self::Eof => "Eof",
+            Self::Number(..) => "Number",
+            Self::Ident { .. } => "Ident",
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_existing_body() {
+        cov_mark::check!(generate_variant_name_match_body_not_empty);
+        check_assist_not_applicable(
+            generate_variant_name_match,
+            r#"
+enum Shape { Dot }
+
+impl Shape {
+    fn name(&self) -> &'static str {
+        "dot"$0
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_return_types() {
+        check_assist_not_applicable(
+            generate_variant_name_match,
+            r#"
+enum Shape { Dot }
+
+impl Shape {
+    fn name(&self) -> u32 {$0}
+}
+"#,
+        );
+    }
+}
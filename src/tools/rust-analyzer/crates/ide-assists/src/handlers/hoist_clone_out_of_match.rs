@@ -0,0 +1,216 @@
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: hoist_clone_out_of_match
+//
+// Borrows the place every arm of a match clones and clones the result of the match once.
+//
+// ```
+// # //- minicore: clone
+// #[derive(Clone)]
+// struct Name;
+// struct Names { first: Name, last: Name }
+//
+// fn pick(names: &Names, first: bool) -> Name {
+//     $0match first {
+//         true => names.first.clone(),
+//         false => names.last.clone(),
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(Clone)]
+// struct Name;
+// struct Names { first: Name, last: Name }
+//
+// fn pick(names: &Names, first: bool) -> Name {
+//     (match first {
+//         true => &names.first,
+//         false => &names.last,
+//     }).clone()
+// }
+// ```
+pub(crate) fn hoist_clone_out_of_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let mut method = None;
+    let mut ty = None;
+    let mut places = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let ast::Expr::MethodCallExpr(call) = arm.expr()? else { return None };
+        let name = call.name_ref()?.to_string();
+        if !matches!(name.as_str(), "clone" | "to_owned")
+            || call.arg_list()?.args().next().is_some()
+        {
+            return None;
+        }
+        match &method {
+            None => method = Some(name),
+            Some(it) if *it == name => (),
+            Some(_) => return None,
+        }
+        let receiver = call.receiver()?;
+        if !is_place(&receiver) {
+            return None;
+        }
+        let receiver_ty = ctx.sema.type_of_expr(&receiver)?.original;
+        match &ty {
+            None => ty = Some(receiver_ty),
+            Some(it) if *it == receiver_ty => (),
+            Some(_) => {
+                cov_mark::hit!(hoist_clone_out_of_match_different_types);
+                return None;
+            }
+        }
+        places.push((call, receiver));
+    }
+    let method = method?;
+    // Borrowing a reference would clone the reference rather than what it points to.
+    let borrow = if ty?.is_reference() { "" } else { "&" };
+
+    acc.add(
+        AssistId("hoist_clone_out_of_match", AssistKind::RefactorRewrite),
+        format!("Call `{method}` once after the match"),
+        match_expr.syntax().text_range(),
+        |builder| {
+            let range = match_expr.syntax().text_range();
+            builder.insert(range.start(), "(");
+            for (call, receiver) in &places {
+                builder.replace(call.syntax().text_range(), format!("{borrow}{receiver}"));
+            }
+            builder.insert(range.end(), format!(").{method}()"));
+        },
+    )
+}
+
+fn is_place(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::PathExpr(_) => true,
+        ast::Expr::FieldExpr(it) => it.expr().map_or(false, |it| is_place(&it)),
+        ast::Expr::IndexExpr(it) => it.base().map_or(false, |it| is_place(&it)),
+        ast::Expr::ParenExpr(it) => it.expr().map_or(false, |it| is_place(&it)),
+        ast::Expr::PrefixExpr(it) => it.op_kind() == Some(ast::UnaryOp::Deref),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_clone_of_string_fields() {
+        check_assist(
+            hoist_clone_out_of_match,
+            r#"
+//- minicore: clone
+#[derive(Clone)]
+struct Str;
+enum Kind { Home, Work }
+struct Contact { home: Str, work: Str }
+
+impl Contact {
+    fn address(&self, kind: Kind) -> Str {
+        let address = $0match kind {
+            Kind::Home => self.home.clone(),
+            Kind::Work => (*self).work.clone(),
+        };
+        address
+    }
+}
+"#,
+            r#"
+#[derive(Clone)]
+struct Str;
+enum Kind { Home, Work }
+struct Contact { home: Str, work: Str }
+
+impl Contact {
+    fn address(&self, kind: Kind) -> Str {
+        let address = (match kind {
+            Kind::Home => &self.home,
+            Kind::Work => &(*self).work,
+        }).clone();
+        address
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_clone_of_references() {
+        check_assist(
+            hoist_clone_out_of_match,
+            r#"
+//- minicore: clone
+#[derive(Clone)]
+struct Str;
+
+fn pick(a: &Str, b: &Str, first: bool) -> Str {
+    match$0 first {
+        true => a.clone(),
+        false => b.clone(),
+    }
+}
+"#,
+            r#"
+#[derive(Clone)]
+struct Str;
+
+fn pick(a: &Str, b: &Str, first: bool) -> Str {
+    (match first {
+        true => a,
+        false => b,
+    }).clone()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_types() {
+        cov_mark::check!(hoist_clone_out_of_match_different_types);
+        check_assist_not_applicable(
+            hoist_clone_out_of_match,
+            r#"
+//- minicore: clone
+#[derive(Clone)]
+struct Str;
+#[derive(Clone)]
+struct Path;
+
+fn pick(a: Str, b: Path, first: bool) {
+    match$0 first {
+        true => a.clone(),
+        false => b.clone(),
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_temporaries() {
+        check_assist_not_applicable(
+            hoist_clone_out_of_match,
+            r#"
+//- minicore: clone
+#[derive(Clone)]
+struct Str;
+
+fn make() -> Str { Str }
+
+fn pick(a: Str, first: bool) -> Str {
+    match$0 first {
+        true => a.clone(),
+        false => make().clone(),
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,293 @@
+use hir::{ModuleDef, PathResolution};
+use syntax::{
+    ast::{self, AstNode, HasArgList},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: hoist_constructor_out_of_match
+//
+// Moves a tuple struct or variant constructor all arms of a match wrap their value in out of
+// the match. Arguments other than the value must be the same in all arms.
+//
+// ```
+// enum Out { Val(u32), None }
+// enum Key { A, B }
+//
+// fn lookup(key: Key) -> Out {
+//     $0match key {
+//         Key::A => Out::Val(1),
+//         Key::B => Out::Val(2),
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Out { Val(u32), None }
+// enum Key { A, B }
+//
+// fn lookup(key: Key) -> Out {
+//     Out::Val(match key {
+//         Key::A => 1,
+//         Key::B => 2,
+//     })
+// }
+// ```
+pub(crate) fn hoist_constructor_out_of_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    if arms.len() < 2 {
+        return None;
+    }
+
+    let mut calls = Vec::with_capacity(arms.len());
+    for arm in &arms {
+        let ast::Expr::CallExpr(call) = arm.expr()? else { return None };
+        let ast::Expr::PathExpr(callee) = call.expr()? else { return None };
+        let constructor = match ctx.sema.resolve_path(&callee.path()?)? {
+            PathResolution::Def(def @ ModuleDef::Variant(_))
+            | PathResolution::Def(def @ ModuleDef::Adt(hir::Adt::Struct(_))) => def,
+            _ => return None,
+        };
+        let args = call.arg_list()?.args().collect::<Vec<_>>();
+        if args.is_empty() {
+            return None;
+        }
+        calls.push((arm, call, callee, constructor, args));
+    }
+    let (_, _, callee, constructor, first_args) = &calls[0];
+    // A match can only pick the arguments, the constructor has to be the same in all arms.
+    if calls.iter().any(|(_, _, _, it, _)| it != constructor) {
+        cov_mark::hit!(hoist_constructor_out_of_match_different_constructors);
+        return None;
+    }
+    if calls.iter().any(|(.., args)| args.len() != first_args.len()) {
+        return None;
+    }
+    let differs = |idx: usize| {
+        let first = first_args[idx].syntax().text();
+        calls.iter().any(|(.., args)| args[idx].syntax().text() != first)
+    };
+    let varying = (0..first_args.len()).filter(|&idx| differs(idx)).collect::<Vec<_>>();
+    let idx = match varying.as_slice() {
+        [idx] => *idx,
+        [] if first_args.len() == 1 => 0,
+        _ => {
+            cov_mark::hit!(hoist_constructor_out_of_match_several_varying_arguments);
+            return None;
+        }
+    };
+    let match_range = match_expr.syntax().text_range();
+    let (before, after) = (&first_args[..idx], &first_args[idx + 1..]);
+    if !before.iter().chain(after).all(|arg| is_hoistable(ctx, arg, match_range)) {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("hoist_constructor_out_of_match", AssistKind::RefactorRewrite),
+        format!("Hoist `{callee}` out of the match"),
+        target,
+        |builder| {
+            let before = before.iter().map(|it| format!("{it}, ")).collect::<String>();
+            let after = after.iter().map(|it| format!(", {it}")).collect::<String>();
+            builder.insert(target.start(), format!("{callee}({before}"));
+            for (arm, call, .., args) in &calls {
+                let arg = &args[idx];
+                builder.replace(call.syntax().text_range(), arg.to_string());
+                // Blocks don't need the comma separating the arm from the next one.
+                if let (Some(comma), ast::Expr::BlockExpr(_)) = (arm.comma_token(), arg) {
+                    builder.delete(comma.text_range());
+                }
+            }
+            builder.insert(target.end(), format!("{after})"));
+        },
+    )
+}
+
+/// Checks whether a shared argument can be evaluated before the match, where the bindings of
+/// the arms are gone.
+fn is_hoistable(ctx: &AssistContext<'_>, arg: &ast::Expr, match_range: TextRange) -> bool {
+    match arg {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::PathExpr(path) => match path.path().and_then(|it| ctx.sema.resolve_path(&it)) {
+            Some(PathResolution::Local(local)) => {
+                !match_range.contains_range(local.source(ctx.db()).value.syntax().text_range())
+            }
+            Some(_) => true,
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_variant_constructor() {
+        check_assist(
+            hoist_constructor_out_of_match,
+            r#"
+//- minicore: option
+fn first(flag: bool, n: u8) -> Option<u8> {
+    let res = match$0 flag {
+        true => Some(n + 1),
+        false => Option::Some({
+            let m = n * 2;
+            m
+        }),
+    };
+    res
+}
+"#,
+            r#"
+fn first(flag: bool, n: u8) -> Option<u8> {
+    let res = Some(match flag {
+        true => n + 1,
+        false => {
+            let m = n * 2;
+            m
+        }
+    });
+    res
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_struct_constructor() {
+        check_assist(
+            hoist_constructor_out_of_match,
+            r#"
+struct Meters(f32);
+
+fn length(big: bool) -> Meters {
+    match$0 big {
+        true => Meters(10.0),
+        false => Meters(1.0),
+    }
+}
+"#,
+            r#"
+struct Meters(f32);
+
+fn length(big: bool) -> Meters {
+    Meters(match big {
+        true => 10.0,
+        false => 1.0,
+    })
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_constructor_with_shared_arguments() {
+        check_assist(
+            hoist_constructor_out_of_match,
+            r#"
+enum Event { Key(u8, u32, bool), Click }
+const NOW: u32 = 0;
+
+fn key(upper: bool, down: bool) -> Event {
+    match$0 upper {
+        true => Event::Key(b'A', NOW, down),
+        false => Event::Key(b'a', NOW, down),
+    }
+}
+"#,
+            r#"
+enum Event { Key(u8, u32, bool), Click }
+const NOW: u32 = 0;
+
+fn key(upper: bool, down: bool) -> Event {
+    Event::Key(match upper {
+        true => b'A',
+        false => b'a',
+    }, NOW, down)
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_several_varying_arguments() {
+        cov_mark::check!(hoist_constructor_out_of_match_several_varying_arguments);
+        check_assist_not_applicable(
+            hoist_constructor_out_of_match,
+            r#"
+struct Point(i32, i32);
+
+fn corner(left: bool) -> Point {
+    match$0 left {
+        true => Point(0, 0),
+        false => Point(10, 1),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_shared_arm_binding() {
+        check_assist_not_applicable(
+            hoist_constructor_out_of_match,
+            r#"
+//- minicore: option
+enum Wrapper { X(u8, u8) }
+
+fn wrap(v: Option<u8>) -> Wrapper {
+    match$0 v {
+        Some(n) => Wrapper::X(n, 0),
+        n => Wrapper::X(n, 1),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_variants_of_same_arity() {
+        check_assist_not_applicable(
+            hoist_constructor_out_of_match,
+            r#"
+enum Wrapper { X(u8), Y(u8) }
+fn inner_a() -> u8 { 0 }
+fn inner_b() -> u8 { 1 }
+
+fn wrap(a: bool) -> Wrapper {
+    match$0 a {
+        true => Wrapper::X(inner_a()),
+        false => Wrapper::Y(inner_b()),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_constructors() {
+        cov_mark::check!(hoist_constructor_out_of_match_different_constructors);
+        check_assist_not_applicable(
+            hoist_constructor_out_of_match,
+            r#"
+//- minicore: result
+fn check(ok: bool) -> Result<u8, u8> {
+    match$0 ok {
+        true => Ok(1),
+        false => Err(2),
+    }
+}
+"#,
+        );
+    }
+}
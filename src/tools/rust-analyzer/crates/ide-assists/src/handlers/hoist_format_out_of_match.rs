@@ -0,0 +1,253 @@
+use itertools::Itertools;
+use syntax::{
+    ast::{self, AstNode, HasName},
+    NodeOrToken, SyntaxKind, SyntaxToken, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: hoist_format_out_of_match
+//
+// Turns a match whose arms all call `format!` with the same template into one `format!`
+// call, matching only on the one argument that differs between the arms.
+//
+// ```
+// enum Unit { Meters, Feet }
+//
+// fn label(unit: Unit, n: u32) -> String {
+//     $0match unit {
+//         Unit::Meters => format!("{} {}", n, "m"),
+//         Unit::Feet => format!("{} {}", n, "ft"),
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Unit { Meters, Feet }
+//
+// fn label(unit: Unit, n: u32) -> String {
+//     format!("{} {}", n, match unit {
+//         Unit::Meters => "m",
+//         Unit::Feet => "ft",
+//     })
+// }
+// ```
+pub(crate) fn hoist_format_out_of_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let mut calls = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let ast::Expr::MacroExpr(expr) = arm.expr()? else { return None };
+        let call = expr.macro_call()?;
+        if call.path()?.to_string() != "format" {
+            return None;
+        }
+        let bindings = arm
+            .pat()?
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter_map(|it| Some(it.name()?.to_string()))
+            .collect::<Vec<_>>();
+        calls.push((expr, format_args(&call.token_tree()?), bindings));
+    }
+    let ((_, first, _), rest) = calls.split_first()?;
+    let template = first.first()?;
+    if rest.iter().any(|(_, it, _)| it.first() != Some(template)) {
+        cov_mark::hit!(hoist_format_out_of_match_different_templates);
+        return None;
+    }
+    if rest.iter().any(|(_, it, _)| it.len() != first.len()) {
+        return None;
+    }
+
+    let varying = (1..first.len())
+        .filter(|&i| rest.iter().any(|(_, it, _)| it[i] != first[i]))
+        .collect::<Vec<_>>();
+    let [varying] = varying[..] else { return None };
+    // A named argument keeps its name outside of the match, which isn't worth untangling.
+    if calls.iter().any(|(_, it, _)| it[varying].named) {
+        return None;
+    }
+    // The other arguments are evaluated outside of the arms, where their bindings don't exist.
+    let uses_arm_binding = calls.iter().any(|(_, args, bindings)| {
+        let mut hoisted = args.iter().enumerate().filter(|&(i, _)| i != varying);
+        hoisted.any(|(_, arg)| arg.names.iter().any(|name| bindings.contains(name)))
+    });
+    if uses_arm_binding {
+        cov_mark::hit!(hoist_format_out_of_match_arm_binding);
+        return None;
+    }
+
+    acc.add(
+        AssistId("hoist_format_out_of_match", AssistKind::RefactorRewrite),
+        "Call `format!` once after the match",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let range = match_expr.syntax().text_range();
+            let before = first[..varying].iter().map(|it| &it.text).join(", ");
+            builder.insert(range.start(), format!("format!({before}, "));
+            for (expr, args, _) in &calls {
+                builder.replace(expr.syntax().text_range(), args[varying].text.clone());
+            }
+            let after = first[varying + 1..].iter().map(|it| format!(", {}", it.text)).join("");
+            builder.insert(range.end(), format!("{after})"));
+        },
+    )
+}
+
+#[derive(Default, PartialEq, Eq)]
+struct FormatArg {
+    text: String,
+    named: bool,
+    /// The identifiers the argument mentions, including the ones captured by a template.
+    names: Vec<String>,
+}
+
+/// Splits the arguments of a `format!` call at the top-level commas.
+fn format_args(token_tree: &ast::TokenTree) -> Vec<FormatArg> {
+    let mut args = vec![FormatArg::default()];
+    let elements = token_tree.token_trees_and_tokens().collect::<Vec<_>>();
+    // Skip the delimiters of the call itself.
+    for element in elements.iter().skip(1).take(elements.len().saturating_sub(2)) {
+        let arg = args.last_mut().unwrap();
+        match element {
+            NodeOrToken::Token(it) if it.kind() == T![,] => args.push(FormatArg::default()),
+            NodeOrToken::Token(it) => {
+                arg.named |= it.kind() == T![=];
+                arg.text.push_str(it.text());
+                arg.names.extend(names(it));
+            }
+            NodeOrToken::Node(it) => {
+                arg.text.push_str(&it.to_string());
+                let tokens = it.syntax().descendants_with_tokens().filter_map(|it| it.into_token());
+                arg.names.extend(tokens.flat_map(|it| names(&it)));
+            }
+        }
+    }
+    for arg in &mut args {
+        arg.text = arg.text.trim().to_string();
+    }
+    args.retain(|it| !it.text.is_empty());
+    args
+}
+
+/// The identifier `token` is, or the names a string literal captures like `{name:?}`.
+fn names(token: &SyntaxToken) -> Vec<String> {
+    match token.kind() {
+        SyntaxKind::IDENT => vec![token.text().to_string()],
+        SyntaxKind::STRING => token
+            .text()
+            .split('{')
+            .skip(1)
+            .map(|it| it.split(|c: char| !c.is_alphanumeric() && c != '_').next().unwrap_or(""))
+            .filter(|it| it.starts_with(|c: char| c.is_alphabetic() || c == '_'))
+            .map(ToString::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_single_varying_argument() {
+        check_assist(
+            hoist_format_out_of_match,
+            r#"
+enum Op { Add, Sub }
+
+fn describe(op: Op, a: u8, b: u8) -> String {
+    let text = match$0 op {
+        Op::Add => format!("{a} {} {}", "+", (b, 1).0,),
+        Op::Sub if a > b => format!("{a} {} {}",  "-", (b, 1).0),
+        Op::Sub => format!("{a} {} {}", "minus", (b, 1).0),
+    };
+    text
+}
+"#,
+            r#"
+enum Op { Add, Sub }
+
+fn describe(op: Op, a: u8, b: u8) -> String {
+    let text = format!("{a} {} {}", match op {
+        Op::Add => "+",
+        Op::Sub if a > b => "-",
+        Op::Sub => "minus",
+    }, (b, 1).0);
+    text
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_templates() {
+        cov_mark::check!(hoist_format_out_of_match_different_templates);
+        check_assist_not_applicable(
+            hoist_format_out_of_match,
+            r#"
+fn describe(n: Option<u8>) -> String {
+    match$0 n {
+        Some(n) => format!("some {}", n),
+        None => format!("none {}", 0),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_two_varying_arguments() {
+        check_assist_not_applicable(
+            hoist_format_out_of_match,
+            r#"
+fn describe(b: bool) -> String {
+    match$0 b {
+        true => format!("{} {}", 1, 2),
+        false => format!("{} {}", 3, 4),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_hoisted_argument_uses_arm_binding() {
+        cov_mark::check!(hoist_format_out_of_match_arm_binding);
+        check_assist_not_applicable(
+            hoist_format_out_of_match,
+            r#"
+enum Shape { Circle(u8), Square(u8) }
+
+fn describe(shape: Shape) -> String {
+    match$0 shape {
+        Shape::Circle(n) => format!("{} {}", "circle", n),
+        Shape::Square(n) => format!("{} {}", "square", n),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_template_captures_arm_binding() {
+        cov_mark::check!(hoist_format_out_of_match_arm_binding);
+        check_assist_not_applicable(
+            hoist_format_out_of_match,
+            r#"
+enum Shape { Circle(u8), Square(u8) }
+
+fn describe(shape: Shape) -> String {
+    match$0 shape {
+        Shape::Circle(n) => format!("{n:>3} {}", "circle"),
+        Shape::Square(n) => format!("{n:>3} {}", "square"),
+    }
+}
+"#,
+        );
+    }
+}
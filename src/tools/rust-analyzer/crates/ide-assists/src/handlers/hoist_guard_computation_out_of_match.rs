@@ -0,0 +1,313 @@
+use syntax::ast::{self, edit::IndentLevel, AstNode};
+
+use crate::{
+    utils::{is_pattern_value, suggest_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: hoist_guard_computation_out_of_match
+//
+// Replaces a match whose arms are only picked by guards comparing the same computation to
+// constants with a match on the result of the computation, which is then done only once.
+//
+// ```
+// # //- minicore: derive, eq
+// #[derive(PartialEq)]
+// enum Kind { Digit, Letter, Other }
+// struct Token(char);
+// impl Token {
+//     fn kind(&self) -> Kind { Kind::Other }
+// }
+//
+// fn weight(token: &Token) -> u32 {
+//     $0match token {
+//         _ if token.kind() == Kind::Digit => 1,
+//         _ if token.kind() == Kind::Letter => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(PartialEq)]
+// enum Kind { Digit, Letter, Other }
+// struct Token(char);
+// impl Token {
+//     fn kind(&self) -> Kind { Kind::Other }
+// }
+//
+// fn weight(token: &Token) -> u32 {
+//     let $0kind = token.kind();
+//     match kind {
+//         Kind::Digit => 1,
+//         Kind::Letter => 2,
+//         _ => 0,
+//     }
+// }
+// ```
+pub(crate) fn hoist_guard_computation_out_of_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    // The scrutinee isn't evaluated anymore, which only makes no difference for a place.
+    if !matches!(match_expr.expr()?, ast::Expr::PathExpr(_) | ast::Expr::FieldExpr(_)) {
+        return None;
+    }
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let (fallback, arms) = arms.split_last()?;
+    if !matches!(fallback.pat()?, ast::Pat::WildcardPat(_)) || fallback.guard().is_some() {
+        return None;
+    }
+
+    let mut computation: Option<ast::Expr> = None;
+    let mut values = Vec::new();
+    for arm in arms {
+        let Some((computed, value)) = compared(ctx, arm) else {
+            cov_mark::hit!(hoist_guard_computation_out_of_match_not_a_comparison);
+            return None;
+        };
+        match &computation {
+            Some(it) if it.syntax().text() != computed.syntax().text() => {
+                cov_mark::hit!(hoist_guard_computation_out_of_match_different_computations);
+                return None;
+            }
+            Some(_) => {}
+            None => computation = Some(computed),
+        }
+        values.push((arm.pat()?, value));
+    }
+    let computation = computation?;
+
+    // The new variable is declared in a statement in front of the match.
+    let stmt = match_expr.syntax().parent()?;
+    let is_whole_stmt = ast::StmtList::can_cast(stmt.kind())
+        || ast::ExprStmt::can_cast(stmt.kind())
+        || ast::LetStmt::cast(stmt.clone()).map_or(false, |it| {
+            it.initializer().map_or(false, |init| init.syntax() == match_expr.syntax())
+        });
+    if !is_whole_stmt {
+        return None;
+    }
+    let name = suggest_name::for_variable(&computation, &ctx.sema);
+    let name = suggest_name::unique_in_scope(&name, &ctx.sema.scope(match_expr.syntax())?, &[]);
+    let insert_at = match ast::StmtList::can_cast(stmt.kind()) {
+        true => match_expr.syntax().text_range().start(),
+        false => stmt.text_range().start(),
+    };
+
+    acc.add(
+        AssistId("hoist_guard_computation_out_of_match", AssistKind::RefactorRewrite),
+        "Match on the computation shared by the guards",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            match ctx.config.snippet_cap {
+                Some(cap) => builder.insert_snippet(
+                    cap,
+                    insert_at,
+                    format!("let $0{name} = {computation};\n{indent}"),
+                ),
+                None => builder.insert(insert_at, format!("let {name} = {computation};\n{indent}")),
+            }
+            if let Some(scrutinee) = match_expr.expr() {
+                builder.replace(scrutinee.syntax().text_range(), name.clone());
+            }
+            for (arm, (pat, value)) in arms.iter().zip(&values) {
+                let end = arm
+                    .guard()
+                    .map_or(pat.syntax().text_range().end(), |it| it.syntax().text_range().end());
+                let range = pat.syntax().text_range().cover_offset(end);
+                builder.replace(range, value.to_string());
+            }
+        },
+    )
+}
+
+/// Splits the guard `_ if computed == value` of `arm`, where `value` can be a pattern.
+fn compared(ctx: &AssistContext<'_>, arm: &ast::MatchArm) -> Option<(ast::Expr, ast::Expr)> {
+    if !matches!(arm.pat()?, ast::Pat::WildcardPat(_)) {
+        return None;
+    }
+    let ast::Expr::BinExpr(cmp) = arm.guard()?.condition()? else { return None };
+    if cmp.op_kind()? != ast::BinaryOp::CmpOp(ast::CmpOp::Eq { negated: false }) {
+        return None;
+    }
+    let (lhs, rhs) = (cmp.lhs()?, cmp.rhs()?);
+    let (lhs_ty, rhs_ty) =
+        (ctx.sema.type_of_expr(&lhs)?.original, ctx.sema.type_of_expr(&rhs)?.original);
+    match (is_pattern_value(&ctx.sema, &lhs, &rhs_ty), is_pattern_value(&ctx.sema, &rhs, &lhs_ty)) {
+        (false, true) => Some((lhs, rhs)),
+        (true, false) => Some((rhs, lhs)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_method_computation() {
+        check_assist(
+            hoist_guard_computation_out_of_match,
+            r#"
+struct Request { path: u32 }
+impl Request {
+    fn route(&self) -> u8 { 0 }
+}
+const HOME: u8 = 0;
+
+fn handle(req: &Request) -> &'static str {
+    let page = match$0 req {
+        _ if req.route() == HOME => "home",
+        _ if 1 == req.route() => {
+            let _ = req.path;
+            "about"
+        }
+        _ => "missing",
+    };
+    page
+}
+"#,
+            r#"
+struct Request { path: u32 }
+impl Request {
+    fn route(&self) -> u8 { 0 }
+}
+const HOME: u8 = 0;
+
+fn handle(req: &Request) -> &'static str {
+    let $0route = req.route();
+    let page = match route {
+        HOME => "home",
+        1 => {
+            let _ = req.path;
+            "about"
+        }
+        _ => "missing",
+    };
+    page
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_computation_with_name_in_scope() {
+        check_assist(
+            hoist_guard_computation_out_of_match,
+            r#"
+enum Kind { Digit, Other }
+struct Token(char);
+impl Token {
+    fn kind(&self) -> Kind { Kind::Other }
+}
+
+fn weight(token: &Token, kind: u32) -> u32 {
+    $0match token {
+        _ if token.kind() == Kind::Digit => kind,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+enum Kind { Digit, Other }
+struct Token(char);
+impl Token {
+    fn kind(&self) -> Kind { Kind::Other }
+}
+
+fn weight(token: &Token, kind: u32) -> u32 {
+    let $0kind1 = token.kind();
+    match kind1 {
+        Kind::Digit => kind,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_computations() {
+        cov_mark::check!(hoist_guard_computation_out_of_match_different_computations);
+        check_assist_not_applicable(
+            hoist_guard_computation_out_of_match,
+            r#"
+fn len(x: u32) -> u32 { x }
+fn width(x: u32) -> u32 { x }
+
+fn handle(x: u32) -> u32 {
+    $0match x {
+        _ if len(x) == 1 => 1,
+        _ if width(x) == 2 => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_guards() {
+        cov_mark::check!(hoist_guard_computation_out_of_match_not_a_comparison);
+        check_assist_not_applicable(
+            hoist_guard_computation_out_of_match,
+            r#"
+fn len(x: u32) -> u32 { x }
+
+fn handle(x: u32) -> u32 {
+    $0match x {
+        _ if len(x) == 1 => 1,
+        _ if len(x) > 2 => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_const_without_structural_eq() {
+        cov_mark::check!(hoist_guard_computation_out_of_match_not_a_comparison);
+        check_assist_not_applicable(
+            hoist_guard_computation_out_of_match,
+            r#"
+//- minicore: eq
+struct Id(u32);
+impl PartialEq for Id {
+    fn eq(&self, other: &Id) -> bool { self.0 == other.0 }
+}
+const ROOT: Id = Id(0);
+fn id(x: u32) -> Id { Id(x) }
+
+fn handle(x: u32) -> u32 {
+    $0match x {
+        _ if id(x) == ROOT => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_float_literals() {
+        check_assist_not_applicable(
+            hoist_guard_computation_out_of_match,
+            r#"
+fn scale(x: f32) -> f32 { x }
+
+fn handle(x: f32) -> u32 {
+    $0match x {
+        _ if scale(x) == 1.0 => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
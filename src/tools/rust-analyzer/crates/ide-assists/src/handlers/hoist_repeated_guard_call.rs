@@ -0,0 +1,343 @@
+use hir::PathResolution;
+use syntax::{
+    algo::neighbor,
+    ast::{self, edit::IndentLevel, AstNode, HasName},
+    match_ast, Direction, SyntaxNode, TextRange,
+};
+
+use crate::{utils::suggest_name, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: hoist_repeated_guard_call
+//
+// Computes a call that appears in both the guard and the body of a match arm only once, by
+// binding it in the arm and checking the guard with an `if`. The arm that the guard used to
+// fall through to is reused as the `else` branch. As the call is then made as soon as the
+// pattern matches, it must not be evaluated conditionally.
+//
+// ```
+// struct Cache;
+// impl Cache {
+//     fn lookup(&self, key: u32) -> Option<u32> { None }
+// }
+//
+// fn get(cache: &Cache, key: Option<u32>) -> u32 {
+//     match key {
+//         Some(k) $0if cache.lookup(k).is_some() => cache.lookup(k).unwrap(),
+//         _ => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Cache;
+// impl Cache {
+//     fn lookup(&self, key: u32) -> Option<u32> { None }
+// }
+//
+// fn get(cache: &Cache, key: Option<u32>) -> u32 {
+//     match key {
+//         Some(k) => {
+//             let lookup = cache.lookup(k);
+//             if lookup.is_some() {
+//                 lookup.unwrap()
+//             } else {
+//                 0
+//             }
+//         }
+//         _ => 0,
+//     }
+// }
+// ```
+pub(crate) fn hoist_repeated_guard_call(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let guard = arm.guard()?.condition()?;
+    let body = arm.expr()?;
+    let pat = arm.pat()?;
+
+    let call = guard.syntax().descendants().filter(is_call).find(|call| {
+        let text = call.text();
+        body.syntax().descendants().any(|it| is_call(&it) && it.text() == text)
+    });
+    let call = match call.and_then(ast::Expr::cast) {
+        Some(it) => it,
+        None => {
+            cov_mark::hit!(hoist_repeated_guard_call_no_repetition);
+            return None;
+        }
+    };
+    let text = call.syntax().text();
+    let is_conditionally_called = guard
+        .syntax()
+        .descendants()
+        .map(|it| (it, guard.syntax()))
+        .chain(body.syntax().descendants().map(|it| (it, body.syntax())))
+        .filter(|(it, _)| is_call(it) && it.text() == text)
+        .any(|(it, root)| is_conditional(&it, root));
+    if is_conditionally_called {
+        cov_mark::hit!(hoist_repeated_guard_call_conditional);
+        return None;
+    }
+
+    // Without the guard the arm catches everything `pat` matches, so the body of the arm
+    // the guard used to fall through to has to go into the `else` branch.
+    let next = neighbor(&arm, Direction::Next)?;
+    if next.guard().is_some() {
+        return None;
+    }
+    let next_pat = next.pat()?;
+    let merge_next = next_pat.syntax().text() == pat.syntax().text();
+    if !merge_next {
+        if !matches!(next_pat, ast::Pat::WildcardPat(_)) {
+            return None;
+        }
+        // The fallback body must not see the arm's bindings in place of what it used to see.
+        let bindings = pat
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter_map(|it| Some(it.name()?.to_string()))
+            .collect::<Vec<_>>();
+        let shadowed =
+            next.expr()?.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+                matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(_)))
+                    && bindings.iter().any(|it| path.syntax().text() == it.as_str())
+            });
+        if shadowed {
+            return None;
+        }
+    }
+    let fallback = next.expr()?;
+
+    let name = suggest_name::for_variable(&call, &ctx.sema);
+    let name = suggest_name::unique_in_scope(&name, &ctx.sema.scope(body.syntax())?, &[]);
+    let target = match merge_next {
+        true => TextRange::new(arm.syntax().text_range().start(), next.syntax().text_range().end()),
+        false => arm.syntax().text_range(),
+    };
+    acc.add(
+        AssistId("hoist_repeated_guard_call", AssistKind::RefactorRewrite),
+        "Hoist repeated call out of guard",
+        target,
+        |builder| {
+            let call_text = call.syntax().text().to_string();
+            let condition = replace_calls(guard.syntax(), &call_text, &name);
+            let indent = IndentLevel::from_node(arm.syntax());
+            let then_branch =
+                branch(&body, replace_calls(body.syntax(), &call_text, &name), indent);
+            let else_branch = branch(&fallback, fallback.syntax().text().to_string(), indent);
+            let inner = IndentLevel(indent.0 + 1);
+            builder.replace(
+                target,
+                format!(
+                    "{pat} => {{\n{inner}let {name} = {call};\n\
+                     {inner}if {condition} {then_branch} else {else_branch}\n{indent}}}"
+                ),
+            );
+        },
+    )
+}
+
+fn is_call(node: &SyntaxNode) -> bool {
+    ast::CallExpr::can_cast(node.kind()) || ast::MethodCallExpr::can_cast(node.kind())
+}
+
+/// Checks whether `node` might not be evaluated whenever `root` is.
+fn is_conditional(node: &SyntaxNode, root: &SyntaxNode) -> bool {
+    let mut node = node.clone();
+    while &node != root {
+        let Some(parent) = node.parent() else { return false };
+        let is_conditional = match_ast! {
+            match parent {
+                ast::BinExpr(it) => {
+                    matches!(it.op_kind(), Some(ast::BinaryOp::LogicOp(_)))
+                        && it.rhs().map_or(false, |rhs| rhs.syntax() == &node)
+                },
+                ast::IfExpr(it) => it.condition().map_or(true, |cond| cond.syntax() != &node),
+                ast::MatchExpr(it) => it.expr().map_or(true, |expr| expr.syntax() != &node),
+                ast::ClosureExpr(_) => true,
+                ast::LoopExpr(_) => true,
+                ast::WhileExpr(_) => true,
+                ast::ForExpr(_) => true,
+                _ => false,
+            }
+        };
+        if is_conditional {
+            return true;
+        }
+        node = parent;
+    }
+    false
+}
+
+/// Returns the text of `node` with every call whose text is `call` replaced by `name`.
+fn replace_calls(node: &SyntaxNode, call: &str, name: &str) -> String {
+    let start = node.text_range().start();
+    let mut text = node.text().to_string();
+    let mut ranges = node
+        .descendants()
+        .filter(|it| is_call(it) && it.text() == call)
+        .map(|it| it.text_range() - start)
+        .collect::<Vec<_>>();
+    // Nested matches are rewritten along with the outermost one.
+    ranges.dedup_by(|inner, outer| outer.contains_range(*inner));
+    for range in ranges.into_iter().rev() {
+        text.replace_range(std::ops::Range::<usize>::from(range), name);
+    }
+    text
+}
+
+/// Renders the arm body `expr`, as `text`, as a branch of an `if` nested in an arm indented
+/// by `indent`.
+fn branch(expr: &ast::Expr, text: String, indent: IndentLevel) -> String {
+    // Everything moves one level deeper.
+    let text = text.replace('\n', &format!("\n{}", IndentLevel(1)));
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => text,
+        _ => format!("{{\n{}{text}\n{}}}", IndentLevel(indent.0 + 2), IndentLevel(indent.0 + 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_into_same_pattern_fallback() {
+        check_assist(
+            hoist_repeated_guard_call,
+            r#"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>) -> u32 {
+    match key {
+        Some(k) $0if cache.lookup(k).is_some() && k > 2 => {
+            let hit = cache.lookup(k).unwrap();
+            hit * 2
+        }
+        Some(k) => k,
+        None => 0,
+    }
+}
+"#,
+            r#"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>) -> u32 {
+    match key {
+        Some(k) => {
+            let lookup = cache.lookup(k);
+            if lookup.is_some() && k > 2 {
+                let hit = lookup.unwrap();
+                hit * 2
+            } else {
+                k
+            }
+        }
+        None => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_with_name_in_scope() {
+        check_assist(
+            hoist_repeated_guard_call,
+            r#"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>, lookup: u32) -> u32 {
+    match key {
+        Some(k) $0if cache.lookup(k).is_some() => cache.lookup(k).unwrap() + lookup,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>, lookup: u32) -> u32 {
+    match key {
+        Some(k) => {
+            let lookup1 = cache.lookup(k);
+            if lookup1.is_some() {
+                lookup1.unwrap() + lookup
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_call_is_short_circuited() {
+        cov_mark::check!(hoist_repeated_guard_call_conditional);
+        check_assist_not_applicable(
+            hoist_repeated_guard_call,
+            r#"
+fn parse(s: &str) -> Result<u32, ()> { Err(()) }
+
+fn value(input: Option<&str>) -> u32 {
+    match input {
+        Some(s) $0if s.len() > 2 && parse(&s[2..]).is_ok() => parse(&s[2..]).unwrap(),
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_repetition() {
+        cov_mark::check!(hoist_repeated_guard_call_no_repetition);
+        check_assist_not_applicable(
+            hoist_repeated_guard_call,
+            r#"
+fn parse(s: &str) -> Result<u32, ()> { Err(()) }
+
+fn value(input: Option<&str>) -> u32 {
+    match input {
+        Some(s) $0if parse(s).is_ok() => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_fallback_sees_bindings() {
+        check_assist_not_applicable(
+            hoist_repeated_guard_call,
+            r#"
+fn parse(s: &str) -> Result<u32, ()> { Err(()) }
+
+fn value(input: Option<&str>, s: u32) -> u32 {
+    match input {
+        Some(s) $0if parse(s).is_ok() => parse(s).unwrap(),
+        _ => s,
+    }
+}
+"#,
+        );
+    }
+}
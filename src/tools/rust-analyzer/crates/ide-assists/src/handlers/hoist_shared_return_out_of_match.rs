@@ -0,0 +1,260 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    SyntaxKind, SyntaxNode, TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: hoist_shared_return_out_of_match
+//
+// Removes the `return` all arms of a match end with, and returns once after the match instead.
+//
+// ```
+// # //- minicore: result
+// fn apply(cmd: u8, log: &mut Vec<u8>) -> Result<(), ()> {
+//     $0match cmd {
+//         0 => {
+//             log.clear();
+//             return Ok(());
+//         }
+//         n => {
+//             log.push(n);
+//             return Ok(());
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// fn apply(cmd: u8, log: &mut Vec<u8>) -> Result<(), ()> {
+//     match cmd {
+//         0 => {
+//             log.clear();
+//         }
+//         n => {
+//             log.push(n);
+//         }
+//     }
+//     Ok(())
+// }
+// ```
+pub(crate) fn hoist_shared_return_out_of_match(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    // Nothing after the match is reachable as all arms return, so the `return` goes right after.
+    let (stmt, stmt_list) = match match_expr.syntax().parent()? {
+        parent if ast::ExprStmt::can_cast(parent.kind()) => {
+            let stmt_list = parent.parent().and_then(ast::StmtList::cast)?;
+            (parent, stmt_list)
+        }
+        parent => (match_expr.syntax().clone(), ast::StmtList::cast(parent)?),
+    };
+
+    let mut exits = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let (exit, ret) = match arm.expr()? {
+            ast::Expr::ReturnExpr(it) => (Exit::Arm(arm.clone(), it.syntax().text_range()), it),
+            ast::Expr::BlockExpr(block) => {
+                let stmts = block.stmt_list()?;
+                let (node, ret) = match (stmts.tail_expr(), stmts.statements().last()) {
+                    (Some(ast::Expr::ReturnExpr(it)), _) => (it.syntax().clone(), it),
+                    (None, Some(ast::Stmt::ExprStmt(stmt))) => match stmt.expr()? {
+                        ast::Expr::ReturnExpr(it) => (stmt.syntax().clone(), it),
+                        _ => return None,
+                    },
+                    _ => return None,
+                };
+                let only = stmts.statements().all(|it| it.syntax() == &node);
+                (if only { Exit::Body(block) } else { Exit::LastStmt(node) }, ret)
+            }
+            _ => return None,
+        };
+        exits.push((exit, ret.expr()?));
+    }
+    let (_, value) = exits.first()?;
+    let value = value.clone();
+    if exits.iter().any(|(_, it)| it.syntax().text() != value.syntax().text()) {
+        cov_mark::hit!(hoist_shared_return_out_of_match_different_values);
+        return None;
+    }
+    // The value is evaluated after the match now, where the bindings of the arms are gone.
+    let match_range = match_expr.syntax().text_range();
+    let uses_inner_local = value.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        let Some(PathResolution::Local(local)) = ctx.sema.resolve_path(&path) else { return false };
+        match_range.contains_range(local.source(ctx.db()).value.syntax().text_range())
+    });
+    if uses_inner_local {
+        return None;
+    }
+    let is_fn_body =
+        stmt_list.syntax().parent().and_then(ast::BlockExpr::cast).map_or(false, |block| {
+            block.syntax().parent().map_or(false, |it| it.kind() == SyntaxKind::FN)
+        });
+    let is_last = stmt.next_sibling().is_none();
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("hoist_shared_return_out_of_match", AssistKind::RefactorRewrite),
+        "Return once after the match",
+        target,
+        |builder| {
+            for (exit, _) in &exits {
+                match exit {
+                    Exit::Arm(arm, body) => {
+                        builder.replace(*body, "{}");
+                        if let Some(comma) = arm.comma_token() {
+                            builder.delete(comma.text_range());
+                        }
+                    }
+                    Exit::Body(block) => builder.replace(block.syntax().text_range(), "{}"),
+                    Exit::LastStmt(node) => builder.delete(with_leading_whitespace(node)),
+                }
+            }
+            let indent = IndentLevel::from_node(&stmt);
+            let ret = match is_fn_body && is_last {
+                true => value.to_string(),
+                false => format!("return {value};"),
+            };
+            builder.insert(stmt.text_range().end(), format!("\n{indent}{ret}"));
+        },
+    )
+}
+
+/// How an arm ends with the shared `return`.
+enum Exit {
+    /// The `return` is the body of the arm.
+    Arm(ast::MatchArm, TextRange),
+    /// The `return` is the only statement of the block body.
+    Body(ast::BlockExpr),
+    /// The `return` follows other statements.
+    LastStmt(SyntaxNode),
+}
+
+fn with_leading_whitespace(node: &SyntaxNode) -> TextRange {
+    let range = node.text_range();
+    match node.prev_sibling_or_token() {
+        Some(it) if it.kind() == SyntaxKind::WHITESPACE => {
+            TextRange::new(it.text_range().start(), range.end())
+        }
+        _ => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn hoist_from_three_arms() {
+        check_assist(
+            hoist_shared_return_out_of_match,
+            r#"
+//- minicore: result
+enum Cmd { Push(u8), Pop, Clear }
+struct Log;
+impl Log {
+    fn push(&mut self, v: u8) {}
+    fn pop(&mut self) {}
+}
+
+fn apply(cmd: Cmd, log: &mut Log) -> Result<(), u8> {
+    match$0 cmd {
+        Cmd::Push(v) => {
+            if v == 0 {
+                return Err(v);
+            }
+            log.push(v);
+            return Ok(());
+        }
+        Cmd::Pop => {
+            log.pop();
+            return Ok(())
+        }
+        Cmd::Clear => return Ok(()),
+    }
+}
+"#,
+            r#"
+enum Cmd { Push(u8), Pop, Clear }
+struct Log;
+impl Log {
+    fn push(&mut self, v: u8) {}
+    fn pop(&mut self) {}
+}
+
+fn apply(cmd: Cmd, log: &mut Log) -> Result<(), u8> {
+    match cmd {
+        Cmd::Push(v) => {
+            if v == 0 {
+                return Err(v);
+            }
+            log.push(v);
+        }
+        Cmd::Pop => {
+            log.pop();
+        }
+        Cmd::Clear => {}
+    }
+    Ok(())
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn hoist_in_loop() {
+        check_assist(
+            hoist_shared_return_out_of_match,
+            r#"
+//- minicore: result
+fn first(items: &[u8]) -> Result<(), ()> {
+    loop {
+        match$0 items.len() {
+            0 => {
+                return Ok(());
+            }
+            _ => return Ok(()),
+        };
+    }
+}
+"#,
+            r#"
+fn first(items: &[u8]) -> Result<(), ()> {
+    loop {
+        match items.len() {
+            0 => {}
+            _ => {}
+        };
+        return Ok(());
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_values() {
+        cov_mark::check!(hoist_shared_return_out_of_match_different_values);
+        check_assist_not_applicable(
+            hoist_shared_return_out_of_match,
+            r#"
+//- minicore: result
+fn apply(cmd: u8) -> Result<(), u8> {
+    match$0 cmd {
+        0 => return Ok(()),
+        1 => {
+            let x = 1;
+            return Ok(());
+        }
+        n => return Err(n),
+    }
+}
+"#,
+        );
+    }
+}
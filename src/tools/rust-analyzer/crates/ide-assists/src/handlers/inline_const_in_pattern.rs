@@ -0,0 +1,142 @@
+use hir::{HasSource, PathResolution};
+use syntax::ast::{self, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: inline_const_in_pattern
+//
+// Replaces a constant in a match arm pattern with the literal it is defined as, keeping the
+// name of the constant in a comment.
+//
+// ```
+// const MAX_RETRIES: u32 = 3;
+//
+// fn give_up(retries: u32) -> bool {
+//     match retries {
+//         $0MAX_RETRIES => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// const MAX_RETRIES: u32 = 3;
+//
+// fn give_up(retries: u32) -> bool {
+//     match retries {
+//         3 /* MAX_RETRIES */ => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn inline_const_in_pattern(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let pat = ctx.find_node_at_offset::<ast::Pat>()?;
+    pat.syntax().ancestors().find_map(ast::MatchArm::cast)?;
+    let konst = match &pat {
+        ast::Pat::IdentPat(it) => match ctx.sema.resolve_bind_pat_to_const(it)? {
+            hir::ModuleDef::Const(it) => it,
+            _ => return None,
+        },
+        ast::Pat::PathPat(it) => match ctx.sema.resolve_path(&it.path()?)? {
+            PathResolution::Def(hir::ModuleDef::Const(it)) => it,
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let name = konst.name(ctx.db())?;
+
+    let literal = match konst.source(ctx.db())?.value.body()? {
+        it @ ast::Expr::Literal(_) => it,
+        ast::Expr::PrefixExpr(it)
+            if it.op_kind() == Some(ast::UnaryOp::Neg)
+                && matches!(it.expr(), Some(ast::Expr::Literal(_))) =>
+        {
+            it.into()
+        }
+        _ => {
+            cov_mark::hit!(inline_const_in_pattern_not_a_literal);
+            return None;
+        }
+    };
+
+    acc.add(
+        AssistId("inline_const_in_pattern", AssistKind::RefactorInline),
+        format!("Inline `{name}`"),
+        pat.syntax().text_range(),
+        |builder| {
+            builder.replace(pat.syntax().text_range(), format!("{literal} /* {name} */"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn inline_const_path_in_range() {
+        check_assist(
+            inline_const_in_pattern,
+            r#"
+mod limits {
+    pub const MIN: i64 = -40;
+    pub const MAX: i64 = 50;
+}
+
+fn valid(t: i64) -> bool {
+    match t {
+        limits::M$0IN..=limits::MAX => true,
+        _ => false,
+    }
+}
+"#,
+            r#"
+mod limits {
+    pub const MIN: i64 = -40;
+    pub const MAX: i64 = 50;
+}
+
+fn valid(t: i64) -> bool {
+    match t {
+        -40 /* MIN */..=limits::MAX => true,
+        _ => false,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_computed_const() {
+        cov_mark::check!(inline_const_in_pattern_not_a_literal);
+        check_assist_not_applicable(
+            inline_const_in_pattern,
+            r#"
+const LIMIT: u32 = 1 << 4;
+
+fn over(n: u32) -> bool {
+    match n {
+        LIMIT$0 => true,
+        _ => false,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_bindings() {
+        check_assist_not_applicable(
+            inline_const_in_pattern,
+            r#"
+fn over(n: u32) -> bool {
+    match n {
+        limit$0 => limit > 3,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode,
+    },
+    SyntaxNode, TextRange,
+};
+
+use crate::{utils::pat_variant, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: merge_if_lets_into_match
+//
+// Merges two consecutive `if let` statements matching different variants of the same value
+// into a single `match`.
+//
+// ```
+// enum Msg { Quit, Move(u32), Write(u32) }
+//
+// fn handle(msg: Msg) {
+//     $0if let Msg::Move(x) = &msg {
+//         move_to(x);
+//     }
+//     if let Msg::Write(y) = &msg {
+//         write(y);
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Msg { Quit, Move(u32), Write(u32) }
+//
+// fn handle(msg: Msg) {
+//     match &msg {
+//         Msg::Move(x) => {
+//             move_to(x);
+//         }
+//         Msg::Write(y) => {
+//             write(y);
+//         }
+//         _ => {}
+//     }
+// }
+// ```
+pub(crate) fn merge_if_lets_into_match(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let if_kw = ctx.find_token_syntax_at_offset(syntax::T![if])?;
+    let first_if = if_kw.parent().and_then(ast::IfExpr::cast)?;
+    let first_stmt = first_if.syntax().parent().and_then(ast::ExprStmt::cast)?;
+    let next = first_stmt.syntax().next_sibling()?;
+    let second_if = match ast::ExprStmt::cast(next.clone()) {
+        Some(stmt) => stmt.expr(),
+        None => ast::Expr::cast(next.clone()),
+    };
+    let second_if = match second_if? {
+        ast::Expr::IfExpr(it) => it,
+        _ => return None,
+    };
+
+    let (first_pat, first_scrutinee, first_body) = if_let_parts(&first_if)?;
+    let (second_pat, second_scrutinee, second_body) = if_let_parts(&second_if)?;
+    if first_scrutinee.syntax().text() != second_scrutinee.syntax().text() {
+        return None;
+    }
+
+    // Only different variants are guaranteed to be mutually exclusive.
+    let first_variant = pat_variant(ctx, &first_pat)?;
+    let second_variant = pat_variant(ctx, &second_pat)?;
+    if first_variant == second_variant {
+        return None;
+    }
+
+    // If the first body touches the matched value, the second condition might have
+    // matched after it ran.
+    let scrutinee_locals = locals_in(ctx, first_scrutinee.syntax());
+    if locals_in(ctx, first_body.syntax()).iter().any(|it| scrutinee_locals.contains(it)) {
+        cov_mark::hit!(merge_if_lets_into_match_body_uses_scrutinee);
+        return None;
+    }
+
+    let target = TextRange::new(first_stmt.syntax().text_range().start(), next.text_range().end());
+    acc.add(
+        AssistId("merge_if_lets_into_match", AssistKind::RefactorRewrite),
+        "Merge `if let`s into `match`",
+        target,
+        |builder| {
+            // The bodies move one level deeper, into the arms.
+            let body = |block: ast::BlockExpr, if_expr: &ast::IfExpr| {
+                block.dedent(if_expr.indent_level()).indent(IndentLevel(1)).into()
+            };
+            let arms = [
+                make::match_arm([first_pat], None, body(first_body, &first_if)),
+                make::match_arm([second_pat], None, body(second_body, &second_if)),
+                make::match_arm([make::wildcard_pat().into()], None, make::expr_empty_block()),
+            ];
+            let match_expr = make::expr_match(first_scrutinee, make::match_arm_list(arms))
+                .indent(first_if.indent_level());
+            builder.replace(target, match_expr.to_string());
+        },
+    )
+}
+
+fn if_let_parts(if_expr: &ast::IfExpr) -> Option<(ast::Pat, ast::Expr, ast::BlockExpr)> {
+    if if_expr.else_branch().is_some() {
+        return None;
+    }
+    let let_expr = match if_expr.condition()? {
+        ast::Expr::LetExpr(it) => it,
+        _ => return None,
+    };
+    Some((let_expr.pat()?, let_expr.expr()?, if_expr.then_branch()?))
+}
+
+fn locals_in(ctx: &AssistContext<'_>, node: &SyntaxNode) -> Vec<hir::Local> {
+    node.descendants()
+        .filter_map(ast::Path::cast)
+        .filter_map(|path| match ctx.sema.resolve_path(&path)? {
+            PathResolution::Local(it) => Some(it),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn merge_consecutive_if_lets() {
+        check_assist(
+            merge_if_lets_into_match,
+            r#"
+enum Msg { Quit, Move { x: u32 }, Write(u32) }
+
+fn handle(msg: Msg, log: &mut u32) {
+    i$0f let Msg::Move { x } = &msg {
+        *log += x;
+    }
+    if let Msg::Write(y) = &msg {
+        *log += y;
+    }
+    *log += 1;
+}
+"#,
+            r#"
+enum Msg { Quit, Move { x: u32 }, Write(u32) }
+
+fn handle(msg: Msg, log: &mut u32) {
+    match &msg {
+        Msg::Move { x } => {
+            *log += x;
+        }
+        Msg::Write(y) => {
+            *log += y;
+        }
+        _ => {}
+    }
+    *log += 1;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_with_tail_if_let() {
+        check_assist(
+            merge_if_lets_into_match,
+            r#"
+enum Msg { Quit, Write(u32) }
+
+fn handle(msg: Msg) {
+    $0if let Msg::Quit = msg { stop(); }
+    if let Msg::Write(y) = msg { write(y); }
+}
+"#,
+            r#"
+enum Msg { Quit, Write(u32) }
+
+fn handle(msg: Msg) {
+    match msg {
+        Msg::Quit => { stop(); }
+        Msg::Write(y) => { write(y); }
+        _ => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_body_uses_scrutinee() {
+        cov_mark::check!(merge_if_lets_into_match_body_uses_scrutinee);
+        check_assist_not_applicable(
+            merge_if_lets_into_match,
+            r#"
+enum Msg { Quit, Write(u32) }
+
+fn handle(mut msg: Msg) {
+    $0if let Msg::Quit = msg {
+        msg = Msg::Write(0);
+    }
+    if let Msg::Write(y) = msg {
+        write(y);
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_same_variant() {
+        check_assist_not_applicable(
+            merge_if_lets_into_match,
+            r#"
+enum Msg { Quit, Write(u32) }
+
+fn handle(msg: Msg) {
+    $0if let Msg::Write(1) = msg { one(); }
+    if let Msg::Write(y) = msg { write(y); }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_different_scrutinees() {
+        check_assist_not_applicable(
+            merge_if_lets_into_match,
+            r#"
+enum Msg { Quit, Write(u32) }
+
+fn handle(msg: Msg, other: Msg) {
+    $0if let Msg::Quit = msg { stop(); }
+    if let Msg::Write(y) = other { write(y); }
+}
+"#,
+        );
+    }
+}
@@ -2,11 +2,14 @@
 use std::{collections::HashMap, iter::successors};
 use syntax::{
     algo::neighbor,
-    ast::{self, AstNode, HasName},
+    ast::{self, AstNode, HasAttrs, HasName},
     Direction,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists, TextRange};
+use crate::{
+    handlers::sort_or_pattern::{binds_variables, sort_by_variant_order},
+    AssistContext, AssistId, AssistKind, Assists, TextRange,
+};
 
 // Assist: merge_match_arms
 //
@@ -41,13 +44,23 @@ pub(crate) fn merge_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Op
     let current_expr = current_arm.expr()?;
     let current_text_range = current_arm.syntax().text_range();
     let current_arm_types = get_arm_types(ctx, &current_arm);
+    let current_attrs = arm_attrs(&current_arm);
+    // Whether a block ends in a statement or in an expression only makes a difference to its
+    // value, which a match of type `()` doesn't have.
+    let is_unit = current_arm
+        .syntax()
+        .ancestors()
+        .find_map(ast::MatchExpr::cast)
+        .and_then(|it| ctx.sema.type_of_expr(&it.into()))
+        .map_or(false, |it| it.original.is_unit());
+    let current_body = body_text(&current_expr, is_unit);
 
     // We check if the following match arms match this one. We could, but don't,
     // compare to the previous match arm as well.
     let arms_to_merge = successors(Some(current_arm), |it| neighbor(it, Direction::Next))
         .take_while(|arm| match arm.expr() {
             Some(expr) if arm.guard().is_none() => {
-                let same_text = expr.syntax().text() == current_expr.syntax().text();
+                let same_text = body_text(&expr, is_unit) == current_body;
                 if !same_text {
                     return false;
                 }
@@ -61,35 +74,85 @@ pub(crate) fn merge_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Op
     if arms_to_merge.len() <= 1 {
         return None;
     }
+    // The attributes of the current arm are kept for the merged one, which is only correct
+    // if they apply to all merged arms alike.
+    if arms_to_merge.iter().any(|arm| arm_attrs(arm) != current_attrs) {
+        cov_mark::hit!(merge_match_arms_different_attrs);
+        return None;
+    }
 
+    let [first, .., last] = &*arms_to_merge else { return None };
+    let start = match first.pat() {
+        Some(pat) => pat.syntax().text_range().start(),
+        None => first.syntax().text_range().start(),
+    };
+    let merged_range = TextRange::new(start, last.syntax().text_range().end());
+    let pats = if arms_to_merge.iter().any(contains_placeholder) {
+        "_".into()
+    } else {
+        arms_to_merge
+            .iter()
+            .filter_map(ast::MatchArm::pat)
+            // This is the source text of the pattern, so its formatting is kept as is.
+            .map(|x| x.syntax().to_string())
+            .collect::<Vec<String>>()
+            .join(" | ")
+    };
     acc.add(
         AssistId("merge_match_arms", AssistKind::RefactorRewrite),
         "Merge match arms",
         current_text_range,
-        |edit| {
-            let pats = if arms_to_merge.iter().any(contains_placeholder) {
-                "_".into()
-            } else {
-                arms_to_merge
-                    .iter()
-                    .filter_map(ast::MatchArm::pat)
-                    .map(|x| x.syntax().to_string())
-                    .collect::<Vec<String>>()
-                    .join(" | ")
-            };
-
-            let arm = format!("{pats} => {current_expr},");
-
-            if let [first, .., last] = &*arms_to_merge {
-                let start = first.syntax().text_range().start();
-                let end = last.syntax().text_range().end();
-
-                edit.replace(TextRange::new(start, end), arm);
-            }
-        },
+        |edit| edit.replace(merged_range, format!("{pats} => {current_expr},")),
+    );
+
+    // The merged alternatives can be put in declaration order right away, the way
+    // `sort_or_pattern` would do it afterwards.
+    let alternatives = arms_to_merge
+        .iter()
+        .filter_map(ast::MatchArm::pat)
+        .flat_map(|pat| match pat {
+            ast::Pat::OrPat(it) => it.pats().collect(),
+            _ => vec![pat],
+        })
+        .collect::<Vec<_>>();
+    if binds_variables(ctx, &alternatives) {
+        return Some(());
+    }
+    let sorted = sort_by_variant_order(ctx, &alternatives).filter(|it| *it != alternatives)?;
+    let pats = sorted.iter().map(|x| x.syntax().to_string()).collect::<Vec<String>>().join(" | ");
+    acc.add(
+        AssistId("merge_match_arms", AssistKind::RefactorRewrite),
+        "Merge match arms and sort alternatives",
+        current_text_range,
+        |edit| edit.replace(merged_range, format!("{pats} => {current_expr},")),
     )
 }
 
+fn arm_attrs(arm: &ast::MatchArm) -> Vec<String> {
+    arm.attrs().map(|attr| attr.syntax().to_string()).collect()
+}
+
+/// Returns the text of `body`, without the semicolon of a trailing statement if `is_unit`.
+fn body_text(body: &ast::Expr, is_unit: bool) -> String {
+    let text = body.syntax().to_string();
+    let ast::Expr::BlockExpr(block) = body else { return text };
+    let Some(stmt_list) = block.stmt_list().filter(|it| is_unit && it.tail_expr().is_none()) else {
+        return text;
+    };
+    let semicolon = match stmt_list.statements().last() {
+        Some(ast::Stmt::ExprStmt(stmt)) => stmt.semicolon_token(),
+        _ => None,
+    };
+    match semicolon {
+        Some(semicolon) => {
+            let offset = body.syntax().text_range().start();
+            let range = semicolon.text_range() - offset;
+            format!("{}{}", &text[..range.start().into()], &text[range.end().into()..])
+        }
+        None => text,
+    }
+}
+
 fn contains_placeholder(a: &ast::MatchArm) -> bool {
     matches!(a.pat(), Some(ast::Pat::WildcardPat(..)))
 }
@@ -166,7 +229,7 @@ fn recurse(
 
 #[cfg(test)]
 mod tests {
-    use crate::tests::{check_assist, check_assist_not_applicable};
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
 
     use super::*;
 
@@ -819,4 +882,196 @@ fn func(binary: &[u8]) {
         "#,
         )
     }
+
+    #[test]
+    fn merge_match_arms_preserves_attrs() {
+        check_assist(
+            merge_match_arms,
+            r#"
+enum X { A, B, C }
+
+fn main() {
+    let x = X::A;
+    let y = match x {
+        #[cfg(feature = "a")]
+        X::A => { 1i32$0 }
+        #[cfg(feature = "a")]
+        X::B => { 1i32 }
+        X::C => { 2i32 }
+    }
+}
+"#,
+            r#"
+enum X { A, B, C }
+
+fn main() {
+    let x = X::A;
+    let y = match x {
+        #[cfg(feature = "a")]
+        X::A | X::B => { 1i32 },
+        X::C => { 2i32 }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_different_attrs() {
+        cov_mark::check!(merge_match_arms_different_attrs);
+        check_assist_not_applicable(
+            merge_match_arms,
+            r#"
+enum X { A, B, C }
+
+fn main() {
+    let x = X::A;
+    let y = match x {
+        #[rustfmt::skip]
+        X::A => { 1i32$0 }
+        X::B => { 1i32 }
+        X::C => { 2i32 }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_keeps_pattern_formatting() {
+        check_assist(
+            merge_match_arms,
+            r#"
+struct Foo { a: i32, b: i32 }
+
+fn func(foo: Foo) -> i32 {
+    match foo {
+        Foo { a : 1, .. } => $00,
+        Foo {  b:2 ,.. } => 0,
+        _ => 1,
+    }
+}
+"#,
+            r#"
+struct Foo { a: i32, b: i32 }
+
+fn func(foo: Foo) -> i32 {
+    match foo {
+        Foo { a : 1, .. } | Foo {  b:2 ,.. } => 0,
+        _ => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_unit_ignores_trailing_semicolon() {
+        check_assist(
+            merge_match_arms,
+            r#"
+fn log() {}
+enum X { A, B, C }
+
+fn main(x: X) {
+    match x {
+        X::A => { log(); }$0
+        X::B => { log() }
+        X::C => {}
+    }
+}
+"#,
+            r#"
+fn log() {}
+enum X { A, B, C }
+
+fn main(x: X) {
+    match x {
+        X::A | X::B => { log(); },
+        X::C => {}
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_value_keeps_trailing_semicolon() {
+        check_assist_not_applicable(
+            merge_match_arms,
+            r#"
+fn fail() -> ! { loop {} }
+enum X { A, B, C }
+
+fn main(x: X) {
+    let n: i32 = match x {
+        X::A => { fail(); }$0
+        X::B => { fail() }
+        X::C => 1,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_and_sort_alternatives() {
+        check_assist_by_label(
+            merge_match_arms,
+            r#"
+enum X { A, B, C, D }
+
+fn main() {
+    let x = X::A;
+    let y = match x {
+        X::C | X::A => $01i32,
+        X::B => 1i32,
+        X::D => 2i32,
+    };
+}
+"#,
+            r#"
+enum X { A, B, C, D }
+
+fn main() {
+    let x = X::A;
+    let y = match x {
+        X::A | X::B | X::C => 1i32,
+        X::D => 2i32,
+    };
+}
+"#,
+            "Merge match arms and sort alternatives",
+        );
+    }
+
+    #[test]
+    fn merge_match_arms_does_not_sort_alternatives_with_bindings() {
+        check_assist(
+            merge_match_arms,
+            r#"
+enum X { A(i32), B(i32), C }
+
+fn main() {
+    let x = X::C;
+    let y = match x {
+        X::B(n) => $0n,
+        X::A(n) => n,
+        X::C => 0,
+    };
+}
+"#,
+            r#"
+enum X { A(i32), B(i32), C }
+
+fn main() {
+    let x = X::C;
+    let y = match x {
+        X::B(n) | X::A(n) => n,
+        X::C => 0,
+    };
+}
+"#,
+        );
+    }
 }
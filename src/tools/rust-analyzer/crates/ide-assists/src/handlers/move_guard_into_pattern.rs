@@ -0,0 +1,372 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{self, AstNode, BinaryOp, CmpOp, HasName, UnaryOp},
+    SyntaxKind::WHITESPACE,
+};
+
+use crate::{utils::is_pattern_value, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: move_guard_into_pattern
+//
+// Moves a match guard comparing a binding of the pattern against a literal or a constant
+// into the pattern itself.
+//
+// ```
+// # //- minicore: derive, eq
+// #[derive(PartialEq)]
+// enum Kind { Click, Scroll }
+// struct Event { kind: Kind, x: u32 }
+//
+// fn handle(event: Event) {
+//     match event {
+//         Event { kind, .. } $0if kind == Kind::Click => (),
+//         _ => (),
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(PartialEq)]
+// enum Kind { Click, Scroll }
+// struct Event { kind: Kind, x: u32 }
+//
+// fn handle(event: Event) {
+//     match event {
+//         Event { kind: Kind::Click, .. } => (),
+//         _ => (),
+//     }
+// }
+// ```
+pub(crate) fn move_guard_into_pattern(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let guard = match_arm.guard()?;
+    let pat = match_arm.pat()?;
+    let bin_expr = match guard.condition()? {
+        ast::Expr::BinExpr(it) => it,
+        _ => return None,
+    };
+    if bin_expr.op_kind()? != BinaryOp::CmpOp(CmpOp::Eq { negated: false }) {
+        return None;
+    }
+    let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+
+    let (ident_pat, compared, value) =
+        match (bound_ident_pat(ctx, &pat, &lhs), bound_ident_pat(ctx, &pat, &rhs)) {
+            (Some(ident_pat), None) => (ident_pat, lhs, rhs),
+            (None, Some(ident_pat)) => (ident_pat, rhs, lhs),
+            _ => return None,
+        };
+    let compared_ty = ctx.sema.type_of_expr(&compared)?.original;
+    if !is_pattern_value(&ctx.sema, &value, &compared_ty) {
+        cov_mark::hit!(move_guard_into_pattern_not_a_constant);
+        return None;
+    }
+
+    // The binding disappears from the pattern, so it must not be needed anywhere else.
+    let local = ctx.sema.to_def(&ident_pat)?;
+    let used_in_body = match_arm.expr()?.syntax().descendants().filter_map(ast::Path::cast).any(
+        |path| matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if it == local),
+    );
+    if used_in_body {
+        cov_mark::hit!(move_guard_into_pattern_binding_used);
+        return None;
+    }
+
+    // Shorthand record fields need to spell out the field name once the binding is gone.
+    let shorthand_field = ident_pat
+        .syntax()
+        .parent()
+        .and_then(ast::RecordPatField::cast)
+        .filter(|field| field.name_ref().is_none());
+    let (replaced, replacement) = match shorthand_field {
+        Some(field) => (field.syntax().text_range(), format!("{}: {value}", ident_pat.name()?)),
+        None => (ident_pat.syntax().text_range(), value.to_string()),
+    };
+
+    let target = guard.syntax().text_range();
+    acc.add(
+        AssistId("move_guard_into_pattern", AssistKind::RefactorRewrite),
+        "Move guard into pattern",
+        target,
+        |builder| {
+            builder.replace(replaced, replacement);
+            if let Some(element) = guard.syntax().prev_sibling_or_token() {
+                if element.kind() == WHITESPACE {
+                    builder.delete(element.text_range());
+                }
+            }
+            builder.delete(target);
+        },
+    )
+}
+
+/// Returns the plain binding of `pat` that `expr` refers to, looking through one
+/// dereference for bindings introduced by matching on a reference.
+fn bound_ident_pat(
+    ctx: &AssistContext<'_>,
+    pat: &ast::Pat,
+    expr: &ast::Expr,
+) -> Option<ast::IdentPat> {
+    let expr = match expr {
+        ast::Expr::PrefixExpr(it) if it.op_kind()? == UnaryOp::Deref => it.expr()?,
+        _ => expr.clone(),
+    };
+    let path = match expr {
+        ast::Expr::PathExpr(it) => it.path()?,
+        _ => return None,
+    };
+    let local = match ctx.sema.resolve_path(&path)? {
+        PathResolution::Local(it) => it,
+        _ => return None,
+    };
+    pat.syntax().descendants().filter_map(ast::IdentPat::cast).find(|ident_pat| {
+        ident_pat.ref_token().is_none()
+            && ident_pat.mut_token().is_none()
+            && ident_pat.pat().is_none()
+            && ctx.sema.to_def(ident_pat) == Some(local)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_struct_field_comparison() {
+        check_assist(
+            move_guard_into_pattern,
+            r#"
+enum Kind { Click, Scroll }
+struct Event { kind: Kind, x: u32 }
+
+fn handle(event: Event) {
+    match event {
+        Event { kind, x } if kind == Kind::Click$0 => x,
+        _ => 0,
+    };
+}
+"#,
+            r#"
+enum Kind { Click, Scroll }
+struct Event { kind: Kind, x: u32 }
+
+fn handle(event: Event) {
+    match event {
+        Event { kind: Kind::Click, x } => x,
+        _ => 0,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_literal_comparison_through_deref() {
+        check_assist(
+            move_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn handle(point: &Point) {
+    match point {
+        Point { x: px, .. } $0if 0 == *px => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn handle(point: &Point) {
+    match point {
+        Point { x: 0, .. } => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_const_comparison_in_tuple_struct() {
+        check_assist(
+            move_guard_into_pattern,
+            r#"
+const ORIGIN: u32 = 0;
+struct Offset(u32);
+
+fn handle(offset: Offset) {
+    match offset {
+        Offset(n) $0if n == ORIGIN => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+const ORIGIN: u32 = 0;
+struct Offset(u32);
+
+fn handle(offset: Offset) {
+    match offset {
+        Offset(ORIGIN) => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_constant_comparison() {
+        cov_mark::check!(move_guard_into_pattern_not_a_constant);
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn handle(point: Point, limit: u32) {
+    match point {
+        Point { x, .. } $0if x == limit => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_derived_const_comparison() {
+        check_assist(
+            move_guard_into_pattern,
+            r#"
+//- minicore: derive, eq
+#[derive(PartialEq, Eq)]
+struct Id(u32);
+const ROOT: Id = Id(0);
+struct Node { id: Id }
+
+fn handle(node: Node) {
+    match node {
+        Node { id } $0if id == ROOT => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+#[derive(PartialEq, Eq)]
+struct Id(u32);
+const ROOT: Id = Id(0);
+struct Node { id: Id }
+
+fn handle(node: Node) {
+    match node {
+        Node { id: ROOT } => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_const_without_structural_eq() {
+        cov_mark::check!(move_guard_into_pattern_not_a_constant);
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+//- minicore: eq
+struct Id(u32);
+impl PartialEq for Id {
+    fn eq(&self, other: &Id) -> bool { self.0 == other.0 }
+}
+const ROOT: Id = Id(0);
+struct Node { id: Id }
+
+fn handle(node: Node) {
+    match node {
+        Node { id } $0if id == ROOT => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_literal_of_other_type() {
+        cov_mark::check!(move_guard_into_pattern_not_a_constant);
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+//- minicore: eq
+struct Name;
+impl PartialEq<&str> for Name {
+    fn eq(&self, _: &&str) -> bool { true }
+}
+struct User { name: Name }
+
+fn handle(user: User) {
+    match user {
+        User { name } $0if name == "root" => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_float_literal() {
+        cov_mark::check!(move_guard_into_pattern_not_a_constant);
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+struct Point { x: f32 }
+
+fn handle(point: Point) {
+    match point {
+        Point { x } $0if x == 0.0 => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_binding_is_used() {
+        cov_mark::check!(move_guard_into_pattern_binding_used);
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn handle(point: Point) {
+    match point {
+        Point { x, .. } $0if x == 0 => x,
+        _ => 1,
+    };
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_guards() {
+        check_assist_not_applicable(
+            move_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn handle(point: Point) {
+    match point {
+        Point { x, .. } $0if x > 0 => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+}
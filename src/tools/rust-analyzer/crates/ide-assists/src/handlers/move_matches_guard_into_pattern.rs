@@ -0,0 +1,231 @@
+use hir::PathResolution;
+use syntax::{
+    ast::{self, AstNode, HasName},
+    NodeOrToken,
+    SyntaxKind::{COMMA, IDENT, WHITESPACE},
+    T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: move_matches_guard_into_pattern
+//
+// Replaces an arm binding the whole value and checking it with `matches!` in the guard with
+// an arm matching the pattern of the `matches!` directly.
+//
+// ```
+// struct Point { x: u32, y: u32 }
+//
+// fn on_axis(p: Point) -> bool {
+//     match p {
+//         p $0if matches!(p, Point { y: 0, .. }) => true,
+//         _ => false,
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Point { x: u32, y: u32 }
+//
+// fn on_axis(p: Point) -> bool {
+//     match p {
+//         Point { y: 0, .. } => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn move_matches_guard_into_pattern(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let guard = match_arm.guard()?;
+    let ast::Pat::IdentPat(ident_pat) = match_arm.pat()? else { return None };
+    if ident_pat.ref_token().is_some()
+        || ident_pat.mut_token().is_some()
+        || ident_pat.pat().is_some()
+    {
+        return None;
+    }
+    let name = ident_pat.name()?;
+    let ast::Expr::MacroExpr(macro_expr) = guard.condition()? else { return None };
+    let (scrutinee, pattern) = matches_args(&macro_expr.macro_call()?)?;
+    if scrutinee != name.text() {
+        return None;
+    }
+    // Values failing the guard fall through to the arms below, just like those not matching the
+    // pattern, so there has to be one of them.
+    match_arm.syntax().next_sibling().and_then(ast::MatchArm::cast)?;
+
+    let body = match_arm.expr()?;
+    let local = ctx.sema.to_def(&ident_pat)?;
+    let used_in_body = body.syntax().descendants().filter_map(ast::Path::cast).any(
+        |path| matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(it)) if it == local),
+    );
+    if used_in_body {
+        cov_mark::hit!(move_matches_guard_into_pattern_binding_used);
+        return None;
+    }
+    // Bindings of the pattern are visible in the body once it is part of the arm.
+    let shadows = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::NameRef::cast)
+        .any(|name_ref| pattern.bindings.iter().any(|it| name_ref.text() == it.as_str()));
+    if shadows {
+        cov_mark::hit!(move_matches_guard_into_pattern_shadows);
+        return None;
+    }
+
+    let target = guard.syntax().text_range();
+    acc.add(
+        AssistId("move_matches_guard_into_pattern", AssistKind::RefactorRewrite),
+        "Move `matches!` guard into pattern",
+        target,
+        |builder| {
+            builder.replace(ident_pat.syntax().text_range(), pattern.text);
+            if let Some(element) = guard.syntax().prev_sibling_or_token() {
+                if element.kind() == WHITESPACE {
+                    builder.delete(element.text_range());
+                }
+            }
+            builder.delete(target);
+        },
+    )
+}
+
+struct MatchesPattern {
+    text: String,
+    /// Identifiers that may be bindings rather than paths.
+    bindings: Vec<String>,
+}
+
+/// Splits `matches!(scrutinee, pattern)`, where the pattern has no guard of its own.
+fn matches_args(macro_call: &ast::MacroCall) -> Option<(String, MatchesPattern)> {
+    if macro_call.path()?.syntax().text() != "matches" {
+        return None;
+    }
+    let tt = macro_call.token_tree()?;
+    let mut elements = tt.syntax().children_with_tokens().collect::<Vec<_>>();
+    // Drop the delimiters.
+    elements.pop();
+    if !elements.is_empty() {
+        elements.remove(0);
+    }
+    let comma = elements.iter().position(|it| it.kind() == COMMA)?;
+    let (scrutinee, pattern) = (&elements[..comma], &elements[comma + 1..]);
+    if pattern.iter().any(|it| it.kind() == T![if]) {
+        return None;
+    }
+    let text = |elements: &[syntax::SyntaxElement]| {
+        elements.iter().map(|it| it.to_string()).collect::<String>().trim().to_owned()
+    };
+    let pattern_text = text(pattern);
+    let pattern_text = pattern_text.strip_suffix(',').unwrap_or(&pattern_text).trim_end();
+    if pattern_text.is_empty() {
+        return None;
+    }
+
+    let tokens = tt.syntax().descendants_with_tokens().filter_map(NodeOrToken::into_token);
+    let tokens = tokens.filter(|it| !it.kind().is_trivia()).collect::<Vec<_>>();
+    let bindings = tokens
+        .iter()
+        .enumerate()
+        .filter(|&(idx, token)| {
+            let prev = idx.checked_sub(1).map(|it| tokens[it].kind());
+            let next = tokens.get(idx + 1).map(|it| it.kind());
+            token.kind() == IDENT
+                && token.text().starts_with(|c: char| c.is_lowercase() || c == '_')
+                && prev != Some(T![::])
+                && !matches!(next, Some(T![::] | T!['('] | T!['{'] | T![!]))
+        })
+        .map(|(_, token)| token.text().to_owned())
+        .collect();
+    Some((text(scrutinee), MatchesPattern { text: pattern_text.to_owned(), bindings }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_record_pattern() {
+        check_assist(
+            move_matches_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn quadrant(p: Point) -> u32 {
+    match p {
+        q $0if matches!(q, Point { y: 0, .. } | Point { x: 0, .. },) => 0,
+        Point { x: 1, .. } => 1,
+        _ => 2,
+    }
+}
+"#,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn quadrant(p: Point) -> u32 {
+    match p {
+        Point { y: 0, .. } | Point { x: 0, .. } => 0,
+        Point { x: 1, .. } => 1,
+        _ => 2,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_binding_is_used() {
+        cov_mark::check!(move_matches_guard_into_pattern_binding_used);
+        check_assist_not_applicable(
+            move_matches_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn x(p: Point) -> u32 {
+    match p {
+        p $0if matches!(p, Point { y: 0, .. }) => p.x,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_pattern_shadows() {
+        cov_mark::check!(move_matches_guard_into_pattern_shadows);
+        check_assist_not_applicable(
+            move_matches_guard_into_pattern,
+            r#"
+struct Point { x: u32, y: u32 }
+
+fn x(p: Point, y: u32) -> u32 {
+    match p {
+        p $0if matches!(p, Point { x: 0, y }) => y,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_following_arm() {
+        check_assist_not_applicable(
+            move_matches_guard_into_pattern,
+            r#"
+fn zero(n: u32) -> bool {
+    match n {
+        m $0if matches!(m, 0) => true,
+    }
+}
+"#,
+        );
+    }
+}
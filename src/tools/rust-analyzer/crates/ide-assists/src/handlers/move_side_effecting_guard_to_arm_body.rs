@@ -0,0 +1,251 @@
+use syntax::{
+    ast::{self, edit::AstNodeEdit, make, AstNode},
+    SyntaxKind, TextRange,
+};
+
+use crate::{
+    handlers::split_match_guard::sees_bindings, AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: move_side_effecting_guard_to_arm_body
+//
+// Moves a match guard calling functions, which may have side effects, into the arm body. The
+// values the guard rejects are handled by the body of the next arm in the `else` branch, which
+// keeps the calls running for the same values as before.
+//
+// ```
+// # //- minicore: option
+// fn check(n: u8) -> bool { n > 2 }
+//
+// fn classify(v: Option<u8>) -> u8 {
+//     match v {
+//         Some(n) if $0check(n) => n,
+//         Some(n) => n + 1,
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn check(n: u8) -> bool { n > 2 }
+//
+// fn classify(v: Option<u8>) -> u8 {
+//     match v {
+//         Some(n) => if check(n) {
+//             n
+//         } else {
+//             n + 1
+//         },
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn move_side_effecting_guard_to_arm_body(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let guard = ctx.find_node_at_offset::<ast::MatchGuard>()?;
+    let arm = guard.syntax().parent().and_then(ast::MatchArm::cast)?;
+    let condition = guard.condition()?;
+    let calls = condition.syntax().descendants().any(|it| {
+        matches!(
+            it.kind(),
+            SyntaxKind::CALL_EXPR | SyntaxKind::METHOD_CALL_EXPR | SyntaxKind::MACRO_EXPR
+        )
+    });
+    if !calls || condition.syntax().descendants().any(|it| ast::LetExpr::can_cast(it.kind())) {
+        return None;
+    }
+
+    // The values the guard rejects go on to the next arm, which must take all of them.
+    let pat = arm.pat()?;
+    let next = arm.syntax().next_sibling().and_then(ast::MatchArm::cast);
+    let (next, merged) = match next.as_ref().and_then(|it| Some((it, it.pat()?))) {
+        Some((next, next_pat)) if next.guard().is_none() => match next_pat {
+            // The bindings of the pattern are in scope of the `else` branch now.
+            ast::Pat::WildcardPat(_) if !sees_bindings(ctx, &pat, next) => (next, false),
+            it if it.syntax().text() == pat.syntax().text() => (next, true),
+            _ => {
+                cov_mark::hit!(move_side_effecting_guard_to_arm_body_unpreservable);
+                return None;
+            }
+        },
+        _ => {
+            cov_mark::hit!(move_side_effecting_guard_to_arm_body_unpreservable);
+            return None;
+        }
+    };
+
+    let body = arm.expr()?;
+    let fallback = next.expr()?;
+    let target = guard.syntax().text_range();
+    acc.add(
+        AssistId("move_side_effecting_guard_to_arm_body", AssistKind::RefactorRewrite),
+        "Move guard to arm body",
+        target,
+        |builder| {
+            let indent = body.indent_level();
+            let if_expr = make::expr_if(
+                condition,
+                as_block(&body),
+                Some(ast::ElseBranch::Block(as_block(&fallback))),
+            )
+            .indent(indent);
+            let pat_end = pat.syntax().text_range().end();
+            builder.delete(TextRange::new(pat_end, target.end()));
+            builder.replace(body.syntax().text_range(), if_expr.to_string());
+            if merged {
+                let start = arm.syntax().text_range().end();
+                let end = match next.comma_token() {
+                    Some(it) => it.text_range().end(),
+                    None => next.syntax().text_range().end(),
+                };
+                builder.delete(TextRange::new(start, end));
+            }
+        },
+    )
+}
+
+fn as_block(expr: &ast::Expr) -> ast::BlockExpr {
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => block.reset_indent(),
+        _ => make::block_expr(None, Some(expr.reset_indent())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_guard_merging_next_arm() {
+        check_assist(
+            move_side_effecting_guard_to_arm_body,
+            r#"
+//- minicore: option
+fn log_and_check(n: u8) -> bool { n > 2 }
+
+fn classify(v: Option<u8>) -> u8 {
+    match v {
+        None => 0,
+        Some(n) if log_$0and_check(n) => {
+            let m = n * 2;
+            m
+        }
+        Some(n) => n + 1,
+    }
+}
+"#,
+            r#"
+fn log_and_check(n: u8) -> bool { n > 2 }
+
+fn classify(v: Option<u8>) -> u8 {
+    match v {
+        None => 0,
+        Some(n) => if log_and_check(n) {
+            let m = n * 2;
+            m
+        } else {
+            n + 1
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn move_guard_before_wildcard() {
+        check_assist(
+            move_side_effecting_guard_to_arm_body,
+            r#"
+struct Cache;
+impl Cache {
+    fn remember(&mut self, key: u32) -> bool { true }
+}
+
+fn lookup(key: u32, cache: &mut Cache) -> &'static str {
+    match key {
+        0 => "zero",
+        k if cache$0.remember(k) => "new",
+        _ => "seen",
+    }
+}
+"#,
+            r#"
+struct Cache;
+impl Cache {
+    fn remember(&mut self, key: u32) -> bool { true }
+}
+
+fn lookup(key: u32, cache: &mut Cache) -> &'static str {
+    match key {
+        0 => "zero",
+        k => if cache.remember(k) {
+            "new"
+        } else {
+            "seen"
+        },
+        _ => "seen",
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_before_other_pattern() {
+        cov_mark::check!(move_side_effecting_guard_to_arm_body_unpreservable);
+        check_assist_not_applicable(
+            move_side_effecting_guard_to_arm_body,
+            r#"
+//- minicore: option
+fn check(n: u8) -> bool { true }
+
+fn classify(v: Option<u8>) -> u8 {
+    match v {
+        Some(n) if che$0ck(n) => n,
+        Some(1) => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_pattern_shadows_fallback() {
+        cov_mark::check!(move_side_effecting_guard_to_arm_body_unpreservable);
+        check_assist_not_applicable(
+            move_side_effecting_guard_to_arm_body,
+            r#"
+//- minicore: option
+fn check(n: u8) -> bool { true }
+
+fn pick(v: Option<u8>, n: u8) -> u8 {
+    match v {
+        Some(n) if che$0ck(n) => n,
+        _ => n,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_pure_guard() {
+        check_assist_not_applicable(
+            move_side_effecting_guard_to_arm_body,
+            r#"
+fn classify(v: u8) -> u8 {
+    match v {
+        n if n > $02 => n,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
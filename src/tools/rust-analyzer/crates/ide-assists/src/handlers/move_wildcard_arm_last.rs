@@ -0,0 +1,133 @@
+use syntax::ast::{self, edit_in_place::Removable, AstNode};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: move_wildcard_arm_last
+//
+// Moves a `_` arm that is not the last arm of a match to the end. The arms that followed it
+// were unreachable and are matched after the move, which the label points out.
+//
+// ```
+// enum Dir { Up, Down, Left, Right }
+//
+// fn vertical(dir: Dir) -> bool {
+//     match dir {
+//         Dir::Up => true,
+//         $0_ => false,
+//         Dir::Down => true,
+//     }
+// }
+// ```
+// ->
+// ```
+// enum Dir { Up, Down, Left, Right }
+//
+// fn vertical(dir: Dir) -> bool {
+//     match dir {
+//         Dir::Up => true,
+//         Dir::Down => true,
+//         _ => false,
+//     }
+// }
+// ```
+pub(crate) fn move_wildcard_arm_last(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    if !matches!(arm.pat()?, ast::Pat::WildcardPat(_)) || arm.guard().is_some() {
+        return None;
+    }
+    let arm_list = arm.syntax().parent().and_then(ast::MatchArmList::cast)?;
+    let shadowed = arm_list.arms().skip_while(|it| it != &arm).skip(1).count();
+    if shadowed == 0 {
+        cov_mark::hit!(move_wildcard_arm_last_already_last);
+        return None;
+    }
+
+    // Every arm after the wildcard is dead code now and starts matching once it has moved.
+    let label = match shadowed {
+        1 => "Move wildcard arm last (makes 1 arm reachable)".to_string(),
+        n => format!("Move wildcard arm last (makes {n} arms reachable)"),
+    };
+    acc.add(
+        AssistId("move_wildcard_arm_last", AssistKind::RefactorRewrite),
+        label,
+        arm.syntax().text_range(),
+        |builder| {
+            let arm_list = builder.make_mut(arm_list);
+            let arm = builder.make_mut(arm);
+            let moved = arm.clone_subtree().clone_for_update();
+            arm.remove();
+            arm_list.add_arm(moved);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn move_mid_match_wildcard() {
+        check_assist_by_label(
+            move_wildcard_arm_last,
+            r#"
+fn describe(n: u32) -> &'static str {
+    match n {
+        0 => "zero",
+        _$0 => {
+            "many"
+        }
+        1 => "one",
+        2 | 3 => "few"
+    }
+}
+"#,
+            r#"
+fn describe(n: u32) -> &'static str {
+    match n {
+        0 => "zero",
+        1 => "one",
+        2 | 3 => "few",
+        _ => {
+            "many"
+        }
+    }
+}
+"#,
+            "Move wildcard arm last (makes 2 arms reachable)",
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_already_last() {
+        cov_mark::check!(move_wildcard_arm_last_already_last);
+        check_assist_not_applicable(
+            move_wildcard_arm_last,
+            r#"
+fn describe(n: u32) -> &'static str {
+    match n {
+        0 => "zero",
+        $0_ => "many",
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_guarded_wildcard() {
+        check_assist_not_applicable(
+            move_wildcard_arm_last,
+            r#"
+fn describe(n: u32) -> &'static str {
+    match n {
+        $0_ if n > 9 => "many",
+        0 => "zero",
+        _ => "some",
+    }
+}
+"#,
+        );
+    }
+}
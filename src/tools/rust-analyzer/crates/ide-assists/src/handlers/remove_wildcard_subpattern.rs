@@ -0,0 +1,139 @@
+use syntax::{
+    ast::{self, AstNode, HasName},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: remove_wildcard_subpattern
+//
+// Removes the redundant `@ _` from bindings, in all arms of the enclosing `match`.
+//
+// ```
+// fn show(n: u32) -> u32 {
+//     match n {
+//         0 => 1,
+//         x$0 @ _ => x * 2,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn show(n: u32) -> u32 {
+//     match n {
+//         0 => 1,
+//         x => x * 2,
+//     }
+// }
+// ```
+pub(crate) fn remove_wildcard_subpattern(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let ident_pat = ctx.find_node_at_offset::<ast::IdentPat>()?;
+    let redundant = redundant_subpattern(&ident_pat)?;
+    let ranges = match ident_pat.syntax().ancestors().find_map(ast::MatchExpr::cast) {
+        Some(match_expr) => match_expr
+            .match_arm_list()?
+            .syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .filter_map(|it| redundant_subpattern(&it))
+            .collect::<Vec<_>>(),
+        None => vec![redundant],
+    };
+
+    let target = ident_pat.syntax().text_range();
+    acc.add(
+        AssistId("remove_wildcard_subpattern", AssistKind::RefactorRewrite),
+        "Remove redundant `@ _`",
+        target,
+        |builder| {
+            for range in ranges {
+                builder.delete(range);
+            }
+        },
+    )
+}
+
+/// Returns the range of the `@ _` following the name of the binding.
+fn redundant_subpattern(ident_pat: &ast::IdentPat) -> Option<TextRange> {
+    ident_pat.at_token()?;
+    let ast::Pat::WildcardPat(wildcard) = ident_pat.pat()? else { return None };
+    let name = ident_pat.name()?;
+    Some(TextRange::new(name.syntax().text_range().end(), wildcard.syntax().text_range().end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn remove_in_all_arms() {
+        check_assist(
+            remove_wildcard_subpattern,
+            r#"
+//- minicore: option
+fn pick(a: Option<u8>, b: u8) -> u8 {
+    match (a, b) {
+        (Some(x @ _), y@_) => x + y,
+        (None, ref y$0 @ _) => *y,
+    }
+}
+"#,
+            r#"
+fn pick(a: Option<u8>, b: u8) -> u8 {
+    match (a, b) {
+        (Some(x), y) => x + y,
+        (None, ref y) => *y,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn remove_outside_of_match() {
+        check_assist(
+            remove_wildcard_subpattern,
+            r#"
+fn f() {
+    let x$0 @ _ = 1;
+}
+"#,
+            r#"
+fn f() {
+    let x = 1;
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_plain_binding() {
+        check_assist_not_applicable(
+            remove_wildcard_subpattern,
+            r#"
+fn show(n: u32) -> u32 {
+    match n {
+        x$0 => x,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_other_subpattern() {
+        check_assist_not_applicable(
+            remove_wildcard_subpattern,
+            r#"
+fn show(n: u32) -> u32 {
+    match n {
+        x$0 @ 1..=9 => x,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,224 @@
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, AstNode};
+
+use crate::{
+    utils::{is_unwrapping, needs_parens_as_receiver, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: replace_infallible_match_with_unwrap
+//
+// Replaces a match on a `Result` whose error type has no values, which makes the `Err` arm
+// unreachable, with `unwrap`. The error type has to be `Debug`, which `unwrap` needs.
+//
+// ```
+// # //- minicore: result, infallible, fmt
+// use core::convert::Infallible;
+//
+// fn value(res: Result<u32, Infallible>) -> u32 {
+//     $0match res {
+//         Ok(v) => v,
+//         Err(e) => match e {},
+//     }
+// }
+// ```
+// ->
+// ```
+// use core::convert::Infallible;
+//
+// fn value(res: Result<u32, Infallible>) -> u32 {
+//     res.unwrap()
+// }
+// ```
+pub(crate) fn replace_infallible_match_with_unwrap(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(match_expr.syntax())?.krate());
+    let result_enum = famous_defs.core_result_Result()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    if !matches!(scrutinee_ty.as_adt(), Some(hir::Adt::Enum(it)) if it == result_enum) {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = arms.as_slice() else { return None };
+    if first.guard().is_some() || second.guard().is_some() {
+        return None;
+    }
+    let is_ok = |arm: &ast::MatchArm| {
+        let [variant]: [hir::Variant; 1] = variants(ctx, &arm.pat()?)?.try_into().ok()?;
+        Some(variant.name(ctx.db()).to_smol_str() == "Ok")
+    };
+    let ok_arm = match (is_ok(first)?, is_ok(second)?) {
+        (true, false) => first,
+        (false, true) => second,
+        _ => return None,
+    };
+    if !is_unwrapping(ok_arm)? {
+        return None;
+    }
+
+    let err_ty = scrutinee_ty.type_arguments().nth(1)?;
+    let is_uninhabited = err_ty.is_never()
+        || matches!(err_ty.as_adt(), Some(hir::Adt::Enum(it)) if it.variants(ctx.db()).is_empty());
+    if !is_uninhabited {
+        cov_mark::hit!(replace_infallible_match_with_unwrap_inhabited);
+        return None;
+    }
+    // `core` implements `Debug` for `!`.
+    let is_debug = err_ty.is_never()
+        || famous_defs.core_fmt_Debug().map_or(false, |it| err_ty.impls_trait(ctx.db(), it, &[]));
+    if !is_debug {
+        cov_mark::hit!(replace_infallible_match_with_unwrap_not_debug);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("replace_infallible_match_with_unwrap", AssistKind::RefactorRewrite),
+        "Replace match with `unwrap`",
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(target, format!("{receiver}.unwrap()"));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn replace_infallible_result_match() {
+        check_assist(
+            replace_infallible_match_with_unwrap,
+            r#"
+//- minicore: result, infallible, fmt
+use core::convert::Infallible;
+
+struct Parser;
+impl Parser {
+    fn parse(&self) -> Result<u8, Infallible> { loop {} }
+}
+
+fn first(p: &Parser) -> u8 {
+    match$0 p.parse() {
+        Err(never) => match never {},
+        Ok(byte) => byte,
+    }
+}
+"#,
+            r#"
+use core::convert::Infallible;
+
+struct Parser;
+impl Parser {
+    fn parse(&self) -> Result<u8, Infallible> { loop {} }
+}
+
+fn first(p: &Parser) -> u8 {
+    p.parse().unwrap()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_never_result_match() {
+        check_assist(
+            replace_infallible_match_with_unwrap,
+            r#"
+//- minicore: result
+fn value(res: Result<u32, !>) -> u32 {
+    match$0 res {
+        Ok(v) => v,
+        Err(_) => unreachable!(),
+    }
+}
+"#,
+            r#"
+fn value(res: Result<u32, !>) -> u32 {
+    res.unwrap()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_empty_enum_result_match() {
+        check_assist(
+            replace_infallible_match_with_unwrap,
+            r#"
+//- minicore: result, fmt
+enum Void {}
+impl core::fmt::Debug for Void {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { match *self {} }
+}
+
+fn value(res: Result<u32, Void>) -> u32 {
+    match$0 res {
+        Ok(v) => v,
+        Err(void) => match void {},
+    }
+}
+"#,
+            r#"
+enum Void {}
+impl core::fmt::Debug for Void {
+    fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> core::fmt::Result { match *self {} }
+}
+
+fn value(res: Result<u32, Void>) -> u32 {
+    res.unwrap()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_error_that_is_not_debug() {
+        cov_mark::check!(replace_infallible_match_with_unwrap_not_debug);
+        check_assist_not_applicable(
+            replace_infallible_match_with_unwrap,
+            r#"
+//- minicore: result, fmt
+enum Void {}
+
+fn value(res: Result<u32, Void>) -> u32 {
+    match$0 res {
+        Ok(v) => v,
+        Err(void) => match void {},
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_inhabited_error() {
+        cov_mark::check!(replace_infallible_match_with_unwrap_inhabited);
+        check_assist_not_applicable(
+            replace_infallible_match_with_unwrap,
+            r#"
+//- minicore: result
+enum Error { Io }
+
+fn value(res: Result<u32, Error>) -> u32 {
+    match$0 res {
+        Ok(v) => v,
+        Err(_) => 0,
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use hir::PathResolution;
+use ide_db::famous_defs::FamousDefs;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode, HasArgList, HasAttrs, HasGenericParams, HasName},
+    SyntaxKind, TextRange, TextSize,
+};
+
+use crate::{
+    handlers::generate_derive::derive_insertion_offset,
+    utils::{arm_value, variants},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: replace_match_impl_with_derive
+//
+// Replaces a manual `PartialEq` or `Clone` impl of an enum, whose match does exactly what the
+// derive of the trait would, with `#[derive]`.
+//
+// ```
+// # //- minicore: eq, derive
+// enum Shape { Circle(u32), Empty }
+//
+// impl PartialEq for Shape {
+//     fn eq(&self, other: &Self) -> bool {
+//         $0match (self, other) {
+//             (Shape::Circle(a), Shape::Circle(b)) => a == b,
+//             (Shape::Empty, Shape::Empty) => true,
+//             _ => false,
+//         }
+//     }
+// }
+// ```
+// ->
+// ```
+// #[derive(PartialEq)]
+// enum Shape { Circle(u32), Empty }
+// ```
+pub(crate) fn replace_match_impl_with_derive(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let stmt_list = match_expr.syntax().parent().and_then(ast::StmtList::cast)?;
+    if stmt_list.statements().next().is_some() {
+        return None;
+    }
+    let fn_ = stmt_list.syntax().parent()?.parent().and_then(ast::Fn::cast)?;
+    let impl_ = fn_.syntax().parent()?.parent().and_then(ast::Impl::cast)?;
+    // The derive generates exactly one method and no bounds of its own choosing.
+    if impl_.assoc_item_list()?.assoc_items().count() != 1
+        || impl_.excl_token().is_some()
+        || impl_.generic_param_list().is_some()
+        || impl_.where_clause().is_some()
+    {
+        return None;
+    }
+
+    let impl_def = ctx.sema.to_def(&impl_)?;
+    let trait_ref = impl_def.trait_ref(ctx.db())?;
+    let enum_ = match trait_ref.self_ty().as_adt()? {
+        hir::Adt::Enum(it) => it,
+        _ => return None,
+    };
+    let famous_defs = FamousDefs(&ctx.sema, impl_def.module(ctx.db()).krate());
+    let trait_ = trait_ref.trait_();
+    let derived = if Some(trait_) == famous_defs.core_cmp_PartialEq() {
+        if trait_ref.get_type_argument(1)? != trait_ref.self_ty() {
+            return None;
+        }
+        derives_partial_eq(ctx, &fn_, &match_expr, enum_)
+    } else if Some(trait_) == famous_defs.core_clone_Clone() {
+        derives_clone(ctx, &fn_, &match_expr, enum_)
+    } else {
+        return None;
+    };
+    if derived.is_none() {
+        cov_mark::hit!(replace_match_impl_with_derive_not_derive_shape);
+        return None;
+    }
+
+    let enum_src = ctx.sema.source(enum_)?;
+    if enum_src.file_id.is_macro() || enum_src.file_id.original_file(ctx.db()) != ctx.file_id() {
+        return None;
+    }
+    let adt = ast::Adt::Enum(enum_src.value);
+    if adt.generic_param_list().is_some() {
+        return None;
+    }
+    let trait_name = trait_.name(ctx.db());
+    let derive = adt
+        .attrs()
+        .filter_map(|it| it.as_simple_call())
+        .find(|(name, _)| name == "derive")
+        .map(|(_, tt)| tt);
+    let (offset, derive) = match derive {
+        Some(tt) => {
+            let empty = tt.syntax().children_with_tokens().count() == 2;
+            let offset = tt.syntax().text_range().end() - TextSize::of(')');
+            (offset, if empty { trait_name.to_string() } else { format!(", {trait_name}") })
+        }
+        None => {
+            let indent = IndentLevel::from_node(adt.syntax());
+            (derive_insertion_offset(&adt)?, format!("#[derive({trait_name})]\n{indent}"))
+        }
+    };
+
+    let impl_range = impl_.syntax().text_range();
+    let start = match impl_.syntax().prev_sibling_or_token() {
+        Some(it) if it.kind() == SyntaxKind::WHITESPACE => it.text_range().start(),
+        _ => impl_range.start(),
+    };
+    acc.add(
+        AssistId("replace_match_impl_with_derive", AssistKind::RefactorRewrite),
+        format!("Replace impl with `#[derive({trait_name})]`"),
+        match_expr.syntax().text_range(),
+        |builder| {
+            builder.insert(offset, derive);
+            builder.delete(TextRange::new(start, impl_range.end()));
+        },
+    )
+}
+
+/// Checks that `eq` matches `(self, other)` like the derive does: the values are equal when they
+/// are the same variant and their fields are pairwise equal, compared in declaration order.
+fn derives_partial_eq(
+    ctx: &AssistContext<'_>,
+    fn_: &ast::Fn,
+    match_expr: &ast::MatchExpr,
+    enum_: hir::Enum,
+) -> Option<()> {
+    let params = fn_.param_list()?.params().collect::<Vec<_>>();
+    let [other] = params.as_slice() else { return None };
+    let ast::Pat::IdentPat(other) = other.pat()? else { return None };
+    let ast::Expr::TupleExpr(scrutinee) = match_expr.expr()? else { return None };
+    let [lhs, rhs]: [ast::Expr; 2] = scrutinee.fields().collect::<Vec<_>>().try_into().ok()?;
+    if fn_.name()?.text() != "eq"
+        || lhs.syntax().text() != "self"
+        || rhs.syntax().text() != other.name()?.text().as_str()
+    {
+        return None;
+    }
+
+    let count = enum_.variants(ctx.db()).len();
+    let mut seen = Vec::new();
+    let mut arms = match_expr.match_arm_list()?.arms().peekable();
+    while let Some(arm) = arms.next() {
+        if arm.guard().is_some() || has_statements(&arm) {
+            return None;
+        }
+        let body = arm_value(arm.expr()?)?;
+        let ast::Pat::TuplePat(pat) = arm.pat()? else {
+            // Values of different variants are never equal.
+            let last = arms.peek().is_none();
+            match arm.pat()? {
+                ast::Pat::WildcardPat(_) if last && is_bool(&body, false) => continue,
+                _ => return None,
+            }
+        };
+        let [lhs, rhs]: [ast::Pat; 2] = pat.fields().collect::<Vec<_>>().try_into().ok()?;
+        let (variant, lhs) = field_bindings(ctx, &lhs)?;
+        let (rhs_variant, rhs) = field_bindings(ctx, &rhs)?;
+        if variant != rhs_variant || seen.contains(&variant) {
+            return None;
+        }
+        seen.push(variant);
+
+        let comparisons = conjuncts(body.clone());
+        let fields_compared = comparisons.len() == lhs.len()
+            && comparisons.iter().zip(lhs.iter().zip(&rhs)).all(|(cmp, (lhs, rhs))| {
+                let ast::Expr::BinExpr(cmp) = cmp else { return false };
+                let (Some(l), Some(r)) = (cmp.lhs(), cmp.rhs()) else { return false };
+                cmp.op_kind() == Some(ast::BinaryOp::CmpOp(ast::CmpOp::Eq { negated: false }))
+                    && l.syntax().text() == lhs.text().as_str()
+                    && r.syntax().text() == rhs.text().as_str()
+            });
+        if !(lhs.is_empty() && is_bool(&body, true) || fields_compared) {
+            return None;
+        }
+    }
+    (seen.len() == count).then_some(())
+}
+
+/// Checks that `clone` matches `self` like the derive does: every variant is rebuilt from the
+/// clones of its fields, in declaration order.
+fn derives_clone(
+    ctx: &AssistContext<'_>,
+    fn_: &ast::Fn,
+    match_expr: &ast::MatchExpr,
+    enum_: hir::Enum,
+) -> Option<()> {
+    if fn_.name()?.text() != "clone"
+        || fn_.param_list()?.params().next().is_some()
+        || match_expr.expr()?.syntax().text() != "self"
+    {
+        return None;
+    }
+
+    let count = enum_.variants(ctx.db()).len();
+    let mut seen = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        if arm.guard().is_some() || has_statements(&arm) {
+            return None;
+        }
+        let (variant, bindings) = field_bindings(ctx, &arm.pat()?)?;
+        if seen.contains(&variant) {
+            return None;
+        }
+        seen.push(variant);
+
+        let is_variant = |path: ast::Path| {
+            matches!(
+                ctx.sema.resolve_path(&path),
+                Some(PathResolution::Def(hir::ModuleDef::Variant(it))) if it == variant
+            )
+        };
+        let values = match arm_value(arm.expr()?)? {
+            ast::Expr::PathExpr(it) if is_variant(it.path()?) => Vec::new(),
+            ast::Expr::CallExpr(it) => {
+                let ast::Expr::PathExpr(callee) = it.expr()? else { return None };
+                if !is_variant(callee.path()?) {
+                    return None;
+                }
+                it.arg_list()?.args().collect()
+            }
+            ast::Expr::RecordExpr(it) => {
+                let list = it.record_expr_field_list()?;
+                if !is_variant(it.path()?) || list.dotdot_token().is_some() {
+                    return None;
+                }
+                let fields = variant.fields(ctx.db());
+                let mut values = Vec::new();
+                for (field, expr_field) in fields.iter().zip(list.fields()) {
+                    if expr_field.field_name()?.text()
+                        != field.name(ctx.db()).to_smol_str().as_str()
+                    {
+                        return None;
+                    }
+                    values.push(expr_field.expr()?);
+                }
+                values
+            }
+            _ => return None,
+        };
+        let cloned = values.len() == bindings.len()
+            && values.iter().zip(&bindings).all(|(value, binding)| match value {
+                ast::Expr::MethodCallExpr(call) => {
+                    call.name_ref().map_or(false, |it| it.text() == "clone")
+                        && call.arg_list().map_or(false, |it| it.args().next().is_none())
+                        && call
+                            .receiver()
+                            .map_or(false, |it| it.syntax().text() == binding.text().as_str())
+                }
+                _ => false,
+            });
+        if !cloned {
+            return None;
+        }
+    }
+    (seen.len() == count).then_some(())
+}
+
+/// Returns the variant `pat` matches and the names it binds its fields to, in declaration order,
+/// when it binds every field to a plain name.
+fn field_bindings(
+    ctx: &AssistContext<'_>,
+    pat: &ast::Pat,
+) -> Option<(hir::Variant, Vec<ast::Name>)> {
+    let [variant]: [hir::Variant; 1] = variants(ctx, pat)?.try_into().ok()?;
+    let fields = variant.fields(ctx.db());
+    let binding = |pat: ast::Pat| match pat {
+        ast::Pat::IdentPat(it)
+            if it.ref_token().is_none()
+                && it.mut_token().is_none()
+                && it.pat().is_none()
+                && ctx.sema.resolve_bind_pat_to_const(&it).is_none() =>
+        {
+            it.name()
+        }
+        _ => None,
+    };
+    let names = match pat {
+        ast::Pat::PathPat(_) | ast::Pat::IdentPat(_) => Vec::new(),
+        ast::Pat::TupleStructPat(it) => it.fields().map(binding).collect::<Option<_>>()?,
+        ast::Pat::RecordPat(it) => {
+            let list = it.record_pat_field_list()?;
+            if list.rest_pat().is_some() {
+                return None;
+            }
+            let pats = list
+                .fields()
+                .map(|it| Some((it.field_name()?.to_string(), it.pat()?)))
+                .collect::<Option<Vec<_>>>()?;
+            fields
+                .iter()
+                .map(|field| {
+                    let name = field.name(ctx.db()).to_string();
+                    let (_, pat) = pats.iter().find(|(it, _)| *it == name)?;
+                    binding(pat.clone())
+                })
+                .collect::<Option<_>>()?
+        }
+        _ => return None,
+    };
+    (names.len() == fields.len()).then_some((variant, names))
+}
+
+/// Checks whether the body of `arm` does anything besides evaluating to a value.
+fn has_statements(arm: &ast::MatchArm) -> bool {
+    arm.expr()
+        .map_or(false, |it| it.syntax().descendants().any(|it| ast::Stmt::can_cast(it.kind())))
+}
+
+/// Splits `a && b && c` into its operands.
+fn conjuncts(expr: ast::Expr) -> Vec<ast::Expr> {
+    match &expr {
+        ast::Expr::BinExpr(bin)
+            if bin.op_kind() == Some(ast::BinaryOp::LogicOp(ast::LogicOp::And)) =>
+        {
+            let mut operands = bin.lhs().map(conjuncts).unwrap_or_default();
+            operands.extend(bin.rhs());
+            operands
+        }
+        _ => vec![expr],
+    }
+}
+
+fn is_bool(expr: &ast::Expr, value: bool) -> bool {
+    match expr {
+        ast::Expr::Literal(it) => it.kind() == ast::LiteralKind::Bool(value),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn replace_partial_eq_adding_to_existing_derive() {
+        check_assist(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: eq, clone, derive
+/// A token of the input.
+#[derive(Clone)]
+enum Token {
+    Number(u64),
+    Op { symbol: char, binary: bool },
+    Eof,
+}
+
+impl PartialEq for Token {
+    fn eq(&self, other: &Token) -> bool {
+        match$0 (self, other) {
+            (Token::Op { binary: b1, symbol: s1 }, Token::Op { symbol: s2, binary: b2 }) => {
+                s1 == s2 && b1 == b2
+            }
+            (Token::Number(l), Token::Number(r)) => l == r,
+            (Token::Eof, Token::Eof) => true,
+            _ => false,
+        }
+    }
+}
+
+fn main() {}
+"#,
+            r#"
+/// A token of the input.
+#[derive(Clone, PartialEq)]
+enum Token {
+    Number(u64),
+    Op { symbol: char, binary: bool },
+    Eof,
+}
+
+fn main() {}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_clone() {
+        check_assist(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: clone, derive
+mod ast {
+    pub enum Node {
+        Leaf(String),
+        Pair { left: Box<Node>, right: Box<Node> },
+    }
+
+    impl Clone for Node {
+        fn clone(&self) -> Self {
+            $0match self {
+                Node::Leaf(name) => Node::Leaf(name.clone()),
+                Node::Pair { left, right } => Node::Pair { left: left.clone(), right: right.clone() },
+            }
+        }
+    }
+}
+"#,
+            r#"
+mod ast {
+    #[derive(Clone)]
+    pub enum Node {
+        Leaf(String),
+        Pair { left: Box<Node>, right: Box<Node> },
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_fields_are_skipped() {
+        cov_mark::check!(replace_match_impl_with_derive_not_derive_shape);
+        check_assist_not_applicable(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: eq, derive
+enum Entry { Named(u32, &'static str), Anonymous }
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        match$0 (self, other) {
+            (Entry::Named(a, _), Entry::Named(b, _)) => a == b,
+            (Entry::Anonymous, Entry::Anonymous) => true,
+            _ => false,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_arms_with_statements() {
+        check_assist_not_applicable(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: eq, derive
+enum Entry { Named(u32), Anonymous }
+fn log() {}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        match$0 (self, other) {
+            (Entry::Named(a), Entry::Named(b)) => a == b,
+            (Entry::Anonymous, Entry::Anonymous) => {
+                log();
+                true
+            }
+            _ => false,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_fields_compared_out_of_order() {
+        cov_mark::check!(replace_match_impl_with_derive_not_derive_shape);
+        check_assist_not_applicable(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: eq, derive
+enum Range { Span(u32, u32), Empty }
+
+impl PartialEq for Range {
+    fn eq(&self, other: &Self) -> bool {
+        match$0 (self, other) {
+            (Range::Span(a1, a2), Range::Span(b1, b2)) => a2 == b2 && a1 == b1,
+            (Range::Empty, Range::Empty) => true,
+            _ => false,
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_equal_variants_of_different_kind() {
+        cov_mark::check!(replace_match_impl_with_derive_not_derive_shape);
+        check_assist_not_applicable(
+            replace_match_impl_with_derive,
+            r#"
+//- minicore: eq, derive
+enum Size { Zero, Empty, Some(u32) }
+
+impl PartialEq for Size {
+    fn eq(&self, other: &Self) -> bool {
+        match$0 (self, other) {
+            (Size::Some(a), Size::Some(b)) => a == b,
+            (Size::Zero, Size::Zero) => true,
+            (Size::Empty, Size::Empty) => true,
+            _ => matches!((self, other), (Size::Zero | Size::Empty, Size::Zero | Size::Empty)),
+        }
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,334 @@
+use hir::{AsAssocItem, PathResolution};
+use ide_db::famous_defs::FamousDefs;
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{
+    utils::{is_unwrapping, needs_parens_as_receiver, variant_name},
+    AssistContext, AssistId, AssistKind, Assists,
+};
+
+// Assist: replace_match_with_unwrap_or_default
+//
+// Replaces a match on an `Option` returning the default value of the wrapped type for `None`
+// with `unwrap_or_default`.
+//
+// ```
+// # //- minicore: option
+// fn width(w: Option<u32>) -> u32 {
+//     $0match w {
+//         Some(w) => w,
+//         None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn width(w: Option<u32>) -> u32 {
+//     w.unwrap_or_default()
+// }
+// ```
+pub(crate) fn replace_match_with_unwrap_or_default(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scrutinee = match_expr.expr()?;
+    let famous_defs = FamousDefs(&ctx.sema, ctx.sema.scope(match_expr.syntax())?.krate());
+    let option_enum = famous_defs.core_option_Option()?;
+    let scrutinee_ty = ctx.sema.type_of_expr(&scrutinee)?.original;
+    if !matches!(scrutinee_ty.as_adt(), Some(hir::Adt::Enum(it)) if it == option_enum) {
+        return None;
+    }
+
+    let arms = match_expr.match_arm_list()?.arms().collect::<Vec<_>>();
+    let [first, second] = arms.as_slice() else { return None };
+    if first.guard().is_some() || second.guard().is_some() {
+        return None;
+    }
+    let (some_arm, none_arm) = match (first.pat()?, second.pat()?) {
+        (ast::Pat::TupleStructPat(_), _) => (first, second),
+        (_, ast::Pat::TupleStructPat(_)) => (second, first),
+        _ => return None,
+    };
+    if variant_name(ctx, &some_arm.pat()?)? != "Some"
+        || !is_unwrapping(some_arm)?
+        || variant_name(ctx, &none_arm.pat()?)? != "None"
+    {
+        return None;
+    }
+    let default = none_arm.expr()?;
+    let ty = ctx.sema.type_of_expr(&default)?.original;
+    if !is_default_value(ctx, &famous_defs, &ty, &default) {
+        cov_mark::hit!(replace_match_with_unwrap_or_default_not_default);
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("replace_match_with_unwrap_or_default", AssistKind::RefactorRewrite),
+        "Replace match with `unwrap_or_default`",
+        target,
+        |builder| {
+            let receiver = match needs_parens_as_receiver(&scrutinee) {
+                true => format!("({scrutinee})"),
+                false => scrutinee.to_string(),
+            };
+            builder.replace(target, format!("{receiver}.unwrap_or_default()"));
+        },
+    )
+}
+
+/// Collections whose `new` creates the same empty collection as `Default::default`.
+fn empty_by_new(famous_defs: &FamousDefs<'_, '_>) -> Vec<hir::Struct> {
+    [
+        famous_defs.alloc_string_String(),
+        famous_defs.alloc_vec_Vec(),
+        famous_defs.alloc_collections_VecDeque(),
+        famous_defs.alloc_collections_LinkedList(),
+        famous_defs.alloc_collections_BinaryHeap(),
+        famous_defs.std_collections_HashMap(),
+        famous_defs.std_collections_HashSet(),
+        famous_defs.alloc_collections_BTreeMap(),
+        famous_defs.alloc_collections_BTreeSet(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Checks whether `expr` is written out to be what `Default::default` returns for `ty`.
+fn is_default_value(
+    ctx: &AssistContext<'_>,
+    famous_defs: &FamousDefs<'_, '_>,
+    ty: &hir::Type,
+    expr: &ast::Expr,
+) -> bool {
+    match expr {
+        ast::Expr::Literal(lit) => match lit.kind() {
+            ast::LiteralKind::IntNumber(it) => ty.is_int_or_uint() && it.value() == Some(0),
+            ast::LiteralKind::FloatNumber(it) => {
+                let is_float = ty.as_builtin().map_or(false, |it| it.is_float());
+                is_float && it.value() == Some(0.0)
+            }
+            ast::LiteralKind::Bool(it) => !it,
+            ast::LiteralKind::Char(it) => it.value() == Some('\0'),
+            ast::LiteralKind::String(it) => {
+                let is_str = ty.as_reference().and_then(|(it, _)| it.as_builtin());
+                is_str.map_or(false, |it| it.is_str())
+                    && it.value().map_or(false, |it| it.is_empty())
+            }
+            _ => false,
+        },
+        ast::Expr::MacroExpr(it) => {
+            let Some(call) = it.macro_call() else { return false };
+            let is_vec = call.path().map_or(false, |it| it.syntax().text() == "vec");
+            let is_empty = call
+                .token_tree()
+                .map_or(false, |it| it.syntax().children_with_tokens().count() == 2);
+            is_vec
+                && is_empty
+                && famous_defs.alloc_vec_Vec().map_or(false, |it| is_collection(ty, it))
+        }
+        ast::Expr::CallExpr(call) => {
+            if call.arg_list().map_or(true, |it| it.args().next().is_some()) {
+                return false;
+            }
+            let Some(ast::Expr::PathExpr(callee)) = call.expr() else { return false };
+            let Some(PathResolution::Def(hir::ModuleDef::Function(func))) =
+                callee.path().and_then(|it| ctx.sema.resolve_path(&it))
+            else {
+                return false;
+            };
+            let Some(default_trait) = famous_defs.core_default_Default() else { return false };
+            let trait_ = func
+                .as_assoc_item(ctx.db())
+                .and_then(|it| it.containing_trait_or_trait_impl(ctx.db()));
+            if trait_ == Some(default_trait) {
+                return true;
+            }
+            // Only the inherent `new` of the collection itself, which takes no arguments.
+            let is_inherent = match func.as_assoc_item(ctx.db()).map(|it| it.container(ctx.db())) {
+                Some(hir::AssocItemContainer::Impl(it)) => {
+                    it.trait_(ctx.db()).is_none() && it.self_ty(ctx.db()).as_adt() == ty.as_adt()
+                }
+                _ => false,
+            };
+            is_inherent
+                && func.name(ctx.db()).to_smol_str() == "new"
+                && func.assoc_fn_params(ctx.db()).is_empty()
+                && empty_by_new(famous_defs).into_iter().any(|it| is_collection(ty, it))
+                && ty.impls_trait(ctx.db(), default_trait, &[])
+        }
+        _ => false,
+    }
+}
+
+fn is_collection(ty: &hir::Type, collection: hir::Struct) -> bool {
+    ty.as_adt() == Some(hir::Adt::Struct(collection))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    const ALLOC: &str = r#"//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn new() -> Self { loop {} }
+    }
+    impl<T> Default for Vec<T> {
+        fn default() -> Self { Self::new() }
+    }
+}
+"#;
+
+    #[test]
+    fn replace_integer_default() {
+        check_assist(
+            replace_match_with_unwrap_or_default,
+            r#"
+//- minicore: option
+fn count(n: Option<i64>) -> i64 {
+    match$0 n {
+        None => 0x0,
+        Some(c) => c,
+    }
+}
+"#,
+            r#"
+fn count(n: Option<i64>) -> i64 {
+    n.unwrap_or_default()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_empty_string_default() {
+        check_assist(
+            replace_match_with_unwrap_or_default,
+            r#"
+//- minicore: option
+struct User { nick: Option<&'static str> }
+
+fn nick(user: &User) -> &str {
+    match$0 user.nick {
+        Some(nick) => nick,
+        None => "",
+    }
+}
+"#,
+            r#"
+struct User { nick: Option<&'static str> }
+
+fn nick(user: &User) -> &str {
+    user.nick.unwrap_or_default()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_empty_vec_default() {
+        check_assist(
+            replace_match_with_unwrap_or_default,
+            &format!(
+                r#"
+//- minicore: option, default
+//- /main.rs crate:main deps:alloc
+use alloc::vec::Vec;
+
+fn items(list: Option<Vec<u8>>) -> Vec<u8> {{
+    match$0 list {{
+        Some(list) => list,
+        None => Vec::new(),
+    }}
+}}
+{ALLOC}"#
+            ),
+            r#"
+use alloc::vec::Vec;
+
+fn items(list: Option<Vec<u8>>) -> Vec<u8> {
+    list.unwrap_or_default()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn replace_default_call() {
+        check_assist(
+            replace_match_with_unwrap_or_default,
+            r#"
+//- minicore: option, default
+struct Config;
+impl Default for Config {
+    fn default() -> Self { Config }
+}
+
+fn config(c: Option<Config>) -> Config {
+    match$0 c {
+        Some(c) => c,
+        None => Config::default(),
+    }
+}
+"#,
+            r#"
+struct Config;
+impl Default for Config {
+    fn default() -> Self { Config }
+}
+
+fn config(c: Option<Config>) -> Config {
+    c.unwrap_or_default()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_other_value() {
+        cov_mark::check!(replace_match_with_unwrap_or_default_not_default);
+        check_assist_not_applicable(
+            replace_match_with_unwrap_or_default,
+            r#"
+//- minicore: option
+fn count(n: Option<u32>) -> u32 {
+    match$0 n {
+        Some(c) => c,
+        None => 1,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_user_defined_vec() {
+        cov_mark::check!(replace_match_with_unwrap_or_default_not_default);
+        check_assist_not_applicable(
+            replace_match_with_unwrap_or_default,
+            r#"
+//- minicore: option, default
+struct Vec<T>(T);
+impl<T> Vec<T> {
+    fn new() -> Self { loop {} }
+}
+impl<T> Default for Vec<T> {
+    fn default() -> Self { Self::new() }
+}
+
+fn items(list: Option<Vec<u8>>) -> Vec<u8> {
+    match$0 list {
+        Some(list) => list,
+        None => Vec::new(),
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use hir::{PathResolution, ScopeDef};
+use syntax::ast::{self, AstNode, HasArgList};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: replace_variant_array_with_enum_iter
+//
+// Replaces iterating over an array listing every variant of an enum that derives strum's
+// `EnumIter` with a call to the generated `iter`.
+//
+// ```
+// mod strum { pub trait IntoEnumIterator {} }
+// use strum::IntoEnumIterator;
+//
+// enum Color { Red, Green }
+// impl IntoEnumIterator for Color {}
+//
+// fn count() -> usize {
+//     [Color::Red, $0Color::Green].into_iter().count()
+// }
+// ```
+// ->
+// ```
+// mod strum { pub trait IntoEnumIterator {} }
+// use strum::IntoEnumIterator;
+//
+// enum Color { Red, Green }
+// impl IntoEnumIterator for Color {}
+//
+// fn count() -> usize {
+//     Color::iter().count()
+// }
+// ```
+pub(crate) fn replace_variant_array_with_enum_iter(
+    acc: &mut Assists,
+    ctx: &AssistContext<'_>,
+) -> Option<()> {
+    let array = ctx.find_node_at_offset::<ast::ArrayExpr>()?;
+    let call = array.syntax().parent().and_then(ast::MethodCallExpr::cast)?;
+    if call.name_ref()?.text() != "into_iter" || call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+
+    let mut variants = Vec::new();
+    let mut qualifiers = Vec::new();
+    for expr in array.exprs() {
+        let ast::Expr::PathExpr(path_expr) = expr else { return None };
+        let path = path_expr.path()?;
+        match ctx.sema.resolve_path(&path)? {
+            PathResolution::Def(hir::ModuleDef::Variant(it)) => variants.push(it),
+            _ => return None,
+        }
+        qualifiers.push(path.qualifier()?.to_string());
+    }
+    let enum_ = variants.first()?.parent_enum(ctx.db());
+    // `EnumIter` yields the variants in the order they are declared in.
+    if variants != enum_.variants(ctx.db()) {
+        cov_mark::hit!(replace_variant_array_with_enum_iter_not_all_variants);
+        return None;
+    }
+
+    let scope = ctx.sema.scope(call.syntax())?;
+    let mut enum_iter = None;
+    scope.process_all_names(&mut |name, def| {
+        if let ScopeDef::ModuleDef(hir::ModuleDef::Trait(it)) = def {
+            if name.to_smol_str() == "IntoEnumIterator" {
+                enum_iter = Some(it);
+            }
+        }
+    });
+    let derives_iter =
+        enum_iter.map_or(false, |it| enum_.ty(ctx.db()).impls_trait(ctx.db(), it, &[]));
+    if !derives_iter {
+        cov_mark::hit!(replace_variant_array_with_enum_iter_no_derive);
+        return None;
+    }
+    let qualifier = match qualifiers.iter().all(|it| *it == qualifiers[0]) {
+        true => qualifiers[0].clone(),
+        false => enum_.name(ctx.db()).to_string(),
+    };
+
+    let target = call.syntax().text_range();
+    acc.add(
+        AssistId("replace_variant_array_with_enum_iter", AssistKind::RefactorRewrite),
+        "Replace with `iter` of `EnumIter`",
+        target,
+        |builder| builder.replace(target, format!("{qualifier}::iter()")),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn replace_all_variants_in_impl() {
+        check_assist(
+            replace_variant_array_with_enum_iter,
+            r#"
+mod strum { pub trait IntoEnumIterator {} }
+use strum::IntoEnumIterator;
+
+enum Level { Low, Mid, High }
+// What `#[derive(EnumIter)]` expands to.
+impl IntoEnumIterator for Level {}
+
+impl Level {
+    fn names() -> usize {
+        [Self::Low, Self::Mid, Self::High]$0
+            .into_iter()
+            .map(|level| match level {
+                Self::Low => 0,
+                Self::Mid => 1,
+                Self::High => 2,
+            })
+            .count()
+    }
+}
+"#,
+            r#"
+mod strum { pub trait IntoEnumIterator {} }
+use strum::IntoEnumIterator;
+
+enum Level { Low, Mid, High }
+// What `#[derive(EnumIter)]` expands to.
+impl IntoEnumIterator for Level {}
+
+impl Level {
+    fn names() -> usize {
+        Self::iter()
+            .map(|level| match level {
+                Self::Low => 0,
+                Self::Mid => 1,
+                Self::High => 2,
+            })
+            .count()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_derive() {
+        cov_mark::check!(replace_variant_array_with_enum_iter_no_derive);
+        check_assist_not_applicable(
+            replace_variant_array_with_enum_iter,
+            r#"
+enum Level { Low, High }
+
+fn count() -> usize {
+    [Level::Low, $0Level::High].into_iter().count()
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_some_variants() {
+        cov_mark::check!(replace_variant_array_with_enum_iter_not_all_variants);
+        check_assist_not_applicable(
+            replace_variant_array_with_enum_iter,
+            r#"
+mod strum { pub trait IntoEnumIterator {} }
+use strum::IntoEnumIterator;
+
+enum Level { Low, Mid, High }
+impl IntoEnumIterator for Level {}
+
+fn count() -> usize {
+    [Level::High, $0Level::Mid, Level::Low].into_iter().count()
+}
+"#,
+        );
+    }
+}
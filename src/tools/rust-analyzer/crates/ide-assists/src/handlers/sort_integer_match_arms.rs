@@ -0,0 +1,238 @@
+use stdx::format_to;
+use syntax::{
+    ast::{self, edit::IndentLevel, AstNode},
+    SyntaxKind, T,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: sort_integer_match_arms
+//
+// Sorts the arms of a match on integer literals by their key, and notes the range of the keys
+// when it has no gaps, which makes it easy to check for a jump table.
+//
+// ```
+// fn name(n: u8) -> &'static str {
+//     $0match n {
+//         2 => "two",
+//         0 => "zero",
+//         1 => "one",
+//         _ => "many",
+//     }
+// }
+// ```
+// ->
+// ```
+// fn name(n: u8) -> &'static str {
+//     match n {
+//         // dense 0..3
+//         0 => "zero",
+//         1 => "one",
+//         2 => "two",
+//         _ => "many",
+//     }
+// }
+// ```
+pub(crate) fn sort_integer_match_arms(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let arm_list = match_expr.match_arm_list()?;
+    // Comments between the arms would end up next to other arms.
+    if arm_list.syntax().descendants_with_tokens().any(|it| it.kind() == SyntaxKind::COMMENT) {
+        return None;
+    }
+
+    let mut arms = arm_list.arms().collect::<Vec<_>>();
+    let fallback = match arms.last()?.pat()? {
+        ast::Pat::WildcardPat(_) => arms.pop(),
+        _ => None,
+    };
+    if arms.iter().chain(&fallback).any(|arm| arm.guard().is_some()) {
+        cov_mark::hit!(sort_integer_match_arms_unsafe_arm);
+        return None;
+    }
+    let mut keyed = Vec::with_capacity(arms.len());
+    for arm in arms {
+        let key = match arm.pat()? {
+            ast::Pat::LiteralPat(pat) => int_key(&pat)?,
+            ast::Pat::IdentPat(_) => {
+                cov_mark::hit!(sort_integer_match_arms_unsafe_arm);
+                return None;
+            }
+            _ => return None,
+        };
+        keyed.push((key, arm));
+    }
+    if keyed.len() < 2 || keyed.windows(2).all(|it| it[0].0 < it[1].0) {
+        return None;
+    }
+    keyed.sort_by_key(|(key, _)| *key);
+    // Only distinct keys can be reordered without changing which arm is taken.
+    if keyed.windows(2).any(|it| it[0].0 == it[1].0) {
+        return None;
+    }
+
+    let target = arm_list.syntax().text_range();
+    acc.add(
+        AssistId("sort_integer_match_arms", AssistKind::RefactorRewrite),
+        "Sort match arms by key",
+        target,
+        |builder| {
+            let indent = IndentLevel::from_node(match_expr.syntax());
+            let arm_indent = IndentLevel(indent.0 + 1);
+            let mut buf = String::from("{");
+            let (first, last) = (keyed[0].0, keyed[keyed.len() - 1].0);
+            // The end of the range isn't representable when the keys go up to `u128::MAX`.
+            let is_dense = (last - first).checked_add(1) == Some(keyed.len() as u128);
+            if let Some(end) = last.checked_add(1).filter(|_| is_dense) {
+                format_to!(buf, "\n{arm_indent}// dense {first}..{end}");
+            }
+            let count = keyed.len() + fallback.iter().count();
+            for (idx, arm) in keyed.iter().map(|(_, arm)| arm).chain(&fallback).enumerate() {
+                let needs_comma = idx + 1 < count
+                    && arm.comma_token().is_none()
+                    && !matches!(arm.expr(), Some(ast::Expr::BlockExpr(_)));
+                let comma = if needs_comma { "," } else { "" };
+                format_to!(buf, "\n{arm_indent}{arm}{comma}");
+            }
+            format_to!(buf, "\n{indent}}}");
+            builder.replace(target, buf);
+        },
+    )
+}
+
+fn int_key(pat: &ast::LiteralPat) -> Option<u128> {
+    if pat.syntax().first_token()?.kind() == T![-] {
+        return None;
+    }
+    match pat.literal()?.kind() {
+        ast::LiteralKind::IntNumber(it) => it.value(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn sort_scrambled_dense_arms() {
+        check_assist(
+            sort_integer_match_arms,
+            r#"
+fn op(code: u32, x: i32) -> i32 {
+    match$0 code {
+        3 => x * x,
+        1 => {
+            let y = x + 1;
+            y
+        }
+        0 => 0,
+        2 => -x,
+        _ => unimplemented!()
+    }
+}
+"#,
+            r#"
+fn op(code: u32, x: i32) -> i32 {
+    match code {
+        // dense 0..4
+        0 => 0,
+        1 => {
+            let y = x + 1;
+            y
+        }
+        2 => -x,
+        3 => x * x,
+        _ => unimplemented!()
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn sort_sparse_arms_without_comment() {
+        check_assist(
+            sort_integer_match_arms,
+            r#"
+fn size(n: u8) -> u8 {
+    match$0 n {
+        0x10 => 2,
+        4 => 1,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn size(n: u8) -> u8 {
+    match n {
+        4 => 1,
+        0x10 => 2,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_guard() {
+        cov_mark::check!(sort_integer_match_arms_unsafe_arm);
+        check_assist_not_applicable(
+            sort_integer_match_arms,
+            r#"
+fn op(code: u32, flag: bool) -> u32 {
+    match$0 code {
+        2 if flag => 1,
+        0 => 0,
+        _ => 2,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_sorted() {
+        check_assist_not_applicable(
+            sort_integer_match_arms,
+            r#"
+fn op(code: u32) -> u32 {
+    match$0 code {
+        // dense 0..2
+        0 => 0,
+        1 => 1,
+        _ => 2,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn sort_without_range_up_to_max() {
+        check_assist(
+            sort_integer_match_arms,
+            r#"
+fn top(n: u128) -> u8 {
+    match$0 n {
+        340282366920938463463374607431768211455 => 1,
+        340282366920938463463374607431768211454 => 2,
+        _ => 0,
+    }
+}
+"#,
+            r#"
+fn top(n: u128) -> u8 {
+    match n {
+        340282366920938463463374607431768211454 => 2,
+        340282366920938463463374607431768211455 => 1,
+        _ => 0,
+    }
+}
+"#,
+        );
+    }
+}
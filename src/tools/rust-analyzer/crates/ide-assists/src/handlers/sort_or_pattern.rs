@@ -0,0 +1,253 @@
+use ide_db::source_change::SourceChangeBuilder;
+use syntax::ast::{self, AstNode};
+
+use crate::{utils::pat_variant, AssistContext, AssistId, AssistKind, Assists, GroupLabel};
+
+// Assist: sort_or_pattern
+//
+// Sorts the alternatives of an or-pattern alphabetically or in the declaration order of
+// the enum variants they refer to.
+//
+// ```
+// enum X { A, B, C }
+//
+// fn handle(x: X) {
+//     match x {
+//         X::C | $0X::A | X::B => (),
+//     }
+// }
+// ```
+// ->
+// ```
+// enum X { A, B, C }
+//
+// fn handle(x: X) {
+//     match x {
+//         X::A | X::B | X::C => (),
+//     }
+// }
+// ```
+pub(crate) fn sort_or_pattern(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let or_pat = ctx.find_node_at_offset::<ast::OrPat>()?;
+    let pats = or_pat.pats().collect::<Vec<_>>();
+    if pats.len() < 2 {
+        return None;
+    }
+
+    if binds_variables(ctx, &pats) {
+        cov_mark::hit!(sort_or_pattern_with_bindings);
+        return None;
+    }
+
+    let group = GroupLabel("Sort or-pattern alternatives".to_owned());
+    let target = or_pat.syntax().text_range();
+
+    let mut alphabetical = pats.clone();
+    alphabetical.sort_by_key(|pat| pat.syntax().to_string());
+    if alphabetical != pats {
+        acc.add_group(
+            &group,
+            AssistId("sort_or_pattern", AssistKind::RefactorRewrite),
+            "Sort alternatives alphabetically",
+            target,
+            |builder| replace_pats(builder, &pats, &alphabetical),
+        );
+    }
+
+    if let Some(declaration_order) = sort_by_variant_order(ctx, &pats) {
+        if declaration_order != pats {
+            acc.add_group(
+                &group,
+                AssistId("sort_or_pattern", AssistKind::RefactorRewrite),
+                "Sort alternatives by declaration order",
+                target,
+                |builder| replace_pats(builder, &pats, &declaration_order),
+            );
+        }
+    }
+
+    Some(())
+}
+
+/// Checks whether any of `pats` binds a variable.
+///
+/// The alternatives of an or-pattern have to bind the same variables, but the bindings could
+/// still be shuffled around in ways the user cares about, so those are left alone.
+pub(crate) fn binds_variables(ctx: &AssistContext<'_>, pats: &[ast::Pat]) -> bool {
+    pats.iter().any(|pat| {
+        pat.syntax()
+            .descendants()
+            .filter_map(ast::IdentPat::cast)
+            .any(|ident| ctx.sema.resolve_bind_pat_to_const(&ident).is_none())
+    })
+}
+
+fn replace_pats(builder: &mut SourceChangeBuilder, old: &[ast::Pat], new: &[ast::Pat]) {
+    for (old, new) in old.iter().zip(new) {
+        builder.replace(old.syntax().text_range(), new.syntax().text());
+    }
+}
+
+/// Sorts `pats` in the declaration order of the variants of the single enum they refer to.
+pub(crate) fn sort_by_variant_order(
+    ctx: &AssistContext<'_>,
+    pats: &[ast::Pat],
+) -> Option<Vec<ast::Pat>> {
+    let mut enum_ = None;
+    let mut keyed = pats
+        .iter()
+        .map(|pat| {
+            let variant = pat_variant(ctx, pat)?;
+            let parent = variant.parent_enum(ctx.db());
+            if *enum_.get_or_insert(parent) != parent {
+                return None;
+            }
+            let idx = parent.variants(ctx.db()).iter().position(|it| *it == variant)?;
+            Some((idx, pat.clone()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    keyed.sort_by_key(|(idx, _)| *idx);
+    Some(keyed.into_iter().map(|(_, pat)| pat).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_by_label, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn sort_unit_variants_alphabetically() {
+        check_assist_by_label(
+            sort_or_pattern,
+            r#"
+enum X { B, C, A }
+
+fn handle(x: X) {
+    match x {
+        X::C $0| X::A | X::B => (),
+    }
+}
+"#,
+            r#"
+enum X { B, C, A }
+
+fn handle(x: X) {
+    match x {
+        X::A | X::B | X::C => (),
+    }
+}
+"#,
+            "Sort alternatives alphabetically",
+        );
+    }
+
+    #[test]
+    fn sort_unit_variants_by_declaration_order() {
+        check_assist_by_label(
+            sort_or_pattern,
+            r#"
+enum X { B, C, A }
+
+fn handle(x: X) {
+    match x {
+        X::A $0| X::C | X::B => (),
+    }
+}
+"#,
+            r#"
+enum X { B, C, A }
+
+fn handle(x: X) {
+    match x {
+        X::B | X::C | X::A => (),
+    }
+}
+"#,
+            "Sort alternatives by declaration order",
+        );
+    }
+
+    #[test]
+    fn sort_imported_variants_and_wildcard_payloads() {
+        check_assist(
+            sort_or_pattern,
+            r#"
+enum X { A(u8), B { b: u8 }, C }
+use X::*;
+
+fn handle(x: X) {
+    match x {
+        C | B { .. } $0| A(_) => (),
+    }
+}
+"#,
+            r#"
+enum X { A(u8), B { b: u8 }, C }
+use X::*;
+
+fn handle(x: X) {
+    match x {
+        A(_) | B { .. } | C => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn sort_literals() {
+        check_assist(
+            sort_or_pattern,
+            r#"
+fn handle(c: char) {
+    match c {
+        'c' | 'a' $0| 'b' => (),
+        _ => (),
+    }
+}
+"#,
+            r#"
+fn handle(c: char) {
+    match c {
+        'a' | 'b' | 'c' => (),
+        _ => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_sorted() {
+        check_assist_not_applicable(
+            sort_or_pattern,
+            r#"
+enum X { A, B, C }
+
+fn handle(x: X) {
+    match x {
+        X::A $0| X::B | X::C => (),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_bindings() {
+        cov_mark::check!(sort_or_pattern_with_bindings);
+        check_assist_not_applicable(
+            sort_or_pattern,
+            r#"
+enum X { A(u8), B(u8) }
+
+fn handle(x: X) {
+    match x {
+        X::B(v) $0| X::A(v) => (),
+    }
+}
+"#,
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use hir::PathResolution;
+use syntax::{
+    algo::neighbor,
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        AstNode, HasName,
+    },
+    Direction,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: split_match_guard
+//
+// Splits the last `&&` operand off a match guard into an `if` in the arm body. What the guard
+// used to fall through to becomes the `else` branch, so this is only offered when that is the
+// next arm and it matches whatever the guarded arm's pattern does.
+//
+// ```
+// fn classify(n: Option<i32>) -> &'static str {
+//     match n {
+//         Some(x) if x > 0 $0&& x < 10 => "digit",
+//         _ => "other",
+//     }
+// }
+// ```
+// ->
+// ```
+// fn classify(n: Option<i32>) -> &'static str {
+//     match n {
+//         Some(x) if x > 0 => if x < 10 {
+//             "digit"
+//         } else {
+//             "other"
+//         }
+//         _ => "other",
+//     }
+// }
+// ```
+pub(crate) fn split_match_guard(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let arm = ctx.find_node_at_offset::<ast::MatchArm>()?;
+    let guard = arm.guard()?;
+    if !guard.syntax().text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let ast::Expr::BinExpr(condition) = guard.condition()? else { return None };
+    if condition.op_kind()? != ast::BinaryOp::LogicOp(ast::LogicOp::And) {
+        return None;
+    }
+    let (lhs, rhs) = (condition.lhs()?, condition.rhs()?);
+    let pat = arm.pat()?;
+    let body = arm.expr()?;
+
+    let fallthrough = neighbor(&arm, Direction::Next).and_then(|next| {
+        if next.guard().is_some() {
+            return None;
+        }
+        let next_pat = next.pat()?;
+        let same_pat = next_pat.syntax().text() == pat.syntax().text();
+        let catch_all =
+            matches!(next_pat, ast::Pat::WildcardPat(_)) && !sees_bindings(ctx, &pat, &next);
+        if !same_pat && !catch_all {
+            return None;
+        }
+        next.expr()
+    });
+    let Some(fallthrough) = fallthrough else {
+        cov_mark::hit!(split_match_guard_unknown_fallthrough);
+        return None;
+    };
+
+    acc.add(
+        AssistId("split_match_guard", AssistKind::RefactorRewrite),
+        "Split guard into nested `if`",
+        guard.syntax().text_range(),
+        |builder| {
+            let indent = IndentLevel::from_node(arm.syntax());
+            let then_branch = branch(&body, indent);
+            let else_branch = branch(&fallthrough, indent);
+            builder.replace(
+                arm.syntax().text_range(),
+                format!("{pat} if {lhs} => if {rhs} {then_branch} else {else_branch}"),
+            );
+        },
+    )
+}
+
+/// Checks whether `arm` refers to a name that the bindings of `pat` would shadow.
+pub(crate) fn sees_bindings(ctx: &AssistContext<'_>, pat: &ast::Pat, arm: &ast::MatchArm) -> bool {
+    let bindings = pat
+        .syntax()
+        .descendants()
+        .filter_map(ast::IdentPat::cast)
+        .filter_map(|it| Some(it.name()?.to_string()))
+        .collect::<Vec<_>>();
+    arm.syntax().descendants().filter_map(ast::Path::cast).any(|path| {
+        matches!(ctx.sema.resolve_path(&path), Some(PathResolution::Local(_)))
+            && bindings.iter().any(|it| path.syntax().text() == it.as_str())
+    })
+}
+
+/// Renders the body of an arm indented by `indent` as a block at the same level.
+fn branch(expr: &ast::Expr, indent: IndentLevel) -> String {
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => block.to_string(),
+        _ => format!("{{\n{}{}\n{indent}}}", IndentLevel(indent.0 + 1), expr.reset_indent()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn split_with_same_pattern_fallthrough() {
+        check_assist(
+            split_match_guard,
+            r#"
+enum Msg { Move(i32, i32), Quit }
+
+fn handle(msg: Msg) -> i32 {
+    match msg {
+        Msg::Move(x, y) if$0 x > 0 && y > 0 => {
+            let d = x + y;
+            d * 2
+        }
+        Msg::Move(x, y) => x - y,
+        Msg::Quit => 0,
+    }
+}
+"#,
+            r#"
+enum Msg { Move(i32, i32), Quit }
+
+fn handle(msg: Msg) -> i32 {
+    match msg {
+        Msg::Move(x, y) if x > 0 => if y > 0 {
+            let d = x + y;
+            d * 2
+        } else {
+            x - y
+        }
+        Msg::Move(x, y) => x - y,
+        Msg::Quit => 0,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_guarded_next_arm() {
+        cov_mark::check!(split_match_guard_unknown_fallthrough);
+        check_assist_not_applicable(
+            split_match_guard,
+            r#"
+fn classify(n: Option<i32>) -> i32 {
+    match n {
+        Some(x) if x > 0 $0&& x < 10 => 1,
+        Some(x) if x < 0 => 2,
+        _ => 3,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_wildcard_sees_bindings() {
+        check_assist_not_applicable(
+            split_match_guard,
+            r#"
+fn classify(n: Option<i32>, x: i32) -> i32 {
+    match n {
+        Some(x) if x > 0 $0&& x < 10 => 1,
+        _ => x,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_or_guard() {
+        check_assist_not_applicable(
+            split_match_guard,
+            r#"
+fn classify(n: Option<i32>) -> i32 {
+    match n {
+        Some(x) if x > 0 $0|| x < -10 => 1,
+        _ => 3,
+    }
+}
+"#,
+        );
+    }
+}
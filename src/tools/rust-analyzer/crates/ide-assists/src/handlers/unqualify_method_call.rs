@@ -1,9 +1,9 @@
 use syntax::{
-    ast::{self, make, AstNode, HasArgList},
+    ast::{self, AstNode, HasArgList},
     TextRange,
 };
 
-use crate::{AssistContext, AssistId, AssistKind, Assists};
+use crate::{utils::needs_parens_as_receiver, AssistContext, AssistId, AssistKind, Assists};
 
 // Assist: unqualify_method_call
 //
@@ -80,24 +80,6 @@ pub(crate) fn unqualify_method_call(acc: &mut Assists, ctx: &AssistContext<'_>)
     )
 }
 
-fn needs_parens_as_receiver(expr: &ast::Expr) -> bool {
-    // Make `(expr).dummy()`
-    let dummy_call = make::expr_method_call(
-        make::expr_paren(expr.clone()),
-        make::name_ref("dummy"),
-        make::arg_list([]),
-    );
-
-    // Get the `expr` clone with the right parent back
-    // (unreachable!s are fine since we've just constructed the expression)
-    let ast::Expr::MethodCallExpr(call) = &dummy_call else { unreachable!() };
-    let Some(receiver) = call.receiver() else { unreachable!() };
-    let ast::Expr::ParenExpr(parens) = receiver else { unreachable!() };
-    let Some(expr) = parens.expr() else { unreachable!() };
-
-    expr.needs_parens_in(dummy_call.syntax().clone())
-}
-
 #[cfg(test)]
 mod tests {
     use crate::tests::{check_assist, check_assist_not_applicable};
@@ -0,0 +1,188 @@
+use hir::{BindingMode, Mutability};
+use syntax::{
+    ast::{self, AstNode, HasName},
+    TextRange,
+};
+
+use crate::{AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: use_match_ergonomics
+//
+// Removes the `&` and `ref` from the patterns of a match on a reference, which default binding
+// modes make unnecessary.
+//
+// ```
+// # //- minicore: option
+// fn len(opt: &Option<String>) -> usize {
+//     $0match opt {
+//         &Some(ref s) => s.len(),
+//         &None => 0,
+//     }
+// }
+// ```
+// ->
+// ```
+// fn len(opt: &Option<String>) -> usize {
+//     match opt {
+//         Some(s) => s.len(),
+//         None => 0,
+//     }
+// }
+// ```
+pub(crate) fn use_match_ergonomics(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let mut deletions = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let pat = arm.pat()?;
+        let alternatives = match &pat {
+            ast::Pat::OrPat(it) => it.pats().collect(),
+            _ => vec![pat],
+        };
+        for pat in alternatives {
+            match pat {
+                ast::Pat::RefPat(ref_pat) => {
+                    let inner = ref_pat.pat()?;
+                    deletions.push(TextRange::new(
+                        ref_pat.syntax().text_range().start(),
+                        inner.syntax().text_range().start(),
+                    ));
+                    let mutability = match ref_pat.mut_token() {
+                        Some(_) => Mutability::Mut,
+                        None => Mutability::Shared,
+                    };
+                    if !remove_ref_bindings(ctx, &inner, mutability, &mut deletions) {
+                        cov_mark::hit!(use_match_ergonomics_explicit_mode_required);
+                        return None;
+                    }
+                }
+                ast::Pat::WildcardPat(_) => (),
+                ast::Pat::IdentPat(it) if it.pat().is_none() => (),
+                _ => return None,
+            }
+        }
+    }
+    if deletions.is_empty() {
+        return None;
+    }
+
+    let target = match_expr.syntax().text_range();
+    acc.add(
+        AssistId("use_match_ergonomics", AssistKind::RefactorRewrite),
+        "Use default binding modes",
+        target,
+        |builder| {
+            for range in deletions {
+                builder.delete(range);
+            }
+        },
+    )
+}
+
+/// Collects the `ref`s of the bindings in `pat`, which binds the same types without them once
+/// the reference around it is no longer destructured. Returns `false` if some binding would
+/// bind something else.
+fn remove_ref_bindings(
+    ctx: &AssistContext<'_>,
+    pat: &ast::Pat,
+    mutability: Mutability,
+    deletions: &mut Vec<TextRange>,
+) -> bool {
+    for node in pat.syntax().descendants() {
+        if ast::RefPat::can_cast(node.kind()) {
+            return false;
+        }
+        let Some(ident_pat) = ast::IdentPat::cast(node) else { continue };
+        if ctx.sema.resolve_bind_pat_to_const(&ident_pat).is_some() {
+            continue;
+        }
+        if ctx.sema.binding_mode_of_pat(&ident_pat) != Some(BindingMode::Ref(mutability)) {
+            return false;
+        }
+        let (Some(ref_token), Some(name)) = (ident_pat.ref_token(), ident_pat.name()) else {
+            return false;
+        };
+        deletions.push(TextRange::new(
+            ref_token.text_range().start(),
+            name.syntax().text_range().start(),
+        ));
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn modernize_reference_patterns() {
+        check_assist(
+            use_match_ergonomics,
+            r#"
+//- minicore: option
+struct Point { x: i32, y: i32 }
+enum Shape { Dot(Point), Line(Point, Point), Empty }
+
+fn first_x(shape: &Shape) -> Option<&i32> {
+    match$0 shape {
+        &Shape::Dot(Point { ref x, .. }) | &Shape::Line(Point { ref x, .. }, _) => Some(x),
+        &Shape::Empty => None,
+    }
+}
+"#,
+            r#"
+struct Point { x: i32, y: i32 }
+enum Shape { Dot(Point), Line(Point, Point), Empty }
+
+fn first_x(shape: &Shape) -> Option<&i32> {
+    match shape {
+        Shape::Dot(Point { x, .. }) | Shape::Line(Point { x, .. }, _) => Some(x),
+        Shape::Empty => None,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn modernize_mutable_reference_patterns() {
+        check_assist(
+            use_match_ergonomics,
+            r#"
+//- minicore: option
+fn bump(opt: &mut Option<u32>) {
+    match$0 opt {
+        &mut Some(ref mut n) => *n += 1,
+        other => *other = Some(0),
+    }
+}
+"#,
+            r#"
+fn bump(opt: &mut Option<u32>) {
+    match opt {
+        Some(n) => *n += 1,
+        other => *other = Some(0),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_binding_by_value() {
+        cov_mark::check!(use_match_ergonomics_explicit_mode_required);
+        check_assist_not_applicable(
+            use_match_ergonomics,
+            r#"
+//- minicore: option
+fn get(opt: &Option<u32>) -> u32 {
+    match$0 opt {
+        &Some(n) => n,
+        &None => 0,
+    }
+}
+"#,
+        );
+    }
+}
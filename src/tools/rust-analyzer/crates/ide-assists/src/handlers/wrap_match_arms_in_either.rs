@@ -0,0 +1,348 @@
+use hir::PathResolution;
+use ide_db::{
+    famous_defs::FamousDefs,
+    imports::insert_use::{insert_use, ImportScope},
+};
+use syntax::{
+    ast::{
+        self,
+        edit::{AstNodeEdit, IndentLevel},
+        make, AstNode, HasModuleItem,
+    },
+    ted, SyntaxElement,
+};
+
+use crate::{utils::module_item, AssistContext, AssistId, AssistKind, Assists};
+
+// Assist: wrap_match_arms_in_either
+//
+// Unifies match arms producing two different iterator types by wrapping them in
+// `Either::Left` and `Either::Right`. `itertools::Either` is used when `itertools` is a
+// dependency, otherwise a local `Either` iterator is generated.
+//
+// ```
+// # //- minicore: iterator
+// struct Evens;
+// impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+// struct Odds;
+// impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+//
+// fn numbers(even: bool) -> impl Iterator<Item = u32> {
+//     $0match even {
+//         true => Evens,
+//         false => Odds,
+//     }
+// }
+// ```
+// ->
+// ```
+// struct Evens;
+// impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+// struct Odds;
+// impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+//
+// enum Either<L, R> {
+//     Left(L),
+//     Right(R),
+// }
+//
+// impl<L, R> Iterator for Either<L, R>
+// where
+//     L: Iterator,
+//     R: Iterator<Item = L::Item>,
+// {
+//     type Item = L::Item;
+//
+//     fn next(&mut self) -> Option<Self::Item> {
+//         match self {
+//             Either::Left(it) => it.next(),
+//             Either::Right(it) => it.next(),
+//         }
+//     }
+// }
+//
+// fn numbers(even: bool) -> impl Iterator<Item = u32> {
+//     match even {
+//         true => Either::Left(Evens),
+//         false => Either::Right(Odds),
+//     }
+// }
+// ```
+pub(crate) fn wrap_match_arms_in_either(acc: &mut Assists, ctx: &AssistContext<'_>) -> Option<()> {
+    let match_expr = ctx.find_node_at_offset::<ast::MatchExpr>()?;
+    let scope = ctx.sema.scope(match_expr.syntax())?;
+    let iterator = FamousDefs(&ctx.sema, scope.krate()).core_iter_Iterator()?;
+
+    let mut types: Vec<hir::Type> = Vec::new();
+    let mut wrapped = Vec::new();
+    for arm in match_expr.match_arm_list()?.arms() {
+        let expr = arm.expr()?;
+        let ty = ctx.sema.type_of_expr(&expr)?.original;
+        // Diverging arms coerce to anything and don't need a wrapper.
+        if ty.is_never() {
+            continue;
+        }
+        if !ty.impls_trait(ctx.db(), iterator, &[]) {
+            return None;
+        }
+        let side = match types.iter().position(|it| *it == ty) {
+            Some(it) => it,
+            None => {
+                types.push(ty);
+                types.len() - 1
+            }
+        };
+        let expr = match &expr {
+            ast::Expr::BlockExpr(block) if block.modifier().is_none() => block.tail_expr()?,
+            _ => expr,
+        };
+        wrapped.push((expr, side));
+    }
+    match types.len() {
+        2 => (),
+        0 | 1 => return None,
+        _ => {
+            cov_mark::hit!(wrap_match_arms_in_either_too_many_types);
+            return None;
+        }
+    }
+
+    let has_itertools = scope
+        .krate()
+        .dependencies(ctx.db())
+        .iter()
+        .any(|dep| dep.name.to_smol_str() == "itertools");
+    let either_in_scope = matches!(
+        scope.speculative_resolve(&make::ext::ident_path("Either")),
+        Some(PathResolution::Def(hir::ModuleDef::Adt(hir::Adt::Enum(_))))
+    );
+    let import_scope = ImportScope::find_insert_use_container(match_expr.syntax(), &ctx.sema)?;
+    // Without `itertools`, the local `Either` goes right before the item the match is in.
+    let item = module_item(match_expr.syntax())?;
+
+    acc.add(
+        AssistId("wrap_match_arms_in_either", AssistKind::RefactorRewrite),
+        "Wrap match arms in `Either`",
+        match_expr.syntax().text_range(),
+        |builder| {
+            let wrapped = wrapped
+                .into_iter()
+                .map(|(expr, side)| (builder.make_mut(expr), side))
+                .collect::<Vec<_>>();
+            let import_scope = match import_scope {
+                ImportScope::File(it) => ImportScope::File(builder.make_mut(it)),
+                ImportScope::Module(it) => ImportScope::Module(builder.make_mut(it)),
+                ImportScope::Block(it) => ImportScope::Block(builder.make_mut(it)),
+            };
+            let item = builder.make_mut(item);
+
+            for (expr, side) in &wrapped {
+                let ctor = if *side == 0 { "Either::Left" } else { "Either::Right" };
+                let call = make::expr_call(
+                    make::expr_path(make::path_from_text(ctor)),
+                    make::arg_list([expr.clone_subtree()]),
+                )
+                .clone_for_update();
+                ted::replace(expr.syntax(), call.syntax());
+            }
+            if either_in_scope {
+                return;
+            }
+            if has_itertools {
+                insert_use(
+                    &import_scope,
+                    make::path_from_text("itertools::Either"),
+                    &ctx.config.insert_use,
+                );
+            } else {
+                let indent = IndentLevel::from_node(item.syntax());
+                let mut elements: Vec<SyntaxElement> = Vec::new();
+                for def in ast::SourceFile::parse(LOCAL_EITHER).tree().items() {
+                    elements.push(def.indent(indent).clone_for_update().syntax().clone().into());
+                    elements.push(make::tokens::whitespace(&format!("\n\n{indent}")).into());
+                }
+                ted::insert_all_raw(ted::Position::before(item.syntax()), elements);
+            }
+        },
+    )
+}
+
+const LOCAL_EITHER: &str = "\
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Iterator for Either<L, R>
+where
+    L: Iterator,
+    R: Iterator<Item = L::Item>,
+{
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Either::Left(it) => it.next(),
+            Either::Right(it) => it.next(),
+        }
+    }
+}";
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::{check_assist, check_assist_not_applicable};
+
+    use super::*;
+
+    #[test]
+    fn wrap_with_local_either() {
+        check_assist(
+            wrap_match_arms_in_either,
+            r#"
+//- minicore: iterator
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+mod numbers {
+    use super::*;
+
+    fn numbers(kind: u8) -> impl Iterator<Item = u32> {
+        match$0 kind {
+            0 => Evens,
+            1 => {
+                let odds = Odds;
+                odds
+            }
+            2 => Odds,
+            _ => loop {},
+        }
+    }
+}
+"#,
+            r#"
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+mod numbers {
+    use super::*;
+
+    enum Either<L, R> {
+        Left(L),
+        Right(R),
+    }
+
+    impl<L, R> Iterator for Either<L, R>
+    where
+        L: Iterator,
+        R: Iterator<Item = L::Item>,
+    {
+        type Item = L::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            match self {
+                Either::Left(it) => it.next(),
+                Either::Right(it) => it.next(),
+            }
+        }
+    }
+
+    fn numbers(kind: u8) -> impl Iterator<Item = u32> {
+        match kind {
+            0 => Either::Left(Evens),
+            1 => {
+                let odds = Odds;
+                Either::Right(odds)
+            }
+            2 => Either::Right(Odds),
+            _ => loop {},
+        }
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn wrap_with_itertools_either() {
+        check_assist(
+            wrap_match_arms_in_either,
+            r#"
+//- minicore: iterator
+//- /main.rs crate:main deps:itertools
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+fn numbers(even: bool) -> impl Iterator<Item = u32> {
+    match$0 even {
+        true => Evens,
+        false => Odds,
+    }
+}
+//- /itertools.rs crate:itertools
+pub enum Either<L, R> { Left(L), Right(R) }
+"#,
+            r#"
+use itertools::Either;
+
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+fn numbers(even: bool) -> impl Iterator<Item = u32> {
+    match even {
+        true => Either::Left(Evens),
+        false => Either::Right(Odds),
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_three_types() {
+        cov_mark::check!(wrap_match_arms_in_either_too_many_types);
+        check_assist_not_applicable(
+            wrap_match_arms_in_either,
+            r#"
+//- minicore: iterator
+struct A;
+impl Iterator for A { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct B;
+impl Iterator for B { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct C;
+impl Iterator for C { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+fn numbers(kind: u8) -> impl Iterator<Item = u32> {
+    match$0 kind {
+        0 => A,
+        1 => B,
+        _ => C,
+    }
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_for_non_iterators() {
+        check_assist_not_applicable(
+            wrap_match_arms_in_either,
+            r#"
+//- minicore: iterator
+fn numbers(even: bool) -> u32 {
+    match$0 even {
+        true => 0u8,
+        false => 1u32,
+    }
+}
+"#,
+        );
+    }
+}
@@ -111,68 +111,137 @@ mod handlers {
     mod add_label_to_loop;
     mod add_lifetime_to_type;
     mod add_missing_impl_members;
+    mod add_ref_to_moving_bindings;
     mod add_turbo_fish;
+    mod add_whole_variant_binding;
+    mod add_wildcard_match_arm;
+    mod allow_match_same_arms;
     mod apply_demorgan;
     mod auto_import;
+    mod box_match_arms_as_trait_object;
     mod change_visibility;
+    mod collapse_uniform_match;
+    mod convert_arm_flag_to_labeled_block;
+    mod convert_bit_match_to_const_fn;
+    mod convert_bool_match_to_matches_macro;
     mod convert_bool_then;
+    mod convert_boxed_error_to_into;
+    mod convert_byte_patterns_to_byte_chars;
+    mod convert_checked_match_to_saturating;
+    mod convert_closure_arm_to_fn_item;
     mod convert_comment_block;
+    mod convert_cyclic_match_to_modular_arithmetic;
+    mod convert_find_loop_to_find_map;
+    mod convert_flag_match_to_or_accumulation;
+    mod convert_get_mut_match_to_entry;
+    mod convert_guarded_arms_to_guard_ladder;
+    mod convert_index_loop_to_position;
     mod convert_integer_literal;
     mod convert_into_to_from;
     mod convert_iter_for_each_to_for;
     mod convert_let_else_to_match;
+    mod convert_match_loop_to_for_each;
+    mod convert_match_loop_to_partition;
+    mod convert_match_to_cow;
+    mod convert_match_to_downcast_chain;
+    mod convert_match_to_dyn_dispatch;
+    mod convert_match_to_expect;
+    mod convert_match_to_fn_dispatch;
+    mod convert_match_to_guard_clauses;
+    mod convert_match_to_indexed_access;
     mod convert_match_to_let_else;
+    mod convert_match_to_variant_table;
+    mod convert_ordering_match_to_min_max;
+    mod convert_panic_arm_to_err;
+    mod convert_poll_match_to_ready;
+    mod convert_prefix_match_to_strip_prefix;
+    mod convert_range_binding_to_guard;
+    mod convert_range_match_to_table;
+    mod convert_removal_loop_to_retain;
+    mod convert_result_match_to_map;
+    mod convert_result_match_to_try_block;
+    mod convert_str_match_to_sorted_table;
+    mod convert_string_match_to_command_enum;
+    mod convert_try_loop_to_collect;
+    mod convert_tuple_match_to_if_chain;
+    mod convert_tuple_match_to_nested_match;
+    mod convert_tuple_match_to_struct;
     mod convert_tuple_struct_to_named_struct;
     mod convert_named_struct_to_tuple_struct;
     mod convert_to_guarded_return;
     mod convert_two_arm_bool_match_to_matches_macro;
+    mod convert_variant_if_chain_to_match;
+    mod convert_vec_pushes_to_vec_macro;
     mod convert_while_to_loop;
     mod desugar_doc_comment;
     mod destructure_tuple_binding;
     mod expand_glob_import;
+    mod extract_event_enum_from_log_match;
     mod extract_expressions_from_format_string;
     mod extract_function;
+    mod extract_match_closure_to_fn;
+    mod extract_match_to_from_impl;
     mod extract_module;
+    mod extract_shared_match_guard;
     mod extract_struct_from_enum_variant;
+    mod extract_transition_table_from_match;
     mod extract_type_alias;
     mod extract_variable;
     mod add_missing_match_arms;
+    mod extract_variant_weight_method;
     mod fix_visibility;
     mod flip_binexpr;
     mod flip_comma;
     mod flip_trait_bound;
+    mod generate_code_conversions_from_match;
     mod generate_constant;
     mod generate_default_from_enum_variant;
     mod generate_default_from_new;
     mod generate_deref;
     mod generate_derive;
     mod generate_documentation_template;
+    mod generate_enum_from_int_match;
     mod generate_enum_is_method;
     mod generate_enum_projection_method;
     mod generate_enum_variant;
     mod generate_from_impl_for_enum;
     mod generate_function;
     mod generate_getter;
+    mod generate_hash_match;
     mod generate_impl;
     mod generate_is_empty_from_len;
+    mod generate_match_arm_tests;
     mod generate_new;
     mod generate_setter;
     mod generate_delegate_methods;
     mod add_return_type;
+    mod generate_variant_name_match;
+    mod hoist_clone_out_of_match;
+    mod hoist_constructor_out_of_match;
+    mod hoist_format_out_of_match;
+    mod hoist_guard_computation_out_of_match;
+    mod hoist_repeated_guard_call;
+    mod hoist_shared_return_out_of_match;
     mod inline_call;
+    mod inline_const_in_pattern;
     mod inline_local_variable;
     mod inline_macro;
     mod inline_type_alias;
     mod introduce_named_lifetime;
     mod invert_if;
+    mod merge_if_lets_into_match;
     mod merge_imports;
     mod merge_match_arms;
     mod move_bounds;
     mod move_const_to_impl;
     mod move_guard;
+    mod move_guard_into_pattern;
+    mod move_matches_guard_into_pattern;
     mod move_module_to_file;
+    mod move_side_effecting_guard_to_arm_body;
     mod move_to_mod_rs;
     mod move_from_mod_rs;
+    mod move_wildcard_arm_last;
     mod number_representation;
     mod promote_local_to_const;
     mod pull_assignment_up;
@@ -183,8 +252,12 @@ mod handlers {
     mod remove_mut;
     mod remove_unused_param;
     mod remove_parentheses;
+    mod remove_wildcard_subpattern;
     mod reorder_fields;
     mod reorder_impl_items;
+    mod replace_infallible_match_with_unwrap;
+    mod replace_match_impl_with_derive;
+    mod replace_match_with_unwrap_or_default;
     mod replace_try_expr_with_match;
     mod replace_derive_with_manual_impl;
     mod replace_if_let_with_match;
@@ -195,7 +268,11 @@ mod handlers {
     mod replace_qualified_name_with_use;
     mod replace_string_with_char;
     mod replace_turbofish_with_explicit_type;
+    mod replace_variant_array_with_enum_iter;
+    mod sort_integer_match_arms;
+    mod sort_or_pattern;
     mod split_import;
+    mod split_match_guard;
     mod unmerge_match_arm;
     mod unwrap_tuple;
     mod sort_items;
@@ -205,6 +282,8 @@ mod handlers {
     mod unwrap_block;
     mod unwrap_result_return_type;
     mod unqualify_method_call;
+    mod use_match_ergonomics;
+    mod wrap_match_arms_in_either;
     mod wrap_return_type_in_result;
 
     pub(crate) fn all() -> &'static [Handler] {
@@ -215,53 +294,117 @@ pub(crate) fn all() -> &'static [Handler] {
             add_label_to_loop::add_label_to_loop,
             add_missing_match_arms::add_missing_match_arms,
             add_lifetime_to_type::add_lifetime_to_type,
+            add_ref_to_moving_bindings::add_ref_to_moving_bindings,
             add_return_type::add_return_type,
             add_turbo_fish::add_turbo_fish,
+            add_whole_variant_binding::add_whole_variant_binding,
+            add_wildcard_match_arm::add_wildcard_match_arm,
+            allow_match_same_arms::allow_match_same_arms,
             apply_demorgan::apply_demorgan,
             auto_import::auto_import,
+            box_match_arms_as_trait_object::box_match_arms_as_trait_object,
             change_visibility::change_visibility,
+            collapse_uniform_match::collapse_uniform_match,
+            convert_arm_flag_to_labeled_block::convert_arm_flag_to_labeled_block,
+            convert_bit_match_to_const_fn::convert_bit_match_to_const_fn,
+            convert_bool_match_to_matches_macro::convert_bool_match_to_matches_macro,
             convert_bool_then::convert_bool_then_to_if,
             convert_bool_then::convert_if_to_bool_then,
+            convert_boxed_error_to_into::convert_boxed_error_to_into,
+            convert_byte_patterns_to_byte_chars::convert_byte_patterns_to_byte_chars,
+            convert_checked_match_to_saturating::convert_checked_match_to_saturating,
+            convert_closure_arm_to_fn_item::convert_closure_arm_to_fn_item,
             convert_comment_block::convert_comment_block,
+            convert_cyclic_match_to_modular_arithmetic::convert_cyclic_match_to_modular_arithmetic,
+            convert_find_loop_to_find_map::convert_find_loop_to_find_map,
+            convert_flag_match_to_or_accumulation::convert_flag_match_to_or_accumulation,
+            convert_get_mut_match_to_entry::convert_get_mut_match_to_entry,
+            convert_guarded_arms_to_guard_ladder::convert_guarded_arms_to_guard_ladder,
+            convert_index_loop_to_position::convert_index_loop_to_position,
             convert_integer_literal::convert_integer_literal,
             convert_into_to_from::convert_into_to_from,
             convert_iter_for_each_to_for::convert_iter_for_each_to_for,
             convert_iter_for_each_to_for::convert_for_loop_with_for_each,
             convert_let_else_to_match::convert_let_else_to_match,
+            convert_match_loop_to_for_each::convert_match_loop_to_for_each,
+            convert_match_loop_to_partition::convert_match_loop_to_partition,
+            convert_match_to_cow::convert_match_to_cow,
+            convert_match_to_downcast_chain::convert_match_to_downcast_chain,
+            convert_match_to_dyn_dispatch::convert_match_to_dyn_dispatch,
+            convert_match_to_expect::convert_match_to_expect,
+            convert_match_to_fn_dispatch::convert_match_to_fn_dispatch,
+            convert_match_to_guard_clauses::convert_match_to_guard_clauses,
+            convert_match_to_indexed_access::convert_match_to_indexed_access,
+            convert_match_to_variant_table::convert_match_to_variant_table,
             convert_named_struct_to_tuple_struct::convert_named_struct_to_tuple_struct,
             convert_match_to_let_else::convert_match_to_let_else,
+            convert_ordering_match_to_min_max::convert_ordering_match_to_min_max,
+            convert_panic_arm_to_err::convert_panic_arm_to_err,
+            convert_poll_match_to_ready::convert_poll_match_to_ready,
+            convert_prefix_match_to_strip_prefix::convert_prefix_match_to_strip_prefix,
+            convert_range_binding_to_guard::convert_range_binding_to_guard,
+            convert_range_match_to_table::convert_range_match_to_table,
+            convert_removal_loop_to_retain::convert_removal_loop_to_retain,
+            convert_result_match_to_map::convert_result_match_to_map,
+            convert_result_match_to_try_block::convert_result_match_to_try_block,
+            convert_str_match_to_sorted_table::convert_str_match_to_sorted_table,
+            convert_string_match_to_command_enum::convert_string_match_to_command_enum,
             convert_to_guarded_return::convert_to_guarded_return,
+            convert_try_loop_to_collect::convert_try_loop_to_collect,
+            convert_tuple_match_to_if_chain::convert_tuple_match_to_if_chain,
+            convert_tuple_match_to_nested_match::convert_tuple_match_to_nested_match,
+            convert_tuple_match_to_struct::convert_tuple_match_to_struct,
             convert_tuple_struct_to_named_struct::convert_tuple_struct_to_named_struct,
             convert_two_arm_bool_match_to_matches_macro::convert_two_arm_bool_match_to_matches_macro,
+            convert_variant_if_chain_to_match::convert_variant_if_chain_to_match,
+            convert_vec_pushes_to_vec_macro::convert_vec_pushes_to_vec_macro,
             convert_while_to_loop::convert_while_to_loop,
             desugar_doc_comment::desugar_doc_comment,
             destructure_tuple_binding::destructure_tuple_binding,
             expand_glob_import::expand_glob_import,
+            extract_event_enum_from_log_match::extract_event_enum_from_log_match,
             extract_expressions_from_format_string::extract_expressions_from_format_string,
+            extract_match_closure_to_fn::extract_match_closure_to_fn,
+            extract_match_to_from_impl::extract_match_to_from_impl,
+            extract_shared_match_guard::extract_shared_match_guard,
             extract_struct_from_enum_variant::extract_struct_from_enum_variant,
+            extract_transition_table_from_match::extract_transition_table_from_match,
             extract_type_alias::extract_type_alias,
+            extract_variant_weight_method::extract_variant_weight_method,
             fix_visibility::fix_visibility,
             flip_binexpr::flip_binexpr,
             flip_comma::flip_comma,
             flip_trait_bound::flip_trait_bound,
+            generate_code_conversions_from_match::generate_code_conversions_from_match,
             generate_constant::generate_constant,
             generate_default_from_enum_variant::generate_default_from_enum_variant,
             generate_default_from_new::generate_default_from_new,
             generate_derive::generate_derive,
             generate_documentation_template::generate_documentation_template,
             generate_documentation_template::generate_doc_example,
+            generate_enum_from_int_match::generate_enum_from_int_match,
             generate_enum_is_method::generate_enum_is_method,
             generate_enum_projection_method::generate_enum_as_method,
             generate_enum_projection_method::generate_enum_try_into_method,
             generate_enum_variant::generate_enum_variant,
             generate_from_impl_for_enum::generate_from_impl_for_enum,
             generate_function::generate_function,
+            generate_hash_match::generate_hash_match,
             generate_impl::generate_impl,
             generate_impl::generate_trait_impl,
             generate_is_empty_from_len::generate_is_empty_from_len,
+            generate_match_arm_tests::generate_match_arm_tests,
             generate_new::generate_new,
+            generate_variant_name_match::generate_variant_name_match,
+            hoist_clone_out_of_match::hoist_clone_out_of_match,
+            hoist_constructor_out_of_match::hoist_constructor_out_of_match,
+            hoist_format_out_of_match::hoist_format_out_of_match,
+            hoist_guard_computation_out_of_match::hoist_guard_computation_out_of_match,
+            hoist_repeated_guard_call::hoist_repeated_guard_call,
+            hoist_shared_return_out_of_match::hoist_shared_return_out_of_match,
             inline_call::inline_call,
             inline_call::inline_into_callers,
+            inline_const_in_pattern::inline_const_in_pattern,
             inline_local_variable::inline_local_variable,
             inline_type_alias::inline_type_alias,
             inline_type_alias::inline_type_alias_uses,
@@ -269,15 +412,20 @@ pub(crate) fn all() -> &'static [Handler] {
             introduce_named_generic::introduce_named_generic,
             introduce_named_lifetime::introduce_named_lifetime,
             invert_if::invert_if,
+            merge_if_lets_into_match::merge_if_lets_into_match,
             merge_imports::merge_imports,
             merge_match_arms::merge_match_arms,
             move_bounds::move_bounds_to_where_clause,
             move_const_to_impl::move_const_to_impl,
             move_guard::move_arm_cond_to_match_guard,
             move_guard::move_guard_to_arm_body,
+            move_guard_into_pattern::move_guard_into_pattern,
+            move_matches_guard_into_pattern::move_matches_guard_into_pattern,
             move_module_to_file::move_module_to_file,
+            move_side_effecting_guard_to_arm_body::move_side_effecting_guard_to_arm_body,
             move_to_mod_rs::move_to_mod_rs,
             move_from_mod_rs::move_from_mod_rs,
+            move_wildcard_arm_last::move_wildcard_arm_last,
             number_representation::reformat_number_literal,
             pull_assignment_up::pull_assignment_up,
             promote_local_to_const::promote_local_to_const,
@@ -290,8 +438,12 @@ pub(crate) fn all() -> &'static [Handler] {
             remove_mut::remove_mut,
             remove_unused_param::remove_unused_param,
             remove_parentheses::remove_parentheses,
+            remove_wildcard_subpattern::remove_wildcard_subpattern,
             reorder_fields::reorder_fields,
             reorder_impl_items::reorder_impl_items,
+            replace_infallible_match_with_unwrap::replace_infallible_match_with_unwrap,
+            replace_match_impl_with_derive::replace_match_impl_with_derive,
+            replace_match_with_unwrap_or_default::replace_match_with_unwrap_or_default,
             replace_try_expr_with_match::replace_try_expr_with_match,
             replace_derive_with_manual_impl::replace_derive_with_manual_impl,
             replace_if_let_with_match::replace_if_let_with_match,
@@ -304,8 +456,12 @@ pub(crate) fn all() -> &'static [Handler] {
             replace_arith_op::replace_arith_with_wrapping,
             replace_arith_op::replace_arith_with_checked,
             replace_arith_op::replace_arith_with_saturating,
+            replace_variant_array_with_enum_iter::replace_variant_array_with_enum_iter,
+            sort_integer_match_arms::sort_integer_match_arms,
             sort_items::sort_items,
+            sort_or_pattern::sort_or_pattern,
             split_import::split_import,
+            split_match_guard::split_match_guard,
             toggle_ignore::toggle_ignore,
             unmerge_match_arm::unmerge_match_arm,
             unmerge_use::unmerge_use,
@@ -314,6 +470,8 @@ pub(crate) fn all() -> &'static [Handler] {
             unwrap_result_return_type::unwrap_result_return_type,
             unwrap_tuple::unwrap_tuple,
             unqualify_method_call::unqualify_method_call,
+            use_match_ergonomics::use_match_ergonomics,
+            wrap_match_arms_in_either::wrap_match_arms_in_either,
             wrap_return_type_in_result::wrap_return_type_in_result,
             // These are manually sorted for better priorities. By default,
             // priority is determined by the size of the target range (smaller
@@ -195,6 +195,36 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_add_ref_to_moving_bindings() {
+    check_doc_test(
+        "add_ref_to_moving_bindings",
+        r#####"
+//- minicore: copy
+struct Name;
+struct Person { name: Name, age: u32 }
+
+fn greet(person: Person) -> Person {
+    match person {
+        Person { name, age }$0 => hello(name, age),
+    }
+    person
+}
+"#####,
+        r#####"
+struct Name;
+struct Person { name: Name, age: u32 }
+
+fn greet(person: Person) -> Person {
+    match person {
+        Person { ref name, age } => hello(name, age),
+    }
+    person
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_add_return_type() {
     check_doc_test(
@@ -227,6 +257,89 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_add_whole_variant_binding() {
+    check_doc_test(
+        "add_whole_variant_binding",
+        r#####"
+enum Shape { Circle { radius: u32 }, Square(u32) }
+fn draw(shape: &Shape) {}
+
+fn render(shape: &Shape) {
+    match shape {
+        $0Shape::Circle { radius } if *radius > 0 => draw(shape),
+        _ => {}
+    }
+}
+"#####,
+        r#####"
+enum Shape { Circle { radius: u32 }, Square(u32) }
+fn draw(shape: &Shape) {}
+
+fn render(shape: &Shape) {
+    match shape {
+        circle @ Shape::Circle { radius } if *radius > 0 => draw(circle),
+        _ => {}
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_add_wildcard_match_arm() {
+    check_doc_test(
+        "add_wildcard_match_arm",
+        r#####"
+enum Action { Move, Jump, Stop }
+
+fn handle(action: Action) -> u32 {
+    match action {
+        Action::Move => 1,$0
+    }
+}
+"#####,
+        r#####"
+enum Action { Move, Jump, Stop }
+
+fn handle(action: Action) -> u32 {
+    match action {
+        Action::Move => 1,
+        $0_ => unimplemented!(),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_allow_match_same_arms() {
+    check_doc_test(
+        "allow_match_same_arms",
+        r#####"
+enum Key { Up, Down }
+
+fn step(key: Key) -> i32 {
+    match key {
+        Key::Up => 1,
+        $0Key::Down => 1,
+    }
+}
+"#####,
+        r#####"
+enum Key { Up, Down }
+
+fn step(key: Key) -> i32 {
+    match key {
+        Key::Up => 1,
+        #[allow(clippy::match_same_arms)]
+        Key::Down => 1,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_apply_demorgan() {
     check_doc_test(
@@ -265,6 +378,41 @@ pub mod std { pub mod collections { pub struct HashMap { } } }
     )
 }
 
+#[test]
+fn doctest_box_match_arms_as_trait_object() {
+    check_doc_test(
+        "box_match_arms_as_trait_object",
+        r#####"
+trait Shape {}
+struct Circle;
+struct Square;
+impl Shape for Circle {}
+impl Shape for Square {}
+
+fn shape(round: bool) {
+    let s = $0match round {
+        true => Circle,
+        false => Square,
+    };
+}
+"#####,
+        r#####"
+trait Shape {}
+struct Circle;
+struct Square;
+impl Shape for Circle {}
+impl Shape for Square {}
+
+fn shape(round: bool) {
+    let s = match round {
+        true => Box::new(Circle) as Box<dyn Shape>,
+        false => Box::new(Square) as Box<dyn Shape>,
+    };
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_change_visibility() {
     check_doc_test(
@@ -278,6 +426,146 @@ pub(crate) fn frobnicate() {}
     )
 }
 
+#[test]
+fn doctest_collapse_uniform_match() {
+    check_doc_test(
+        "collapse_uniform_match",
+        r#####"
+enum Mode { Read, Write }
+struct File { mode: Mode, name: String }
+
+impl File {
+    fn name(&self) -> &String {
+        $0match self.mode {
+            Mode::Read => &self.name,
+            Mode::Write => &self.name,
+        }
+    }
+}
+"#####,
+        r#####"
+enum Mode { Read, Write }
+struct File { mode: Mode, name: String }
+
+impl File {
+    fn name(&self) -> &String {
+        &self.name
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_arm_flag_to_labeled_block() {
+    check_doc_test(
+        "convert_arm_flag_to_labeled_block",
+        r#####"
+fn price(item: Option<u32>) -> u32 {
+    match item {
+        Some(n) => {$0
+            let mut price = 0;
+            let mut done = false;
+            if n == 0 {
+                price = 1;
+                done = true;
+            }
+            if !done {
+                let base = n * 2;
+                if base > 100 {
+                    price = 100;
+                    done = true;
+                }
+                if !done {
+                    price = base + 1;
+                }
+            }
+            price
+        }
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn price(item: Option<u32>) -> u32 {
+    match item {
+        Some(n) => 'arm: {
+            if n == 0 {
+                break 'arm 1;
+            }
+            let base = n * 2;
+            if base > 100 {
+                break 'arm 100;
+            }
+            base + 1
+        }
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_bit_match_to_const_fn() {
+    check_doc_test(
+        "convert_bit_match_to_const_fn",
+        r#####"
+enum Perm { Read, Write, Exec }
+
+fn mask(perm: Perm) -> u32 {
+    $0match perm {
+        Perm::Read => 1,
+        Perm::Write => 2,
+        Perm::Exec => 4,
+    }
+}
+"#####,
+        r#####"
+enum Perm { Read, Write, Exec }
+
+impl Perm {
+    const fn $0as_bit(&self) -> u32 {
+        match self {
+            Self::Read => 1,
+            Self::Write => 2,
+            Self::Exec => 4,
+        }
+    }
+}
+
+fn mask(perm: Perm) -> u32 {
+    perm.as_bit()
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_bool_match_to_matches_macro() {
+    check_doc_test(
+        "convert_bool_match_to_matches_macro",
+        r#####"
+enum State { Active, Idle, Done }
+
+fn is_idle(state: State) -> bool {
+    match state$0 {
+        State::Active => false,
+        State::Idle => true,
+        _ => false,
+    }
+}
+"#####,
+        r#####"
+enum State { Active, Idle, Done }
+
+fn is_idle(state: State) -> bool {
+    matches!(state, State::Idle)
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_bool_then_to_if() {
     check_doc_test(
@@ -289,205 +577,1321 @@ fn main() {
 }
 "#####,
         r#####"
-fn main() {
-    if 0 == 0 {
-        Some(val)
-    } else {
-        None
-    }
+fn main() {
+    if 0 == 0 {
+        Some(val)
+    } else {
+        None
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_boxed_error_to_into() {
+    check_doc_test(
+        "convert_boxed_error_to_into",
+        r#####"
+//- minicore: from, error, result
+struct Box<T: ?Sized>(T);
+impl<T> Box<T> { pub fn new(x: T) -> Self { loop {} } }
+impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+    fn from(err: E) -> Self { loop {} }
+}
+#[derive(Debug)]
+struct ParseError;
+impl core::fmt::Display for ParseError {}
+impl core::error::Error for ParseError {}
+fn parse(s: Option<u8>) -> Result<u8, Box<dyn core::error::Error>> {
+    match s {
+        Some(b) => Ok(b),
+        $0None => Err(Box::new(ParseError)),
+    }
+}
+"#####,
+        r#####"
+struct Box<T: ?Sized>(T);
+impl<T> Box<T> { pub fn new(x: T) -> Self { loop {} } }
+impl<E: core::error::Error> From<E> for Box<dyn core::error::Error> {
+    fn from(err: E) -> Self { loop {} }
+}
+#[derive(Debug)]
+struct ParseError;
+impl core::fmt::Display for ParseError {}
+impl core::error::Error for ParseError {}
+fn parse(s: Option<u8>) -> Result<u8, Box<dyn core::error::Error>> {
+    match s {
+        Some(b) => Ok(b),
+        None => Err(ParseError.into()),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_byte_patterns_to_byte_chars() {
+    check_doc_test(
+        "convert_byte_patterns_to_byte_chars",
+        r#####"
+fn classify(b: u8) -> u32 {
+    $0match b {
+        0x41..=0x5a => 1,
+        0x0a | 32 => 2,
+        _ => 0,
+    }
+}
+"#####,
+        r#####"
+fn classify(b: u8) -> u32 {
+    match b {
+        b'A'..=b'Z' => 1,
+        0x0a | b' ' => 2,
+        _ => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_checked_match_to_saturating() {
+    check_doc_test(
+        "convert_checked_match_to_saturating",
+        r#####"
+//- minicore: option
+fn next(n: u32, step: u32) -> u32 {
+    $0match n.checked_add(step) {
+        Some(v) => v,
+        None => u32::MAX,
+    }
+}
+"#####,
+        r#####"
+fn next(n: u32, step: u32) -> u32 {
+    n.saturating_add(step)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_closure_arm_to_fn_item() {
+    check_doc_test(
+        "convert_closure_arm_to_fn_item",
+        r#####"
+fn double(x: u32) -> u32 { x * 2 }
+
+fn pick(twice: bool) -> fn(u32) -> u32 {
+    match twice {
+        true => $0|x| double(x),
+        false => |x| x,
+    }
+}
+"#####,
+        r#####"
+fn double(x: u32) -> u32 { x * 2 }
+
+fn pick(twice: bool) -> fn(u32) -> u32 {
+    match twice {
+        true => double,
+        false => |x| x,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_cyclic_match_to_modular_arithmetic() {
+    check_doc_test(
+        "convert_cyclic_match_to_modular_arithmetic",
+        r#####"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Light { Red, Green, Yellow }
+
+fn next(light: Light) -> Light {
+    $0match light {
+        Light::Red => Light::Green,
+        Light::Green => Light::Yellow,
+        Light::Yellow => Light::Red,
+    }
+}
+"#####,
+        r#####"
+#[derive(Clone, Copy)]
+enum Light { Red, Green, Yellow }
+
+fn next(light: Light) -> Light {
+    [Light::Red, Light::Green, Light::Yellow][(light as usize + 1) % 3]
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_find_loop_to_find_map() {
+    check_doc_test(
+        "convert_find_loop_to_find_map",
+        r#####"
+//- minicore: iterator, option
+enum Entry { File(u32), Dir }
+
+fn first_file(entries: impl Iterator<Item = Entry>) -> Option<u32> {
+    $0for entry in entries {
+        match entry {
+            Entry::File(size) => return Some(size),
+            Entry::Dir => {}
+        }
+    }
+    None
+}
+"#####,
+        r#####"
+enum Entry { File(u32), Dir }
+
+fn first_file(entries: impl Iterator<Item = Entry>) -> Option<u32> {
+    entries.find_map(|entry| match entry {
+        Entry::File(size) => Some(size),
+        Entry::Dir => None,
+    })
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_flag_match_to_or_accumulation() {
+    check_doc_test(
+        "convert_flag_match_to_or_accumulation",
+        r#####"
+enum Perm { Read, Write, Exec }
+const READ: u8 = 4;
+const WRITE: u8 = 2;
+const EXEC: u8 = 1;
+
+fn add(mode: &mut u8, perm: Perm) {
+    $0match perm {
+        Perm::Read => *mode |= READ,
+        Perm::Write => *mode |= WRITE,
+        Perm::Exec => *mode |= EXEC,
+    }
+}
+"#####,
+        r#####"
+enum Perm { Read, Write, Exec }
+const READ: u8 = 4;
+const WRITE: u8 = 2;
+const EXEC: u8 = 1;
+
+fn add(mode: &mut u8, perm: Perm) {
+    *mode |= match perm {
+        Perm::Read => READ,
+        Perm::Write => WRITE,
+        Perm::Exec => EXEC,
+    };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_for_loop_with_for_each() {
+    check_doc_test(
+        "convert_for_loop_with_for_each",
+        r#####"
+fn main() {
+    let x = vec![1, 2, 3];
+    for$0 v in x {
+        let y = v * 2;
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    let x = vec![1, 2, 3];
+    x.into_iter().for_each(|v| {
+        let y = v * 2;
+    });
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_get_mut_match_to_entry() {
+    check_doc_test(
+        "convert_get_mut_match_to_entry",
+        r#####"
+//- minicore: option
+//- /main.rs crate:main deps:std
+use std::collections::HashMap;
+
+fn count(map: &mut HashMap<char, u32>, c: char) {
+    $0match map.get_mut(&c) {
+        Some(n) => *n += 1,
+        None => {
+            map.insert(c, 1);
+        }
+    }
+}
+//- /std.rs crate:std
+pub mod collections {
+    pub struct HashMap<K, V>(K, V);
+    impl<K, V> HashMap<K, V> {
+        pub fn get_mut(&mut self, k: &K) -> Option<&mut V> { loop {} }
+        pub fn insert(&mut self, k: K, v: V) -> Option<V> { loop {} }
+    }
+}
+"#####,
+        r#####"
+use std::collections::HashMap;
+
+fn count(map: &mut HashMap<char, u32>, c: char) {
+    map.entry(c).and_modify(|n| *n += 1).or_insert(1);
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_guarded_arms_to_guard_ladder() {
+    check_doc_test(
+        "convert_guarded_arms_to_guard_ladder",
+        r#####"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        $0Some(n) if n > 10 => 2,
+        Some(n) if n > 5 => 1,
+        Some(n) => 0,
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn classify(x: Option<u32>) -> u8 {
+    match x {
+        Some(n) => match () {
+            _ if n > 10 => 2,
+            _ if n > 5 => 1,
+            _ => 0,
+        },
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_if_to_bool_then() {
+    check_doc_test(
+        "convert_if_to_bool_then",
+        r#####"
+//- minicore: option
+fn main() {
+    if$0 cond {
+        Some(val)
+    } else {
+        None
+    }
+}
+"#####,
+        r#####"
+fn main() {
+    cond.then(|| val)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_index_loop_to_position() {
+    check_doc_test(
+        "convert_index_loop_to_position",
+        r#####"
+//- minicore: iterator, option
+enum Cell { Empty, Full }
+
+fn first_empty(cells: impl Iterator<Item = Cell>) -> Option<usize> {
+    let mut found = None;
+    $0for (i, cell) in cells.enumerate() {
+        match cell {
+            Cell::Empty => {
+                found = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    found
+}
+"#####,
+        r#####"
+enum Cell { Empty, Full }
+
+fn first_empty(cells: impl Iterator<Item = Cell>) -> Option<usize> {
+    let found = cells.into_iter().position(|cell| matches!(cell, Cell::Empty));
+    found
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_integer_literal() {
+    check_doc_test(
+        "convert_integer_literal",
+        r#####"
+const _: i32 = 10$0;
+"#####,
+        r#####"
+const _: i32 = 0b1010;
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_into_to_from() {
+    check_doc_test(
+        "convert_into_to_from",
+        r#####"
+//- minicore: from
+impl $0Into<Thing> for usize {
+    fn into(self) -> Thing {
+        Thing {
+            b: self.to_string(),
+            a: self
+        }
+    }
+}
+"#####,
+        r#####"
+impl From<usize> for Thing {
+    fn from(val: usize) -> Self {
+        Thing {
+            b: val.to_string(),
+            a: val
+        }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_iter_for_each_to_for() {
+    check_doc_test(
+        "convert_iter_for_each_to_for",
+        r#####"
+//- minicore: iterators
+use core::iter;
+fn main() {
+    let iter = iter::repeat((9, 2));
+    iter.for_each$0(|(x, y)| {
+        println!("x: {}, y: {}", x, y);
+    });
+}
+"#####,
+        r#####"
+use core::iter;
+fn main() {
+    let iter = iter::repeat((9, 2));
+    for (x, y) in iter {
+        println!("x: {}, y: {}", x, y);
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_let_else_to_match() {
+    check_doc_test(
+        "convert_let_else_to_match",
+        r#####"
+fn main() {
+    let Ok(mut x) = f() else$0 { return };
+}
+"#####,
+        r#####"
+fn main() {
+    let mut x = match f() {
+        Ok(x) => x,
+        _ => return,
+    };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_loop_to_for_each() {
+    check_doc_test(
+        "convert_match_loop_to_for_each",
+        r#####"
+//- minicore: iterator
+enum Event { Click(u32), Key(char) }
+fn click(x: u32) {}
+
+fn dispatch(events: impl Iterator<Item = Event>) {
+    $0for event in events {
+        match event {
+            Event::Click(x) => click(x),
+            Event::Key(_) => {}
+        }
+    }
+}
+"#####,
+        r#####"
+enum Event { Click(u32), Key(char) }
+fn click(x: u32) {}
+
+fn dispatch(events: impl Iterator<Item = Event>) {
+    events.for_each(|event| match event {
+        Event::Click(x) => click(x),
+        Event::Key(_) => {}
+    });
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_loop_to_partition() {
+    check_doc_test(
+        "convert_match_loop_to_partition",
+        r#####"
+//- minicore: iterator, option
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+fn split(items: impl Iterator<Item = Option<u32>>) {
+    let mut some = Vec::new();
+    let mut none = Vec::new();
+    $0for item in items {
+        match item {
+            Some(_) => some.push(item),
+            None => none.push(item),
+        }
+    }
+}
+"#####,
+        r#####"
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+fn split(items: impl Iterator<Item = Option<u32>>) {
+    let (some, none): (Vec<_>, Vec<_>) = items.partition(|item| matches!(*item, Some(_)));
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_cow() {
+    check_doc_test(
+        "convert_match_to_cow",
+        r#####"
+//- /main.rs crate:main deps:std,alloc
+use std::string::String;
+
+fn describe(n: u32) -> String { String }
+
+fn name(n: u32) {
+    let name = $0match n {
+        0 => "zero",
+        n => describe(n),
+    };
+}
+//- /std.rs crate:std deps:alloc
+pub use alloc::{borrow, string};
+//- /alloc.rs crate:alloc
+pub mod string { pub struct String; }
+pub mod borrow { pub enum Cow<'a, B: ?Sized> { Borrowed(&'a B), Owned(crate::string::String) } }
+"#####,
+        r#####"
+use std::{string::String, borrow::Cow};
+
+fn describe(n: u32) -> String { String }
+
+fn name(n: u32) {
+    let name = match n {
+        0 => Cow::Borrowed("zero"),
+        n => Cow::Owned(describe(n)),
+    };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_downcast_chain() {
+    check_doc_test(
+        "convert_match_to_downcast_chain",
+        r#####"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square) }
+
+fn draw(shape: &Shape) {
+    $0match shape {
+        Shape::Circle(c) => circle(c),
+        Shape::Square(s) => square(s),
+    }
+}
+"#####,
+        r#####"
+struct Circle;
+struct Square;
+enum Shape { Circle(Circle), Square(Square) }
+
+fn draw(shape: &Shape) {
+    if let Some(c) = shape.downcast_ref::<Circle>() {
+        circle(c)
+    } else if let Some(s) = shape.downcast_ref::<Square>() {
+        square(s)
+    } else {
+        ${0:todo!()}
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_dyn_dispatch() {
+    check_doc_test(
+        "convert_match_to_dyn_dispatch",
+        r#####"
+struct Circle;
+impl Circle { fn circle_area(&self) -> u32 { 3 } }
+struct Square;
+impl Square { fn square_area(&self) -> u32 { 4 } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn area(&self) -> u32 {
+        $0match self {
+            Shape::Circle(c) => c.circle_area(),
+            Shape::Square(s) => s.square_area(),
+        }
+    }
+}
+"#####,
+        r#####"
+struct Circle;
+impl Circle { fn circle_area(&self) -> u32 { 3 } }
+struct Square;
+impl Square { fn square_area(&self) -> u32 { 4 } }
+
+enum Shape { Circle(Circle), Square(Square) }
+
+impl Shape {
+    fn area(&self) -> u32 {
+        self.as_dyn().area()
+    }
+}
+
+trait Area {
+    fn area(&self) -> u32;
+}
+
+impl Area for Circle {
+    fn area(&self) -> u32 {
+        self.circle_area()
+    }
+}
+
+impl Area for Square {
+    fn area(&self) -> u32 {
+        self.square_area()
+    }
+}
+
+impl Shape {
+    fn as_dyn(&self) -> &dyn Area {
+        match self {
+            Shape::Circle(c) => c,
+            Shape::Square(s) => s,
+        }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_expect() {
+    check_doc_test(
+        "convert_match_to_expect",
+        r#####"
+//- minicore: option
+fn port(config: Option<u16>) -> u16 {
+    $0match config {
+        Some(port) => port,
+        None => panic!("missing config"),
+    }
+}
+"#####,
+        r#####"
+fn port(config: Option<u16>) -> u16 {
+    config.expect("missing config")
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_fn_dispatch() {
+    check_doc_test(
+        "convert_match_to_fn_dispatch",
+        r#####"
+enum Event { Key, Click }
+fn on_key(code: u32) -> bool { true }
+fn on_click(code: u32) -> bool { false }
+
+fn dispatch(event: Event, code: u32) -> bool {
+    $0match event {
+        Event::Key => on_key(code),
+        Event::Click => on_click(code),
+    }
+}
+"#####,
+        r#####"
+enum Event { Key, Click }
+fn on_key(code: u32) -> bool { true }
+fn on_click(code: u32) -> bool { false }
+
+fn dispatch(event: Event, code: u32) -> bool {
+    let f: fn(u32) -> bool = match event {
+        Event::Key => on_key,
+        Event::Click => on_click,
+    };
+    f(code)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_guard_clauses() {
+    check_doc_test(
+        "convert_match_to_guard_clauses",
+        r#####"
+//- minicore: result
+fn parse(input: Option<u8>) -> Result<u8, ()> {
+    $0match input {
+        None => return Err(()),
+        Some(0) => return Err(()),
+        Some(n) => {
+            let doubled = n * 2;
+            Ok(doubled)
+        }
+    }
+}
+"#####,
+        r#####"
+fn parse(input: Option<u8>) -> Result<u8, ()> {
+    let n = match input {
+        None => return Err(()),
+        Some(0) => return Err(()),
+        Some(n) => n,
+    };
+    let doubled = n * 2;
+    Ok(doubled)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_indexed_access() {
+    check_doc_test(
+        "convert_match_to_indexed_access",
+        r#####"
+//- minicore: copy, derive
+#[derive(Clone, Copy)]
+enum Size { Small, Medium, Large }
+
+fn price(size: Size, prices: [u32; 3]) -> u32 {
+    $0match size {
+        Size::Small => prices[0],
+        Size::Medium => prices[1],
+        Size::Large => prices[2],
+    }
+}
+"#####,
+        r#####"
+#[derive(Clone, Copy)]
+enum Size { Small, Medium, Large }
+
+fn price(size: Size, prices: [u32; 3]) -> u32 {
+    prices[size as usize]
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_let_else() {
+    check_doc_test(
+        "convert_match_to_let_else",
+        r#####"
+//- minicore: option
+fn foo(opt: Option<()>) {
+    let val = $0match opt {
+        Some(it) => it,
+        None => return,
+    };
+}
+"#####,
+        r#####"
+fn foo(opt: Option<()>) {
+    let Some(val) = opt else { return };
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_match_to_variant_table() {
+    check_doc_test(
+        "convert_match_to_variant_table",
+        r#####"
+enum Size { Small, Medium, Large }
+
+fn widths(size: Size) {
+    let width = $0match size {
+        Size::Small => 8,
+        Size::Medium => 16,
+        Size::Large => 32,
+    };
+}
+"#####,
+        r#####"
+enum Size { Small, Medium, Large }
+
+fn widths(size: Size) {
+    let width = [Size::Small, Size::Medium, Size::Large].map(|size| match size {
+        Size::Small => 8,
+        Size::Medium => 16,
+        Size::Large => 32,
+    });
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_named_struct_to_tuple_struct() {
+    check_doc_test(
+        "convert_named_struct_to_tuple_struct",
+        r#####"
+struct Point$0 { x: f32, y: f32 }
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Point { x, y }
+    }
+
+    pub fn x(&self) -> f32 {
+        self.x
+    }
+
+    pub fn y(&self) -> f32 {
+        self.y
+    }
+}
+"#####,
+        r#####"
+struct Point(f32, f32);
+
+impl Point {
+    pub fn new(x: f32, y: f32) -> Self {
+        Point(x, y)
+    }
+
+    pub fn x(&self) -> f32 {
+        self.0
+    }
+
+    pub fn y(&self) -> f32 {
+        self.1
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_ordering_match_to_min_max() {
+    check_doc_test(
+        "convert_ordering_match_to_min_max",
+        r#####"
+//- minicore: ord
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    $0match a.cmp(&b) {
+        Ordering::Less | Ordering::Equal => a,
+        Ordering::Greater => b,
+    }
+}
+"#####,
+        r#####"
+use core::cmp::Ordering;
+
+fn smaller(a: u32, b: u32) -> u32 {
+    a.min(b)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_panic_arm_to_err() {
+    check_doc_test(
+        "convert_panic_arm_to_err",
+        r#####"
+//- minicore: result
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => $0panic!("unexpected"),
+    }
+}
+"#####,
+        r#####"
+enum MyError { Unexpected }
+
+fn parse(c: char) -> Result<u8, MyError> {
+    match c {
+        '0' => Ok(0),
+        _ => Err(${0:MyError::Unexpected}),
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_poll_match_to_ready() {
+    check_doc_test(
+        "convert_poll_match_to_ready",
+        r#####"
+//- minicore: future
+use core::task::{Context, Poll};
+
+fn poll_value(inner: Poll<u32>, cx: &mut Context<'_>) -> Poll<u32> {
+    let value = $0match inner {
+        Poll::Ready(v) => v,
+        Poll::Pending => return Poll::Pending,
+    };
+    Poll::Ready(value + 1)
+}
+"#####,
+        r#####"
+use core::task::{Context, Poll, ready};
+
+fn poll_value(inner: Poll<u32>, cx: &mut Context<'_>) -> Poll<u32> {
+    let value = ready!(inner);
+    Poll::Ready(value + 1)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_prefix_match_to_strip_prefix() {
+    check_doc_test(
+        "convert_prefix_match_to_strip_prefix",
+        r#####"
+//- minicore: option
+fn run(line: &str) -> u32 {
+    $0match line {
+        _ if line.starts_with("cmd:") => 1,
+        _ if line.starts_with("msg:") => 2,
+        _ => 0,
+    }
+}
+"#####,
+        r#####"
+fn run(line: &str) -> u32 {
+    if let Some(rest) = line.strip_prefix("cmd:") {
+        1
+    } else if let Some(rest) = line.strip_prefix("msg:") {
+        2
+    } else {
+        0
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_range_binding_to_guard() {
+    check_doc_test(
+        "convert_range_binding_to_guard",
+        r#####"
+fn classify(x: u32) -> &'static str {
+    match x {
+        $0n @ 1..=10 => "small",
+        _ => "big",
+    }
+}
+"#####,
+        r#####"
+fn classify(x: u32) -> &'static str {
+    match x {
+        n if (1..=10).contains(&n) => "small",
+        _ => "big",
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_range_match_to_table() {
+    check_doc_test(
+        "convert_range_match_to_table",
+        r#####"
+enum Tier { Low, Mid, High }
+
+fn tier(n: u32) -> Tier {
+    $0match n {
+        0..=9 => Tier::Low,
+        10..=99 => Tier::Mid,
+        _ => Tier::High,
+    }
+}
+"#####,
+        r#####"
+enum Tier { Low, Mid, High }
+
+const TIER_RANGES: [(core::ops::RangeInclusive<u32>, Tier); 2] = [
+    (0..=9, Tier::Low),
+    (10..=99, Tier::Mid),
+];
+
+fn $0tier_for(key: u32) -> Tier {
+    for (range, value) in TIER_RANGES {
+        if range.contains(&key) {
+            return value;
+        }
+    }
+    Tier::High
+}
+
+fn tier(n: u32) -> Tier {
+    tier_for(n)
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_removal_loop_to_retain() {
+    check_doc_test(
+        "convert_removal_loop_to_retain",
+        r#####"
+//- minicore: index
+//- /main.rs crate:main deps:std,alloc
+enum Job { Done, Pending(u32) }
+
+fn prune(jobs: &mut Vec<Job>) {
+    let mut i = 0;
+    $0while i < jobs.len() {
+        match jobs[i] {
+            Job::Done => {
+                jobs.remove(i);
+            }
+            _ => i += 1,
+        }
+    }
+}
+//- /std.rs crate:std deps:alloc
+pub mod prelude { pub mod rust_2021 { pub use alloc::vec::Vec; } }
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> {
+        pub fn len(&self) -> usize { 0 }
+        pub fn remove(&mut self, index: usize) -> T { loop {} }
+    }
+    impl<T> core::ops::Index<usize> for Vec<T> { type Output = T; }
+}
+"#####,
+        r#####"
+enum Job { Done, Pending(u32) }
+
+fn prune(jobs: &mut Vec<Job>) {
+    jobs.retain(|it| !matches!(*it, Job::Done));
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_for_loop_with_for_each() {
+fn doctest_convert_result_match_to_map() {
     check_doc_test(
-        "convert_for_loop_with_for_each",
+        "convert_result_match_to_map",
         r#####"
-fn main() {
-    let x = vec![1, 2, 3];
-    for$0 v in x {
-        let y = v * 2;
+//- minicore: result
+fn double(res: Result<u32, ()>) -> Result<u32, ()> {
+    $0match res {
+        Ok(v) => Ok(v * 2),
+        Err(e) => Err(e),
     }
 }
 "#####,
         r#####"
-fn main() {
-    let x = vec![1, 2, 3];
-    x.into_iter().for_each(|v| {
-        let y = v * 2;
-    });
+fn double(res: Result<u32, ()>) -> Result<u32, ()> {
+    res.map(|v| v * 2)
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_if_to_bool_then() {
+fn doctest_convert_result_match_to_try_block() {
     check_doc_test(
-        "convert_if_to_bool_then",
+        "convert_result_match_to_try_block",
         r#####"
-//- minicore: option
-fn main() {
-    if$0 cond {
-        Some(val)
-    } else {
-        None
+//- minicore: result
+#![feature(try_blocks)]
+
+fn sum(a: Result<u32, ()>, b: Result<u32, ()>) -> Result<u32, ()> {
+    $0match a {
+        Ok(x) => match b {
+            Ok(y) => Ok(x + y),
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
     }
 }
 "#####,
         r#####"
-fn main() {
-    cond.then(|| val)
+#![feature(try_blocks)]
+
+fn sum(a: Result<u32, ()>, b: Result<u32, ()>) -> Result<u32, ()> {
+    try {
+        let x = a?;
+        let y = b?;
+        x + y
+    }
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_integer_literal() {
+fn doctest_convert_str_match_to_sorted_table() {
     check_doc_test(
-        "convert_integer_literal",
-        r#####"
-const _: i32 = 10$0;
+        "convert_str_match_to_sorted_table",
+        r#####"
+fn code(s: &str) -> u8 {
+    $0match s {
+        "p" => 15, "o" => 14, "n" => 13, "m" => 12,
+        "l" => 11, "k" => 10, "j" => 9, "i" => 8,
+        "h" => 7, "g" => 6, "f" => 5, "e" => 4,
+        "d" => 3, "c" => 2, "b" => 1, "a" => 0,
+        _ => 255,
+    }
+}
 "#####,
         r#####"
-const _: i32 = 0b1010;
+const CODE_TABLE: [(&str, u8); 16] = [
+    ("a", 0),
+    ("b", 1),
+    ("c", 2),
+    ("d", 3),
+    ("e", 4),
+    ("f", 5),
+    ("g", 6),
+    ("h", 7),
+    ("i", 8),
+    ("j", 9),
+    ("k", 10),
+    ("l", 11),
+    ("m", 12),
+    ("n", 13),
+    ("o", 14),
+    ("p", 15),
+];
+
+fn code(s: &str) -> u8 {
+    match CODE_TABLE.binary_search_by_key(&s, |&(key, _)| key) {
+        Ok(idx) => CODE_TABLE[idx].1,
+        Err(_) => 255,
+    }
+}
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_into_to_from() {
+fn doctest_convert_string_match_to_command_enum() {
     check_doc_test(
-        "convert_into_to_from",
+        "convert_string_match_to_command_enum",
         r#####"
-//- minicore: from
-impl $0Into<Thing> for usize {
-    fn into(self) -> Thing {
-        Thing {
-            b: self.to_string(),
-            a: self
-        }
+fn run(cmd: &str) -> u32 {
+    $0match cmd {
+        "start" => 1,
+        "stop" | "halt" => 2,
+        _ => 0,
     }
 }
 "#####,
         r#####"
-impl From<usize> for Thing {
-    fn from(val: usize) -> Self {
-        Thing {
-            b: val.to_string(),
-            a: val
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum $0Command {
+    Start,
+    Stop,
+}
+
+impl core::str::FromStr for Command {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Ok(Self::Start),
+            "stop" | "halt" => Ok(Self::Stop),
+            _ => Err(()),
         }
     }
 }
+
+fn run(cmd: &str) -> u32 {
+    match cmd.parse::<Command>() {
+        Ok(Command::Start) => 1,
+        Ok(Command::Stop) => 2,
+        _ => 0,
+    }
+}
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_iter_for_each_to_for() {
+fn doctest_convert_to_guarded_return() {
     check_doc_test(
-        "convert_iter_for_each_to_for",
+        "convert_to_guarded_return",
         r#####"
-//- minicore: iterators
-use core::iter;
 fn main() {
-    let iter = iter::repeat((9, 2));
-    iter.for_each$0(|(x, y)| {
-        println!("x: {}, y: {}", x, y);
-    });
+    $0if cond {
+        foo();
+        bar();
+    }
 }
 "#####,
         r#####"
-use core::iter;
 fn main() {
-    let iter = iter::repeat((9, 2));
-    for (x, y) in iter {
-        println!("x: {}, y: {}", x, y);
+    if !cond {
+        return;
     }
+    foo();
+    bar();
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_let_else_to_match() {
+fn doctest_convert_try_loop_to_collect() {
     check_doc_test(
-        "convert_let_else_to_match",
-        r#####"
-fn main() {
-    let Ok(mut x) = f() else$0 { return };
+        "convert_try_loop_to_collect",
+        r#####"
+//- minicore: iterator, option, result
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+fn parse(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+    let mut out = Vec::new();
+    $0for item in items {
+        match item {
+            Some(b) => out.push(b),
+            None => return Err(()),
+        }
+    }
+    Ok(())
 }
 "#####,
         r#####"
-fn main() {
-    let mut x = match f() {
-        Ok(x) => x,
-        _ => return,
-    };
+struct Vec<T>(T);
+impl<T> Vec<T> { fn new() -> Self { loop {} } fn push(&mut self, x: T) {} }
+fn parse(items: impl Iterator<Item = Option<u8>>) -> Result<(), ()> {
+    let out = items.map(|item| match item {
+        Some(b) => Ok(b),
+        None => Err(()),
+    }).collect::<Result<Vec<_>, _>>()?;
+    Ok(())
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_match_to_let_else() {
+fn doctest_convert_tuple_match_to_if_chain() {
     check_doc_test(
-        "convert_match_to_let_else",
+        "convert_tuple_match_to_if_chain",
         r#####"
-//- minicore: option
-fn foo(opt: Option<()>) {
-    let val = $0match opt {
-        Some(it) => it,
-        None => return,
-    };
+fn compare(a: u32, b: u32) -> &'static str {
+    $0match (a, b) {
+        (0, _) => "zero",
+        (x, y) if x == y => "same",
+        _ => "different",
+    }
 }
 "#####,
         r#####"
-fn foo(opt: Option<()>) {
-    let Some(val) = opt else { return };
+fn compare(a: u32, b: u32) -> &'static str {
+    if a == 0 {
+        "zero"
+    } else if a == b {
+        "same"
+    } else {
+        "different"
+    }
 }
 "#####,
     )
 }
 
 #[test]
-fn doctest_convert_named_struct_to_tuple_struct() {
+fn doctest_convert_tuple_match_to_nested_match() {
     check_doc_test(
-        "convert_named_struct_to_tuple_struct",
+        "convert_tuple_match_to_nested_match",
         r#####"
-struct Point$0 { x: f32, y: f32 }
-
-impl Point {
-    pub fn new(x: f32, y: f32) -> Self {
-        Point { x, y }
-    }
-
-    pub fn x(&self) -> f32 {
-        self.x
-    }
+enum Dir { N, E, S }
 
-    pub fn y(&self) -> f32 {
-        self.y
+fn turn(from: Dir, to: Dir) -> i32 {
+    $0match (from, to) {
+        (Dir::N, Dir::E) => 1,
+        (Dir::E, Dir::N) => -1,
+        (Dir::N, _) => 2,
+        _ => 0,
     }
 }
 "#####,
         r#####"
-struct Point(f32, f32);
-
-impl Point {
-    pub fn new(x: f32, y: f32) -> Self {
-        Point(x, y)
-    }
-
-    pub fn x(&self) -> f32 {
-        self.0
-    }
+enum Dir { N, E, S }
 
-    pub fn y(&self) -> f32 {
-        self.1
+fn turn(from: Dir, to: Dir) -> i32 {
+    match from {
+        Dir::N => match to {
+            Dir::E => 1,
+            _ => 2,
+        },
+        Dir::E => match to {
+            Dir::N => -1,
+            _ => 0,
+        },
+        _ => 0,
     }
 }
 "#####,
@@ -495,24 +1899,28 @@ pub fn y(&self) -> f32 {
 }
 
 #[test]
-fn doctest_convert_to_guarded_return() {
+fn doctest_convert_tuple_match_to_struct() {
     check_doc_test(
-        "convert_to_guarded_return",
+        "convert_tuple_match_to_struct",
         r#####"
-fn main() {
-    $0if cond {
-        foo();
-        bar();
-    }
+fn parse_header(tag: u8) {
+    let (len, signed) = $0match tag {
+        0 => (1, false),
+        _ => (4, true),
+    };
 }
 "#####,
         r#####"
-fn main() {
-    if !cond {
-        return;
-    }
-    foo();
-    bar();
+struct $0ParseHeader {
+    len: i32,
+    signed: bool,
+}
+
+fn parse_header(tag: u8) {
+    let ParseHeader { len, signed } = match tag {
+        0 => ParseHeader { len: 1, signed: false },
+        _ => ParseHeader { len: 4, signed: true },
+    };
 }
 "#####,
     )
@@ -579,6 +1987,73 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_convert_variant_if_chain_to_match() {
+    check_doc_test(
+        "convert_variant_if_chain_to_match",
+        r#####"
+enum Size { Small, Medium, Large }
+
+fn size(n: u32) -> Size {
+    $0if n < 10 {
+        Size::Small
+    } else if n < 100 {
+        Size::Medium
+    } else {
+        Size::Large
+    }
+}
+"#####,
+        r#####"
+enum Size { Small, Medium, Large }
+
+fn size(n: u32) -> Size {
+    match () {
+        _ if n < 10 => Size::Small,
+        _ if n < 100 => Size::Medium,
+        _ => Size::Large,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_convert_vec_pushes_to_vec_macro() {
+    check_doc_test(
+        "convert_vec_pushes_to_vec_macro",
+        r#####"
+//- /main.rs crate:main deps:std,alloc
+fn digits(even: bool) -> Vec<u8> {
+    match even {
+        true => {$0
+            let mut v = Vec::new();
+            v.push(0);
+            v.push(2);
+            v
+        }
+        false => vec![1, 3],
+    }
+}
+//- /std.rs crate:std deps:alloc
+pub mod prelude { pub mod rust_2021 { pub use alloc::vec::Vec; } }
+//- /alloc.rs crate:alloc
+pub mod vec {
+    pub struct Vec<T>(T);
+    impl<T> Vec<T> { pub fn new() -> Self { loop {} } pub fn push(&mut self, value: T) {} }
+}
+"#####,
+        r#####"
+fn digits(even: bool) -> Vec<u8> {
+    match even {
+        true => vec![0, 2],
+        false => vec![1, 3],
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_convert_while_to_loop() {
     check_doc_test(
@@ -664,6 +2139,50 @@ fn qux(bar: Bar, baz: Baz) {}
     )
 }
 
+#[test]
+fn doctest_extract_event_enum_from_log_match() {
+    check_doc_test(
+        "extract_event_enum_from_log_match",
+        r#####"
+macro_rules! info { ($($t:tt)*) => {} }
+enum State { Idle, Busy(u32) }
+
+fn report(state: State) {
+    $0match state {
+        State::Idle => info!("idle"),
+        State::Busy(jobs) => info!("busy with {jobs} jobs"),
+    }
+}
+"#####,
+        r#####"
+macro_rules! info { ($($t:tt)*) => {} }
+enum State { Idle, Busy(u32) }
+
+enum Event {
+    Idle,
+    Busy { jobs: u32 },
+}
+
+impl std::fmt::Display for Event {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Event::Idle => write!(f, "idle"),
+            Event::Busy { jobs } => write!(f, "busy with {jobs} jobs"),
+        }
+    }
+}
+
+fn report(state: State) {
+    let $0event = match state {
+        State::Idle => Event::Idle,
+        State::Busy(jobs) => Event::Busy { jobs },
+    };
+    info!("{event}");
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_expressions_from_format_string() {
     check_doc_test(
@@ -709,16 +2228,85 @@ fn main() {
 }
 "#####,
         r#####"
-fn main() {
-    let n = 1;
-    fun_name(n);
-    let g = 3;
+fn main() {
+    let n = 1;
+    fun_name(n);
+    let g = 3;
+}
+
+fn $0fun_name(n: i32) {
+    let m = n + 2;
+    // calculate
+    let k = m + n;
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_extract_match_closure_to_fn() {
+    check_doc_test(
+        "extract_match_closure_to_fn",
+        r#####"
+//- minicore: fn
+fn apply(f: impl Fn(u8) -> bool) {}
+
+fn run() {
+    apply($0|b| match b {
+        b'0'..=b'9' => true,
+        _ => false,
+    });
+}
+"#####,
+        r#####"
+fn apply(f: impl Fn(u8) -> bool) {}
+
+fn run() {
+    apply(fun_name);
+}
+
+fn $0fun_name(b: u8) -> bool {
+    match b {
+        b'0'..=b'9' => true,
+        _ => false,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_extract_match_to_from_impl() {
+    check_doc_test(
+        "extract_match_to_from_impl",
+        r#####"
+enum Level { Low, High }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        $0match self {
+            Self::Low => 0,
+            Self::High => 1,
+        }
+    }
+}
+"#####,
+        r#####"
+enum Level { Low, High }
+
+impl Level {
+    fn to_code(self) -> u8 {
+        self.into()
+    }
 }
 
-fn $0fun_name(n: i32) {
-    let m = n + 2;
-    // calculate
-    let k = m + n;
+impl From<Level> for u8 {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::Low => 0,
+            Level::High => 1,
+        }
+    }
 }
 "#####,
     )
@@ -751,6 +2339,32 @@ fn bar(name: i32) -> i32 {
     )
 }
 
+#[test]
+fn doctest_extract_shared_match_guard() {
+    check_doc_test(
+        "extract_shared_match_guard",
+        r#####"
+fn exit_code(status: u8, verbose: bool) -> i32 {
+    match status {
+        0 if $0verbose && status < 10 => 0,
+        1 if verbose && status < 10 => 1,
+        _ => 2,
+    }
+}
+"#####,
+        r#####"
+fn exit_code(status: u8, verbose: bool) -> i32 {
+    let $0cond = verbose && status < 10;
+    match status {
+        0 if cond => 0,
+        1 if cond => 1,
+        _ => 2,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_struct_from_enum_variant() {
     check_doc_test(
@@ -766,6 +2380,71 @@ enum A { One(One) }
     )
 }
 
+#[test]
+fn doctest_extract_transition_table_from_match() {
+    check_doc_test(
+        "extract_transition_table_from_match",
+        r#####"
+//- minicore: copy
+enum Mode { Normal, Insert }
+impl Clone for Mode { fn clone(&self) -> Self { *self } }
+impl Copy for Mode {}
+struct Keys;
+impl Keys { fn next(&mut self) -> char { 'i' } fn flush(&mut self) {} }
+
+fn run(keys: &mut Keys) {
+    let mut mode = Mode::Normal;
+    loop {
+        let key = keys.next();
+        mode = $0match (mode, key) {
+            (Mode::Normal, 'i') => Mode::Insert,
+            (Mode::Normal, _) => Mode::Normal,
+            (Mode::Insert, 'q') => {
+                keys.flush();
+                Mode::Normal
+            }
+            (Mode::Insert, _) => Mode::Insert,
+        };
+    }
+}
+"#####,
+        r#####"
+enum Mode { Normal, Insert }
+impl Clone for Mode { fn clone(&self) -> Self { *self } }
+impl Copy for Mode {}
+struct Keys;
+impl Keys { fn next(&mut self) -> char { 'i' } fn flush(&mut self) {} }
+
+enum Action {
+    None,
+    Action1,
+}
+
+const TRANSITIONS: &[(fn(Mode, char) -> bool, Action, Mode)] = &[
+    (|state, input| matches!((state, input), (Mode::Normal, 'i')), Action::None, Mode::Insert),
+    (|state, input| matches!((state, input), (Mode::Normal, _)), Action::None, Mode::Normal),
+    (|state, input| matches!((state, input), (Mode::Insert, 'q')), Action::Action1, Mode::Normal),
+    (|state, input| matches!((state, input), (Mode::Insert, _)), Action::None, Mode::Insert),
+];
+
+fn run(keys: &mut Keys) {
+    let mut mode = Mode::Normal;
+    loop {
+        let key = keys.next();
+        let (_, action, next) = TRANSITIONS.iter().find(|(accepts, ..)| accepts(mode, key)).unwrap();
+        match action {
+            Action::None => {}
+            Action::Action1 => {
+                keys.flush();
+            }
+        }
+        mode = *next;
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_extract_type_alias() {
     check_doc_test(
@@ -803,6 +2482,47 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_extract_variant_weight_method() {
+    check_doc_test(
+        "extract_variant_weight_method",
+        r#####"
+//- minicore: iterator, add
+enum Coin { Penny, Dime, Quarter }
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let mut sum = 0;
+    $0for coin in coins {
+        sum += match coin {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+            Coin::Quarter => 25,
+        };
+    }
+    sum
+}
+"#####,
+        r#####"
+enum Coin { Penny, Dime, Quarter }
+
+impl Coin {
+    fn $0weight(&self) -> u32 {
+        match self {
+            Coin::Penny => 1,
+            Coin::Dime => 10,
+            Coin::Quarter => 25,
+        }
+    }
+}
+
+fn total<'a>(coins: impl Iterator<Item = &'a Coin>) -> u32 {
+    let sum: u32 = coins.map(Coin::weight).sum();
+    sum
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_fix_visibility() {
     check_doc_test(
@@ -873,6 +2593,47 @@ fn foo<T: Copy + Clone>() { }
     )
 }
 
+#[test]
+fn doctest_generate_code_conversions_from_match() {
+    check_doc_test(
+        "generate_code_conversions_from_match",
+        r#####"
+enum Status { Ok, NotFound }
+
+fn send(status: Status) -> u16 {
+    $0match status {
+        Status::Ok => 200,
+        Status::NotFound => 404,
+    }
+}
+"#####,
+        r#####"
+enum Status { Ok, NotFound }
+
+impl Status {
+    fn $0code(&self) -> u16 {
+        match self {
+            Self::Ok => 200,
+            Self::NotFound => 404,
+        }
+    }
+
+    fn from_code(code: u16) -> Option<Self> {
+        match code {
+            200 => Some(Self::Ok),
+            404 => Some(Self::NotFound),
+            _ => None,
+        }
+    }
+}
+
+fn send(status: Status) -> u16 {
+    status.code()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_constant() {
     check_doc_test(
@@ -1122,6 +2883,52 @@ fn as_text(&self) -> Option<&String> {
     )
 }
 
+#[test]
+fn doctest_generate_enum_from_int_match() {
+    check_doc_test(
+        "generate_enum_from_int_match",
+        r#####"
+fn run(opcode: u8) -> u32 {
+    $0match opcode {
+        1 => 10,
+        2 | 3 => 20,
+        _ => 0,
+    }
+}
+"#####,
+        r#####"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum $0Opcode {
+    V1 = 1,
+    V2 = 2,
+    V3 = 3,
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            3 => Ok(Self::V3),
+            _ => Err(value),
+        }
+    }
+}
+
+fn run(opcode: u8) -> u32 {
+    match Opcode::try_from(opcode) {
+        Ok(Opcode::V1) => 10,
+        Ok(Opcode::V2) | Ok(Opcode::V3) => 20,
+        Err(_) => 0,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_enum_is_method() {
     check_doc_test(
@@ -1315,6 +3122,40 @@ fn $0name_mut(&mut self) -> &mut String {
     )
 }
 
+#[test]
+fn doctest_generate_hash_match() {
+    check_doc_test(
+        "generate_hash_match",
+        r#####"
+//- minicore: hash
+use core::hash::{Hash, Hasher};
+
+enum Shape { Dot, Circle(u32) }
+
+impl Hash for Shape {
+    fn hash<H: Hasher>(&self, state: &mut H) {$0}
+}
+"#####,
+        r#####"
+use core::hash::{Hash, Hasher};
+
+enum Shape { Dot, Circle(u32) }
+
+impl Hash for Shape {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            Self::Dot => {}
+            Self::Circle(arg0) => {
+                arg0.hash(state);
+            }
+        }
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_impl() {
     check_doc_test(
@@ -1368,6 +3209,48 @@ pub fn is_empty(&self) -> bool {
     )
 }
 
+#[test]
+fn doctest_generate_match_arm_tests() {
+    check_doc_test(
+        "generate_match_arm_tests",
+        r#####"
+enum Shape { Circle(u32), Empty }
+
+fn area(shape: Shape) -> u32 {
+    $0match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Empty => 0,
+    }
+}
+"#####,
+        r#####"
+enum Shape { Circle(u32), Empty }
+
+fn area(shape: Shape) -> u32 {
+    match shape {
+        Shape::Circle(r) => 3 * r * r,
+        Shape::Empty => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn area_circle() {
+        assert_eq!(area(Shape::Circle(todo!())), ${0:todo!()});
+    }
+
+    #[test]
+    fn area_empty() {
+        assert_eq!(area(Shape::Empty), todo!());
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_generate_new() {
     check_doc_test(
@@ -1433,6 +3316,237 @@ impl<T: Clone> $0 for Ctx<T> {
     )
 }
 
+#[test]
+fn doctest_generate_variant_name_match() {
+    check_doc_test(
+        "generate_variant_name_match",
+        r#####"
+enum Shape { Dot, Circle(u32), Rect { w: u32, h: u32 } }
+
+impl Shape {
+    fn name(&self) -> &'static str {$0}
+}
+"#####,
+        r#####"
+enum Shape { Dot, Circle(u32), Rect { w: u32, h: u32 } }
+
+impl Shape {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Dot => "Dot",
+            Self::Circle(..) => "Circle",
+            Self::Rect { .. } => "Rect",
+        }
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_clone_out_of_match() {
+    check_doc_test(
+        "hoist_clone_out_of_match",
+        r#####"
+//- minicore: clone
+#[derive(Clone)]
+struct Name;
+struct Names { first: Name, last: Name }
+
+fn pick(names: &Names, first: bool) -> Name {
+    $0match first {
+        true => names.first.clone(),
+        false => names.last.clone(),
+    }
+}
+"#####,
+        r#####"
+#[derive(Clone)]
+struct Name;
+struct Names { first: Name, last: Name }
+
+fn pick(names: &Names, first: bool) -> Name {
+    (match first {
+        true => &names.first,
+        false => &names.last,
+    }).clone()
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_constructor_out_of_match() {
+    check_doc_test(
+        "hoist_constructor_out_of_match",
+        r#####"
+enum Out { Val(u32), None }
+enum Key { A, B }
+
+fn lookup(key: Key) -> Out {
+    $0match key {
+        Key::A => Out::Val(1),
+        Key::B => Out::Val(2),
+    }
+}
+"#####,
+        r#####"
+enum Out { Val(u32), None }
+enum Key { A, B }
+
+fn lookup(key: Key) -> Out {
+    Out::Val(match key {
+        Key::A => 1,
+        Key::B => 2,
+    })
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_format_out_of_match() {
+    check_doc_test(
+        "hoist_format_out_of_match",
+        r#####"
+enum Unit { Meters, Feet }
+
+fn label(unit: Unit, n: u32) -> String {
+    $0match unit {
+        Unit::Meters => format!("{} {}", n, "m"),
+        Unit::Feet => format!("{} {}", n, "ft"),
+    }
+}
+"#####,
+        r#####"
+enum Unit { Meters, Feet }
+
+fn label(unit: Unit, n: u32) -> String {
+    format!("{} {}", n, match unit {
+        Unit::Meters => "m",
+        Unit::Feet => "ft",
+    })
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_guard_computation_out_of_match() {
+    check_doc_test(
+        "hoist_guard_computation_out_of_match",
+        r#####"
+//- minicore: derive, eq
+#[derive(PartialEq)]
+enum Kind { Digit, Letter, Other }
+struct Token(char);
+impl Token {
+    fn kind(&self) -> Kind { Kind::Other }
+}
+
+fn weight(token: &Token) -> u32 {
+    $0match token {
+        _ if token.kind() == Kind::Digit => 1,
+        _ if token.kind() == Kind::Letter => 2,
+        _ => 0,
+    }
+}
+"#####,
+        r#####"
+#[derive(PartialEq)]
+enum Kind { Digit, Letter, Other }
+struct Token(char);
+impl Token {
+    fn kind(&self) -> Kind { Kind::Other }
+}
+
+fn weight(token: &Token) -> u32 {
+    let $0kind = token.kind();
+    match kind {
+        Kind::Digit => 1,
+        Kind::Letter => 2,
+        _ => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_repeated_guard_call() {
+    check_doc_test(
+        "hoist_repeated_guard_call",
+        r#####"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>) -> u32 {
+    match key {
+        Some(k) $0if cache.lookup(k).is_some() => cache.lookup(k).unwrap(),
+        _ => 0,
+    }
+}
+"#####,
+        r#####"
+struct Cache;
+impl Cache {
+    fn lookup(&self, key: u32) -> Option<u32> { None }
+}
+
+fn get(cache: &Cache, key: Option<u32>) -> u32 {
+    match key {
+        Some(k) => {
+            let lookup = cache.lookup(k);
+            if lookup.is_some() {
+                lookup.unwrap()
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_hoist_shared_return_out_of_match() {
+    check_doc_test(
+        "hoist_shared_return_out_of_match",
+        r#####"
+//- minicore: result
+fn apply(cmd: u8, log: &mut Vec<u8>) -> Result<(), ()> {
+    $0match cmd {
+        0 => {
+            log.clear();
+            return Ok(());
+        }
+        n => {
+            log.push(n);
+            return Ok(());
+        }
+    }
+}
+"#####,
+        r#####"
+fn apply(cmd: u8, log: &mut Vec<u8>) -> Result<(), ()> {
+    match cmd {
+        0 => {
+            log.clear();
+        }
+        n => {
+            log.push(n);
+        }
+    }
+    Ok(())
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_call() {
     check_doc_test(
@@ -1454,6 +3568,33 @@ fn foo(name: Option<&str>) {
     )
 }
 
+#[test]
+fn doctest_inline_const_in_pattern() {
+    check_doc_test(
+        "inline_const_in_pattern",
+        r#####"
+const MAX_RETRIES: u32 = 3;
+
+fn give_up(retries: u32) -> bool {
+    match retries {
+        $0MAX_RETRIES => true,
+        _ => false,
+    }
+}
+"#####,
+        r#####"
+const MAX_RETRIES: u32 = 3;
+
+fn give_up(retries: u32) -> bool {
+    match retries {
+        3 /* MAX_RETRIES */ => true,
+        _ => false,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_inline_into_callers() {
     check_doc_test(
@@ -1694,6 +3835,40 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_merge_if_lets_into_match() {
+    check_doc_test(
+        "merge_if_lets_into_match",
+        r#####"
+enum Msg { Quit, Move(u32), Write(u32) }
+
+fn handle(msg: Msg) {
+    $0if let Msg::Move(x) = &msg {
+        move_to(x);
+    }
+    if let Msg::Write(y) = &msg {
+        write(y);
+    }
+}
+"#####,
+        r#####"
+enum Msg { Quit, Move(u32), Write(u32) }
+
+fn handle(msg: Msg) {
+    match &msg {
+        Msg::Move(x) => {
+            move_to(x);
+        }
+        Msg::Write(y) => {
+            write(y);
+        }
+        _ => {}
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_merge_imports() {
     check_doc_test(
@@ -1823,6 +3998,38 @@ fn t() {}
     )
 }
 
+#[test]
+fn doctest_move_guard_into_pattern() {
+    check_doc_test(
+        "move_guard_into_pattern",
+        r#####"
+//- minicore: derive, eq
+#[derive(PartialEq)]
+enum Kind { Click, Scroll }
+struct Event { kind: Kind, x: u32 }
+
+fn handle(event: Event) {
+    match event {
+        Event { kind, .. } $0if kind == Kind::Click => (),
+        _ => (),
+    }
+}
+"#####,
+        r#####"
+#[derive(PartialEq)]
+enum Kind { Click, Scroll }
+struct Event { kind: Kind, x: u32 }
+
+fn handle(event: Event) {
+    match event {
+        Event { kind: Kind::Click, .. } => (),
+        _ => (),
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_guard_to_arm_body() {
     check_doc_test(
@@ -1852,6 +4059,33 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_move_matches_guard_into_pattern() {
+    check_doc_test(
+        "move_matches_guard_into_pattern",
+        r#####"
+struct Point { x: u32, y: u32 }
+
+fn on_axis(p: Point) -> bool {
+    match p {
+        p $0if matches!(p, Point { y: 0, .. }) => true,
+        _ => false,
+    }
+}
+"#####,
+        r#####"
+struct Point { x: u32, y: u32 }
+
+fn on_axis(p: Point) -> bool {
+    match p {
+        Point { y: 0, .. } => true,
+        _ => false,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_module_to_file() {
     check_doc_test(
@@ -1867,6 +4101,39 @@ fn t() {}
     )
 }
 
+#[test]
+fn doctest_move_side_effecting_guard_to_arm_body() {
+    check_doc_test(
+        "move_side_effecting_guard_to_arm_body",
+        r#####"
+//- minicore: option
+fn check(n: u8) -> bool { n > 2 }
+
+fn classify(v: Option<u8>) -> u8 {
+    match v {
+        Some(n) if $0check(n) => n,
+        Some(n) => n + 1,
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn check(n: u8) -> bool { n > 2 }
+
+fn classify(v: Option<u8>) -> u8 {
+    match v {
+        Some(n) => if check(n) {
+            n
+        } else {
+            n + 1
+        },
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_move_to_mod_rs() {
     check_doc_test(
@@ -1883,6 +4150,35 @@ fn t() {}
     )
 }
 
+#[test]
+fn doctest_move_wildcard_arm_last() {
+    check_doc_test(
+        "move_wildcard_arm_last",
+        r#####"
+enum Dir { Up, Down, Left, Right }
+
+fn vertical(dir: Dir) -> bool {
+    match dir {
+        Dir::Up => true,
+        $0_ => false,
+        Dir::Down => true,
+    }
+}
+"#####,
+        r#####"
+enum Dir { Up, Down, Left, Right }
+
+fn vertical(dir: Dir) -> bool {
+    match dir {
+        Dir::Up => true,
+        Dir::Down => true,
+        _ => false,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_promote_local_to_const() {
     check_doc_test(
@@ -2089,6 +4385,29 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_remove_wildcard_subpattern() {
+    check_doc_test(
+        "remove_wildcard_subpattern",
+        r#####"
+fn show(n: u32) -> u32 {
+    match n {
+        0 => 1,
+        x$0 @ _ => x * 2,
+    }
+}
+"#####,
+        r#####"
+fn show(n: u32) -> u32 {
+    match n {
+        0 => 1,
+        x => x * 2,
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_reorder_fields() {
     check_doc_test(
@@ -2259,6 +4578,31 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_replace_infallible_match_with_unwrap() {
+    check_doc_test(
+        "replace_infallible_match_with_unwrap",
+        r#####"
+//- minicore: result, infallible, fmt
+use core::convert::Infallible;
+
+fn value(res: Result<u32, Infallible>) -> u32 {
+    $0match res {
+        Ok(v) => v,
+        Err(e) => match e {},
+    }
+}
+"#####,
+        r#####"
+use core::convert::Infallible;
+
+fn value(res: Result<u32, Infallible>) -> u32 {
+    res.unwrap()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_let_with_if_let() {
     check_doc_test(
@@ -2285,6 +4629,31 @@ fn compute() -> Option<i32> { None }
     )
 }
 
+#[test]
+fn doctest_replace_match_impl_with_derive() {
+    check_doc_test(
+        "replace_match_impl_with_derive",
+        r#####"
+//- minicore: eq, derive
+enum Shape { Circle(u32), Empty }
+
+impl PartialEq for Shape {
+    fn eq(&self, other: &Self) -> bool {
+        $0match (self, other) {
+            (Shape::Circle(a), Shape::Circle(b)) => a == b,
+            (Shape::Empty, Shape::Empty) => true,
+            _ => false,
+        }
+    }
+}
+"#####,
+        r#####"
+#[derive(PartialEq)]
+enum Shape { Circle(u32), Empty }
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_match_with_if_let() {
     check_doc_test(
@@ -2313,6 +4682,27 @@ fn handle(action: Action) {
     )
 }
 
+#[test]
+fn doctest_replace_match_with_unwrap_or_default() {
+    check_doc_test(
+        "replace_match_with_unwrap_or_default",
+        r#####"
+//- minicore: option
+fn width(w: Option<u32>) -> u32 {
+    $0match w {
+        Some(w) => w,
+        None => 0,
+    }
+}
+"#####,
+        r#####"
+fn width(w: Option<u32>) -> u32 {
+    w.unwrap_or_default()
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_replace_or_else_with_or() {
     check_doc_test(
@@ -2427,6 +4817,63 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_replace_variant_array_with_enum_iter() {
+    check_doc_test(
+        "replace_variant_array_with_enum_iter",
+        r#####"
+mod strum { pub trait IntoEnumIterator {} }
+use strum::IntoEnumIterator;
+
+enum Color { Red, Green }
+impl IntoEnumIterator for Color {}
+
+fn count() -> usize {
+    [Color::Red, $0Color::Green].into_iter().count()
+}
+"#####,
+        r#####"
+mod strum { pub trait IntoEnumIterator {} }
+use strum::IntoEnumIterator;
+
+enum Color { Red, Green }
+impl IntoEnumIterator for Color {}
+
+fn count() -> usize {
+    Color::iter().count()
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_sort_integer_match_arms() {
+    check_doc_test(
+        "sort_integer_match_arms",
+        r#####"
+fn name(n: u8) -> &'static str {
+    $0match n {
+        2 => "two",
+        0 => "zero",
+        1 => "one",
+        _ => "many",
+    }
+}
+"#####,
+        r#####"
+fn name(n: u8) -> &'static str {
+    match n {
+        // dense 0..3
+        0 => "zero",
+        1 => "one",
+        2 => "two",
+        _ => "many",
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_sort_items() {
     check_doc_test(
@@ -2518,6 +4965,31 @@ enum Animal {
     )
 }
 
+#[test]
+fn doctest_sort_or_pattern() {
+    check_doc_test(
+        "sort_or_pattern",
+        r#####"
+enum X { A, B, C }
+
+fn handle(x: X) {
+    match x {
+        X::C | $0X::A | X::B => (),
+    }
+}
+"#####,
+        r#####"
+enum X { A, B, C }
+
+fn handle(x: X) {
+    match x {
+        X::A | X::B | X::C => (),
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_split_import() {
     check_doc_test(
@@ -2531,6 +5003,33 @@ fn doctest_split_import() {
     )
 }
 
+#[test]
+fn doctest_split_match_guard() {
+    check_doc_test(
+        "split_match_guard",
+        r#####"
+fn classify(n: Option<i32>) -> &'static str {
+    match n {
+        Some(x) if x > 0 $0&& x < 10 => "digit",
+        _ => "other",
+    }
+}
+"#####,
+        r#####"
+fn classify(n: Option<i32>) -> &'static str {
+    match n {
+        Some(x) if x > 0 => if x < 10 {
+            "digit"
+        } else {
+            "other"
+        }
+        _ => "other",
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_toggle_ignore() {
     check_doc_test(
@@ -2677,6 +5176,84 @@ fn main() {
     )
 }
 
+#[test]
+fn doctest_use_match_ergonomics() {
+    check_doc_test(
+        "use_match_ergonomics",
+        r#####"
+//- minicore: option
+fn len(opt: &Option<String>) -> usize {
+    $0match opt {
+        &Some(ref s) => s.len(),
+        &None => 0,
+    }
+}
+"#####,
+        r#####"
+fn len(opt: &Option<String>) -> usize {
+    match opt {
+        Some(s) => s.len(),
+        None => 0,
+    }
+}
+"#####,
+    )
+}
+
+#[test]
+fn doctest_wrap_match_arms_in_either() {
+    check_doc_test(
+        "wrap_match_arms_in_either",
+        r#####"
+//- minicore: iterator
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+fn numbers(even: bool) -> impl Iterator<Item = u32> {
+    $0match even {
+        true => Evens,
+        false => Odds,
+    }
+}
+"#####,
+        r#####"
+struct Evens;
+impl Iterator for Evens { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+struct Odds;
+impl Iterator for Odds { type Item = u32; fn next(&mut self) -> Option<u32> { None } }
+
+enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> Iterator for Either<L, R>
+where
+    L: Iterator,
+    R: Iterator<Item = L::Item>,
+{
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Either::Left(it) => it.next(),
+            Either::Right(it) => it.next(),
+        }
+    }
+}
+
+fn numbers(even: bool) -> impl Iterator<Item = u32> {
+    match even {
+        true => Either::Left(Evens),
+        false => Either::Right(Odds),
+    }
+}
+"#####,
+    )
+}
+
 #[test]
 fn doctest_wrap_return_type_in_result() {
     check_doc_test(
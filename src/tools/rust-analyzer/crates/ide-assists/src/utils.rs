@@ -4,7 +4,10 @@
 
 pub(crate) use gen_trait_fn_body::gen_trait_fn_body;
 use hir::{db::HirDatabase, HirDisplay, Semantics};
-use ide_db::{famous_defs::FamousDefs, path_transform::PathTransform, RootDatabase, SnippetCap};
+use ide_db::{
+    defs::Definition, famous_defs::FamousDefs, path_transform::PathTransform,
+    search::ReferenceCategory, syntax_helpers::node_ext::preorder_expr, RootDatabase, SnippetCap,
+};
 use stdx::format_to;
 use syntax::{
     ast::{
@@ -15,7 +18,7 @@
     },
     ted, AstNode, AstToken, Direction, SourceFile,
     SyntaxKind::*,
-    SyntaxNode, TextRange, TextSize, T,
+    SyntaxNode, TextRange, TextSize, WalkEvent, T,
 };
 
 use crate::assist_context::{AssistContext, SourceChangeBuilder};
@@ -327,6 +330,403 @@ fn calc_depth(pat: &ast::Pat, depth: usize) -> usize {
     }
 }
 
+/// Whether `expr` needs parentheses to be the receiver of a method call.
+pub(crate) fn needs_parens_as_receiver(expr: &ast::Expr) -> bool {
+    // Make `(expr).dummy()`
+    let dummy_call = make::expr_method_call(
+        make::expr_paren(expr.clone()),
+        make::name_ref("dummy"),
+        make::arg_list([]),
+    );
+
+    // Get the `expr` clone with the right parent back
+    // (unreachable!s are fine since we've just constructed the expression)
+    let ast::Expr::MethodCallExpr(call) = &dummy_call else { unreachable!() };
+    let Some(receiver) = call.receiver() else { unreachable!() };
+    let ast::Expr::ParenExpr(parens) = receiver else { unreachable!() };
+    let Some(expr) = parens.expr() else { unreachable!() };
+
+    expr.needs_parens_in(dummy_call.syntax().clone())
+}
+
+/// Whether `expr` can be moved into a pattern matching the values of type `ty` that it is
+/// compared to: a literal, a unit variant or a constant of a type with structural equality.
+pub(crate) fn is_pattern_value(
+    sema: &Semantics<'_, RootDatabase>,
+    expr: &ast::Expr,
+    ty: &hir::Type,
+) -> bool {
+    let Some(expr_ty) = sema.type_of_expr(expr).map(|it| it.original) else { return false };
+    if expr_ty != *ty || expr_ty.as_builtin().map_or(false, |it| it.is_float()) {
+        return false;
+    }
+    match expr {
+        ast::Expr::Literal(_) => true,
+        ast::Expr::PathExpr(it) => match it.path().and_then(|path| sema.resolve_path(&path)) {
+            Some(hir::PathResolution::Def(hir::ModuleDef::Variant(_))) => true,
+            Some(hir::PathResolution::Def(hir::ModuleDef::Const(_))) => {
+                sema.scope(expr.syntax()).map_or(false, |scope| {
+                    has_structural_eq(sema, &FamousDefs(sema, scope.krate()), &expr_ty)
+                })
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Constants can only be used as patterns if their type derives `PartialEq` and `Eq`.
+fn has_structural_eq(
+    sema: &Semantics<'_, RootDatabase>,
+    famous_defs: &FamousDefs<'_, '_>,
+    ty: &hir::Type,
+) -> bool {
+    let ty = ty.strip_references();
+    if ty.as_adt().is_none() {
+        return ty.as_builtin().map_or(false, |it| !it.is_float());
+    }
+    let impls = hir::Impl::all_for_type(sema.db, ty);
+    [famous_defs.core_cmp_PartialEq(), famous_defs.core_cmp_Eq()].into_iter().all(|trait_| {
+        trait_.is_some()
+            && impls.iter().any(|imp| {
+                imp.trait_(sema.db) == trait_ && imp.is_builtin_derive(sema.db).is_some()
+            })
+    })
+}
+
+/// Whether the functions can be called in the same way, so one can stand in for the other.
+pub(crate) fn same_signature(
+    sema: &Semantics<'_, RootDatabase>,
+    lhs: hir::Function,
+    rhs: hir::Function,
+) -> bool {
+    let db = sema.db;
+    let self_access = |it: hir::Function| it.self_param(db).map(|param| param.access(db));
+    let param_tys = |it: hir::Function| {
+        it.params_without_self(db).into_iter().map(|param| param.ty().clone()).collect::<Vec<_>>()
+    };
+    let abi = |it: hir::Function| {
+        sema.source(it).map(|source| source.value.abi().map(|abi| abi.to_string()))
+    };
+    let same_abi = match (abi(lhs), abi(rhs)) {
+        (Some(lhs), Some(rhs)) => lhs == rhs,
+        _ => false,
+    };
+    same_abi
+        && self_access(lhs) == self_access(rhs)
+        && lhs.is_unsafe_to_call(db) == rhs.is_unsafe_to_call(db)
+        && lhs.ret_type(db) == rhs.ret_type(db)
+        && param_tys(lhs) == param_tys(rhs)
+}
+
+/// Returns the variants matched by `pat`, if it is made of paths to variants only.
+pub(crate) fn variants(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<Vec<hir::Variant>> {
+    match pat {
+        ast::Pat::OrPat(it) => {
+            it.pats().map(|it| variants(ctx, &it)).collect::<Option<Vec<_>>>().map(|it| it.concat())
+        }
+        _ => pat_variant(ctx, pat).map(|it| vec![it]),
+    }
+}
+
+/// Returns the variant `pat` refers to, if it is a path, tuple struct or record pattern of one.
+pub(crate) fn pat_variant(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<hir::Variant> {
+    let path = match pat {
+        ast::Pat::IdentPat(it) => {
+            return match ctx.sema.resolve_bind_pat_to_const(it)? {
+                hir::ModuleDef::Variant(it) => Some(it),
+                _ => None,
+            }
+        }
+        ast::Pat::PathPat(it) => it.path()?,
+        ast::Pat::TupleStructPat(it) => it.path()?,
+        ast::Pat::RecordPat(it) => it.path()?,
+        _ => return None,
+    };
+    match ctx.sema.resolve_path(&path)? {
+        hir::PathResolution::Def(hir::ModuleDef::Variant(it)) => Some(it),
+        _ => None,
+    }
+}
+
+/// Computes the discriminants of the variants of an enum, if they are all known integers.
+pub(crate) fn discriminants(
+    ctx: &AssistContext<'_>,
+    variants: &[hir::Variant],
+) -> Option<Vec<u128>> {
+    let mut next = 0;
+    let mut res = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let value = match variant.value(ctx.db()) {
+            Some(expr) => int_literal(&expr)?,
+            None => next,
+        };
+        res.push(value);
+        next = value.checked_add(1)?;
+    }
+    Some(res)
+}
+
+/// Returns the value of an integer literal expression.
+pub(crate) fn int_literal(expr: &ast::Expr) -> Option<u128> {
+    let ast::Expr::Literal(lit) = expr else { return None };
+    match lit.kind() {
+        ast::LiteralKind::IntNumber(it) => it.value(),
+        _ => None,
+    }
+}
+
+/// Checks that `body` is empty or only holds a placeholder macro like `unimplemented!()`.
+pub(crate) fn is_stub(body: &ast::BlockExpr) -> bool {
+    let Some(stmts) = body.stmt_list() else { return false };
+    if stmts.statements().next().is_some() {
+        return false;
+    }
+    match stmts.tail_expr() {
+        None => true,
+        Some(ast::Expr::MacroExpr(it)) => it
+            .macro_call()
+            .and_then(|it| it.path())
+            .map_or(false, |it| matches!(it.to_string().as_str(), "todo" | "unimplemented")),
+        Some(_) => false,
+    }
+}
+
+/// Renders `iterable` of a `for` loop as an expression the iterator methods can be called on.
+pub(crate) fn iterator_of(
+    sema: &hir::Semantics<'_, ide_db::RootDatabase>,
+    iterable: &ast::Expr,
+) -> String {
+    if let Some((expr_behind_ref, method)) = is_ref_and_impls_iter_method(sema, iterable) {
+        // We have either "for x in &col" and col implements a method called iter
+        //             or "for x in &mut col" and col implements a method called iter_mut
+        format!("{expr_behind_ref}.{method}()")
+    } else if let ast::Expr::RangeExpr(..) = iterable {
+        // range expressions need to be parenthesized for the syntax to be correct
+        format!("({iterable})")
+    } else if impls_core_iter(sema, iterable) {
+        format!("{iterable}")
+    } else if let ast::Expr::RefExpr(_) = iterable {
+        format!("({iterable}).into_iter()")
+    } else {
+        format!("{iterable}.into_iter()")
+    }
+}
+
+/// If iterable is a reference where the expression behind the reference implements a method
+/// returning an Iterator called iter or iter_mut (depending on the type of reference) then return
+/// the expression behind the reference and the method name
+fn is_ref_and_impls_iter_method(
+    sema: &hir::Semantics<'_, ide_db::RootDatabase>,
+    iterable: &ast::Expr,
+) -> Option<(ast::Expr, hir::Name)> {
+    let ref_expr = match iterable {
+        ast::Expr::RefExpr(r) => r,
+        _ => return None,
+    };
+    let wanted_method =
+        if ref_expr.mut_token().is_some() { hir::known::iter_mut } else { hir::known::iter };
+    let expr_behind_ref = ref_expr.expr()?;
+    let ty = sema.type_of_expr(&expr_behind_ref)?.adjusted();
+    let scope = sema.scope(iterable.syntax())?;
+    let krate = scope.krate();
+    let iter_trait = FamousDefs(sema, krate).core_iter_Iterator()?;
+
+    let has_wanted_method = ty
+        .iterate_method_candidates(
+            sema.db,
+            &scope,
+            &scope.visible_traits().0,
+            None,
+            Some(&wanted_method),
+            |func| {
+                if func.ret_type(sema.db).impls_trait(sema.db, iter_trait, &[]) {
+                    return Some(());
+                }
+                None
+            },
+        )
+        .is_some();
+    if !has_wanted_method {
+        return None;
+    }
+
+    Some((expr_behind_ref, wanted_method))
+}
+
+/// Whether iterable implements core::Iterator
+fn impls_core_iter(sema: &hir::Semantics<'_, ide_db::RootDatabase>, iterable: &ast::Expr) -> bool {
+    (|| {
+        let it_typ = sema.type_of_expr(iterable)?.adjusted();
+
+        let module = sema.scope(iterable.syntax())?.module();
+
+        let krate = module.krate();
+        let iter_trait = FamousDefs(sema, krate).core_iter_Iterator()?;
+        cov_mark::hit!(test_already_impls_iterator);
+        Some(it_typ.impls_trait(sema.db, iter_trait, &[]))
+    })()
+    .unwrap_or(false)
+}
+
+/// checks if this expr requires `&mut` access, recurses on field access
+pub(crate) fn expr_require_exclusive_access(
+    ctx: &AssistContext<'_>,
+    expr: &ast::Expr,
+) -> Option<bool> {
+    if let ast::Expr::MacroExpr(_) = expr {
+        // FIXME: expand macro and check output for mutable usages of the variable?
+        return None;
+    }
+
+    let parent = expr.syntax().parent()?;
+
+    if let Some(bin_expr) = ast::BinExpr::cast(parent.clone()) {
+        if matches!(bin_expr.op_kind()?, ast::BinaryOp::Assignment { .. }) {
+            return Some(bin_expr.lhs()?.syntax() == expr.syntax());
+        }
+        return Some(false);
+    }
+
+    if let Some(ref_expr) = ast::RefExpr::cast(parent.clone()) {
+        return Some(ref_expr.mut_token().is_some());
+    }
+
+    if let Some(method_call) = ast::MethodCallExpr::cast(parent.clone()) {
+        let func = ctx.sema.resolve_method_call(&method_call)?;
+        let self_param = func.self_param(ctx.db())?;
+        let access = self_param.access(ctx.db());
+
+        return Some(matches!(access, hir::Access::Exclusive));
+    }
+
+    if let Some(field) = ast::FieldExpr::cast(parent) {
+        return expr_require_exclusive_access(ctx, &field.into());
+    }
+
+    Some(false)
+}
+
+/// Returns the name bound by `let mut name = Vec::new();`, and whether the vector has to stay
+/// mutable once the pushes to it in `replaced` are gone.
+pub(crate) fn new_vec_binding(
+    ctx: &AssistContext<'_>,
+    let_stmt: &ast::LetStmt,
+    replaced: TextRange,
+) -> Option<(String, bool)> {
+    let ast::Pat::IdentPat(binding) = let_stmt.pat()? else { return None };
+    let ast::Expr::CallExpr(call) = let_stmt.initializer()? else { return None };
+    if call.expr()?.syntax().text() != "Vec::new" || call.arg_list()?.args().next().is_some() {
+        return None;
+    }
+    if let_stmt.ty().is_some() || binding.mut_token().is_none() {
+        return None;
+    }
+    let keep_mut = is_mutated_outside(ctx, ctx.sema.to_def(&binding)?, replaced);
+    Some((binding.name()?.to_string(), keep_mut))
+}
+
+/// Checks whether `local` is changed or mutably borrowed anywhere but in `range`.
+pub(crate) fn is_mutated_outside(
+    ctx: &AssistContext<'_>,
+    local: hir::Local,
+    range: TextRange,
+) -> bool {
+    Definition::Local(local)
+        .usages(&ctx.sema)
+        .all()
+        .iter()
+        .flat_map(|(_, refs)| refs.iter())
+        .filter(|it| !range.contains_range(it.range))
+        .any(|it| {
+            let path = it.name.syntax().ancestors().find_map(ast::PathExpr::cast);
+            it.category == Some(ReferenceCategory::Write)
+                || path.map_or(true, |it| {
+                    expr_require_exclusive_access(ctx, &it.into()).unwrap_or(true)
+                })
+        })
+}
+
+/// Returns the item containing `node` that sits directly in a file or module, next to which
+/// new items can be inserted.
+pub(crate) fn module_item(node: &SyntaxNode) -> Option<ast::Item> {
+    node.ancestors().filter_map(ast::Item::cast).find(|item| {
+        item.syntax()
+            .parent()
+            .map_or(false, |parent| matches!(parent.kind(), SOURCE_FILE | ITEM_LIST))
+    })
+}
+
+/// Returns the name of the enum variant `pat` refers to.
+pub(crate) fn variant_name(ctx: &AssistContext<'_>, pat: &ast::Pat) -> Option<String> {
+    Some(pat_variant(ctx, pat)?.name(ctx.db()).to_string())
+}
+
+/// Checks whether `path` resolves to an enum variant called `name`.
+pub(crate) fn is_variant(ctx: &AssistContext<'_>, path: &ast::Path, name: &str) -> bool {
+    matches!(
+        ctx.sema.resolve_path(path),
+        Some(hir::PathResolution::Def(hir::ModuleDef::Variant(it)))
+            if it.name(ctx.db()).to_smol_str() == name
+    )
+}
+
+/// Checks for an arm like `Ok(x) => x`, which unwraps the single field of a tuple variant.
+pub(crate) fn is_unwrapping(arm: &ast::MatchArm) -> Option<bool> {
+    let ast::Pat::TupleStructPat(pat) = arm.pat()? else { return None };
+    let mut fields = pat.fields();
+    let binding = match (fields.next(), fields.next()) {
+        (Some(ast::Pat::IdentPat(it)), None)
+            if it.ref_token().is_none() && it.mut_token().is_none() && it.pat().is_none() =>
+        {
+            it.name()?
+        }
+        _ => return None,
+    };
+    let ast::Expr::PathExpr(value) = arm.expr()? else { return Some(false) };
+    Some(value.syntax().text() == binding.text().as_str())
+}
+
+/// Returns the value a match arm body evaluates to, looking through the tails of plain blocks.
+pub(crate) fn arm_value(expr: ast::Expr) -> Option<ast::Expr> {
+    match expr {
+        ast::Expr::BlockExpr(block) if block.modifier().is_none() => {
+            arm_value(block.stmt_list()?.tail_expr()?)
+        }
+        it => Some(it),
+    }
+}
+
+/// Checks whether evaluating `expr` can leave it early, with `?`, `return`, `break` or
+/// `continue`, which would behave differently once `expr` is moved into a closure.
+pub(crate) fn has_early_exit(expr: &ast::Expr) -> bool {
+    let mut loop_depth = 0;
+    let mut found = false;
+    preorder_expr(expr, &mut |event| {
+        match event {
+            WalkEvent::Enter(it) => match it {
+                ast::Expr::LoopExpr(_) | ast::Expr::WhileExpr(_) | ast::Expr::ForExpr(_) => {
+                    loop_depth += 1
+                }
+                ast::Expr::TryExpr(_) | ast::Expr::ReturnExpr(_) => found = true,
+                ast::Expr::BreakExpr(it) => {
+                    found |= loop_depth == 0 || it.lifetime().is_some();
+                }
+                ast::Expr::ContinueExpr(it) => {
+                    found |= loop_depth == 0 || it.lifetime().is_some();
+                }
+                _ => (),
+            },
+            WalkEvent::Leave(
+                ast::Expr::LoopExpr(_) | ast::Expr::WhileExpr(_) | ast::Expr::ForExpr(_),
+            ) => loop_depth -= 1,
+            WalkEvent::Leave(_) => (),
+        }
+        found
+    });
+    found
+}
+
 // Uses a syntax-driven approach to find any impl blocks for the struct that
 // exist within the module/file
 //
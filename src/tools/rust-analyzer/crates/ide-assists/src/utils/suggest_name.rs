@@ -1,6 +1,6 @@
 //! This module contains functions to suggest names for expressions, functions and other items
 
-use hir::Semantics;
+use hir::{Semantics, SemanticsScope};
 use ide_db::RootDatabase;
 use itertools::Itertools;
 use stdx::to_lower_snake_case;
@@ -122,6 +122,21 @@ pub(crate) fn for_variable(expr: &ast::Expr, sema: &Semantics<'_, RootDatabase>)
     "var_name".to_string()
 }
 
+/// Appends a number to `name` until it neither clashes with a name visible in `scope` nor with
+/// any of the names in `taken`.
+pub(crate) fn unique_in_scope(name: &str, scope: &SemanticsScope<'_>, taken: &[String]) -> String {
+    let mut names_in_scope = taken.to_vec();
+    scope.process_all_names(&mut |name, _| names_in_scope.push(name.to_string()));
+
+    let mut unique = name.to_string();
+    let mut counter = 0;
+    while names_in_scope.contains(&unique) {
+        counter += 1;
+        unique = format!("{name}{counter}");
+    }
+    unique
+}
+
 fn normalize(name: &str) -> Option<String> {
     let name = to_lower_snake_case(name);
 
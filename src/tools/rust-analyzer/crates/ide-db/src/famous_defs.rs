@@ -1,7 +1,7 @@
 //! See [`FamousDefs`].
 
 use base_db::{CrateOrigin, LangCrateOrigin, SourceDatabase};
-use hir::{Crate, Enum, Macro, Module, ScopeDef, Semantics, Trait};
+use hir::{Crate, Enum, Macro, Module, ScopeDef, Semantics, Struct, Trait};
 
 use crate::RootDatabase;
 
@@ -42,10 +42,30 @@ pub fn proc_macro(&self) -> Option<Crate> {
         self.find_lang_crate(LangCrateOrigin::ProcMacro)
     }
 
+    pub fn core_clone_Clone(&self) -> Option<Trait> {
+        self.find_trait("core:clone:Clone")
+    }
+
+    pub fn core_cmp_PartialEq(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:PartialEq")
+    }
+
+    pub fn core_cmp_Eq(&self) -> Option<Trait> {
+        self.find_trait("core:cmp:Eq")
+    }
+
     pub fn core_cmp_Ord(&self) -> Option<Trait> {
         self.find_trait("core:cmp:Ord")
     }
 
+    pub fn core_cmp_Ordering(&self) -> Option<Enum> {
+        self.find_enum("core:cmp:Ordering")
+    }
+
+    pub fn core_fmt_Debug(&self) -> Option<Trait> {
+        self.find_trait("core:fmt:Debug")
+    }
+
     pub fn core_convert_From(&self) -> Option<Trait> {
         self.find_trait("core:convert:From")
     }
@@ -62,10 +82,18 @@ pub fn core_result_Result(&self) -> Option<Enum> {
         self.find_enum("core:result:Result")
     }
 
+    pub fn core_task_Poll(&self) -> Option<Enum> {
+        self.find_enum("core:task:Poll")
+    }
+
     pub fn core_default_Default(&self) -> Option<Trait> {
         self.find_trait("core:default:Default")
     }
 
+    pub fn core_hash_Hash(&self) -> Option<Trait> {
+        self.find_trait("core:hash:Hash")
+    }
+
     pub fn core_iter_Iterator(&self) -> Option<Trait> {
         self.find_trait("core:iter:traits:iterator:Iterator")
     }
@@ -102,10 +130,54 @@ pub fn core_marker_Copy(&self) -> Option<Trait> {
         self.find_trait("core:marker:Copy")
     }
 
+    pub fn core_marker_Sized(&self) -> Option<Trait> {
+        self.find_trait("core:marker:Sized")
+    }
+
     pub fn core_macros_builtin_derive(&self) -> Option<Macro> {
         self.find_macro("core:macros:builtin:derive")
     }
 
+    pub fn alloc_vec_Vec(&self) -> Option<Struct> {
+        self.find_struct("alloc:vec:Vec")
+    }
+
+    pub fn alloc_string_String(&self) -> Option<Struct> {
+        self.find_struct("alloc:string:String")
+    }
+
+    pub fn alloc_borrow_Cow(&self) -> Option<Enum> {
+        self.find_enum("alloc:borrow:Cow")
+    }
+
+    pub fn alloc_collections_VecDeque(&self) -> Option<Struct> {
+        self.find_struct("alloc:collections:VecDeque")
+    }
+
+    pub fn alloc_collections_LinkedList(&self) -> Option<Struct> {
+        self.find_struct("alloc:collections:LinkedList")
+    }
+
+    pub fn alloc_collections_BinaryHeap(&self) -> Option<Struct> {
+        self.find_struct("alloc:collections:BinaryHeap")
+    }
+
+    pub fn alloc_collections_BTreeMap(&self) -> Option<Struct> {
+        self.find_struct("alloc:collections:BTreeMap")
+    }
+
+    pub fn alloc_collections_BTreeSet(&self) -> Option<Struct> {
+        self.find_struct("alloc:collections:BTreeSet")
+    }
+
+    pub fn std_collections_HashMap(&self) -> Option<Struct> {
+        self.find_struct("std:collections:HashMap")
+    }
+
+    pub fn std_collections_HashSet(&self) -> Option<Struct> {
+        self.find_struct("std:collections:HashSet")
+    }
+
     pub fn builtin_crates(&self) -> impl Iterator<Item = Crate> {
         IntoIterator::into_iter([
             self.std(),
@@ -124,6 +196,13 @@ fn find_trait(&self, path: &str) -> Option<Trait> {
         }
     }
 
+    fn find_struct(&self, path: &str) -> Option<Struct> {
+        match self.find_def(path)? {
+            hir::ScopeDef::ModuleDef(hir::ModuleDef::Adt(hir::Adt::Struct(it))) => Some(it),
+            _ => None,
+        }
+    }
+
     fn find_macro(&self, path: &str) -> Option<Macro> {
         match self.find_def(path)? {
             hir::ScopeDef::ModuleDef(hir::ModuleDef::Macro(it)) => Some(it),
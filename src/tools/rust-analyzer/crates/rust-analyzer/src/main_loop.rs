@@ -940,7 +940,10 @@ fn run_flycheck(this: &mut GlobalState, vfs_path: VfsPath) -> bool {
                 for workspace in params.event.removed {
                     let Ok(path) = workspace.uri.to_file_path() else { continue };
                     let Ok(path) = AbsPathBuf::try_from(path) else { continue };
-                    let Some(position) = config.workspace_roots.iter().position(|it| it == &path) else { continue };
+                    let Some(position) = config.workspace_roots.iter().position(|it| it == &path)
+                    else {
+                        continue;
+                    };
                     config.workspace_roots.remove(position);
                 }
 
@@ -951,10 +954,10 @@ fn run_flycheck(this: &mut GlobalState, vfs_path: VfsPath) -> bool {
                     .filter_map(|it| it.uri.to_file_path().ok())
                     .filter_map(|it| AbsPathBuf::try_from(it).ok());
                 config.workspace_roots.extend(added);
-                    if !config.has_linked_projects() && config.detached_files().is_empty() {
-                        config.rediscover_workspaces();
-                        this.fetch_workspaces_queue.request_op("client workspaces changed".to_string())
-                    }
+                if !config.has_linked_projects() && config.detached_files().is_empty() {
+                    config.rediscover_workspaces();
+                    this.fetch_workspaces_queue.request_op("client workspaces changed".to_string())
+                }
 
                 Ok(())
             })?
@@ -46,6 +46,16 @@ pub fn to_upper_snake_case(s: &str) -> String {
     to_snake_case(s, char::to_uppercase)
 }
 
+pub fn to_upper_camel_case(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter_map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next()?;
+            Some(first.to_uppercase().chain(chars).collect::<String>())
+        })
+        .collect()
+}
+
 // Code partially taken from rust/compiler/rustc_lint/src/nonstandard_style.rs
 // commit: 9626f2b
 fn to_snake_case<F, I>(mut s: &str, change_case: F) -> String
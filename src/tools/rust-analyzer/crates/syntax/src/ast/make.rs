@@ -59,6 +59,9 @@ pub fn expr_unreachable() -> ast::Expr {
     pub fn expr_todo() -> ast::Expr {
         expr_from_text("todo!()")
     }
+    pub fn expr_unimplemented() -> ast::Expr {
+        expr_from_text("unimplemented!()")
+    }
     pub fn expr_ty_default(ty: &ast::Type) -> ast::Expr {
         expr_from_text(&format!("{ty}::default()"))
     }
@@ -179,7 +179,7 @@ pub trait AsRef<T: ?Sized> {
     }
     // endregion:as_ref
     // region:infallible
-    pub enum Infallibe {}
+    pub enum Infallible {}
     // endregion:infallible
 }
 
@@ -481,6 +481,28 @@ pub enum Ordering {
         Greater = 1,
     }
 
+    mod ord_impls {
+        use super::{Ord, Ordering};
+
+        macro_rules! impl_ord {
+            ($($t:ty)*) => {
+                $(
+                    impl Ord for $t {
+                        fn cmp(&self, other: &Self) -> Ordering {
+                            loop {}
+                        }
+                    }
+                )*
+            }
+        }
+
+        impl_ord! {
+            usize u8 u16 u32 u64 u128
+            isize i8 i16 i32 i64 i128
+            bool char
+        }
+    }
+
     // region:derive
     #[rustc_builtin_macro]
     pub macro PartialOrd($item:item) {}
@@ -503,6 +525,14 @@ pub trait Debug {
     pub trait Display {
         fn fmt(&self, f: &mut Formatter<'_>) -> Result;
     }
+
+    // region:infallible
+    impl Debug for crate::convert::Infallible {
+        fn fmt(&self, _: &mut Formatter<'_>) -> Result {
+            match *self {}
+        }
+    }
+    // endregion:infallible
 }
 // endregion:fmt
 